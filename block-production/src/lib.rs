@@ -1,3 +1,5 @@
+use std::convert::Infallible;
+
 use nimiq_account::BlockState;
 use nimiq_block::{
     ForkProof, MacroBlock, MacroBody, MacroHeader, MicroBlock, MicroBody, MicroHeader,
@@ -8,13 +10,16 @@ use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_bls::KeyPair as BlsKeyPair;
 use nimiq_database::traits::WriteTransaction;
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
-use nimiq_keys::KeyPair as SchnorrKeyPair;
+use nimiq_keys::{KeyPair as SchnorrKeyPair, Signature as SchnorrSignature};
 use nimiq_primitives::policy::Policy;
 use nimiq_transaction::{
     extended_transaction::ExtendedTransaction, inherent::Inherent, Transaction,
 };
+use nimiq_vrf::VrfSeed;
 use rand::{CryptoRng, Rng, RngCore};
 
+use crate::signer::{SignerError, ValidatorSigner};
+
 /// Struct that contains all necessary information to actually produce blocks.
 /// It has the validator keys for this validator.
 #[derive(Clone)]
@@ -79,6 +84,60 @@ impl BlockProducer {
         // The rng seed. We need this parameterized in order to have determinism when running unit tests.
         rng: &mut R,
     ) -> MicroBlock {
+        let block = Self::produce_micro_block(
+            blockchain,
+            timestamp,
+            fork_proofs,
+            transactions,
+            extra_data,
+            skip_block_proof,
+            |prev_seed| Ok::<_, Infallible>(prev_seed.sign_next_with_rng(&self.signing_key, rng)),
+            |data| Ok(self.signing_key.sign(data)),
+        );
+        block.unwrap_or_else(|never| match never {})
+    }
+
+    /// Creates the next micro block, using the given signer to sign it.
+    pub fn next_micro_block_with_signer(
+        signer: &dyn ValidatorSigner,
+        // The (upgradable) read locked guard to the blockchain.
+        blockchain: &Blockchain,
+        // The timestamp for the block.
+        timestamp: u64,
+        // Proofs of any forks created by malicious validators. A fork proof may be submitted during
+        // the batch when it happened or in the next one, but not after that.
+        fork_proofs: Vec<ForkProof>,
+        // The transactions to be included in the block body.
+        transactions: Vec<Transaction>,
+        // Extra data for this block.
+        extra_data: Vec<u8>,
+        // Skip block proof.
+        skip_block_proof: Option<SkipBlockProof>,
+    ) -> Result<MicroBlock, SignerError> {
+        Self::produce_micro_block(
+            blockchain,
+            timestamp,
+            fork_proofs,
+            transactions,
+            extra_data,
+            skip_block_proof,
+            |prev_seed| signer.sign_next_seed(prev_seed),
+            |data| signer.sign(data),
+        )
+    }
+
+    /// Creates the next micro block. The VRF seed and the header are signed using the given functions.
+    #[allow(clippy::too_many_arguments)]
+    fn produce_micro_block<E>(
+        blockchain: &Blockchain,
+        timestamp: u64,
+        fork_proofs: Vec<ForkProof>,
+        transactions: Vec<Transaction>,
+        extra_data: Vec<u8>,
+        skip_block_proof: Option<SkipBlockProof>,
+        sign_seed: impl FnOnce(&VrfSeed) -> Result<VrfSeed, E>,
+        sign_header: impl FnOnce(&[u8]) -> Result<SchnorrSignature, E>,
+    ) -> Result<MicroBlock, E> {
         // Calculate the block number. It is simply the previous block number incremented by one.
         let block_number = blockchain.block_number() + 1;
 
@@ -110,7 +169,7 @@ impl BlockProducer {
             // leader.
             prev_seed
         } else {
-            prev_seed.sign_next_with_rng(&self.signing_key, rng)
+            sign_seed(&prev_seed)?
         };
 
         // Create the inherents from the fork proofs or skip block info.
@@ -176,16 +235,16 @@ impl BlockProducer {
         } else {
             // Signs the block header using the signing key.
             let hash = header.hash::<Blake2bHash>();
-            let signature = self.signing_key.sign(hash.as_slice());
+            let signature = sign_header(hash.as_slice())?;
             MicroJustification::Micro(signature)
         };
 
         // Returns the micro block.
-        MicroBlock {
+        Ok(MicroBlock {
             header,
             body: Some(body),
             justification: Some(justification),
-        }
+        })
     }
 
     /// Creates a proposal for the next macro block (checkpoint or election). It is just a proposal,
@@ -229,6 +288,43 @@ impl BlockProducer {
         // The rng seed. We need this parameterized in order to have determinism when running unit tests.
         rng: &mut R,
     ) -> MacroBlock {
+        let block = Self::produce_macro_block_proposal(
+            blockchain,
+            timestamp,
+            round,
+            extra_data,
+            |prev_seed| Ok::<_, Infallible>(prev_seed.sign_next_with_rng(&self.signing_key, rng)),
+        );
+        block.unwrap_or_else(|never| match never {})
+    }
+
+    /// Creates a proposal for the next macro block (checkpoint or election), using the given signer to
+    /// sign its VRF seed. The proposal itself still needs to be signed.
+    // Note: Needs to be called with the Blockchain lock held.
+    pub fn next_macro_block_proposal_with_signer(
+        signer: &dyn ValidatorSigner,
+        // The (upgradable) read locked guard to the blockchain.
+        blockchain: &Blockchain,
+        // The timestamp for the block proposal.
+        timestamp: u64,
+        // The round for the block proposal.
+        round: u32,
+        // Extra data for this block.
+        extra_data: Vec<u8>,
+    ) -> Result<MacroBlock, SignerError> {
+        Self::produce_macro_block_proposal(blockchain, timestamp, round, extra_data, |prev_seed| {
+            signer.sign_next_seed(prev_seed)
+        })
+    }
+
+    /// Creates a proposal for the next macro block. The VRF seed is signed using the given function.
+    fn produce_macro_block_proposal<E>(
+        blockchain: &Blockchain,
+        timestamp: u64,
+        round: u32,
+        extra_data: Vec<u8>,
+        sign_seed: impl FnOnce(&VrfSeed) -> Result<VrfSeed, E>,
+    ) -> Result<MacroBlock, E> {
         // Calculate the block number. It is simply the previous block number incremented by one.
         let block_number = blockchain.block_number() + 1;
 
@@ -253,10 +349,7 @@ impl BlockProducer {
 
         // Calculate the seed for this block by signing the previous block seed with the validator
         // key.
-        let seed = blockchain
-            .head()
-            .seed()
-            .sign_next_with_rng(&self.signing_key, rng);
+        let seed = sign_seed(blockchain.head().seed())?;
 
         // Create the header for the macro block without the state root and the transactions root.
         // We need several fields of this header in order to calculate the transactions and the
@@ -322,7 +415,7 @@ impl BlockProducer {
             .0;
 
         txn.abort();
-        macro_block
+        Ok(macro_block)
     }
 
     pub fn next_macro_body(blockchain: &Blockchain, macro_header: &MacroHeader) -> MacroBody {
@@ -359,5 +452,6 @@ impl BlockProducer {
     }
 }
 
+pub mod signer;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_custom_block;
@@ -0,0 +1,97 @@
+use nimiq_bls::{
+    KeyPair as BlsKeyPair, PublicKey as BlsPublicKey, SigHash, Signature as BlsSignature,
+};
+use nimiq_keys::{
+    KeyPair as SchnorrKeyPair, PublicKey as SchnorrPublicKey, Signature as SchnorrSignature,
+};
+use nimiq_vrf::VrfSeed;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SignerError {
+    #[error("Failed to communicate with the signer: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid response from the signer")]
+    InvalidResponse,
+    #[error("The signer returned a signature that doesn't match its public key")]
+    InvalidSignature,
+    #[error("Signer refused to sign: {0}")]
+    Refused(String),
+}
+
+/// Signs blocks and consensus messages with the signing and voting keys of a validator.
+///
+/// Block production, Tendermint and the skip block aggregation only use the keys through this trait, such that
+/// the keys don't have to be held by the node itself but can e.g. live in a separate signer process.
+pub trait ValidatorSigner: Send + Sync {
+    /// Returns the public key of the signing key.
+    fn signing_public_key(&self) -> SchnorrPublicKey;
+
+    /// Returns the public key of the voting key.
+    fn voting_public_key(&self) -> BlsPublicKey;
+
+    /// Signs `data` with the signing key. This is used for block headers and Tendermint proposals.
+    fn sign(&self, data: &[u8]) -> Result<SchnorrSignature, SignerError>;
+
+    /// Computes the VRF seed following `prev_seed` with the signing key.
+    fn sign_next_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError>;
+
+    /// Signs the hash of a message with the voting key. This is used for Tendermint and skip block votes.
+    fn vote(&self, hash: SigHash) -> Result<BlsSignature, SignerError>;
+
+    /// Returns the signing key pair if it is held in-process. Remote signers never hand out their keys.
+    fn signing_key_pair(&self) -> Option<SchnorrKeyPair> {
+        None
+    }
+
+    /// Returns the voting key pair if it is held in-process. Remote signers never hand out their keys.
+    fn voting_key_pair(&self) -> Option<BlsKeyPair> {
+        None
+    }
+}
+
+/// Signer holding the validator keys in-process.
+#[derive(Clone)]
+pub struct LocalSigner {
+    signing_key: SchnorrKeyPair,
+    voting_key: BlsKeyPair,
+}
+
+impl LocalSigner {
+    pub fn new(signing_key: SchnorrKeyPair, voting_key: BlsKeyPair) -> Self {
+        Self {
+            signing_key,
+            voting_key,
+        }
+    }
+}
+
+impl ValidatorSigner for LocalSigner {
+    fn signing_public_key(&self) -> SchnorrPublicKey {
+        self.signing_key.public
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        self.voting_key.public_key
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<SchnorrSignature, SignerError> {
+        Ok(self.signing_key.sign(data))
+    }
+
+    fn sign_next_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError> {
+        Ok(prev_seed.sign_next(&self.signing_key))
+    }
+
+    fn vote(&self, hash: SigHash) -> Result<BlsSignature, SignerError> {
+        Ok(self.voting_key.sign_hash(hash))
+    }
+
+    fn signing_key_pair(&self) -> Option<SchnorrKeyPair> {
+        Some(self.signing_key.clone())
+    }
+
+    fn voting_key_pair(&self) -> Option<BlsKeyPair> {
+        Some(self.voting_key.clone())
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use log::info;
 use nimiq::prover::prover_main;
@@ -15,6 +15,9 @@ pub use nimiq::{
     },
 };
 
+/// How often the peer contact book is persisted to disk (if configured).
+const PEER_CONTACTS_STORE_INTERVAL: Duration = Duration::from_secs(60);
+
 async fn main_inner() -> Result<(), Error> {
     // Keep for potential future reactivation
     // initialize_deadlock_detection();
@@ -134,6 +137,7 @@ async fn main_inner() -> Result<(), Error> {
             mempool,
             client.consensus_proxy(),
             client.network(),
+            client.validator_proxy(),
             &nimiq_task_metric,
         )
     }
@@ -150,9 +154,17 @@ async fn main_inner() -> Result<(), Error> {
 
     // Run periodically
     let mut interval = tokio::time::interval(Duration::from_secs(statistics_interval));
+    let mut last_peer_contacts_store = Instant::now();
     loop {
         interval.tick().await;
 
+        if last_peer_contacts_store.elapsed() >= PEER_CONTACTS_STORE_INTERVAL {
+            if let Err(error) = client.store_peer_contacts() {
+                log::warn!(%error, "Failed to store peer contacts");
+            }
+            last_peer_contacts_store = Instant::now();
+        }
+
         if show_statistics {
             match client.network().network_info().await {
                 Ok(network_info) => {
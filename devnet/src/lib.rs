@@ -0,0 +1,370 @@
+//! Deterministic local Albatross devnets.
+//!
+//! All keys of a devnet are derived from a single seed, such that the same seed always results in the same genesis
+//! block and node configurations. The generated genesis is used through `NIMIQ_OVERRIDE_DEVNET_CONFIG`, which makes
+//! the nodes run the `dev-albatross` network with it.
+use std::{
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use nimiq_bls::KeyPair as BlsKeyPair;
+use nimiq_genesis_builder::config::{GenesisConfig, GenesisStaker, GenesisValidator};
+use nimiq_keys::{Address, KeyPair, SecureGenerate};
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_serde::Serialize;
+use nimiq_vrf::VrfSeed;
+use rand::{rngs::StdRng, SeedableRng};
+use thiserror::Error;
+use time::OffsetDateTime;
+
+/// The environment variable through which the nodes pick up the generated genesis.
+pub const GENESIS_OVERRIDE_VAR: &str = "NIMIQ_OVERRIDE_DEVNET_CONFIG";
+
+#[derive(Debug, Error)]
+pub enum DevnetError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TOML serialization error: {0}")]
+    Toml(#[from] toml::ser::Error),
+}
+
+/// How the nodes of the devnet connect to each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transport {
+    /// In-process memory transport. The nodes can only be run in a single process.
+    Memory,
+    /// WebSocket connections on the loopback interface.
+    Localhost,
+}
+
+impl FromStr for Transport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "memory" => Ok(Transport::Memory),
+            "localhost" => Ok(Transport::Localhost),
+            _ => Err(format!("Unknown transport: {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DevnetConfig {
+    /// The number of validators, each of which is run by its own node.
+    pub validators: usize,
+    /// The number of stakers delegating to each validator.
+    pub stakers_per_validator: usize,
+    /// The stake of each staker.
+    pub stake: Coin,
+    /// The seed all keys are derived from.
+    pub seed: u64,
+    pub transport: Transport,
+    /// The port of the first node. The other nodes use the following ports.
+    pub port: u16,
+    /// The RPC port of the first node, if the nodes should run an RPC server. The other nodes use the following
+    /// ports.
+    pub rpc_port: Option<u16>,
+    /// The timestamp of the genesis block.
+    pub timestamp: OffsetDateTime,
+}
+
+impl Default for DevnetConfig {
+    fn default() -> Self {
+        Self {
+            validators: 4,
+            stakers_per_validator: 0,
+            stake: Coin::from_u64_unchecked(Policy::VALIDATOR_DEPOSIT),
+            seed: 0,
+            transport: Transport::Memory,
+            port: 8443,
+            rpc_port: None,
+            // Fixed, such that the genesis only depends on the seed.
+            timestamp: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+}
+
+/// The keys of a validator of the devnet.
+pub struct DevnetValidator {
+    /// The key of the validator address, which also receives the rewards.
+    pub address_key: KeyPair,
+    pub signing_key: KeyPair,
+    pub voting_key: BlsKeyPair,
+    pub fee_key: KeyPair,
+}
+
+impl DevnetValidator {
+    pub fn address(&self) -> Address {
+        Address::from(&self.address_key.public)
+    }
+}
+
+pub struct DevnetStaker {
+    pub key: KeyPair,
+    pub delegation: Address,
+}
+
+impl DevnetStaker {
+    pub fn address(&self) -> Address {
+        Address::from(&self.key.public)
+    }
+}
+
+/// The files written for a devnet.
+#[derive(Clone, Debug)]
+pub struct DevnetFiles {
+    pub genesis: PathBuf,
+    /// The private keys of all accounts of the genesis.
+    pub accounts: PathBuf,
+    /// The `client.toml` of each node.
+    pub nodes: Vec<PathBuf>,
+}
+
+pub struct Devnet {
+    config: DevnetConfig,
+    validators: Vec<DevnetValidator>,
+    stakers: Vec<DevnetStaker>,
+}
+
+impl Devnet {
+    /// Derives the keys of all validators and stakers from the seed of the configuration.
+    pub fn generate(config: DevnetConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+
+        let validators: Vec<_> = (0..config.validators)
+            .map(|_| DevnetValidator {
+                address_key: KeyPair::generate(&mut rng),
+                signing_key: KeyPair::generate(&mut rng),
+                voting_key: BlsKeyPair::generate(&mut rng),
+                fee_key: KeyPair::generate(&mut rng),
+            })
+            .collect();
+
+        let stakers = validators
+            .iter()
+            .flat_map(|validator| {
+                let delegation = validator.address();
+                (0..config.stakers_per_validator)
+                    .map(|_| DevnetStaker {
+                        key: KeyPair::generate(&mut rng),
+                        delegation: delegation.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Self {
+            config,
+            validators,
+            stakers,
+        }
+    }
+
+    pub fn config(&self) -> &DevnetConfig {
+        &self.config
+    }
+
+    pub fn validators(&self) -> &[DevnetValidator] {
+        &self.validators
+    }
+
+    pub fn stakers(&self) -> &[DevnetStaker] {
+        &self.stakers
+    }
+
+    pub fn genesis_config(&self) -> GenesisConfig {
+        GenesisConfig {
+            seed_message: Some(format!("Albatross DevNet {}", self.config.seed)),
+            timestamp: Some(self.config.timestamp),
+            vrf_seed: Some(VrfSeed::default()),
+            parent_election_hash: None,
+            parent_hash: None,
+            history_root: None,
+            block_number: Policy::genesis_block_number(),
+            validators: self
+                .validators
+                .iter()
+                .map(|validator| GenesisValidator {
+                    validator_address: validator.address(),
+                    signing_key: validator.signing_key.public,
+                    voting_key: validator.voting_key.public_key,
+                    reward_address: validator.address(),
+                })
+                .collect(),
+            stakers: self
+                .stakers
+                .iter()
+                .map(|staker| GenesisStaker {
+                    staker_address: staker.address(),
+                    balance: self.config.stake,
+                    delegation: staker.delegation.clone(),
+                })
+                .collect(),
+            basic_accounts: vec![],
+            vesting_accounts: vec![],
+            htlc_accounts: vec![],
+        }
+    }
+
+    /// The address the node with the given index listens on.
+    pub fn listen_address(&self, index: usize) -> String {
+        let port = self.config.port as usize + index;
+        match self.config.transport {
+            Transport::Memory => format!("/memory/{port}"),
+            Transport::Localhost => format!("/ip4/127.0.0.1/tcp/{port}/ws"),
+        }
+    }
+
+    /// Returns the `client.toml` of the node with the given index, which stores its state in `node_dir`.
+    pub fn client_config(&self, index: usize, node_dir: &Path) -> String {
+        let validator = &self.validators[index];
+        let seed_nodes: Vec<_> = (0..self.validators.len())
+            .filter(|&other| other != index)
+            .map(|other| {
+                format!(
+                    "{{ address = {} }}",
+                    toml_string(&self.listen_address(other))
+                )
+            })
+            .collect();
+        // All other nodes are needed to establish consensus in small devnets.
+        let min_peers = self.validators.len().saturating_sub(1).min(3);
+
+        let mut config = String::new();
+        let _ = writeln!(config, "[network]");
+        let _ = writeln!(
+            config,
+            "listen_addresses = [{}]",
+            toml_string(&self.listen_address(index))
+        );
+        let _ = writeln!(config, "seed_nodes = [{}]", seed_nodes.join(", "));
+        let _ = writeln!(
+            config,
+            "memory_transport = {}",
+            self.config.transport == Transport::Memory
+        );
+        let _ = writeln!(config);
+        let _ = writeln!(config, "[consensus]");
+        let _ = writeln!(config, "network = \"dev-albatross\"");
+        let _ = writeln!(config, "sync_mode = \"history\"");
+        let _ = writeln!(config, "min_peers = {min_peers}");
+        let _ = writeln!(config);
+        let _ = writeln!(config, "[database]");
+        let _ = writeln!(
+            config,
+            "path = {}",
+            toml_string(&node_dir.to_string_lossy())
+        );
+        let _ = writeln!(config);
+        if let Some(rpc_port) = self.config.rpc_port {
+            let _ = writeln!(config, "[rpc-server]");
+            let _ = writeln!(config, "port = {}", rpc_port as usize + index);
+            let _ = writeln!(config);
+        }
+        let _ = writeln!(config, "[validator]");
+        let _ = writeln!(
+            config,
+            "validator_address = {}",
+            toml_string(&validator.address().to_user_friendly_address())
+        );
+        let _ = writeln!(
+            config,
+            "signing_key = {}",
+            toml_string(&hex::encode(
+                validator.signing_key.private.serialize_to_vec()
+            ))
+        );
+        let _ = writeln!(
+            config,
+            "voting_key = {}",
+            toml_string(&hex::encode(
+                validator.voting_key.secret_key.serialize_to_vec()
+            ))
+        );
+        let _ = writeln!(
+            config,
+            "fee_key = {}",
+            toml_string(&hex::encode(validator.fee_key.private.serialize_to_vec()))
+        );
+        let _ = writeln!(config, "automatic_reactivate = true");
+        config
+    }
+
+    /// Lists the private keys of all accounts of the genesis, e.g. to send transactions in tests.
+    pub fn accounts(&self) -> String {
+        let mut accounts = String::new();
+        for validator in &self.validators {
+            let _ = writeln!(accounts, "[[validators]]");
+            let _ = writeln!(
+                accounts,
+                "address = {}",
+                toml_string(&validator.address().to_user_friendly_address())
+            );
+            let _ = writeln!(
+                accounts,
+                "private_key = {}",
+                toml_string(&hex::encode(
+                    validator.address_key.private.serialize_to_vec()
+                ))
+            );
+            let _ = writeln!(accounts);
+        }
+        for staker in &self.stakers {
+            let _ = writeln!(accounts, "[[stakers]]");
+            let _ = writeln!(
+                accounts,
+                "address = {}",
+                toml_string(&staker.address().to_user_friendly_address())
+            );
+            let _ = writeln!(
+                accounts,
+                "private_key = {}",
+                toml_string(&hex::encode(staker.key.private.serialize_to_vec()))
+            );
+            let _ = writeln!(
+                accounts,
+                "delegation = {}",
+                toml_string(&staker.delegation.to_user_friendly_address())
+            );
+            let _ = writeln!(accounts);
+        }
+        accounts
+    }
+
+    /// Writes the genesis, the accounts and the configuration of each node to `dir`. The nodes store their state in
+    /// `node-<index>` subdirectories, which are left untouched if they already exist.
+    pub fn write(&self, dir: &Path) -> Result<DevnetFiles, DevnetError> {
+        fs::create_dir_all(dir)?;
+        let dir = dir.canonicalize()?;
+
+        let genesis = dir.join("genesis.toml");
+        fs::write(&genesis, toml::to_string(&self.genesis_config())?)?;
+
+        let accounts = dir.join("accounts.toml");
+        fs::write(&accounts, self.accounts())?;
+
+        let mut nodes = vec![];
+        for index in 0..self.validators.len() {
+            let node_dir = dir.join(format!("node-{index}"));
+            fs::create_dir_all(&node_dir)?;
+            let client_config = node_dir.join("client.toml");
+            fs::write(&client_config, self.client_config(index, &node_dir))?;
+            nodes.push(client_config);
+        }
+
+        Ok(DevnetFiles {
+            genesis,
+            accounts,
+            nodes,
+        })
+    }
+}
+
+fn toml_string(s: &str) -> String {
+    toml::Value::String(s.to_string()).to_string()
+}
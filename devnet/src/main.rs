@@ -0,0 +1,186 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use clap::Parser;
+use log::info;
+use nimiq::{
+    client::Client,
+    config::{config::ClientConfig, config_file::ConfigFile},
+    error::Error,
+    extras::{
+        logging::{initialize_logging, log_error_cause_chain},
+        panic::initialize_panic_reporting,
+        signal_handling::initialize_signal_handler,
+    },
+};
+use nimiq_devnet::{Devnet, DevnetConfig, Transport, GENESIS_OVERRIDE_VAR};
+use nimiq_primitives::coin::Coin;
+
+#[derive(Debug, Parser)]
+pub struct DevnetCommandLine {
+    /// Number of validators, each of which is run by its own node.
+    #[clap(long, short, default_value_t = 4)]
+    pub validators: usize,
+
+    /// Number of stakers delegating to each validator.
+    #[clap(long, default_value_t = 0)]
+    pub stakers: usize,
+
+    /// Stake of each staker in NIM.
+    #[clap(long)]
+    pub stake: Option<Coin>,
+
+    /// Seed from which all keys are derived. The same seed always results in the same devnet.
+    #[clap(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// Directory the genesis, the node configurations and the node state are stored in.
+    #[clap(long, short, default_value = "devnet-data")]
+    pub output: PathBuf,
+
+    /// How the nodes connect to each other: `memory` or `localhost`.
+    #[clap(long, short, default_value = "memory")]
+    pub transport: Transport,
+
+    /// Port of the first node. The other nodes use the following ports.
+    #[clap(long, short, default_value_t = 8443)]
+    pub port: u16,
+
+    /// RPC port of the first node. The other nodes use the following ports. If not set, no RPC servers are started.
+    #[clap(long)]
+    pub rpc_port: Option<u16>,
+
+    /// Removes the state of previous runs, such that the devnet starts again from the genesis.
+    #[clap(long)]
+    pub reset: bool,
+
+    /// Only writes the genesis and the node configurations without starting the nodes.
+    #[clap(long)]
+    pub generate_only: bool,
+}
+
+/// Starts a node with the given configuration file in the current runtime.
+async fn start_node(config_path: &Path) -> Result<Client, Error> {
+    let config_file = ConfigFile::from_file(config_path)?;
+
+    let mut builder = ClientConfig::builder();
+    builder.config_file(&config_file)?;
+    let config = builder.build()?;
+    let rpc_config = config.rpc_server.clone();
+
+    let mut client: Client = Client::from_config(
+        config,
+        Box::new(|fut| {
+            tokio::spawn(fut);
+        }),
+    )
+    .await?;
+
+    if let Some(rpc_config) = rpc_config {
+        use nimiq::extras::rpc_server::initialize_rpc_server;
+        let rpc_server = initialize_rpc_server(&client, rpc_config, client.wallet_store())
+            .expect("Failed to initialize RPC server");
+        tokio::spawn(async move { rpc_server.run().await });
+    }
+
+    tokio::spawn(client.take_consensus().unwrap());
+    tokio::spawn(client.take_zkp_component().unwrap());
+    if let Some(validator) = client.take_validator() {
+        info!("Initializing validator {}", validator.validator_address());
+        tokio::spawn(validator);
+    }
+
+    Ok(client)
+}
+
+fn main_inner() -> Result<(), Error> {
+    let command_line = DevnetCommandLine::parse();
+    if command_line.validators == 0 {
+        return Err(Error::config_error(
+            "The devnet needs at least one validator",
+        ));
+    }
+
+    let mut config = DevnetConfig {
+        validators: command_line.validators,
+        stakers_per_validator: command_line.stakers,
+        seed: command_line.seed,
+        transport: command_line.transport,
+        port: command_line.port,
+        rpc_port: command_line.rpc_port,
+        ..Default::default()
+    };
+    if let Some(stake) = command_line.stake {
+        config.stake = stake;
+    }
+    let devnet = Devnet::generate(config);
+
+    if command_line.reset {
+        for index in 0..command_line.validators {
+            let node_dir = command_line.output.join(format!("node-{index}"));
+            if node_dir.exists() {
+                fs::remove_dir_all(node_dir)?;
+            }
+        }
+    }
+    let files = devnet
+        .write(&command_line.output)
+        .map_err(|error| Error::config_error(format!("Failed to write the devnet: {error}")))?;
+    println!("Genesis: {}", files.genesis.display());
+    println!("Accounts: {}", files.accounts.display());
+    for node in &files.nodes {
+        println!("Node: {}", node.display());
+    }
+
+    if command_line.generate_only {
+        return Ok(());
+    }
+
+    // The genesis override is read once, when the network info of the devnet is first needed. It is set before the
+    // runtime starts, since the environment must not be modified while other threads may read it.
+    std::env::set_var(GENESIS_OVERRIDE_VAR, &files.genesis);
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?
+        .block_on(run_nodes(&files.nodes))
+}
+
+/// Starts a node for each of the configuration files and keeps running them.
+async fn run_nodes(nodes: &[PathBuf]) -> Result<(), Error> {
+    // All nodes share the logging configuration of the first one.
+    let config_file = ConfigFile::from_file(&nodes[0])?;
+    initialize_logging(None, Some(&config_file.log))?;
+    initialize_panic_reporting();
+    initialize_signal_handler();
+
+    let mut clients = vec![];
+    for node in nodes {
+        clients.push(start_node(node).await?);
+    }
+
+    let mut interval = tokio::time::interval(Duration::from_secs(10));
+    loop {
+        interval.tick().await;
+        for (index, client) in clients.iter().enumerate() {
+            info!(
+                node = index,
+                consensus_established = client.consensus_proxy().is_established(),
+                head = %client.blockchain_head(),
+                "Node {}",
+                index,
+            );
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = main_inner() {
+        log_error_cause_chain(&e);
+        std::process::exit(1);
+    }
+    std::process::exit(0);
+}
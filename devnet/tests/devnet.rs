@@ -0,0 +1,77 @@
+use nimiq::config::{config::ClientConfig, config_file::ConfigFile};
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_devnet::{Devnet, DevnetConfig, Transport};
+use nimiq_genesis_builder::GenesisBuilder;
+
+fn devnet(seed: u64) -> Devnet {
+    Devnet::generate(DevnetConfig {
+        validators: 4,
+        stakers_per_validator: 2,
+        seed,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn generation_is_deterministic() {
+    let addresses = |devnet: &Devnet| {
+        devnet
+            .validators()
+            .iter()
+            .map(|validator| validator.address())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(addresses(&devnet(1)), addresses(&devnet(1)));
+    assert_ne!(addresses(&devnet(1)), addresses(&devnet(2)));
+    assert_eq!(devnet(1).accounts(), devnet(1).accounts());
+    assert_eq!(devnet(1).stakers().len(), 8);
+}
+
+#[test]
+fn written_devnet_can_be_loaded() {
+    let dir = tempfile::tempdir().unwrap();
+    let devnet = devnet(1);
+    let files = devnet.write(dir.path()).unwrap();
+
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+    let genesis = GenesisBuilder::from_config_file(&files.genesis)
+        .unwrap()
+        .generate(env)
+        .unwrap();
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+    let regenerated = GenesisBuilder::from_config_file(&files.genesis)
+        .unwrap()
+        .generate(env)
+        .unwrap();
+    assert_eq!(genesis.hash, regenerated.hash);
+
+    assert_eq!(files.nodes.len(), 4);
+    for (node, validator) in files.nodes.iter().zip(devnet.validators()) {
+        let config_file = ConfigFile::from_file(node).unwrap();
+        assert!(config_file.network.memory_transport);
+        assert_eq!(config_file.network.seed_nodes.len(), 3);
+        assert_eq!(
+            config_file.validator.as_ref().unwrap().validator_address,
+            validator.address().to_user_friendly_address()
+        );
+
+        let mut builder = ClientConfig::builder();
+        builder.config_file(&config_file).unwrap();
+        let config = builder.build().unwrap();
+        assert!(config.network.memory_transport);
+    }
+}
+
+#[test]
+fn localhost_nodes_listen_on_consecutive_ports() {
+    let devnet = Devnet::generate(DevnetConfig {
+        validators: 2,
+        transport: Transport::Localhost,
+        port: 9000,
+        ..Default::default()
+    });
+
+    assert_eq!(devnet.listen_address(0), "/ip4/127.0.0.1/tcp/9000/ws");
+    assert_eq!(devnet.listen_address(1), "/ip4/127.0.0.1/tcp/9001/ws");
+}
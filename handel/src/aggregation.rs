@@ -1,6 +1,7 @@
 use std::{
     fmt::Debug,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -15,6 +16,7 @@ use tokio_stream::wrappers::IntervalStream;
 use crate::{
     config::Config,
     contribution::AggregatableContribution,
+    diagnostics::{AggregationTracker, HandelDiagnostics},
     identity::IdentityRegistry,
     level::Level,
     network::{LevelUpdateSender, Network},
@@ -64,6 +66,9 @@ struct NextAggregation<
 
     /// the level which needs activation next
     next_level_timeout: usize,
+
+    /// Records the diagnostics of this aggregation, if enabled
+    tracker: AggregationTracker,
 }
 
 impl<
@@ -78,12 +83,27 @@ impl<
         own_contribution: P::Contribution,
         input_stream: LevelUpdateStream<P, TId>,
         sender: LevelUpdateSender<N>,
+        diagnostics: Option<Arc<HandelDiagnostics>>,
     ) -> Self {
         // Invoke the partitioner to create the level structure of peers.
         let levels: Vec<Level> = Level::create_levels(protocol.partitioner(), protocol.identify());
 
+        let tracker = diagnostics.map_or_else(AggregationTracker::disabled, |diagnostics| {
+            diagnostics.start_aggregation(
+                format!("{:?}", protocol.identify()),
+                protocol.node_id(),
+                &config,
+                &levels,
+            )
+        });
+
         // Create an empty todo list which can later be polled for the best available todo.
-        let mut todos = TodoList::new(protocol.identify(), protocol.evaluator(), input_stream);
+        let mut todos = TodoList::new(
+            protocol.identify(),
+            protocol.evaluator(),
+            input_stream,
+            tracker.clone(),
+        );
 
         // Add our own contribution to the todo list.
         todos.add_contribution(own_contribution.clone(), 0);
@@ -111,11 +131,12 @@ impl<
             start_level_interval,
             periodic_update_interval,
             next_level_timeout: 0,
+            tracker,
         }
     }
 
-    /// Starts level `level`
-    fn start_level(&mut self, level: usize) {
+    /// Starts level `level`. `on_timeout` signals whether the level is started because the level timeout elapsed.
+    fn start_level(&mut self, level: usize, on_timeout: bool) {
         let level = self
             .levels
             .get(level)
@@ -128,6 +149,8 @@ impl<
 
         // Try to Start the level
         if level.start() {
+            self.tracker.level_started(level.id, on_timeout);
+
             // In case the level was not started previously send the best contribution to peers on the level
 
             // Don't do anything for level 0 as it only contains this node
@@ -185,16 +208,29 @@ impl<
             return;
         }
 
-        // first get the current contributor count for this level. Release the lock as soon as possible
+        // first get the current contributors for this level. Release the lock as soon as possible
         // to continue working on todos.
-        let num_contributors = {
+        let contributors = {
             let store = self.protocol.store();
             let store = store.read();
             let best = store
                 .best(level_id)
                 .unwrap_or_else(|| panic!("Expected a best signature for level {}", level_id));
-            self.num_contributors(best)
+            self.protocol
+                .registry()
+                .signers_identity(&best.contributors())
         };
+        let num_contributors = contributors.len();
+
+        if self.tracker.is_enabled() {
+            let missing_peers = self.levels[level_id]
+                .peer_ids
+                .iter()
+                .copied()
+                .filter(|&peer_id| !contributors.signers().contains(peer_id))
+                .collect();
+            self.tracker.missing_peers(level_id, missing_peers);
+        }
 
         // If the number of contributors on this level is equal to the number of peers on this level it is completed.
         if num_contributors == num_peers {
@@ -212,10 +248,12 @@ impl<
                     .write()
                     .receive_completed = true;
             }
+            self.tracker.level_completed(level_id);
+
             // if there is a level with a higher id than the completed one it needs to be activated.
             if level_id + 1 < self.levels.len() {
                 // activate next level
-                self.start_level(level_id + 1);
+                self.start_level(level_id + 1, false);
             }
         }
 
@@ -327,7 +365,7 @@ impl<
             self.next_level_timeout += 1;
 
             // finally start the level.
-            self.start_level(level);
+            self.start_level(level, true);
         }
     }
 
@@ -350,6 +388,10 @@ impl<
                             if result.is_ok() {
                                 // special case of full contributions
                                 if todo.level == self.protocol.partitioner().levels() {
+                                    self.tracker.aggregate(
+                                        self.num_contributors(&todo.contribution),
+                                        true,
+                                    );
                                     return (todo.contribution, Some(self));
                                 }
 
@@ -366,7 +408,7 @@ impl<
                                 }
 
                                 // in case the level of this todo has not started, start it now as we have already contributions on it.
-                                self.start_level(todo.level);
+                                self.start_level(todo.level, false);
                                 // check if a level was completed by the addition of the contribution
                                 self.check_completed_level(todo.level);
 
@@ -379,9 +421,15 @@ impl<
                                 };
 
                                 if let Some(best) = best {
+                                    self.tracker.aggregate(
+                                        self.num_contributors(&best),
+                                        self.is_complete_aggregate(&best),
+                                    );
                                     return (best, Some(self));
                                 }
                             } else {
+                                self.tracker.invalid_contribution();
+
                                 // Invalid contributions create a warning, but do not terminate. -> Continue with the next best todo item.
                                 warn!(
                                     id = ?self.protocol.identify(),
@@ -482,14 +530,39 @@ impl<
         own_contribution: TProtocol::Contribution,
         input_stream: LevelUpdateStream<TProtocol, TId>,
         network: TNetwork,
+    ) -> Self {
+        Self::with_diagnostics(
+            protocol,
+            config,
+            own_contribution,
+            input_stream,
+            network,
+            None,
+        )
+    }
+
+    /// Creates a new aggregation that records its diagnostics in `diagnostics`, if given.
+    pub fn with_diagnostics(
+        protocol: TProtocol,
+        config: Config,
+        own_contribution: TProtocol::Contribution,
+        input_stream: LevelUpdateStream<TProtocol, TId>,
+        network: TNetwork,
+        diagnostics: Option<Arc<HandelDiagnostics>>,
     ) -> Self {
         // Create the Sender, buffering a single message per recipient.
         let sender = LevelUpdateSender::new(protocol.partitioner().size(), network);
 
-        let next_aggregation =
-            NextAggregation::new(protocol, config, own_contribution, input_stream, sender)
-                .next()
-                .boxed();
+        let next_aggregation = NextAggregation::new(
+            protocol,
+            config,
+            own_contribution,
+            input_stream,
+            sender,
+            diagnostics,
+        )
+        .next()
+        .boxed();
 
         Self {
             next_aggregation: Some(next_aggregation),
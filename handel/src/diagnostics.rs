@@ -0,0 +1,290 @@
+//! Diagnostics of aggregations.
+//!
+//! An aggregation that doesn't complete in time doesn't tell which of its levels or peers were missing. If enabled,
+//! every aggregation records when its levels were started and completed, which peers are still missing on each level
+//! and what the peers contributed. The diagnostics of the most recent aggregations are kept for inspection.
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use parking_lot::Mutex;
+
+#[cfg(feature = "metrics")]
+use crate::handel_metrics::HandelMetrics;
+use crate::{config::Config, level::Level};
+
+/// The progress of a level of an aggregation.
+#[derive(Clone, Debug)]
+pub struct LevelDiagnostics {
+    pub id: usize,
+    /// The peers on this level.
+    pub peers: Vec<usize>,
+    /// Time from the start of the aggregation until the level was started.
+    pub started_after: Option<Duration>,
+    /// Whether the level was started because the level timeout elapsed, rather than because of the progress of the
+    /// aggregation.
+    pub started_on_timeout: bool,
+    /// Time from the start of the aggregation until contributions of all peers on the level were received.
+    pub completed_after: Option<Duration>,
+    /// The peers on this level whose contributions are not part of the best aggregate of the level.
+    pub missing_peers: Vec<usize>,
+}
+
+/// What a peer contributed to an aggregation.
+#[derive(Clone, Debug, Default)]
+pub struct PeerDiagnostics {
+    /// Level updates received from the peer.
+    pub level_updates: usize,
+    /// Individual contributions received along with the level updates.
+    pub individual_contributions: usize,
+    /// Level updates for a level the peer is not on, which are ignored.
+    pub wrong_level_updates: usize,
+    /// Contributions that the evaluator scored as not useful.
+    pub useless_contributions: usize,
+    /// The score the evaluator gave to the last contribution of the peer.
+    pub last_score: usize,
+    /// The highest score the evaluator gave to a contribution of the peer.
+    pub best_score: usize,
+}
+
+/// Diagnostics of a single aggregation.
+#[derive(Clone, Debug)]
+pub struct AggregationDiagnostics {
+    /// The ID of the aggregation, as given by the protocol.
+    pub id: String,
+    /// Our ID in the aggregation.
+    pub node_id: usize,
+    pub started_at: SystemTime,
+    /// The level timeout of the configuration used.
+    pub level_timeout: Duration,
+    /// The update interval of the configuration used.
+    pub update_interval: Duration,
+    pub levels: Vec<LevelDiagnostics>,
+    pub peers: BTreeMap<usize, PeerDiagnostics>,
+    /// Contributions that failed verification.
+    pub invalid_contributions: usize,
+    /// The number of contributors of the best aggregate.
+    pub contributors: usize,
+    /// Time from the start of the aggregation until the aggregate contained all contributions.
+    pub completed_after: Option<Duration>,
+}
+
+/// Collects the diagnostics of the aggregations of one kind, e.g. of all skip block aggregations.
+pub struct HandelDiagnostics {
+    name: &'static str,
+    capacity: usize,
+    aggregations: Mutex<VecDeque<Arc<Mutex<AggregationDiagnostics>>>>,
+    #[cfg(feature = "metrics")]
+    metrics: HandelMetrics,
+}
+
+impl HandelDiagnostics {
+    /// The number of aggregations kept by default.
+    pub const DEFAULT_CAPACITY: usize = 16;
+
+    /// Creates diagnostics for the aggregations called `name`, keeping the most recent `capacity` of them.
+    pub fn new(name: &'static str, capacity: usize) -> Self {
+        Self {
+            name,
+            capacity,
+            aggregations: Mutex::new(VecDeque::with_capacity(capacity)),
+            #[cfg(feature = "metrics")]
+            metrics: HandelMetrics::default(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the diagnostics of the most recent aggregations, the most recent first.
+    pub fn recent(&self) -> Vec<AggregationDiagnostics> {
+        self.aggregations
+            .lock()
+            .iter()
+            .rev()
+            .map(|aggregation| aggregation.lock().clone())
+            .collect()
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> &HandelMetrics {
+        &self.metrics
+    }
+
+    pub(crate) fn start_aggregation(
+        self: &Arc<Self>,
+        id: String,
+        node_id: usize,
+        config: &Config,
+        levels: &[Level],
+    ) -> AggregationTracker {
+        let levels = levels
+            .iter()
+            .map(|level| LevelDiagnostics {
+                id: level.id,
+                peers: level.peer_ids.clone(),
+                // The first level is started right away.
+                started_after: level.state.read().send_started.then_some(Duration::ZERO),
+                started_on_timeout: false,
+                completed_after: None,
+                missing_peers: level.peer_ids.clone(),
+            })
+            .collect();
+        let aggregation = Arc::new(Mutex::new(AggregationDiagnostics {
+            id,
+            node_id,
+            started_at: SystemTime::now(),
+            level_timeout: config.timeout,
+            update_interval: config.update_interval,
+            levels,
+            peers: BTreeMap::new(),
+            invalid_contributions: 0,
+            contributors: 0,
+            completed_after: None,
+        }));
+
+        let mut aggregations = self.aggregations.lock();
+        if aggregations.len() >= self.capacity {
+            aggregations.pop_front();
+        }
+        if self.capacity > 0 {
+            aggregations.push_back(Arc::clone(&aggregation));
+        }
+        drop(aggregations);
+
+        #[cfg(feature = "metrics")]
+        self.metrics.note_aggregation_started();
+
+        AggregationTracker(Some(Arc::new(TrackerInner {
+            diagnostics: Arc::clone(self),
+            aggregation,
+            started: Instant::now(),
+        })))
+    }
+}
+
+struct TrackerInner {
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    diagnostics: Arc<HandelDiagnostics>,
+    aggregation: Arc<Mutex<AggregationDiagnostics>>,
+    started: Instant,
+}
+
+/// Records the diagnostics of a single aggregation. Does nothing if diagnostics are disabled.
+#[derive(Clone)]
+pub(crate) struct AggregationTracker(Option<Arc<TrackerInner>>);
+
+impl AggregationTracker {
+    pub fn disabled() -> Self {
+        Self(None)
+    }
+
+    /// Returns whether diagnostics are recorded, such that costly diagnostics are only computed if needed.
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn update<F: FnOnce(&mut AggregationDiagnostics, Duration)>(&self, f: F) {
+        if let Some(inner) = &self.0 {
+            f(&mut inner.aggregation.lock(), inner.started.elapsed());
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn metrics(&self) -> Option<&HandelMetrics> {
+        self.0.as_ref().map(|inner| inner.diagnostics.metrics())
+    }
+
+    pub fn level_started(&self, level: usize, on_timeout: bool) {
+        self.update(|aggregation, elapsed| {
+            if let Some(level) = aggregation.levels.get_mut(level) {
+                level.started_after.get_or_insert(elapsed);
+                level.started_on_timeout = on_timeout;
+            }
+        });
+
+        #[cfg(feature = "metrics")]
+        if on_timeout {
+            if let Some(metrics) = self.metrics() {
+                metrics.note_level_timeout(level);
+            }
+        }
+    }
+
+    pub fn level_completed(&self, level: usize) {
+        self.update(|aggregation, elapsed| {
+            if let Some(level_diagnostics) = aggregation.levels.get_mut(level) {
+                level_diagnostics.completed_after = Some(elapsed);
+                level_diagnostics.missing_peers.clear();
+
+                #[cfg(feature = "metrics")]
+                if let Some(metrics) = self.metrics() {
+                    metrics.note_level_completed(level, elapsed);
+                }
+            }
+        });
+    }
+
+    /// Records the peers on `level` that are not part of the best aggregate of the level.
+    pub fn missing_peers(&self, level: usize, missing_peers: Vec<usize>) {
+        self.update(|aggregation, _| {
+            if let Some(level) = aggregation.levels.get_mut(level) {
+                level.missing_peers = missing_peers;
+            }
+        });
+    }
+
+    pub fn level_update(&self, origin: usize, on_level: bool, with_individual: bool) {
+        self.update(|aggregation, _| {
+            let peer = aggregation.peers.entry(origin).or_default();
+            if on_level {
+                peer.level_updates += 1;
+                peer.individual_contributions += with_individual as usize;
+            } else {
+                peer.wrong_level_updates += 1;
+            }
+        });
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.metrics() {
+            metrics.note_level_update(on_level);
+        }
+    }
+
+    /// Records the score the evaluator gave to a contribution of `origin`.
+    pub fn scored(&self, origin: usize, score: usize) {
+        self.update(|aggregation, _| {
+            let peer = aggregation.peers.entry(origin).or_default();
+            peer.last_score = score;
+            peer.best_score = peer.best_score.max(score);
+            peer.useless_contributions += (score == 0) as usize;
+        });
+    }
+
+    pub fn invalid_contribution(&self) {
+        self.update(|aggregation, _| aggregation.invalid_contributions += 1);
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.metrics() {
+            metrics.note_invalid_contribution();
+        }
+    }
+
+    /// Records a new best aggregate with `contributors` contributors, which is `complete` if it contains all of them.
+    pub fn aggregate(&self, contributors: usize, complete: bool) {
+        self.update(|aggregation, elapsed| {
+            aggregation.contributors = contributors;
+            if complete && aggregation.completed_after.is_none() {
+                aggregation.completed_after = Some(elapsed);
+
+                #[cfg(feature = "metrics")]
+                if let Some(metrics) = self.metrics() {
+                    metrics.note_aggregation_completed(elapsed);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use prometheus_client::{
+    encoding::EncodeLabelSet,
+    metrics::{counter::Counter, family::Family, histogram::Histogram},
+    registry::Registry,
+};
+
+pub struct HandelMetrics {
+    aggregations_started: Counter,
+    aggregations_completed: Counter,
+    aggregation_completion_times: Histogram,
+    level_completion_times: Family<LevelLabels, Histogram, fn() -> Histogram>,
+    level_timeouts: Family<LevelLabels, Counter>,
+    level_updates: Family<LevelUpdateLabels, Counter>,
+    invalid_contributions: Counter,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct LevelLabels {
+    level: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct LevelUpdateLabels {
+    on_level: String,
+}
+
+fn completion_time_histogram() -> Histogram {
+    Histogram::new([0.05, 0.1, 0.2, 0.4, 0.8, 1.6, 3.2, 6.4].into_iter())
+}
+
+impl Default for HandelMetrics {
+    fn default() -> Self {
+        Self {
+            aggregations_started: Counter::default(),
+            aggregations_completed: Counter::default(),
+            aggregation_completion_times: completion_time_histogram(),
+            level_completion_times: Family::new_with_constructor(completion_time_histogram),
+            level_timeouts: Family::default(),
+            level_updates: Family::default(),
+            invalid_contributions: Counter::default(),
+        }
+    }
+}
+
+impl HandelMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "aggregations_started",
+            "Count of aggregations started",
+            self.aggregations_started.clone(),
+        );
+
+        registry.register(
+            "aggregations_completed",
+            "Count of aggregations that received all contributions",
+            self.aggregations_completed.clone(),
+        );
+
+        registry.register(
+            "aggregation_completion_times",
+            "Time in seconds until an aggregation received all contributions",
+            self.aggregation_completion_times.clone(),
+        );
+
+        registry.register(
+            "level_completion_times",
+            "Time in seconds from the start of an aggregation until a level received all contributions",
+            self.level_completion_times.clone(),
+        );
+
+        registry.register(
+            "level_timeouts",
+            "Count of levels started because the level timeout elapsed",
+            self.level_timeouts.clone(),
+        );
+
+        registry.register(
+            "level_updates",
+            "Count of level updates received, by whether the sender is on the level",
+            self.level_updates.clone(),
+        );
+
+        registry.register(
+            "invalid_contributions",
+            "Count of contributions that failed verification",
+            self.invalid_contributions.clone(),
+        );
+    }
+
+    #[inline]
+    pub(crate) fn note_aggregation_started(&self) {
+        self.aggregations_started.inc();
+    }
+
+    #[inline]
+    pub(crate) fn note_aggregation_completed(&self, completed_after: Duration) {
+        self.aggregations_completed.inc();
+        self.aggregation_completion_times
+            .observe(completed_after.as_secs_f64());
+    }
+
+    #[inline]
+    pub(crate) fn note_level_completed(&self, level: usize, completed_after: Duration) {
+        self.level_completion_times
+            .get_or_create(&LevelLabels {
+                level: level.to_string(),
+            })
+            .observe(completed_after.as_secs_f64());
+    }
+
+    #[inline]
+    pub(crate) fn note_level_timeout(&self, level: usize) {
+        self.level_timeouts
+            .get_or_create(&LevelLabels {
+                level: level.to_string(),
+            })
+            .inc();
+    }
+
+    #[inline]
+    pub(crate) fn note_level_update(&self, on_level: bool) {
+        self.level_updates
+            .get_or_create(&LevelUpdateLabels {
+                on_level: on_level.to_string(),
+            })
+            .inc();
+    }
+
+    #[inline]
+    pub(crate) fn note_invalid_contribution(&self) {
+        self.invalid_contributions.inc();
+    }
+}
@@ -40,6 +40,11 @@ impl Identity {
         }
     }
 
+    /// Returns the signers of this identity.
+    pub fn signers(&self) -> &BitSet {
+        &self.signers
+    }
+
     /// Returns the identity as a vector of signers.
     pub fn as_vec(&self) -> Vec<usize> {
         self.signers.iter().collect()
@@ -12,7 +12,10 @@ extern crate log;
 pub mod aggregation;
 pub mod config;
 pub mod contribution;
+pub mod diagnostics;
 pub mod evaluator;
+#[cfg(feature = "metrics")]
+pub mod handel_metrics;
 pub mod identity;
 pub mod level;
 pub mod network;
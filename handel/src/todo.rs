@@ -8,8 +8,8 @@ use futures::{stream::BoxStream, Stream, StreamExt};
 use nimiq_macros::store_waker;
 
 use crate::{
-    contribution::AggregatableContribution, evaluator::Evaluator, protocol::Protocol,
-    update::LevelUpdate,
+    contribution::AggregatableContribution, diagnostics::AggregationTracker, evaluator::Evaluator,
+    protocol::Protocol, update::LevelUpdate,
 };
 
 /// A TodoItem represents a contribution which has not yet been aggregated into the store.
@@ -82,6 +82,8 @@ where
     input_stream: BoxStream<'static, LevelUpdate<TProtocol::Contribution>>,
     /// Waker used for the poll next function
     waker: Option<Waker>,
+    /// Records the contributions of the peers for the diagnostics of the aggregation
+    tracker: AggregationTracker,
 }
 
 impl<TId, TProtocol> TodoList<TId, TProtocol>
@@ -92,10 +94,12 @@ where
     /// Create a new TodoList
     /// * `evaluator` - The evaluator which will be used for TodoItem scoring
     /// * `input_stream` - The stream on which new LevelUpdates can be polled, which will then be converted into TodoItems
+    /// * `tracker` - The tracker recording the contributions of the peers
     pub fn new(
         id: TId,
         evaluator: Arc<TProtocol::Evaluator>,
         input_stream: BoxStream<'static, LevelUpdate<TProtocol::Contribution>>,
+        tracker: AggregationTracker,
     ) -> Self {
         Self {
             id,
@@ -103,6 +107,7 @@ where
             evaluator,
             input_stream,
             waker: None,
+            tracker,
         }
     }
 
@@ -186,10 +191,11 @@ where
             // TODO more robust handling of this case, as the aggregation might not be able to finish here (depending on what todos are left).
 
             // A new LevelUpdate is available when the msg is Some:
-            if self
-                .evaluator
-                .level_contains_id(msg.level as usize, msg.origin as usize)
-            {
+            let origin = msg.origin();
+            let on_level = self.evaluator.level_contains_id(msg.level(), origin);
+            self.tracker
+                .level_update(origin, on_level, msg.individual.is_some());
+            if on_level {
                 // Every LevelUpdate contains an aggregate which can be turned into a TodoItem
                 let aggregate_todo = TodoItem {
                     contribution: msg.aggregate,
@@ -198,6 +204,7 @@ where
                 // Score the newly created TodoItem for the aggregate of the LevelUpdate
                 let score = aggregate_todo
                     .evaluate::<TId, TProtocol>(Arc::clone(&self.evaluator), id.clone());
+                self.tracker.scored(origin, score);
                 // TodoItems with a score of 0 are discarded (meaning not added to the set of TodoItems).
                 if score > 0 {
                     trace!(
@@ -229,6 +236,7 @@ where
                     // Score the newly created TodoItem for the individual contribution of the LevelUpdate.
                     let score = individual_todo
                         .evaluate::<TId, TProtocol>(Arc::clone(&self.evaluator), self.id.clone());
+                    self.tracker.scored(origin, score);
                     // TodoItems with a score of 0 are discarded (meaning not added to the set of TodoItems).
                     if score > 0 {
                         if score > best_score {
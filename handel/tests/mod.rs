@@ -11,6 +11,7 @@ use nimiq_handel::{
     aggregation::Aggregation,
     config::Config,
     contribution::{AggregatableContribution, ContributionError},
+    diagnostics::HandelDiagnostics,
     evaluator::WeightedVote,
     identity::{Identity, IdentityRegistry, WeightRegistry},
     network::Network,
@@ -374,6 +375,117 @@ async fn it_can_aggregate() {
     *stopped.write() = true;
 }
 
+#[test(tokio::test)]
+async fn it_records_diagnostics() {
+    let config = Config {
+        update_count: 4,
+        update_interval: Duration::from_millis(500),
+        timeout: Duration::from_millis(500),
+        peer_count: 1,
+    };
+
+    let stopped = Arc::new(RwLock::new(false));
+
+    let mut hub = MockHub::default();
+    let contributor_num: usize = 7;
+
+    let mut networks: Vec<Arc<MockNetwork>> = vec![];
+    // Spawn the aggregations of all other nodes, same as in `it_can_aggregate`.
+    for id in 0..contributor_num {
+        let net = Arc::new(hub.new_network_with_address(id as u64));
+        let protocol = Protocol::new(id, contributor_num + 1, contributor_num);
+        let mut contributors = BitSet::new();
+        contributors.insert(id);
+        let contribution = Contribution {
+            value: id as u64 + 1u64,
+            contributors,
+        };
+        for network in &networks {
+            net.dial_mock(network);
+        }
+        networks.push(net.clone());
+
+        let mut aggregation = Aggregation::new(
+            protocol,
+            config.clone(),
+            contribution,
+            Box::pin(
+                net.receive_messages::<Update<Contribution>>()
+                    .map(move |msg| msg.0 .0),
+            ),
+            NetworkWrapper(net),
+        );
+
+        let r = stopped.clone();
+        tokio::spawn(async move {
+            while let Some(_contribution) = aggregation.next().await {
+                if *r.read() {
+                    return;
+                }
+            }
+        });
+    }
+
+    let net = Arc::new(hub.new_network_with_address(contributor_num as u64));
+    let protocol = Protocol::new(contributor_num, contributor_num + 1, contributor_num + 1);
+    let mut contributors = BitSet::new();
+    contributors.insert(contributor_num);
+    let contribution = Contribution {
+        value: contributor_num as u64 + 1u64,
+        contributors,
+    };
+    for network in &networks {
+        net.dial_mock(network);
+    }
+
+    // Record the diagnostics of the aggregation of the last node.
+    let diagnostics = Arc::new(HandelDiagnostics::new("test", 1));
+    let mut aggregation = Aggregation::with_diagnostics(
+        protocol,
+        config.clone(),
+        contribution,
+        Box::pin(
+            net.receive_messages::<Update<Contribution>>()
+                .map(move |msg| msg.0 .0),
+        ),
+        NetworkWrapper(Arc::clone(&net)),
+        Some(Arc::clone(&diagnostics)),
+    );
+
+    let deadline = tokio::time::Instant::now()
+        .checked_add(tokio::time::Duration::from_millis(300))
+        .unwrap();
+
+    loop {
+        match tokio::time::timeout_at(deadline, aggregation.next()).await {
+            Ok(Some(aggregate)) => {
+                if aggregate.num_contributors() == contributor_num + 1 {
+                    break;
+                }
+            }
+            Ok(None) => panic!("Aggregate returned a None value, which should be unreachable!()"),
+            Err(_) => panic!("Aggregate took too long"),
+        }
+    }
+
+    // The diagnostics show the completed aggregation and the peers that contributed to it.
+    let recent = diagnostics.recent();
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].node_id, contributor_num);
+    assert_eq!(recent[0].level_timeout, config.timeout);
+    assert_eq!(recent[0].contributors, contributor_num + 1);
+    assert!(recent[0].completed_after.is_some());
+    assert!(!recent[0].peers.is_empty());
+    assert!(recent[0]
+        .levels
+        .iter()
+        .all(|level| level.completed_after.is_none() || level.missing_peers.is_empty()));
+
+    drop(aggregation);
+    net.disconnect();
+    *stopped.write() = true;
+}
+
 // additional tests:
 // it_sends_periodic_updates
 // it_activates_levels
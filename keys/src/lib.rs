@@ -47,6 +47,7 @@ macro_rules! implement_simple_add_sum_traits {
 }
 
 pub mod multisig;
+pub mod typed_message;
 
 mod address;
 mod errors;
@@ -264,6 +264,17 @@ impl PublicKey {
     }
 }
 
+/// Aggregates the public keys of the signers into the public key their combined signature verifies against. The
+/// result depends on the order of the public keys, which must be the same as when signing.
+pub fn aggregate_public_keys(public_keys: &[PublicKey]) -> PublicKey {
+    let public_keys_hash = hash_public_keys(public_keys);
+    let delinearized_pk_sum: EdwardsPoint = public_keys
+        .iter()
+        .map(|public_key| public_key.delinearize(&public_keys_hash))
+        .sum();
+    PublicKey::from(delinearized_pk_sum.compress().to_bytes())
+}
+
 pub fn hash_public_keys(public_keys: &[PublicKey]) -> [u8; 64] {
     // 1. Compute hash over public keys public_keys_hash = C = H(P_1 || ... || P_n).
     let mut h: sha2::Sha512 = sha2::Sha512::default();
@@ -0,0 +1,213 @@
+//! Typed structured messages.
+//!
+//! A typed message consists of a domain and a list of named, typed fields. Unlike a plain message, it can be shown
+//! to the user field by field before signing, and the signature is bound to the domain: the name and version of the
+//! application and the network ID. A signature over a typed message is thus never valid for another application,
+//! another network or a plain message, which prevents it from being replayed there.
+//!
+//! The signed data is the hash of `PREFIX || hash(domain) || hash(message)`, where the hash of a struct is the hash
+//! of its type string followed by its encoded fields. Strings are encoded as their hash, such that the encoding of
+//! every field has a fixed length.
+use std::fmt;
+
+use nimiq_hash::{Blake2bHash, Blake2bHasher, HashOutput, Hasher};
+#[cfg(feature = "serde-derive")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Address, KeyPair, PublicKey, Signature};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TypedMessageError {
+    /// Type and field names must be non-empty and consist of ASCII letters, digits and underscores only.
+    #[error("Invalid name: {0:?}")]
+    InvalidName(String),
+    /// A field name was given more than once.
+    #[error("Duplicate field: {0}")]
+    DuplicateField(String),
+}
+
+/// The domain a typed message is signed for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde-derive",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TypedMessageDomain {
+    /// The name of the application requesting the signature, e.g. `Nimiq Login`.
+    pub name: String,
+    /// The version of the application's message schema.
+    pub version: String,
+    /// The ID of the network the signature is valid on.
+    pub network_id: u8,
+}
+
+impl TypedMessageDomain {
+    const TYPE: &'static str = "NimiqTypedMessageDomain(string name,string version,u8 networkId)";
+
+    pub fn hash(&self) -> Blake2bHash {
+        let mut data = vec![];
+        data.extend_from_slice(hash_bytes(Self::TYPE.as_bytes()).as_bytes());
+        data.extend_from_slice(hash_bytes(self.name.as_bytes()).as_bytes());
+        data.extend_from_slice(hash_bytes(self.version.as_bytes()).as_bytes());
+        data.push(self.network_id);
+        hash_bytes(&data)
+    }
+}
+
+/// The value of a field of a typed message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde-derive",
+    derive(Serialize, Deserialize),
+    serde(tag = "type", content = "value", rename_all = "lowercase")
+)]
+pub enum TypedValue {
+    String(String),
+    U64(u64),
+    Bool(bool),
+    Address(Address),
+}
+
+impl TypedValue {
+    /// The name of the type, as used in the type string.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            TypedValue::String(_) => "string",
+            TypedValue::U64(_) => "u64",
+            TypedValue::Bool(_) => "bool",
+            TypedValue::Address(_) => "address",
+        }
+    }
+
+    fn encode(&self, data: &mut Vec<u8>) {
+        match self {
+            TypedValue::String(value) => {
+                data.extend_from_slice(hash_bytes(value.as_bytes()).as_bytes())
+            }
+            TypedValue::U64(value) => data.extend_from_slice(&value.to_be_bytes()),
+            TypedValue::Bool(value) => data.push(*value as u8),
+            TypedValue::Address(value) => data.extend_from_slice(value.as_bytes()),
+        }
+    }
+}
+
+impl fmt::Display for TypedValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypedValue::String(value) => write!(f, "{value:?}"),
+            TypedValue::U64(value) => write!(f, "{value}"),
+            TypedValue::Bool(value) => write!(f, "{value}"),
+            TypedValue::Address(value) => write!(f, "{}", value.to_user_friendly_address()),
+        }
+    }
+}
+
+/// A named field of a typed message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-derive", derive(Serialize, Deserialize))]
+pub struct TypedField {
+    pub name: String,
+    #[cfg_attr(feature = "serde-derive", serde(flatten))]
+    pub value: TypedValue,
+}
+
+/// A structured message to be signed for a domain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde-derive",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TypedMessage {
+    pub domain: TypedMessageDomain,
+    /// The name of the message type, e.g. `Login`.
+    pub message_type: String,
+    pub fields: Vec<TypedField>,
+}
+
+impl TypedMessage {
+    /// The prefix of the signed data, distinguishing it from transactions and plain messages.
+    pub const PREFIX: &'static [u8] = b"\x19Nimiq Typed Message:\n";
+
+    /// Checks that the type and field names are valid and that no field is given twice.
+    pub fn validate(&self) -> Result<(), TypedMessageError> {
+        check_name(&self.message_type)?;
+        for (i, field) in self.fields.iter().enumerate() {
+            check_name(&field.name)?;
+            if self.fields[..i]
+                .iter()
+                .any(|other| other.name == field.name)
+            {
+                return Err(TypedMessageError::DuplicateField(field.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the type string of the message, e.g. `Login(string origin,u64 nonce)`.
+    pub fn type_string(&self) -> String {
+        let fields: Vec<_> = self
+            .fields
+            .iter()
+            .map(|field| format!("{} {}", field.value.type_name(), field.name))
+            .collect();
+        format!("{}({})", self.message_type, fields.join(","))
+    }
+
+    /// Returns the hash of the message type and its fields, without the domain.
+    pub fn message_hash(&self) -> Blake2bHash {
+        let mut data = hash_bytes(self.type_string().as_bytes())
+            .as_bytes()
+            .to_vec();
+        for field in &self.fields {
+            field.value.encode(&mut data);
+        }
+        hash_bytes(&data)
+    }
+
+    /// Returns the data that is signed.
+    pub fn signing_hash(&self) -> Blake2bHash {
+        let mut data = Self::PREFIX.to_vec();
+        data.extend_from_slice(self.domain.hash().as_bytes());
+        data.extend_from_slice(self.message_hash().as_bytes());
+        hash_bytes(&data)
+    }
+
+    pub fn sign(&self, key_pair: &KeyPair) -> Result<Signature, TypedMessageError> {
+        self.validate()?;
+        Ok(key_pair.sign(self.signing_hash().as_bytes()))
+    }
+
+    /// Verifies the signature. Checking that the domain is the expected one is up to the caller.
+    pub fn verify(&self, public_key: &PublicKey, signature: &Signature) -> bool {
+        self.validate().is_ok() && public_key.verify(signature, self.signing_hash().as_bytes())
+    }
+}
+
+impl fmt::Display for TypedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} (version {}, network {})",
+            self.domain.name, self.domain.version, self.domain.network_id
+        )?;
+        write!(f, "{}", self.message_type)?;
+        for field in &self.fields {
+            write!(f, "\n  {}: {}", field.name, field.value)?;
+        }
+        Ok(())
+    }
+}
+
+fn hash_bytes(bytes: &[u8]) -> Blake2bHash {
+    Blake2bHasher::default().digest(bytes)
+}
+
+fn check_name(name: &str) -> Result<(), TypedMessageError> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(TypedMessageError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
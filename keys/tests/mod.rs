@@ -5,6 +5,7 @@ use nimiq_test_log::test;
 use nimiq_test_utils::test_rng::test_rng;
 
 mod multisig;
+mod typed_message;
 
 #[test]
 fn verify_created_signature() {
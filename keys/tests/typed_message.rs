@@ -0,0 +1,100 @@
+use nimiq_keys::{
+    typed_message::{TypedField, TypedMessage, TypedMessageDomain, TypedMessageError, TypedValue},
+    Address, KeyPair,
+};
+use nimiq_test_log::test;
+use nimiq_test_utils::test_rng::test_rng;
+
+fn login_message() -> TypedMessage {
+    TypedMessage {
+        domain: TypedMessageDomain {
+            name: "Nimiq Login".to_string(),
+            version: "1".to_string(),
+            network_id: 5,
+        },
+        message_type: "Login".to_string(),
+        fields: vec![
+            TypedField {
+                name: "origin".to_string(),
+                value: TypedValue::String("https://example.com".to_string()),
+            },
+            TypedField {
+                name: "nonce".to_string(),
+                value: TypedValue::U64(42),
+            },
+            TypedField {
+                name: "account".to_string(),
+                value: TypedValue::Address(Address::from([1u8; Address::SIZE])),
+            },
+            TypedField {
+                name: "remember".to_string(),
+                value: TypedValue::Bool(true),
+            },
+        ],
+    }
+}
+
+#[test]
+fn type_string_lists_fields() {
+    assert_eq!(
+        login_message().type_string(),
+        "Login(string origin,u64 nonce,address account,bool remember)"
+    );
+}
+
+#[test]
+fn signature_is_bound_to_domain_and_fields() {
+    let key_pair = KeyPair::generate(&mut test_rng(false));
+    let message = login_message();
+    let signature = message.sign(&key_pair).unwrap();
+    assert!(message.verify(&key_pair.public, &signature));
+
+    // The signature can't be replayed on another network or for another application.
+    let mut other = message.clone();
+    other.domain.network_id = 24;
+    assert!(!other.verify(&key_pair.public, &signature));
+    let mut other = message.clone();
+    other.domain.name = "Other App".to_string();
+    assert!(!other.verify(&key_pair.public, &signature));
+
+    // Changing a field or its type invalidates the signature.
+    let mut other = message.clone();
+    other.fields[1].value = TypedValue::U64(43);
+    assert!(!other.verify(&key_pair.public, &signature));
+    let mut other = message.clone();
+    other.fields[0].value = TypedValue::String("42".to_string());
+    other.fields[1].value = TypedValue::String("https://example.com".to_string());
+    assert!(!other.verify(&key_pair.public, &signature));
+
+    // The signature doesn't verify against another public key.
+    let other_key_pair = KeyPair::generate(&mut test_rng(false));
+    assert!(!message.verify(&other_key_pair.public, &signature));
+}
+
+#[test]
+fn rejects_invalid_names() {
+    let key_pair = KeyPair::generate(&mut test_rng(false));
+
+    let mut message = login_message();
+    message.message_type = "Login(string x)".to_string();
+    assert_eq!(
+        message.sign(&key_pair),
+        Err(TypedMessageError::InvalidName(
+            "Login(string x)".to_string()
+        ))
+    );
+
+    let mut message = login_message();
+    message.fields[2].name = "nonce".to_string();
+    assert_eq!(
+        message.sign(&key_pair),
+        Err(TypedMessageError::DuplicateField("nonce".to_string()))
+    );
+
+    let mut message = login_message();
+    message.fields[0].name = String::new();
+    assert_eq!(
+        message.validate(),
+        Err(TypedMessageError::InvalidName(String::new()))
+    );
+}
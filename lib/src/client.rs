@@ -1,6 +1,8 @@
-use std::{fs, sync::Arc};
+use std::{fs, path::PathBuf, sync::Arc};
 
 use nimiq_block::Block;
+#[cfg(feature = "validator")]
+use nimiq_block_production::signer::{LocalSigner, ValidatorSigner};
 #[cfg(feature = "full-consensus")]
 use nimiq_blockchain::{Blockchain, BlockchainConfig};
 use nimiq_blockchain_interface::AbstractBlockchain;
@@ -13,6 +15,8 @@ use nimiq_consensus::{
 #[cfg(feature = "zkp-prover")]
 use nimiq_genesis::NetworkId;
 use nimiq_genesis::NetworkInfo;
+#[cfg(feature = "validator")]
+use nimiq_keys::Address;
 use nimiq_light_blockchain::LightBlockchain;
 #[cfg(feature = "validator")]
 use nimiq_mempool::mempool::Mempool;
@@ -22,11 +26,15 @@ use nimiq_network_interface::{
     Multiaddr, Protocol,
 };
 use nimiq_network_libp2p::{
-    discovery::peer_contacts::PeerContact, Config as NetworkConfig, Network,
-    TlsConfig as NetworkTls,
+    discovery::peer_contacts::{PeerContact, SignedPeerContact},
+    Config as NetworkConfig, Network, TlsConfig as NetworkTls,
 };
 use nimiq_primitives::{policy::Policy, task_executor::TaskExecutor};
-use nimiq_utils::time::OffsetTime;
+use nimiq_utils::{file_store::FileStore, time::OffsetTime};
+#[cfg(feature = "validator")]
+use nimiq_validator::lease::{FileLease, ValidatorLease};
+#[cfg(all(feature = "validator", unix))]
+use nimiq_validator::remote_signer::RemoteSigner;
 #[cfg(feature = "validator")]
 use nimiq_validator::validator::Validator as AbstractValidator;
 #[cfg(feature = "validator")]
@@ -81,6 +89,9 @@ pub type ZKPComponentProxy = AbstractZKPComponentProxy<Network>;
 pub(crate) struct ClientInner {
     network: Arc<Network>,
 
+    /// File in which the peer contact book is persisted, if configured.
+    peer_contact_book_file: Option<PathBuf>,
+
     /// The consensus object, which maintains the blockchain, the network and other things to
     /// reach consensus.
     consensus: ConsensusProxy,
@@ -255,6 +266,14 @@ impl ClientInner {
             .map(|seed| seed.address)
             .collect();
 
+        let static_peers: Vec<Multiaddr> = config
+            .network
+            .static_peers
+            .clone()
+            .into_iter()
+            .map(|static_peer| static_peer.address)
+            .collect();
+
         let tls_config = if let Some(tls_config) = config.network.tls {
             // Check that the provided private key has the expected format and convert the PEM file to DER format.
             let private_key = fs::read(tls_config.private_key).and_then(|private_key_bytes| {
@@ -293,15 +312,25 @@ impl ClientInner {
         };
 
         // Setup libp2p network
-        let network_config = NetworkConfig::new(
+        let mut network_config = NetworkConfig::new(
             identity_keypair,
             peer_contact,
             seeds,
+            static_peers,
             network_info.genesis_hash().clone(),
-            false,
+            config.network.memory_transport,
             required_services,
             tls_config,
         );
+        network_config.gossipsub_topic_scores = config.network.gossipsub_topic_scores;
+        network_config.sentries = config
+            .network
+            .sentry_nodes
+            .into_iter()
+            .map(|sentry_node| sentry_node.address)
+            .collect();
+        network_config.protected_peers = config.network.protected_peers;
+        network_config.relay_server = config.network.relay_server;
 
         log::debug!(
             addresses = ?config.network.listen_addresses,
@@ -314,6 +343,20 @@ impl ClientInner {
         let network =
             Arc::new(Network::new(Arc::clone(&time), network_config, executor.clone()).await);
 
+        // Restore the peer contacts we knew about before the last shutdown.
+        let peer_contact_book_file = config.network.peer_contact_book_file.clone();
+        if let Some(path) = peer_contact_book_file.as_ref().filter(|path| path.exists()) {
+            match FileStore::new(path).load::<Vec<SignedPeerContact>>() {
+                Ok(contacts) => {
+                    let num_imported = network.import_peer_contacts(contacts);
+                    log::info!(num_imported, path = ?path.display(), "Imported peer contacts");
+                }
+                Err(error) => {
+                    log::warn!(%error, path = ?path.display(), "Failed to load peer contacts")
+                }
+            }
+        }
+
         // Start buffering network events as early as possible
         let network_events = network.subscribe_events();
 
@@ -503,31 +546,90 @@ impl ClientInner {
                     // Load validator address
                     let automatic_reactivate = validator_config.automatic_reactivate;
 
-                    // Load signing key (before we give away ownership of the storage config)
-                    let signing_key = config.storage.signing_keypair()?;
-
-                    // Load validator key (before we give away ownership of the storage config)
-                    let voting_key = config.storage.voting_keypair()?;
-
                     // Load fee key (before we give away ownership of the storage config)
                     let fee_key = config.storage.fee_keypair()?;
 
+                    // Load the keys to rotate to, if any (before we give away ownership of the storage config)
+                    let key_rotation = config.storage.key_rotation()?;
+                    if let Some(ref key_rotation) = key_rotation {
+                        if validator_config.remote_signer.is_some() {
+                            return Err(Error::config_error(
+                                "Key rotation is not supported with a remote signer",
+                            ));
+                        }
+                        if let Some(validator_key) = key_rotation.validator_key() {
+                            if Address::from(validator_key) != validator_address {
+                                return Err(Error::config_error(
+                                    "The validator address key doesn't match the validator address",
+                                ));
+                            }
+                        }
+                    }
+
+                    // Sign blocks and votes with the remote signer if configured, with the local keys otherwise. The
+                    // local signing and voting keys are neither loaded nor generated when using a remote signer.
+                    let signer: Arc<dyn ValidatorSigner> = match validator_config.remote_signer {
+                        #[cfg(unix)]
+                        Some(path) => {
+                            let signer = RemoteSigner::connect(&path).map_err(|error| {
+                                Error::config_error(format!(
+                                    "Failed to connect to remote signer at {}: {}",
+                                    path.display(),
+                                    error
+                                ))
+                            })?;
+                            Arc::new(signer)
+                        }
+                        #[cfg(not(unix))]
+                        Some(_) => {
+                            return Err(Error::config_error(
+                                "Remote signers are only supported on Unix platforms",
+                            ));
+                        }
+                        None => {
+                            // Load signing key (before we give away ownership of the storage config)
+                            let signing_key = config.storage.signing_keypair()?;
+
+                            // Load validator key (before we give away ownership of the storage config)
+                            let voting_key = config.storage.voting_keypair()?;
+
+                            Arc::new(LocalSigner::new(signing_key, voting_key))
+                        }
+                    };
+
+                    // Run as a hot standby that only becomes active while holding the lease, if configured.
+                    let lease = match validator_config.lease_file {
+                        Some(path) => {
+                            let holder = validator_config
+                                .lease_holder
+                                .unwrap_or_else(|| network.local_peer_id().to_string());
+                            let lease = FileLease::new(path, holder)
+                                .map_err(|error| Error::config_error(error.to_string()))?;
+                            Some(Arc::new(lease) as Arc<dyn ValidatorLease>)
+                        }
+                        None => None,
+                    };
+
                     let validator_network =
                         Arc::new(ValidatorNetworkImpl::new(Arc::clone(&network)));
 
-                    let validator = Validator::new(
+                    let mut validator = Validator::new(
                         environment.clone(),
                         &consensus,
                         Arc::clone(blockchain),
                         validator_network,
                         validator_address,
                         automatic_reactivate,
-                        signing_key,
-                        voting_key,
                         fee_key,
+                        signer,
+                        lease,
                         config.mempool,
                     );
 
+                    if let Some(key_rotation) = key_rotation {
+                        validator.set_key_rotation(key_rotation);
+                    }
+
                     // Use the validator's mempool as TransactionVerificationCache in the blockchain.
                     blockchain.write().tx_verification_cache =
                         Arc::<Mempool>::clone(&validator.mempool);
@@ -548,6 +650,7 @@ impl ClientInner {
         Ok(Client {
             inner: Arc::new(ClientInner {
                 network,
+                peer_contact_book_file,
                 consensus: consensus.proxy(),
                 blockchain: blockchain_proxy,
                 #[cfg(feature = "validator")]
@@ -612,6 +715,15 @@ impl Client {
         Arc::clone(&self.inner.network)
     }
 
+    /// Persists the peer contact book to the configured file. Does nothing if no file is
+    /// configured.
+    pub fn store_peer_contacts(&self) -> Result<(), Error> {
+        if let Some(path) = &self.inner.peer_contact_book_file {
+            FileStore::new(path).store(&self.inner.network.export_peer_contacts())?;
+        }
+        Ok(())
+    }
+
     /// Returns a reference to the blockchain
     pub fn blockchain(&self) -> BlockchainProxy {
         self.inner.blockchain.clone()
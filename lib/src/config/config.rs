@@ -3,12 +3,15 @@ use std::net::IpAddr;
 #[cfg(feature = "metrics-server")]
 use std::net::SocketAddr;
 use std::{
+    collections::HashMap,
     fmt::Debug,
     path::{Path, PathBuf},
+    str::FromStr,
     string::ToString,
 };
 
 use derive_builder::Builder;
+use nimiq_block::{BlockHeaderTopic, BlockTopic};
 #[cfg(feature = "validator")]
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
 #[cfg(feature = "database-storage")]
@@ -17,14 +20,25 @@ use nimiq_database::{mdbx::MdbxDatabase, volatile::VolatileDatabase, DatabasePro
 use nimiq_keys::{Address, KeyPair, PrivateKey};
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::{config::MempoolConfig, filter::MempoolRules};
-use nimiq_network_interface::Multiaddr;
-use nimiq_network_libp2p::{Keypair as IdentityKeypair, Libp2pKeyPair};
+use nimiq_network_interface::network::Topic;
+use nimiq_network_interface::{Multiaddr, Protocol};
+use nimiq_network_libp2p::{
+    libp2p::{gossipsub::TopicScoreParams, PeerId},
+    Keypair as IdentityKeypair, Libp2pKeyPair,
+};
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_serde::Deserialize;
+use nimiq_transaction::{ControlTransactionTopic, TransactionTopic};
 use nimiq_utils::file_store::FileStore;
 #[cfg(feature = "validator")]
 use nimiq_utils::key_rng::SecureGenerate;
+#[cfg(feature = "validator")]
+use nimiq_validator::{
+    key_rotation::{KeyFiles, KeyRotation},
+    lease::FileLease,
+};
 use nimiq_zkp_circuits::DEFAULT_KEYS_PATH;
+use nimiq_zkp_component::types::ZKProofTopic;
 use strum_macros::Display;
 
 #[cfg(feature = "database-storage")]
@@ -36,7 +50,7 @@ use crate::config::consts::default_bind;
 use crate::{
     config::{
         command_line::CommandLine,
-        config_file::{ConfigFile, Seed, TlsSettings},
+        config_file::{ConfigFile, Seed, SentryNode, StaticPeer, TlsSettings},
         paths,
         user_agent::UserAgent,
     },
@@ -115,9 +129,39 @@ pub struct NetworkConfig {
     #[builder(default)]
     pub seeds: Vec<Seed>,
 
+    /// List of peers we always want to be connected to
+    #[builder(default)]
+    pub static_peers: Vec<StaticPeer>,
+
+    /// Sentry nodes this node hides behind. If set, this node only connects to its sentries and is only
+    /// reachable through them.
+    #[builder(default)]
+    pub sentry_nodes: Vec<SentryNode>,
+
+    /// Peers this node acts as a sentry for
+    #[builder(default)]
+    pub protected_peers: Vec<PeerId>,
+
+    /// Whether this node relays connections for peers that are not publicly reachable
+    #[builder(default)]
+    pub relay_server: bool,
+
+    /// Optional file in which the peer contact book is persisted between restarts
+    #[builder(default)]
+    pub peer_contact_book_file: Option<PathBuf>,
+
     /// Optional TLS configuration for secure WebSocket
     #[builder(default)]
     pub tls: Option<TlsConfig>,
+
+    /// Gossipsub peer scoring parameters per topic name. Topics that are not listed use the default parameters.
+    #[builder(default)]
+    pub gossipsub_topic_scores: HashMap<String, TopicScoreParams>,
+
+    /// Whether the node also accepts in-process `/memory/<port>` addresses. Only useful to run several nodes in a
+    /// single process, e.g. for a local devnet.
+    #[builder(default)]
+    pub memory_transport: bool,
 }
 
 /// Configuration for setting TLS for secure WebSocket
@@ -175,6 +219,18 @@ pub struct FileStorageConfig {
     /// The fee key used for the validator, if the file is not present.
     #[cfg(feature = "validator")]
     pub fee_key: Option<String>,
+
+    /// The signing key the validator rotates to.
+    #[cfg(feature = "validator")]
+    pub next_signing_key: Option<String>,
+
+    /// The voting key the validator rotates to.
+    #[cfg(feature = "validator")]
+    pub next_voting_key: Option<String>,
+
+    /// The key of the validator address, authorizing the transaction that registers the next keys.
+    #[cfg(feature = "validator")]
+    pub validator_address_key: Option<String>,
 }
 
 impl FileStorageConfig {
@@ -198,6 +254,12 @@ impl FileStorageConfig {
             signing_key_path: Some(path.join("signing_key.dat")),
             #[cfg(feature = "validator")]
             signing_key: None,
+            #[cfg(feature = "validator")]
+            next_signing_key: None,
+            #[cfg(feature = "validator")]
+            next_voting_key: None,
+            #[cfg(feature = "validator")]
+            validator_address_key: None,
         }
     }
 
@@ -235,7 +297,19 @@ impl Debug for FileStorageConfig {
                 .field("signing_key_path", &self.signing_key_path)
                 .field("signing_key", &self.signing_key.as_ref().map(|_| "***"))
                 .field("fee_key_path", &self.fee_key_path)
-                .field("fee_key", &self.fee_key.as_ref().map(|_| "***"));
+                .field("fee_key", &self.fee_key.as_ref().map(|_| "***"))
+                .field(
+                    "next_signing_key",
+                    &self.next_signing_key.as_ref().map(|_| "***"),
+                )
+                .field(
+                    "next_voting_key",
+                    &self.next_voting_key.as_ref().map(|_| "***"),
+                )
+                .field(
+                    "validator_address_key",
+                    &self.validator_address_key.as_ref().map(|_| "***"),
+                );
         }
         debug_struct.finish()
     }
@@ -250,8 +324,8 @@ pub struct DatabaseConfig {
     #[builder(default = "1024 * 1024 * 1024 * 1024")]
     size: usize,
 
-    /// Max number of DBs. Recommended: 20
-    #[builder(default = "20")]
+    /// Max number of DBs. Recommended: 24
+    #[builder(default = "24")]
     max_dbs: u32,
 
     /// Max number of threads that can open read transactions.
@@ -267,7 +341,7 @@ impl Default for DatabaseConfig {
         Self {
             // 1 TB
             size: 1024 * 1024 * 1024 * 1024,
-            max_dbs: 20,
+            max_dbs: 24,
             max_readers: 600,
         }
     }
@@ -456,6 +530,64 @@ impl StorageConfig {
         })
     }
 
+    /// Returns the keys the validator rotates to, if configured.
+    #[cfg(feature = "validator")]
+    pub(crate) fn key_rotation(&self) -> Result<Option<KeyRotation>, Error> {
+        let file_storage = match self {
+            StorageConfig::Volatile => return Ok(None),
+            StorageConfig::Filesystem(file_storage) => file_storage,
+        };
+
+        let (signing_key, voting_key) = match (
+            file_storage.next_signing_key.as_ref(),
+            file_storage.next_voting_key.as_ref(),
+        ) {
+            (Some(signing_key), Some(voting_key)) => (signing_key, voting_key),
+            (None, None) => return Ok(None),
+            _ => {
+                return Err(Error::config_error(
+                    "Both the next signing key and the next voting key must be specified",
+                ))
+            }
+        };
+        let invalid_key = |name: &str| Error::config_error(format!("Invalid {name}"));
+
+        let signing_key = hex::decode(signing_key)
+            .ok()
+            .and_then(|bytes| PrivateKey::deserialize_from_vec(&bytes).ok())
+            .map(KeyPair::from)
+            .ok_or_else(|| invalid_key("next signing key"))?;
+        let voting_key = hex::decode(voting_key)
+            .ok()
+            .and_then(|bytes| BlsSecretKey::deserialize_from_vec(&bytes).ok())
+            .map(BlsKeyPair::from)
+            .ok_or_else(|| invalid_key("next voting key"))?;
+        let validator_key = file_storage
+            .validator_address_key
+            .as_ref()
+            .map(|key| {
+                hex::decode(key)
+                    .ok()
+                    .and_then(|bytes| PrivateKey::deserialize_from_vec(&bytes).ok())
+                    .map(KeyPair::from)
+                    .ok_or_else(|| invalid_key("validator address key"))
+            })
+            .transpose()?;
+
+        let mut key_rotation = KeyRotation::new(signing_key, voting_key, validator_key);
+        if let (Some(signing_key_path), Some(voting_key_path)) = (
+            file_storage.signing_key_path.clone(),
+            file_storage.voting_key_path.clone(),
+        ) {
+            key_rotation = key_rotation.with_key_files(KeyFiles {
+                signing_key_path,
+                voting_key_path,
+            });
+        }
+
+        Ok(Some(key_rotation))
+    }
+
     pub(crate) fn identity_keypair(&self) -> Result<IdentityKeypair, Error> {
         match self {
             StorageConfig::Volatile => Ok(IdentityKeypair::generate_ed25519()),
@@ -495,6 +627,17 @@ pub struct ValidatorConfig {
 
     /// Config if the validator automatically reactivates itself.
     pub automatic_reactivate: bool,
+
+    /// Path to the Unix domain socket of an external signer process. If set, blocks, votes and staking
+    /// transactions are signed by the signer process and no signing or voting keys are loaded locally.
+    pub remote_signer: Option<PathBuf>,
+
+    /// Path to a lease file shared with hot standbys of this validator. If set, the validator only produces blocks
+    /// and votes while it holds the lease and otherwise stays synced as a standby.
+    pub lease_file: Option<PathBuf>,
+
+    /// Identifies this node in the lease file. Defaults to the peer ID.
+    pub lease_holder: Option<String>,
 }
 
 /// Credentials for JSON RPC server, metrics server or websocket RPC server
@@ -731,6 +874,64 @@ impl ClientConfigBuilder {
 
     /// Applies settings from a configuration file
     pub fn config_file(&mut self, config_file: &ConfigFile) -> Result<&mut Self, Error> {
+        // Static peers and sentries are dialed by peer ID, so their addresses must contain one.
+        for static_peer in &config_file.network.static_peers {
+            if !matches!(static_peer.address.iter().last(), Some(Protocol::P2p(_))) {
+                return Err(Error::config_error(format!(
+                    "Static peer address must end with the peer ID (/p2p/<peer_id>): {}",
+                    static_peer.address
+                )));
+            }
+        }
+        for sentry_node in &config_file.network.sentry_nodes {
+            if !matches!(sentry_node.address.iter().last(), Some(Protocol::P2p(_))) {
+                return Err(Error::config_error(format!(
+                    "Sentry node address must end with the peer ID (/p2p/<peer_id>): {}",
+                    sentry_node.address
+                )));
+            }
+        }
+
+        let protected_peers = config_file
+            .network
+            .protected_peers
+            .iter()
+            .map(|peer_id| {
+                PeerId::from_str(peer_id).map_err(|error| {
+                    Error::config_error(format!("Invalid protected peer ID {peer_id}: {error}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Gossipsub peer scoring per topic
+        let gossipsub = &config_file.network.gossipsub;
+        let mut gossipsub_topic_scores = HashMap::new();
+        for (topic_names, settings) in [
+            (
+                &[BlockTopic::NAME, BlockHeaderTopic::NAME][..],
+                &gossipsub.blocks,
+            ),
+            (&[TransactionTopic::NAME][..], &gossipsub.transactions),
+            (
+                &[ControlTransactionTopic::NAME][..],
+                &gossipsub.control_transactions,
+            ),
+            (&[ZKProofTopic::NAME][..], &gossipsub.zkp),
+        ] {
+            if let Some(settings) = settings {
+                let params = TopicScoreParams::from(settings);
+                params.validate().map_err(|error| {
+                    Error::config_error(format!(
+                        "Invalid gossipsub scoring parameters for topic {}: {}",
+                        topic_names[0], error
+                    ))
+                })?;
+                for topic_name in topic_names {
+                    gossipsub_topic_scores.insert(topic_name.to_string(), params.clone());
+                }
+            }
+        }
+
         // TODO: if the config field of `listen_addresses` is empty, we should at least add `/ip4/127.0.0.1/...`
         self.network(NetworkConfig {
             listen_addresses: config_file
@@ -762,7 +963,25 @@ impl ClientConfigBuilder {
 
             seeds: config_file.network.seed_nodes.clone(),
 
+            static_peers: config_file.network.static_peers.clone(),
+
+            sentry_nodes: config_file.network.sentry_nodes.clone(),
+
+            protected_peers,
+
+            relay_server: config_file.network.relay_server,
+
+            peer_contact_book_file: config_file
+                .network
+                .peer_contact_book_file
+                .as_ref()
+                .map(PathBuf::from),
+
             tls: config_file.network.tls.as_ref().map(|s| s.clone().into()),
+
+            gossipsub_topic_scores,
+
+            memory_transport: config_file.network.memory_transport,
         });
 
         // Configure consensus
@@ -793,9 +1012,19 @@ impl ClientConfigBuilder {
         }
         #[cfg(feature = "validator")]
         if let Some(validator_config) = config_file.validator.as_ref() {
+            if let Some(holder) = &validator_config.lease_holder {
+                if !FileLease::is_valid_holder(holder) {
+                    return Err(Error::config_error(format!(
+                        "Invalid lease holder {holder:?}: must be non-empty and must not contain whitespace"
+                    )));
+                }
+            }
             self.validator(ValidatorConfig {
                 validator_address: Address::from_any_str(&validator_config.validator_address)?,
                 automatic_reactivate: validator_config.automatic_reactivate,
+                remote_signer: validator_config.remote_signer.as_ref().map(PathBuf::from),
+                lease_file: validator_config.lease_file.as_ref().map(PathBuf::from),
+                lease_holder: validator_config.lease_holder.clone(),
             });
 
             if let Some(key_path) = &validator_config.voting_key_file {
@@ -816,6 +1045,9 @@ impl ClientConfigBuilder {
             if let Some(key) = &validator_config.signing_key {
                 file_storage.signing_key = Some(key.to_owned());
             }
+            file_storage.next_signing_key = validator_config.next_signing_key.clone();
+            file_storage.next_voting_key = validator_config.next_voting_key.clone();
+            file_storage.validator_address_key = validator_config.validator_address_key.clone();
         }
         self.storage = Some(file_storage.into());
 
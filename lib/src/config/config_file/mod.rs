@@ -1,4 +1,6 @@
-use std::{collections::HashMap, fmt::Debug, fs::read_to_string, path::Path, str::FromStr};
+use std::{
+    collections::HashMap, fmt::Debug, fs::read_to_string, path::Path, str::FromStr, time::Duration,
+};
 
 use log::level_filters::LevelFilter;
 #[cfg(feature = "nimiq-mempool")]
@@ -8,6 +10,7 @@ use nimiq_mempool::{
     mempool::Mempool,
 };
 use nimiq_network_interface::Multiaddr;
+use nimiq_network_libp2p::libp2p::gossipsub::TopicScoreParams;
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_serde::Deserialize;
 use thiserror::Error;
@@ -133,10 +136,27 @@ pub struct NetworkSettings {
     #[serde(default)]
     pub seed_nodes: Vec<Seed>,
     #[serde(default)]
+    pub static_peers: Vec<StaticPeer>,
+    #[serde(default)]
+    pub sentry_nodes: Vec<SentryNode>,
+    #[serde(default)]
+    pub protected_peers: Vec<String>,
+    #[serde(default)]
+    pub relay_server: bool,
+    #[serde(default)]
     pub user_agent: Option<String>,
 
+    #[serde(default)]
+    pub peer_contact_book_file: Option<String>,
+
     pub tls: Option<TlsSettings>,
     pub instant_inbound: Option<bool>,
+
+    #[serde(default)]
+    pub gossipsub: GossipsubSettings,
+
+    #[serde(default)]
+    pub memory_transport: bool,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -144,6 +164,101 @@ pub struct Seed {
     pub address: Multiaddr,
 }
 
+/// A peer we always want to be connected to. The address must contain the peer ID (`/p2p/<peer_id>`).
+#[derive(Clone, Debug, Deserialize)]
+pub struct StaticPeer {
+    pub address: Multiaddr,
+}
+
+/// A sentry node that shields this node from the rest of the network. The address must contain the peer ID
+/// (`/p2p/<peer_id>`).
+#[derive(Clone, Debug, Deserialize)]
+pub struct SentryNode {
+    pub address: Multiaddr,
+}
+
+/// Gossipsub peer scoring parameters of the topics that are most relevant for spam resistance
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GossipsubSettings {
+    /// Scoring of the blocks topic. Also applies to the block headers topic used by light nodes.
+    pub blocks: Option<GossipsubTopicScoreSettings>,
+    /// Scoring of the transactions topic
+    pub transactions: Option<GossipsubTopicScoreSettings>,
+    /// Scoring of the control transactions topic
+    pub control_transactions: Option<GossipsubTopicScoreSettings>,
+    /// Scoring of the ZKP topic
+    pub zkp: Option<GossipsubTopicScoreSettings>,
+}
+
+/// Peer scoring parameters of a gossipsub topic. Parameters that are not set keep the gossipsub defaults.
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GossipsubTopicScoreSettings {
+    /// Weight of the topic in the overall peer score
+    pub topic_weight: Option<f64>,
+    /// Weight of the time a peer spent in the mesh of the topic
+    pub time_in_mesh_weight: Option<f64>,
+    /// Time in seconds after which the time in mesh counter is increased by one
+    pub time_in_mesh_quantum: Option<u64>,
+    /// Cap of the time in mesh counter
+    pub time_in_mesh_cap: Option<f64>,
+    /// Weight of the messages a peer delivered first
+    pub first_message_deliveries_weight: Option<f64>,
+    /// Decay of the first message deliveries counter
+    pub first_message_deliveries_decay: Option<f64>,
+    /// Cap of the first message deliveries counter
+    pub first_message_deliveries_cap: Option<f64>,
+    /// Weight of the mesh message deliveries deficit. Must be negative.
+    pub mesh_message_deliveries_weight: Option<f64>,
+    /// Number of messages a mesh peer is expected to deliver
+    pub mesh_message_deliveries_threshold: Option<f64>,
+    /// Weight of the invalid messages a peer delivered. Must be negative.
+    pub invalid_message_deliveries_weight: Option<f64>,
+    /// Decay of the invalid message deliveries counter
+    pub invalid_message_deliveries_decay: Option<f64>,
+}
+
+impl From<&GossipsubTopicScoreSettings> for TopicScoreParams {
+    fn from(settings: &GossipsubTopicScoreSettings) -> Self {
+        let mut params = TopicScoreParams::default();
+        if let Some(topic_weight) = settings.topic_weight {
+            params.topic_weight = topic_weight;
+        }
+        if let Some(time_in_mesh_weight) = settings.time_in_mesh_weight {
+            params.time_in_mesh_weight = time_in_mesh_weight;
+        }
+        if let Some(time_in_mesh_quantum) = settings.time_in_mesh_quantum {
+            params.time_in_mesh_quantum = Duration::from_secs(time_in_mesh_quantum);
+        }
+        if let Some(time_in_mesh_cap) = settings.time_in_mesh_cap {
+            params.time_in_mesh_cap = time_in_mesh_cap;
+        }
+        if let Some(weight) = settings.first_message_deliveries_weight {
+            params.first_message_deliveries_weight = weight;
+        }
+        if let Some(decay) = settings.first_message_deliveries_decay {
+            params.first_message_deliveries_decay = decay;
+        }
+        if let Some(cap) = settings.first_message_deliveries_cap {
+            params.first_message_deliveries_cap = cap;
+        }
+        if let Some(weight) = settings.mesh_message_deliveries_weight {
+            params.mesh_message_deliveries_weight = weight;
+        }
+        if let Some(threshold) = settings.mesh_message_deliveries_threshold {
+            params.mesh_message_deliveries_threshold = threshold;
+        }
+        if let Some(weight) = settings.invalid_message_deliveries_weight {
+            params.invalid_message_deliveries_weight = weight;
+        }
+        if let Some(decay) = settings.invalid_message_deliveries_decay {
+            params.invalid_message_deliveries_decay = decay;
+        }
+        params
+    }
+}
+
 /// Settings for configuring TLS for secure WebSocket
 #[derive(Clone, Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -445,6 +560,12 @@ pub struct ValidatorSettings {
     pub fee_key: Option<String>,
     #[serde(default)]
     pub automatic_reactivate: bool,
+    pub remote_signer: Option<String>,
+    pub lease_file: Option<String>,
+    pub lease_holder: Option<String>,
+    pub next_signing_key: Option<String>,
+    pub next_voting_key: Option<String>,
+    pub validator_address_key: Option<String>,
 }
 
 impl Debug for ValidatorSettings {
@@ -458,6 +579,21 @@ impl Debug for ValidatorSettings {
             .field("fee_key_file", &self.fee_key_file)
             .field("fee_key", &self.fee_key.as_ref().map(|_| "***"))
             .field("automatic_reactivate", &self.automatic_reactivate)
+            .field("remote_signer", &self.remote_signer)
+            .field("lease_file", &self.lease_file)
+            .field("lease_holder", &self.lease_holder)
+            .field(
+                "next_signing_key",
+                &self.next_signing_key.as_ref().map(|_| "***"),
+            )
+            .field(
+                "next_voting_key",
+                &self.next_voting_key.as_ref().map(|_| "***"),
+            )
+            .field(
+                "validator_address_key",
+                &self.validator_address_key.as_ref().map(|_| "***"),
+            )
             .finish()
     }
 }
@@ -7,22 +7,41 @@ use nimiq_mempool::mempool::Mempool;
 pub use nimiq_metrics_server::NimiqTaskMonitor;
 use nimiq_network_interface::network::Network;
 
+#[cfg(feature = "validator")]
+use crate::client::ValidatorProxy;
+
 pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
     blockchain_proxy: BlockchainProxy,
     #[cfg(feature = "nimiq-mempool")] mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    #[cfg(feature = "validator")] validator_proxy: Option<ValidatorProxy>,
     task_monitors: &[NimiqTaskMonitor],
 ) {
     #[cfg(not(feature = "nimiq-mempool"))]
     let mempool = None;
+    #[cfg(feature = "validator")]
+    let (handel_diagnostics, tendermint_events) =
+        validator_proxy.map_or((vec![], None), |validator_proxy| {
+            (
+                vec![
+                    validator_proxy.skip_block_diagnostics,
+                    validator_proxy.tendermint_diagnostics,
+                ],
+                Some(validator_proxy.tendermint_events),
+            )
+        });
+    #[cfg(not(feature = "validator"))]
+    let (handel_diagnostics, tendermint_events) = (vec![], None);
     nimiq_metrics_server::start_metrics_server(
         addr,
         blockchain_proxy,
         mempool,
         consensus_proxy,
         network,
+        &handel_diagnostics,
+        tendermint_events,
         task_monitors,
     );
 }
@@ -2,7 +2,7 @@ use std::{collections::HashSet, iter::FromIterator, sync::Arc};
 
 use nimiq_jsonrpc_core::Credentials;
 use nimiq_jsonrpc_server::{AllowListDispatcher, Config, ModularDispatcher, Server as _Server};
-use nimiq_rpc_server::dispatchers::*;
+use nimiq_rpc_server::{dispatchers::*, wallet_history::record_wallet_history};
 use nimiq_wallet::WalletStore;
 
 #[cfg(feature = "rpc-server")]
@@ -38,7 +38,13 @@ pub fn initialize_rpc_server(
 
     let mut dispatcher = ModularDispatcher::default();
 
-    let wallet_dispatcher = WalletDispatcher::new(wallet_store);
+    tokio::spawn(record_wallet_history(
+        Arc::clone(&wallet_store),
+        client.blockchain(),
+    ));
+
+    let wallet_dispatcher =
+        WalletDispatcher::new(wallet_store, client.blockchain(), client.mempool());
     let unlocked_wallets = Arc::clone(&wallet_dispatcher.unlocked_wallets);
 
     dispatcher.add(BlockchainDispatcher::new(client.blockchain()));
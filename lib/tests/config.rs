@@ -113,3 +113,106 @@ fn config_file_partial_db_entry() {
 
     assert_eq!(config.storage, db_config.into());
 }
+
+#[test]
+fn config_file_gossipsub_topic_scores() {
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [network.gossipsub.blocks]
+    topic_weight = 0.7
+    invalid_message_deliveries_weight = -50.0
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    config_builder.config_file(&config_file).unwrap();
+    let config = config_builder.build().unwrap();
+
+    // The block settings also apply to the block headers topic
+    for topic_name in ["blocks", "block-headers"] {
+        let params = &config.network.gossipsub_topic_scores[topic_name];
+        assert_eq!(params.topic_weight, 0.7);
+        assert_eq!(params.invalid_message_deliveries_weight, -50.0);
+    }
+    assert!(!config
+        .network
+        .gossipsub_topic_scores
+        .contains_key("transactions"));
+
+    // Positive weights for invalid messages are rejected
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [network.gossipsub.transactions]
+    invalid_message_deliveries_weight = 1.0
+    "#,
+    )
+    .unwrap();
+    assert!(ClientConfigBuilder::default()
+        .config_file(&config_file)
+        .is_err());
+}
+
+#[test]
+fn config_file_sentry_nodes() {
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [network]
+    sentry_nodes = [
+        { address = "/ip4/10.0.0.1/tcp/8443/ws/p2p/QmNnooDu7bfjPFoTZYxMNLWUQJyrVwtbZg5gBMjTezGAJN" }
+    ]
+    protected_peers = ["QmNnooDu7bfjPFoTZYxMNLWUQJyrVwtbZg5gBMjTezGAJN"]
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    config_builder.config_file(&config_file).unwrap();
+    let config = config_builder.build().unwrap();
+    assert_eq!(config.network.sentry_nodes.len(), 1);
+    assert_eq!(
+        config.network.protected_peers[0].to_string(),
+        "QmNnooDu7bfjPFoTZYxMNLWUQJyrVwtbZg5gBMjTezGAJN"
+    );
+
+    // Sentry node addresses must contain the peer ID
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [network]
+    sentry_nodes = [{ address = "/ip4/10.0.0.1/tcp/8443/ws" }]
+    "#,
+    )
+    .unwrap();
+    assert!(ClientConfigBuilder::default()
+        .config_file(&config_file)
+        .is_err());
+
+    // Invalid protected peer IDs are rejected
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [network]
+    protected_peers = ["not a peer id"]
+    "#,
+    )
+    .unwrap();
+    assert!(ClientConfigBuilder::default()
+        .config_file(&config_file)
+        .is_err());
+}
+
+#[cfg(feature = "validator")]
+#[test]
+fn config_file_invalid_lease_holder() {
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [validator]
+    validator_address = "NQ07 0000 0000 0000 0000 0000 0000 0000 0000"
+    lease_file = "validator.lease"
+    lease_holder = "node one"
+    "#,
+    )
+    .unwrap();
+    assert!(ClientConfigBuilder::default()
+        .config_file(&config_file)
+        .is_err());
+}
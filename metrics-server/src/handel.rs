@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use nimiq_handel::diagnostics::HandelDiagnostics;
+use prometheus_client::registry::Registry;
+
+pub struct AggregationMetrics {}
+
+impl AggregationMetrics {
+    pub fn register(registry: &mut Registry, diagnostics: &[Arc<HandelDiagnostics>]) {
+        let sub_registry = registry.sub_registry_with_prefix("handel");
+
+        for diagnostics in diagnostics {
+            diagnostics
+                .metrics()
+                .register(sub_registry.sub_registry_with_prefix(diagnostics.name()));
+        }
+    }
+}
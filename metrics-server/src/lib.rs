@@ -2,8 +2,10 @@ use std::{fmt::Debug, net::SocketAddr, sync::Arc};
 
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_consensus::ConsensusProxy;
+use nimiq_handel::diagnostics::HandelDiagnostics;
 use nimiq_mempool::mempool::Mempool;
 use nimiq_network_interface::network::Network;
+use nimiq_validator::tendermint_events::TendermintEvents;
 use parking_lot::RwLock;
 use prometheus_client::{
     encoding::{EncodeGaugeValue, EncodeMetric, MetricEncoder},
@@ -17,15 +19,18 @@ use tokio_metrics::TaskMonitor;
 #[cfg(tokio_unstable)]
 use crate::tokio_runtime::TokioRuntimeMetrics;
 use crate::{
-    chain::BlockMetrics, consensus::ConsensusMetrics, mempool::MempoolMetrics,
-    network::NetworkMetrics, server::metrics_server, tokio_task::TokioTaskMetrics,
+    chain::BlockMetrics, consensus::ConsensusMetrics, handel::AggregationMetrics,
+    mempool::MempoolMetrics, network::NetworkMetrics, server::metrics_server,
+    tendermint::TendermintMetrics, tokio_task::TokioTaskMetrics,
 };
 
 mod chain;
 mod consensus;
+mod handel;
 mod mempool;
 mod network;
 mod server;
+mod tendermint;
 #[cfg(tokio_unstable)]
 mod tokio_runtime;
 mod tokio_task;
@@ -84,6 +89,8 @@ pub fn start_metrics_server<TNetwork: Network>(
     mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    handel_diagnostics: &[Arc<HandelDiagnostics>],
+    tendermint_events: Option<Arc<TendermintEvents>>,
     task_monitors: &[NimiqTaskMonitor],
 ) {
     let mut registry = Registry::default();
@@ -92,11 +99,16 @@ pub fn start_metrics_server<TNetwork: Network>(
     BlockMetrics::register(nimiq_registry, blockchain_proxy);
     ConsensusMetrics::register(nimiq_registry, consensus_proxy);
     NetworkMetrics::register(nimiq_registry, network);
+    AggregationMetrics::register(nimiq_registry, handel_diagnostics);
 
     if let Some(mempool) = mempool {
         MempoolMetrics::register(nimiq_registry, mempool);
     }
 
+    if let Some(tendermint_events) = tendermint_events {
+        TendermintMetrics::register(nimiq_registry, tendermint_events);
+    }
+
     // Setup the task metrics
     let task_metrics = Arc::new(RwLock::new(TokioTaskMetrics::new()));
     task_metrics.write().register(
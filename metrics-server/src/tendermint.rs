@@ -0,0 +1,14 @@
+use std::sync::Arc;
+
+use nimiq_validator::tendermint_events::TendermintEvents;
+use prometheus_client::registry::Registry;
+
+pub struct TendermintMetrics {}
+
+impl TendermintMetrics {
+    pub fn register(registry: &mut Registry, events: Arc<TendermintEvents>) {
+        let sub_registry = registry.sub_registry_with_prefix("tendermint");
+
+        events.metrics().register(sub_registry);
+    }
+}
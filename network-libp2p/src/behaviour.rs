@@ -1,71 +1,72 @@
-use std::{iter, sync::Arc};
+use std::{iter, sync::Arc, time::Duration};
 
 use libp2p::{
-    core::either::EitherError,
+    autonat::{Behaviour as AutonatBehaviour, Event as AutonatEvent},
+    dcutr::behaviour::{Behaviour as DcutrBehaviour, Event as DcutrEvent},
     gossipsub::{
-        error::GossipsubHandlerError, Gossipsub, GossipsubEvent, MessageAuthenticity,
-        PeerScoreParams, PeerScoreThresholds,
+        Gossipsub, GossipsubEvent, MessageAuthenticity, PeerScoreParams, PeerScoreThresholds,
     },
     identify::{Behaviour as IdentifyBehaviour, Config as IdentifyConfig, Event as IdentifyEvent},
     kad::{store::MemoryStore, Kademlia, KademliaEvent},
-    ping::{
-        Behaviour as PingBehaviour, Config as PingConfig, Event as PingEvent,
-        Failure as PingFailure,
+    ping::{Behaviour as PingBehaviour, Config as PingConfig, Event as PingEvent},
+    relay::v2::{
+        client::{Client as RelayClientBehaviour, Event as RelayClientEvent},
+        relay::{Config as RelayConfig, Event as RelayEvent, Relay as RelayBehaviour},
     },
     request_response::{
         ProtocolSupport, RequestResponse, RequestResponseConfig,
         RequestResponseEvent as ReqResEvent,
     },
-    swarm::{ConnectionHandlerUpgrErr, NetworkBehaviour},
+    swarm::{
+        behaviour::toggle::Toggle, ConnectionHandler, IntoConnectionHandler, NetworkBehaviour,
+    },
     Multiaddr, PeerId,
 };
 use nimiq_utils::time::OffsetTime;
 use parking_lot::RwLock;
 
 use crate::{
-    connection_pool::{
-        behaviour::{ConnectionPoolBehaviour, ConnectionPoolEvent},
-        handler::ConnectionPoolHandlerError,
-    },
+    connection_pool::behaviour::{ConnectionPoolBehaviour, ConnectionPoolEvent},
     discovery::{
         behaviour::{DiscoveryBehaviour, DiscoveryEvent},
-        handler::DiscoveryHandlerError,
         peer_contacts::PeerContactBook,
     },
     dispatch::codecs::typed::{IncomingRequest, MessageCodec, OutgoingResponse, ReqResProtocol},
     Config,
 };
 
-pub type NimiqNetworkBehaviourError = EitherError<
-    EitherError<
-        EitherError<
-            EitherError<
-                EitherError<
-                    EitherError<std::io::Error, DiscoveryHandlerError>,
-                    GossipsubHandlerError,
-                >,
-                std::io::Error,
-            >,
-            PingFailure,
-        >,
-        ConnectionPoolHandlerError,
-    >,
-    ConnectionHandlerUpgrErr<std::io::Error>,
->;
+/// The error type of the connection handlers of all the behaviours that compose the `NimiqBehaviour`.
+pub type NimiqNetworkBehaviourError = <<<NimiqBehaviour as NetworkBehaviour>::ConnectionHandler as IntoConnectionHandler>::Handler as ConnectionHandler>::Error;
 
 pub type RequestResponseEvent = ReqResEvent<IncomingRequest, OutgoingResponse>;
 
 #[derive(Debug)]
 pub enum NimiqEvent {
+    Autonat(AutonatEvent),
+    Dcutr(DcutrEvent),
     Dht(KademliaEvent),
     Discovery(DiscoveryEvent),
     Gossip(GossipsubEvent),
     Identify(IdentifyEvent),
     Ping(PingEvent),
     Pool(ConnectionPoolEvent),
+    Relay(RelayEvent),
+    RelayClient(RelayClientEvent),
     RequestResponse(RequestResponseEvent),
 }
 
+impl From<AutonatEvent> for NimiqEvent {
+    fn from(event: AutonatEvent) -> Self {
+        Self::Autonat(event)
+    }
+}
+
+impl From<DcutrEvent> for NimiqEvent {
+    fn from(event: DcutrEvent) -> Self {
+        Self::Dcutr(event)
+    }
+}
+
 impl From<KademliaEvent> for NimiqEvent {
     fn from(event: KademliaEvent) -> Self {
         Self::Dht(event)
@@ -102,6 +103,18 @@ impl From<PingEvent> for NimiqEvent {
     }
 }
 
+impl From<RelayEvent> for NimiqEvent {
+    fn from(event: RelayEvent) -> Self {
+        Self::Relay(event)
+    }
+}
+
+impl From<RelayClientEvent> for NimiqEvent {
+    fn from(event: RelayClientEvent) -> Self {
+        Self::RelayClient(event)
+    }
+}
+
 impl From<RequestResponseEvent> for NimiqEvent {
     fn from(event: RequestResponseEvent) -> Self {
         Self::RequestResponse(event)
@@ -111,12 +124,16 @@ impl From<RequestResponseEvent> for NimiqEvent {
 #[derive(NetworkBehaviour)]
 #[behaviour(out_event = "NimiqEvent")]
 pub struct NimiqBehaviour {
+    pub autonat: AutonatBehaviour,
+    pub dcutr: Toggle<DcutrBehaviour>,
     pub dht: Kademlia<MemoryStore>,
     pub discovery: DiscoveryBehaviour,
     pub gossipsub: Gossipsub,
     pub identify: IdentifyBehaviour,
     pub ping: PingBehaviour,
     pub pool: ConnectionPoolBehaviour,
+    pub relay: Toggle<RelayBehaviour>,
+    pub relay_client: RelayClientBehaviour,
     pub request_response: RequestResponse<MessageCodec>,
 }
 
@@ -126,6 +143,7 @@ impl NimiqBehaviour {
         clock: Arc<OffsetTime>,
         contacts: Arc<RwLock<PeerContactBook>>,
         peer_score_params: PeerScoreParams,
+        relay_client: RelayClientBehaviour,
     ) -> Self {
         let public_key = config.keypair.public();
         let peer_id = public_key.to_peer_id();
@@ -134,9 +152,15 @@ impl NimiqBehaviour {
         let store = MemoryStore::new(peer_id);
         let dht = Kademlia::with_config(peer_id, store, config.kademlia);
 
-        // Discovery behaviour
+        // Discovery behaviour:
+        // - Behind sentries, the addresses other peers observe for us must not end up in our peer contact.
+        let behind_sentries = !config.sentries.is_empty();
+        let mut discovery_config = config.discovery.clone();
+        if behind_sentries {
+            discovery_config.add_observed_addresses = false;
+        }
         let discovery = DiscoveryBehaviour::new(
-            config.discovery.clone(),
+            discovery_config,
             config.keypair.clone(),
             Arc::clone(&contacts),
             clock,
@@ -164,9 +188,35 @@ impl NimiqBehaviour {
             Arc::clone(&contacts),
             peer_id,
             config.seeds,
+            config.static_peers,
+            config.sentries,
+            config.protected_peers.clone(),
             config.discovery.required_services,
         );
 
+        // AutoNAT behaviour:
+        // - Other peers are asked to dial us back on our addresses to find out if we are reachable.
+        let autonat = AutonatBehaviour::new(peer_id, config.autonat);
+
+        // Relay behaviour:
+        // - Publicly reachable peers relay connections for peers behind a NAT if configured to.
+        // - Sentries relay all connections to the peers they protect, thus their circuits must not be
+        //   limited to the short duration and small amount of data that suffices for hole punching.
+        let mut relay_config = RelayConfig::default();
+        if !config.protected_peers.is_empty() {
+            relay_config.max_circuit_duration = Duration::from_secs(24 * 60 * 60);
+            relay_config.max_circuit_bytes = u64::MAX;
+        }
+        let relay = Toggle::from(
+            (config.relay_server || !config.protected_peers.is_empty())
+                .then(|| RelayBehaviour::new(peer_id, relay_config)),
+        );
+
+        // DCUtR behaviour:
+        // - Tries to upgrade relayed connections to direct connections using hole punching.
+        // - Disabled behind sentries, since a direct connection would reveal our address.
+        let dcutr = Toggle::from((!behind_sentries).then(DcutrBehaviour::new));
+
         // Request Response behaviour
         let codec = MessageCodec::default();
         let protocol = ReqResProtocol::Version1;
@@ -175,12 +225,16 @@ impl NimiqBehaviour {
             RequestResponse::new(codec, iter::once((protocol, ProtocolSupport::Full)), config);
 
         Self {
+            autonat,
+            dcutr,
             dht,
             discovery,
             gossipsub,
             identify,
             ping,
             pool,
+            relay,
+            relay_client,
             request_response,
         }
     }
@@ -1,14 +1,15 @@
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
     time::Duration,
 };
 
 use libp2p::{
-    gossipsub::{GossipsubConfig, GossipsubConfigBuilder, MessageId},
+    autonat::Config as AutonatConfig,
+    gossipsub::{GossipsubConfig, GossipsubConfigBuilder, MessageId, TopicScoreParams},
     identity::Keypair,
     kad::{KademliaBucketInserts, KademliaConfig, KademliaStoreInserts},
-    Multiaddr,
+    Multiaddr, PeerId,
 };
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::peer_info::Services;
@@ -29,9 +30,24 @@ pub struct Config {
     pub keypair: Keypair,
     pub peer_contact: PeerContact,
     pub seeds: Vec<Multiaddr>,
+    /// Peers we always want to be connected to. Their addresses must include the peer ID (`/p2p/<peer_id>`).
+    pub static_peers: Vec<Multiaddr>,
+    /// Sentry nodes to hide behind. If set, we only connect to the sentries, don't advertise our own addresses
+    /// and are only reachable through circuits relayed by the sentries. Their addresses must include the peer
+    /// ID (`/p2p/<peer_id>`).
+    pub sentries: Vec<Multiaddr>,
+    /// Peers we act as a sentry for. Their connections are not subject to the connection limits and we relay
+    /// circuits to them without the usual limits.
+    pub protected_peers: Vec<PeerId>,
+    /// Whether we relay circuits for peers that are not publicly reachable. Sentries always relay circuits to
+    /// their protected peers.
+    pub relay_server: bool,
     pub discovery: DiscoveryConfig,
     pub kademlia: KademliaConfig,
     pub gossipsub: GossipsubConfig,
+    /// Peer scoring parameters per gossipsub topic name. Topics that are not listed use the default parameters.
+    pub gossipsub_topic_scores: HashMap<String, TopicScoreParams>,
+    pub autonat: AutonatConfig,
     pub memory_transport: bool,
     pub required_services: Services,
     pub tls: Option<TlsConfig>,
@@ -42,6 +58,7 @@ impl Config {
         keypair: Keypair,
         peer_contact: PeerContact,
         seeds: Vec<Multiaddr>,
+        static_peers: Vec<Multiaddr>,
         genesis_hash: Blake2bHash,
         memory_transport: bool,
         required_services: Services,
@@ -75,13 +92,26 @@ impl Config {
         kademlia.set_replication_interval(None);
         kademlia.set_record_filtering(KademliaStoreInserts::FilterBoth);
 
+        // Only probe for public addresses if we are not using the memory transport, since those addresses are
+        // never global.
+        let autonat = AutonatConfig {
+            only_global_ips: !memory_transport,
+            ..Default::default()
+        };
+
         Self {
             keypair,
             peer_contact,
             seeds,
+            static_peers,
+            sentries: Vec::new(),
+            protected_peers: Vec::new(),
+            relay_server: false,
             discovery: DiscoveryConfig::new(genesis_hash, required_services),
             kademlia,
             gossipsub,
+            gossipsub_topic_scores: HashMap::new(),
+            autonat,
             memory_transport,
             required_services,
             tls: tls_settings,
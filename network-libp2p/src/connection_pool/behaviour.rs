@@ -44,6 +44,7 @@ struct ConnectionPoolConfig {
     dialing_count_max: usize,
     retry_down_after: Duration,
     housekeeping_interval: Duration,
+    static_peers_redial_interval: Duration,
 }
 
 impl Default for ConnectionPoolConfig {
@@ -58,6 +59,7 @@ impl Default for ConnectionPoolConfig {
             dialing_count_max: 3,
             retry_down_after: Duration::from_secs(60 * 10), // 10 minutes
             housekeeping_interval: Duration::from_secs(60 * 2), // 2 minutes
+            static_peers_redial_interval: Duration::from_secs(10),
         }
     }
 }
@@ -227,6 +229,14 @@ type PoolNetworkBehaviourAction =
 /// the peer is connected, is being dialed, is down or has failed.
 /// Also watches if we have received more connections than the allowed
 /// configured maximum per peer, IP or subnet.
+///
+/// Static peers are always dialed (and redialed after a disconnection) and
+/// their connections are not subject to the connection limits.
+///
+/// If sentries are configured, the node is hidden behind them: the sentries
+/// are treated as static peers, no other peers are dialed on our own and
+/// direct inbound connections are only accepted from static peers. Other
+/// peers can still reach us through circuits relayed by the sentries.
 pub struct ConnectionPoolBehaviour {
     /// Peer contact book. This is the data structure where information of all
     /// known peers is store. This information includes known addresses and
@@ -239,6 +249,15 @@ pub struct ConnectionPoolBehaviour {
     /// Set of seeds useful when starting to discover other peers.
     seeds: Vec<Multiaddr>,
 
+    /// Peers we always want to be connected to, together with the address they are dialed on.
+    static_peers: HashMap<PeerId, Multiaddr>,
+
+    /// Sentries we are hidden behind. These are also contained in `static_peers`.
+    sentries: HashSet<PeerId>,
+
+    /// Peers (e.g. validators we are a sentry for) whose connections are not subject to the connection limits.
+    protected_peers: HashSet<PeerId>,
+
     /// The set of services that this peer requires.
     required_services: Services,
 
@@ -265,6 +284,9 @@ pub struct ConnectionPoolBehaviour {
 
     /// Interval for which the connection pool housekeeping should be run
     housekeeping_timer: Interval,
+
+    /// Interval in which disconnected static peers are redialed
+    static_peers_timer: Interval,
 }
 
 impl ConnectionPoolBehaviour {
@@ -272,6 +294,9 @@ impl ConnectionPoolBehaviour {
         contacts: Arc<RwLock<PeerContactBook>>,
         own_peer_id: PeerId,
         seeds: Vec<Multiaddr>,
+        static_peers: Vec<Multiaddr>,
+        sentries: Vec<Multiaddr>,
+        protected_peers: Vec<PeerId>,
         required_services: Services,
     ) -> Self {
         let limits = ConnectionPoolLimits {
@@ -281,11 +306,25 @@ impl ConnectionPoolBehaviour {
         };
         let config = ConnectionPoolConfig::default();
         let housekeeping_timer = wasm_timer::Interval::new(config.housekeeping_interval);
+        let static_peers_timer = wasm_timer::Interval::new(config.static_peers_redial_interval);
+
+        let sentries = Self::parse_peer_addresses(sentries);
+        let mut static_peers: HashMap<PeerId, Multiaddr> =
+            Self::parse_peer_addresses(static_peers).collect();
+        let sentries = sentries
+            .map(|(peer_id, address)| {
+                static_peers.insert(peer_id, address);
+                peer_id
+            })
+            .collect();
 
         Self {
             contacts,
             own_peer_id,
             seeds,
+            static_peers,
+            sentries,
+            protected_peers: protected_peers.into_iter().collect(),
             required_services,
             peer_ids: ConnectionState::new(2, config.retry_down_after),
             addresses: ConnectionState::new(4, config.retry_down_after),
@@ -295,9 +334,32 @@ impl ConnectionPoolBehaviour {
             config,
             waker: None,
             housekeeping_timer,
+            static_peers_timer,
         }
     }
 
+    /// Parses addresses that must contain the peer ID (`/p2p/<peer_id>`) as their last protocol.
+    /// Addresses without a valid peer ID are ignored.
+    fn parse_peer_addresses(
+        addresses: Vec<Multiaddr>,
+    ) -> impl Iterator<Item = (PeerId, Multiaddr)> {
+        addresses
+            .into_iter()
+            .filter_map(|address| match address.iter().last() {
+                Some(Protocol::P2p(multihash)) => match PeerId::from_multihash(multihash) {
+                    Ok(peer_id) => Some((peer_id, address)),
+                    Err(_) => {
+                        warn!(%address, "Ignoring peer address with invalid peer ID");
+                        None
+                    }
+                },
+                _ => {
+                    warn!(%address, "Ignoring peer address without peer ID");
+                    None
+                }
+            })
+    }
+
     fn wake(&mut self) {
         if let Some(waker) = self.waker.take() {
             waker.wake();
@@ -318,7 +380,9 @@ impl ConnectionPoolBehaviour {
         );
 
         // Try to maintain at least `peer_count_desired` connections.
+        // Nodes behind sentries only connect to their static peers.
         if self.active
+            && !self.is_behind_sentries()
             && self.peer_ids.num_connected() < self.config.peer_count_desired
             && self.peer_ids.num_dialing() < self.config.dialing_count_max
         {
@@ -349,9 +413,68 @@ impl ConnectionPoolBehaviour {
         self.wake();
     }
 
+    /// Dials all static peers that we are currently neither connected to nor dialing.
+    ///
+    /// Static peers are dialed regardless of the desired peer count and of
+    /// previous failures, such that the connections to them are re-established
+    /// as soon as possible.
+    fn dial_static_peers(&mut self) {
+        let static_peers: Vec<(PeerId, Multiaddr)> = self
+            .static_peers
+            .iter()
+            .filter(|(peer_id, _)| {
+                !self.peer_ids.connected.contains(peer_id)
+                    && !self.peer_ids.dialing.contains(peer_id)
+                    && !self.peer_ids.is_banned(**peer_id)
+            })
+            .map(|(peer_id, address)| (*peer_id, address.clone()))
+            .collect();
+
+        for (peer_id, address) in static_peers {
+            debug!(%peer_id, %address, "Dialing static peer");
+            self.peer_ids.mark_dialing(peer_id);
+            let handler = self.new_handler();
+            self.actions.push_back(NetworkBehaviourAction::Dial {
+                opts: DialOpts::peer_id(peer_id)
+                    .addresses(vec![address])
+                    .condition(PeerCondition::Disconnected)
+                    .build(),
+                handler,
+            });
+        }
+
+        self.wake();
+    }
+
+    /// Returns whether the given peer is configured as a static peer.
+    pub fn is_static_peer(&self, peer_id: &PeerId) -> bool {
+        self.static_peers.contains_key(peer_id)
+    }
+
+    /// Returns whether we are hidden behind sentries.
+    pub fn is_behind_sentries(&self) -> bool {
+        !self.sentries.is_empty()
+    }
+
+    /// Returns whether the given peer is one of our sentries.
+    pub fn is_sentry(&self, peer_id: &PeerId) -> bool {
+        self.sentries.contains(peer_id)
+    }
+
+    /// Returns whether the given peer is one of the peers we protect as a sentry.
+    pub fn is_protected(&self, peer_id: &PeerId) -> bool {
+        self.protected_peers.contains(peer_id)
+    }
+
+    /// Returns whether the connections of the given peer are exempt from the connection limits.
+    fn is_exempt_from_limits(&self, peer_id: &PeerId) -> bool {
+        self.is_static_peer(peer_id) || self.is_protected(peer_id)
+    }
+
     /// Tells the behaviour to start connecting to other peers.
     pub fn start_connecting(&mut self) {
         self.active = true;
+        self.dial_static_peers();
         self.maintain_peers();
     }
 
@@ -426,6 +549,11 @@ impl ConnectionPoolBehaviour {
         services: Services,
         num_peers: usize,
     ) -> Vec<PeerId> {
+        // Nodes behind sentries don't dial other peers on their own.
+        if self.is_behind_sentries() {
+            return vec![];
+        }
+
         let contacts = self.contacts.read();
         let own_contact = contacts.get_own_contact();
         let own_peer_id = own_contact.peer_id();
@@ -525,11 +653,18 @@ impl NetworkBehaviour for ConnectionPoolBehaviour {
     }
 
     fn addresses_of_peer(&mut self, peer_id: &PeerId) -> Vec<Multiaddr> {
-        self.contacts
+        let mut addresses = self
+            .contacts
             .read()
             .get(peer_id)
             .map(|e| e.contact().addresses.clone())
-            .unwrap_or_default()
+            .unwrap_or_default();
+        if let Some(address) = self.static_peers.get(peer_id) {
+            if !addresses.contains(address) {
+                addresses.push(address.clone());
+            }
+        }
+        addresses
     }
 
     fn inject_connection_established(
@@ -583,6 +718,16 @@ impl NetworkBehaviour for ConnectionPoolBehaviour {
         } else if self.peer_ids.is_banned(*peer_id) {
             debug!(%peer_id, "Peer is banned");
             close_reason = Some(ConnectionPoolHandlerError::BannedPeer);
+        } else if self.is_behind_sentries()
+            && endpoint.is_listener()
+            && !self.is_static_peer(peer_id)
+            && !address
+                .iter()
+                .any(|protocol| protocol == Protocol::P2pCircuit)
+        {
+            // Behind sentries, other peers must reach us through a circuit relayed by a sentry.
+            debug!(%peer_id, %address, "Rejecting direct connection from peer that is not static");
+            close_reason = Some(ConnectionPoolHandlerError::NotStaticPeer);
         }
 
         // Get IP from multiaddress if it exists.
@@ -597,44 +742,50 @@ impl NetworkBehaviour for ConnectionPoolBehaviour {
         };
 
         // If we have an IP, check connection limits per IP/subnet.
+        // Static and protected peers are never disconnected because of these limits.
+        let is_exempt_from_limits = self.is_exempt_from_limits(peer_id);
         if let Some(ip) = ip {
-            if self.config.peer_count_per_ip_max
-                < self
-                    .limits
-                    .ip_count
-                    .get(&ip)
-                    .unwrap_or(&0)
-                    .saturating_add(1)
-            {
-                debug!(%ip, "Max peer connections per IP limit reached");
-                close_reason = Some(ConnectionPoolHandlerError::MaxPeerPerIPConnectionsReached);
-            }
+            if !is_exempt_from_limits {
+                if self.config.peer_count_per_ip_max
+                    < self
+                        .limits
+                        .ip_count
+                        .get(&ip)
+                        .unwrap_or(&0)
+                        .saturating_add(1)
+                {
+                    debug!(%ip, "Max peer connections per IP limit reached");
+                    close_reason = Some(ConnectionPoolHandlerError::MaxPeerPerIPConnectionsReached);
+                }
 
-            if ip.is_ipv4()
-                && (self.config.peer_count_per_subnet_max
-                    < self.limits.ipv4_count.saturating_add(1))
-            {
-                debug!("Max peer connections per IPv4 subnet limit reached");
-                close_reason = Some(ConnectionPoolHandlerError::MaxIpv4SubnetConnectionsReached);
-            }
+                if ip.is_ipv4()
+                    && (self.config.peer_count_per_subnet_max
+                        < self.limits.ipv4_count.saturating_add(1))
+                {
+                    debug!("Max peer connections per IPv4 subnet limit reached");
+                    close_reason =
+                        Some(ConnectionPoolHandlerError::MaxIpv4SubnetConnectionsReached);
+                }
 
-            if ip.is_ipv6()
-                && (self.config.peer_count_per_subnet_max
-                    < self.limits.ipv6_count.saturating_add(1))
-            {
-                debug!("Max peer connections per IPv6 subnet limit reached");
-                close_reason = Some(ConnectionPoolHandlerError::MaxIpv6SubnetConnectionsReached);
-            }
+                if ip.is_ipv6()
+                    && (self.config.peer_count_per_subnet_max
+                        < self.limits.ipv6_count.saturating_add(1))
+                {
+                    debug!("Max peer connections per IPv6 subnet limit reached");
+                    close_reason =
+                        Some(ConnectionPoolHandlerError::MaxIpv6SubnetConnectionsReached);
+                }
 
-            if self.config.peer_count_max
-                < self
-                    .limits
-                    .ipv4_count
-                    .saturating_add(self.limits.ipv6_count)
-                    .saturating_add(1)
-            {
-                debug!("Max peer connections limit reached");
-                close_reason = Some(ConnectionPoolHandlerError::MaxPeerConnectionsReached);
+                if self.config.peer_count_max
+                    < self
+                        .limits
+                        .ipv4_count
+                        .saturating_add(self.limits.ipv6_count)
+                        .saturating_add(1)
+                {
+                    debug!("Max peer connections limit reached");
+                    close_reason = Some(ConnectionPoolHandlerError::MaxPeerConnectionsReached);
+                }
             }
 
             if close_reason.is_none() {
@@ -723,9 +874,12 @@ impl NetworkBehaviour for ConnectionPoolBehaviour {
         self.addresses.mark_closed(address.clone());
         self.peer_ids.mark_closed(*peer_id);
         // If the connection was closed for any reason, don't dial the peer again.
+        // Static peers are the exception, they are redialed periodically.
         // FIXME We want to be more selective here and only mark peers as down for specific CloseReasons.
-        self.peer_ids.mark_down(*peer_id);
-        self.addresses.mark_down(address.clone());
+        if !self.is_static_peer(peer_id) {
+            self.peer_ids.mark_down(*peer_id);
+            self.addresses.mark_down(address.clone());
+        }
 
         self.maintain_peers();
     }
@@ -819,6 +973,11 @@ impl NetworkBehaviour for ConnectionPoolBehaviour {
             self.housekeeping();
         }
 
+        // Redial disconnected static peers at regular intervals.
+        if self.static_peers_timer.poll_next_unpin(cx).is_ready() && self.active {
+            self.dial_static_peers();
+        }
+
         store_waker!(self, waker, cx);
 
         Poll::Pending
@@ -48,6 +48,10 @@ pub enum ConnectionPoolHandlerError {
     #[error("Maximum peers connections per IP has been reached")]
     MaxPeerPerIPConnectionsReached,
 
+    /// Only connections from static peers are accepted
+    #[error("Only direct connections from static peers are accepted")]
+    NotStaticPeer,
+
     /// The application sent the network to close the connection with the
     /// provided reason
     #[error("Application sent a close action with reason: {0:?}")]
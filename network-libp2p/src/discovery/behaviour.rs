@@ -52,6 +52,10 @@ pub struct DiscoveryConfig {
 
     /// Whether to keep the connection alive, even if no other behaviour uses it.
     pub keep_alive: KeepAlive,
+
+    /// Whether to add the addresses other peers observed for us to our own contact. This is disabled for nodes
+    /// that must not reveal their addresses (e.g. validators behind sentries).
+    pub add_observed_addresses: bool,
 }
 
 impl DiscoveryConfig {
@@ -65,6 +69,7 @@ impl DiscoveryConfig {
             required_services,
             house_keeping_interval: Duration::from_secs(60),
             keep_alive: KeepAlive::Yes,
+            add_observed_addresses: true,
         }
     }
 }
@@ -137,6 +142,20 @@ impl DiscoveryBehaviour {
     pub fn peer_contact_book(&self) -> Arc<RwLock<PeerContactBook>> {
         Arc::clone(&self.peer_contact_book)
     }
+
+    /// Adds addresses on which we were confirmed to be reachable to our own peer contact.
+    pub fn add_reachable_own_addresses(&self, addresses: Vec<Multiaddr>) {
+        self.peer_contact_book
+            .write()
+            .add_reachable_own_addresses(addresses, &self.keypair);
+    }
+
+    /// Removes addresses on which we are no longer reachable from our own peer contact.
+    pub fn remove_reachable_own_addresses(&self, addresses: Vec<Multiaddr>) {
+        self.peer_contact_book
+            .write()
+            .remove_reachable_own_addresses(addresses, &self.keypair);
+    }
 }
 
 impl NetworkBehaviour for DiscoveryBehaviour {
@@ -242,6 +261,9 @@ impl NetworkBehaviour for DiscoveryBehaviour {
                 }
             }
             HandlerOutEvent::ObservedAddresses { observed_addresses } => {
+                if !self.config.add_observed_addresses {
+                    return;
+                }
                 let score = AddressScore::Infinite;
                 for address in observed_addresses {
                     self.events
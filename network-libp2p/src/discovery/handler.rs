@@ -392,10 +392,12 @@ impl ConnectionHandler for DiscoveryHandler {
                                     let mut peer_contact_book = self.peer_contact_book.write();
 
                                     // Update our own peer contact given the observed addresses we received
-                                    peer_contact_book.add_own_addresses(
-                                        observed_addresses.clone(),
-                                        &self.keypair,
-                                    );
+                                    if self.config.add_observed_addresses {
+                                        peer_contact_book.add_own_addresses(
+                                            observed_addresses.clone(),
+                                            &self.keypair,
+                                        );
+                                    }
 
                                     // Send the HandshakeAck
                                     let response_signature =
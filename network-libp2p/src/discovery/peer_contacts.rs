@@ -322,6 +322,53 @@ impl PeerContactBook {
         }
     }
 
+    /// Returns the signed contacts of all the peers in the contact book (excluding our own contact), such that
+    /// they can be persisted and imported later using `import`.
+    pub fn export(&self) -> Vec<SignedPeerContact> {
+        let own_peer_id = self.own_peer_contact.peer_id();
+        self.peer_contacts
+            .values()
+            .filter(|contact| contact.peer_id() != own_peer_id)
+            .map(|contact| contact.signed().clone())
+            .collect()
+    }
+
+    /// Imports a set of previously exported peer contacts using the service filtering.
+    /// Contacts with an invalid signature, contacts that exceed the maximum age and our own contact are skipped.
+    /// Returns the number of contacts that were imported.
+    pub fn import<I: IntoIterator<Item = SignedPeerContact>>(
+        &mut self,
+        contacts: I,
+        services_filter: Services,
+    ) -> usize {
+        let unix_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        let own_peer_id = *self.own_peer_contact.peer_id();
+        let max_age = Duration::from_secs(PeerContactBook::MAX_PEER_AGE);
+
+        let mut num_imported = 0;
+        for contact in contacts {
+            let peer_id = contact.inner.peer_id();
+            if peer_id == own_peer_id {
+                continue;
+            }
+            if !contact.verify() {
+                debug!(%peer_id, "Skipping imported peer contact with invalid signature");
+                continue;
+            }
+            if PeerContactInfo::from(contact.clone()).exceeds_age(max_age, unix_time) {
+                debug!(%peer_id, "Skipping imported peer contact because of old age");
+                continue;
+            }
+            self.insert_filtered(contact, services_filter);
+            if self.peer_contacts.contains_key(&peer_id) {
+                num_imported += 1;
+            }
+        }
+        num_imported
+    }
+
     /// Gets a peer contact if it exists given its peer_id.
     /// If the peer_id is not found, `None` is returned.
     pub fn get(&self, peer_id: &PeerId) -> Option<Arc<PeerContactInfo>> {
@@ -381,6 +428,51 @@ impl PeerContactBook {
         self.insert(contact.sign(keypair));
     }
 
+    /// Adds a set of addresses on which we were confirmed to be reachable (e.g. by AutoNAT or a relay
+    /// reservation) to our own contact. In contrast to observed addresses, these are kept in our own contact
+    /// until they are explicitly removed using `remove_reachable_own_addresses`.
+    pub fn add_reachable_own_addresses<I: IntoIterator<Item = Multiaddr>>(
+        &mut self,
+        addresses: I,
+        keypair: &Keypair,
+    ) {
+        let mut contact = self.own_peer_contact.contact.inner.clone();
+        let addresses = addresses
+            .into_iter()
+            .filter(|address| !contact.addresses.contains(address))
+            .collect::<Vec<Multiaddr>>();
+        if addresses.is_empty() {
+            return;
+        }
+        debug!(?addresses, "Adding reachable addresses to our own contact");
+        contact.add_addresses(addresses);
+        contact.set_current_time();
+        self.set_own_contact(contact.sign(keypair));
+    }
+
+    /// Removes a set of addresses on which we are no longer reachable from our own contact.
+    pub fn remove_reachable_own_addresses<I: IntoIterator<Item = Multiaddr>>(
+        &mut self,
+        addresses: I,
+        keypair: &Keypair,
+    ) {
+        let mut contact = self.own_peer_contact.contact.inner.clone();
+        let addresses = addresses.into_iter().collect::<Vec<Multiaddr>>();
+        debug!(
+            ?addresses,
+            "Removing unreachable addresses from our own contact"
+        );
+        contact.remove_addresses(addresses);
+        contact.set_current_time();
+        self.set_own_contact(contact.sign(keypair));
+    }
+
+    /// Replaces our own contact and announces it to other peers through the contact book.
+    fn set_own_contact(&mut self, contact: SignedPeerContact) {
+        self.own_peer_contact = contact.clone().into();
+        self.insert(contact);
+    }
+
     /// Updates the timestamp our own contact
     pub fn update_own_contact(&mut self, keypair: &Keypair) {
         // Not really optimal to clone here, but *shrugs*
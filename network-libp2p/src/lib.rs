@@ -11,6 +11,7 @@ mod network;
 #[cfg(feature = "metrics")]
 mod network_metrics;
 mod rate_limiting;
+mod traffic;
 
 pub const REQRES_PROTOCOL: &[u8] = b"/nimiq/reqres/0.0.1";
 pub const MESSAGE_PROTOCOL: &[u8] = b"/nimiq/message/0.0.1";
@@ -26,6 +27,7 @@ pub use libp2p::{
 };
 pub use network::Network;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+pub use traffic::{NetworkStats, TrafficCounters};
 
 /// Wrapper to libp2p Keypair indetity that implements SerDe Serialize/Deserialize
 #[derive(Clone, Debug)]
@@ -4,6 +4,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -13,11 +14,14 @@ use futures::{ready, stream::BoxStream, Stream, StreamExt};
 #[cfg(not(feature = "tokio-time"))]
 use instant::Instant;
 use libp2p::{
+    autonat::{Event as AutonatEvent, NatStatus},
     core,
     core::{
+        multiaddr::Protocol,
         muxing::StreamMuxerBox,
-        transport::{Boxed, MemoryTransport},
+        transport::{Boxed, ListenerId, MemoryTransport},
     },
+    dcutr::behaviour::Event as DcutrEvent,
     gossipsub::{
         error::PublishError, GossipsubEvent, GossipsubMessage, IdentTopic, MessageAcceptance,
         MessageId, PeerScoreParams, TopicHash, TopicScoreParams,
@@ -30,6 +34,10 @@ use libp2p::{
     },
     noise,
     ping::Success as PingSuccess,
+    relay::v2::client::{
+        transport::ClientTransport as RelayClientTransport, Client as RelayClientBehaviour,
+        Event as RelayClientEvent,
+    },
     request_response::{OutboundFailure, RequestId, RequestResponseMessage, ResponseChannel},
     swarm::{
         dial_opts::{DialOpts, PeerCondition},
@@ -71,15 +79,29 @@ use crate::network_metrics::NetworkMetrics;
 use crate::{
     behaviour::{NimiqBehaviour, NimiqEvent, NimiqNetworkBehaviourError, RequestResponseEvent},
     connection_pool::behaviour::ConnectionPoolEvent,
-    discovery::{behaviour::DiscoveryEvent, peer_contacts::PeerContactBook},
+    discovery::{
+        behaviour::DiscoveryEvent,
+        peer_contacts::{PeerContactBook, SignedPeerContact},
+    },
     dispatch::codecs::typed::{IncomingRequest, OutgoingResponse},
     rate_limiting::{PendingDeletion, RateLimit},
+    traffic::{NetworkStats, TrafficAccounting},
     Config, NetworkError, TlsConfig,
 };
 
 /// Maximum simultaneous libp2p connections per peer
 const MAX_CONNECTIONS_PER_PEER: u32 = 2;
 
+/// Maximum number of relays we listen on if we are not publicly reachable
+const MAX_RELAYS: usize = 2;
+
+/// Time we wait before listening on a relay again after its relayed listener closed. It doubles with every
+/// consecutive failure of the relay, up to `MAX_RELAY_BACKOFF`.
+const RELAY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Maximum time we wait before listening on a failed relay again
+const MAX_RELAY_BACKOFF: Duration = Duration::from_secs(30 * 60);
+
 type NimiqSwarm = Swarm<NimiqBehaviour>;
 
 #[derive(Debug)]
@@ -190,6 +212,19 @@ struct TaskState {
     requests_initiated: HashMap<RequestId, Instant>,
     response_channels: HashMap<RequestId, ResponseChannel<OutgoingResponse>>,
     receive_requests: HashMap<RequestType, mpsc::Sender<(Bytes, RequestId, PeerId)>>,
+    /// Types of our outstanding requests, used to account the traffic of their responses.
+    outbound_request_types: HashMap<RequestId, RequestType>,
+    /// Peers and types of the requests we still need to respond to, used to account the traffic of our responses.
+    inbound_requests: HashMap<RequestId, (PeerId, RequestType)>,
+    /// Traffic accounting per peer and per message type.
+    traffic: Arc<TrafficAccounting>,
+    /// Peer scoring parameters per gossipsub topic name, applied when subscribing to a topic.
+    topic_score_params: HashMap<String, TopicScoreParams>,
+    /// Listeners on relayed addresses, mapped to the peer ID of the relay.
+    relay_listeners: HashMap<ListenerId, PeerId>,
+    /// Relays whose relayed listener closed while we still needed it, mapped to the number of consecutive failures
+    /// and the time until which we don't listen on them again.
+    failed_relays: HashMap<PeerId, (u32, Instant)>,
 }
 
 #[derive(Clone, Debug)]
@@ -222,8 +257,12 @@ pub struct Network {
     /// Metrics used for data analysis
     #[cfg(feature = "metrics")]
     metrics: Arc<NetworkMetrics>,
+    /// Traffic accounting per peer and per message type
+    traffic: Arc<TrafficAccounting>,
     /// Required services from other peers. This is defined on init, based on our client type
     required_services: Services,
+    /// Contains all known peer contacts.
+    contacts: Arc<RwLock<PeerContactBook>>,
 }
 
 impl Network {
@@ -241,8 +280,12 @@ impl Network {
         executor: impl TaskExecutor + Send + Clone + 'static,
     ) -> Self {
         let required_services = config.required_services;
-        // TODO: persist to disk
-        let own_peer_contact = config.peer_contact.clone();
+        let mut own_peer_contact = config.peer_contact.clone();
+        // Behind sentries, we are only reachable through the circuits relayed by the sentries. Our own addresses
+        // must thus never be advertised.
+        if !config.sentries.is_empty() {
+            own_peer_contact.addresses.clear();
+        }
         let contacts = Arc::new(RwLock::new(PeerContactBook::new(
             own_peer_contact.sign(&config.keypair),
         )));
@@ -250,6 +293,7 @@ impl Network {
             ip_colocation_factor_threshold: 20.0,
             ..Default::default()
         };
+        let topic_score_params = config.gossipsub_topic_scores.clone();
         let swarm = Self::new_swarm(
             clock,
             config,
@@ -274,6 +318,10 @@ impl Network {
 
         #[cfg(feature = "metrics")]
         let metrics = Arc::new(NetworkMetrics::default());
+        #[cfg(feature = "metrics")]
+        let traffic = Arc::new(TrafficAccounting::new(Arc::clone(&metrics)));
+        #[cfg(not(feature = "metrics"))]
+        let traffic = Arc::new(TrafficAccounting::default());
 
         executor.exec(Box::pin(Self::swarm_task(
             swarm,
@@ -284,7 +332,9 @@ impl Network {
             Arc::clone(&peer_request_limits),
             Arc::clone(&rate_limits_pending_deletion),
             update_scores,
-            contacts,
+            Arc::clone(&contacts),
+            Arc::clone(&traffic),
+            topic_score_params,
             #[cfg(feature = "metrics")]
             metrics.clone(),
         )));
@@ -298,7 +348,9 @@ impl Network {
             peer_request_limits,
             #[cfg(feature = "metrics")]
             metrics,
+            traffic,
             required_services,
+            contacts,
         }
     }
 
@@ -306,6 +358,7 @@ impl Network {
         keypair: &Keypair,
         memory_transport: bool,
         tls: &Option<TlsConfig>,
+        relay_transport: RelayClientTransport,
     ) -> std::io::Result<Boxed<(PeerId, StreamMuxerBox)>> {
         if memory_transport {
             // Memory transport primary for testing
@@ -344,6 +397,9 @@ impl Network {
             let mut yamux = yamux::YamuxConfig::default();
             yamux.set_window_update_mode(yamux::WindowUpdateMode::on_read());
 
+            // Relayed connections (`/p2p-circuit` addresses) are handled by the relay client transport.
+            let transport = relay_transport.or_transport(transport);
+
             Ok(transport
                 .upgrade(core::upgrade::Version::V1)
                 .authenticate(noise::NoiseConfig::xx(noise_keys).into_authenticated())
@@ -383,6 +439,9 @@ impl Network {
             let mut yamux = yamux::YamuxConfig::default();
             yamux.set_window_update_mode(yamux::WindowUpdateMode::on_read());
 
+            // Relayed connections (`/p2p-circuit` addresses) are handled by the relay client transport.
+            let transport = relay_transport.or_transport(transport);
+
             Ok(transport
                 .upgrade(core::upgrade::Version::V1)
                 .authenticate(noise::NoiseConfig::xx(noise_keys).into_authenticated())
@@ -401,10 +460,19 @@ impl Network {
     ) -> Swarm<NimiqBehaviour> {
         let local_peer_id = PeerId::from(config.keypair.public());
 
-        let transport =
-            Self::new_transport(&config.keypair, config.memory_transport, &config.tls).unwrap();
+        let (relay_transport, relay_client) =
+            RelayClientBehaviour::new_transport_and_behaviour(local_peer_id);
+
+        let transport = Self::new_transport(
+            &config.keypair,
+            config.memory_transport,
+            &config.tls,
+            relay_transport,
+        )
+        .unwrap();
 
-        let behaviour = NimiqBehaviour::new(config, clock, contacts, peer_score_params);
+        let behaviour =
+            NimiqBehaviour::new(config, clock, contacts, peer_score_params, relay_client);
 
         let limits = ConnectionLimits::default()
             .with_max_pending_incoming(Some(16))
@@ -441,9 +509,15 @@ impl Network {
         rate_limits_pending_deletion: Arc<Mutex<PendingDeletion>>,
         mut update_scores: Interval,
         contacts: Arc<RwLock<PeerContactBook>>,
+        traffic: Arc<TrafficAccounting>,
+        topic_score_params: HashMap<String, TopicScoreParams>,
         #[cfg(feature = "metrics")] metrics: Arc<NetworkMetrics>,
     ) {
-        let mut task_state = TaskState::default();
+        let mut task_state = TaskState {
+            traffic,
+            topic_score_params,
+            ..Default::default()
+        };
 
         let peer_id = Swarm::local_peer_id(&swarm);
         let task_span = trace_span!("swarm task", peer_id=?peer_id);
@@ -454,6 +528,11 @@ impl Network {
                     validate_msg = validate_rx.recv() => {
                         if let Some(validate_msg) = validate_msg {
                             let topic = validate_msg.topic;
+                            task_state.traffic.note_validation_result(
+                                &validate_msg.pubsub_id.propagation_source,
+                                topic,
+                                &validate_msg.acceptance,
+                            );
                             let result: Result<bool, PublishError> = swarm
                                 .behaviour_mut()
                                 .gossipsub
@@ -509,9 +588,15 @@ impl Network {
         rate_limits_pending_deletion: Arc<Mutex<PendingDeletion>>,
         mut update_scores: Interval,
         contacts: Arc<RwLock<PeerContactBook>>,
+        traffic: Arc<TrafficAccounting>,
+        topic_score_params: HashMap<String, TopicScoreParams>,
         #[cfg(feature = "metrics")] metrics: Arc<NetworkMetrics>,
     ) {
-        let mut task_state = TaskState::default();
+        let mut task_state = TaskState {
+            traffic,
+            topic_score_params,
+            ..Default::default()
+        };
 
         let peer_id = Swarm::local_peer_id(&swarm);
         let task_span = trace_span!("swarm task", peer_id=?peer_id);
@@ -522,6 +607,11 @@ impl Network {
                     validate_msg = validate_rx.recv() => {
                         if let Some(validate_msg) = validate_msg {
                             let topic = validate_msg.topic;
+                            task_state.traffic.note_validation_result(
+                                &validate_msg.pubsub_id.propagation_source,
+                                topic,
+                                &validate_msg.acceptance,
+                            );
                             let result: Result<bool, PublishError> = swarm
                                 .behaviour_mut()
                                 .gossipsub
@@ -599,8 +689,9 @@ impl Network {
                     }
                 }
 
-                // Save dialed peer addresses
-                if endpoint.is_dialer() {
+                // Save dialed peer addresses. The addresses of protected peers are never saved, such that we don't
+                // hand them out to other peers.
+                if endpoint.is_dialer() && !swarm.behaviour().pool.is_protected(&peer_id) {
                     let listen_addr = endpoint.get_remote_address();
 
                     debug!(%peer_id, address = %listen_addr, "Saving peer");
@@ -641,6 +732,7 @@ impl Network {
                 if num_established == 0 {
                     connected_peers.write().remove(&peer_id);
                     swarm.behaviour_mut().remove_peer(peer_id);
+                    state.traffic.remove_peer(&peer_id);
 
                     // Removes or marks to remove the respective rate limits.
                     // Also cleans up the expired rate limits pending to delete.
@@ -684,8 +776,130 @@ impl Network {
                 debug!(%peer_id, "Dialing peer");
             }
 
+            SwarmEvent::NewListenAddr {
+                listener_id,
+                address,
+            } => {
+                // Relayed addresses are only known once the relay accepted our reservation.
+                if state.relay_listeners.contains_key(&listener_id) {
+                    debug!(%address, "Listening on relayed address");
+                    swarm
+                        .behaviour()
+                        .discovery
+                        .add_reachable_own_addresses(vec![address]);
+                }
+            }
+
+            SwarmEvent::ExpiredListenAddr {
+                listener_id,
+                address,
+            } => {
+                if state.relay_listeners.contains_key(&listener_id) {
+                    debug!(%address, "Relayed address expired");
+                    swarm
+                        .behaviour()
+                        .discovery
+                        .remove_reachable_own_addresses(vec![address]);
+                }
+            }
+
+            SwarmEvent::ListenerClosed {
+                listener_id,
+                addresses,
+                reason,
+            } => {
+                if let Some(relay_peer_id) = state.relay_listeners.remove(&listener_id) {
+                    debug!(%relay_peer_id, ?reason, "Relayed listener closed");
+                    swarm
+                        .behaviour()
+                        .discovery
+                        .remove_reachable_own_addresses(addresses);
+
+                    // Look for another relay if we are still not reachable. The failed relay is only retried after
+                    // a backoff, otherwise a relay that keeps refusing us would be retried in a tight loop.
+                    if Self::needs_relays(swarm) {
+                        let (failures, retry_at) = state
+                            .failed_relays
+                            .entry(relay_peer_id)
+                            .or_insert((0, Instant::now()));
+                        let backoff = RELAY_BACKOFF
+                            .saturating_mul(2u32.saturating_pow(*failures))
+                            .min(MAX_RELAY_BACKOFF);
+                        *failures = failures.saturating_add(1);
+                        *retry_at = Instant::now() + backoff;
+                        debug!(%relay_peer_id, ?backoff, "Backing off from relay");
+
+                        Self::listen_on_relays(swarm, state, connected_peers);
+                    }
+                }
+            }
+
             SwarmEvent::Behaviour(event) => {
                 match event {
+                    NimiqEvent::Autonat(event) => match event {
+                        AutonatEvent::StatusChanged { old, new } => {
+                            debug!(?old, ?new, "NAT status changed");
+
+                            // Behind sentries, we must only be reachable through the sentries.
+                            if swarm.behaviour().pool.is_behind_sentries() {
+                                return;
+                            }
+
+                            // The address we were reachable on before might not be valid anymore.
+                            if let NatStatus::Public(address) = old {
+                                swarm
+                                    .behaviour()
+                                    .discovery
+                                    .remove_reachable_own_addresses(vec![address]);
+                            }
+
+                            match new {
+                                NatStatus::Public(address) => {
+                                    swarm
+                                        .behaviour()
+                                        .discovery
+                                        .add_reachable_own_addresses(vec![address]);
+
+                                    // We are directly reachable, so we don't need our relays anymore. The relayed
+                                    // addresses are removed from our contact once the listeners are closed.
+                                    let relay_listeners: Vec<ListenerId> =
+                                        state.relay_listeners.keys().copied().collect();
+                                    for listener_id in relay_listeners {
+                                        swarm.remove_listener(listener_id);
+                                    }
+                                }
+                                NatStatus::Private => {
+                                    Self::listen_on_relays(swarm, state, connected_peers);
+                                }
+                                NatStatus::Unknown => {}
+                            }
+                        }
+                        AutonatEvent::InboundProbe(event) => {
+                            trace!(?event, "AutoNAT inbound probe");
+                        }
+                        AutonatEvent::OutboundProbe(event) => {
+                            trace!(?event, "AutoNAT outbound probe");
+                        }
+                    },
+                    NimiqEvent::Dcutr(event) => match event {
+                        DcutrEvent::DirectConnectionUpgradeSucceeded { remote_peer_id } => {
+                            debug!(
+                                peer_id = %remote_peer_id,
+                                "Upgraded relayed connection to a direct connection",
+                            );
+                        }
+                        DcutrEvent::DirectConnectionUpgradeFailed {
+                            remote_peer_id,
+                            error,
+                        } => {
+                            debug!(
+                                peer_id = %remote_peer_id,
+                                ?error,
+                                "Failed to upgrade relayed connection to a direct connection",
+                            );
+                        }
+                        event => trace!(?event, "DCUtR event"),
+                    },
                     NimiqEvent::Dht(event) => {
                         match event {
                             KademliaEvent::OutboundQueryProgressed {
@@ -824,6 +1038,19 @@ impl Network {
                                 } else {
                                     error!(%peer_id, "Peer joined but it already exists");
                                 }
+
+                                // If we are not reachable, use the new peer as a relay if we still need one. Behind
+                                // sentries, every sentry is used as a relay.
+                                let pool = &swarm.behaviour().pool;
+                                let needs_relay = if pool.is_behind_sentries() {
+                                    pool.is_sentry(&peer_id)
+                                } else {
+                                    swarm.behaviour().autonat.nat_status() == NatStatus::Private
+                                        && state.relay_listeners.len() < MAX_RELAYS
+                                };
+                                if needs_relay {
+                                    Self::listen_on_relays(swarm, state, connected_peers);
+                                }
                             }
                             DiscoveryEvent::Update => {}
                         }
@@ -835,6 +1062,17 @@ impl Network {
                             message,
                         } => {
                             let topic = message.topic.clone();
+                            let message_type = if state.gossip_topics.contains_key(&topic) {
+                                TrafficAccounting::topic_label(topic.as_str())
+                            } else {
+                                TrafficAccounting::unknown_label()
+                            };
+                            state.traffic.note_received(
+                                Some(propagation_source),
+                                message_type,
+                                message.data.len(),
+                            );
+
                             if let Some(topic_info) = state.gossip_topics.get_mut(&topic) {
                                 let (output, validate) = topic_info;
                                 if !&*validate {
@@ -886,7 +1124,11 @@ impl Network {
                                     "Received identity",
                                 );
 
-                                // Save identified peer listen addresses
+                                // Save identified peer listen addresses. The addresses of protected peers are
+                                // never saved, such that we don't hand them out to other peers.
+                                if swarm.behaviour().pool.is_protected(&peer_id) {
+                                    return;
+                                }
                                 for listen_addr in info.listen_addrs {
                                     swarm.behaviour_mut().add_peer_address(peer_id, listen_addr);
 
@@ -937,6 +1179,27 @@ impl Network {
                             ConnectionPoolEvent::PeerJoined { peer_id: _ } => {}
                         };
                     }
+                    NimiqEvent::Relay(event) => {
+                        trace!(?event, "Relay event");
+                    }
+                    NimiqEvent::RelayClient(event) => match event {
+                        RelayClientEvent::ReservationReqAccepted {
+                            relay_peer_id,
+                            renewal,
+                            ..
+                        } => {
+                            debug!(%relay_peer_id, renewal, "Relay accepted our reservation");
+                            state.failed_relays.remove(&relay_peer_id);
+                        }
+                        RelayClientEvent::ReservationReqFailed {
+                            relay_peer_id,
+                            error,
+                            ..
+                        } => {
+                            debug!(%relay_peer_id, ?error, "Relay rejected our reservation");
+                        }
+                        event => trace!(?event, "Relay client event"),
+                    },
                     NimiqEvent::RequestResponse(event) => match event {
                         RequestResponseEvent::Message {
                             peer: peer_id,
@@ -949,6 +1212,12 @@ impl Network {
                             } => {
                                 // TODO Add rate limiting (per peer).
                                 if let Ok(type_id) = peek_type(&request) {
+                                    let message_type = state.traffic.request_type_label(type_id);
+                                    state.traffic.note_received(
+                                        Some(peer_id),
+                                        message_type.clone(),
+                                        request.len(),
+                                    );
                                     trace!(
                                         %request_id,
                                         %peer_id,
@@ -970,16 +1239,25 @@ impl Network {
                                     if let Some(sender) = sender {
                                         if type_id.requires_response() {
                                             state.response_channels.insert(request_id, channel);
+                                            state
+                                                .inbound_requests
+                                                .insert(request_id, (peer_id, type_id));
                                         } else {
                                             // Respond on behalf of the actual
                                             // receiver because the actual
                                             // receiver isn't interested in
                                             // responding.
                                             let response: Result<(), InboundRequestError> = Ok(());
+                                            let response = response.serialize_to_vec();
+                                            state.traffic.note_sent(
+                                                Some(peer_id),
+                                                message_type,
+                                                response.len(),
+                                            );
                                             if swarm
                                                 .behaviour_mut()
                                                 .request_response
-                                                .send_response(channel, response.serialize_to_vec())
+                                                .send_response(channel, response)
                                                 .is_err()
                                             {
                                                 error!(
@@ -1010,10 +1288,16 @@ impl Network {
                                         );
                                         let err: Result<(), InboundRequestError> =
                                             Err(InboundRequestError::NoReceiver);
+                                        let err = err.serialize_to_vec();
+                                        state.traffic.note_sent(
+                                            Some(peer_id),
+                                            message_type,
+                                            err.len(),
+                                        );
                                         if swarm
                                             .behaviour_mut()
                                             .request_response
-                                            .send_response(channel, err.serialize_to_vec())
+                                            .send_response(channel, err)
                                             .is_err()
                                         {
                                             error!(
@@ -1042,6 +1326,16 @@ impl Network {
                                     %peer_id,
                                     "Incoming response from peer",
                                 );
+                                let message_type = state
+                                    .outbound_request_types
+                                    .remove(&request_id)
+                                    .map(|type_id| state.traffic.request_type_label(type_id))
+                                    .unwrap_or_else(TrafficAccounting::unknown_label);
+                                state.traffic.note_received(
+                                    Some(peer_id),
+                                    message_type,
+                                    response.len(),
+                                );
                                 if let Some(channel) = state.requests.remove(&request_id) {
                                     if channel.send(Ok(response.into())).is_err() {
                                         error!(%request_id, %peer_id, error = "receiver hung up", "could not send response to channel");
@@ -1072,6 +1366,7 @@ impl Network {
                                 %error,
                                 "Failed to send request to peer",
                             );
+                            state.outbound_request_types.remove(&request_id);
                             if let Some(channel) = state.requests.remove(&request_id) {
                                 if channel.send(Err(Self::to_response_error(error))).is_err() {
                                     error!(%request_id, %peer_id, error = "receiver hung up", "could not send outbound failure to channel");
@@ -1095,6 +1390,7 @@ impl Network {
                                 %error,
                                 "Response to request sent from peer failed",
                             );
+                            state.inbound_requests.remove(&request_id);
                         }
                         RequestResponseEvent::ResponseSent { peer, request_id } => {
                             trace!(
@@ -1110,6 +1406,83 @@ impl Network {
         }
     }
 
+    /// Returns whether we need to listen on relayed addresses to be reachable, i.e. if we are behind sentries or
+    /// AutoNAT determined that we are not publicly reachable.
+    fn needs_relays(swarm: &NimiqSwarm) -> bool {
+        swarm.behaviour().pool.is_behind_sentries()
+            || swarm.behaviour().autonat.nat_status() == NatStatus::Private
+    }
+
+    /// Starts listening on relayed addresses of connected peers until we listen on `MAX_RELAYS` relays.
+    /// This is used when AutoNAT determined that we are not publicly reachable, such that other peers can connect
+    /// to us through the relays. Direct connections are then established through hole punching (DCUtR).
+    ///
+    /// Behind sentries, all connected sentries are used as relays instead and no other peers.
+    ///
+    /// Relays whose relayed listener recently closed are skipped until their backoff expired.
+    fn listen_on_relays(
+        swarm: &mut NimiqSwarm,
+        state: &mut TaskState,
+        connected_peers: &RwLock<HashMap<PeerId, PeerInfo>>,
+    ) {
+        let pool = &swarm.behaviour().pool;
+        let behind_sentries = pool.is_behind_sentries();
+        let num_relays = if behind_sentries {
+            usize::MAX
+        } else {
+            MAX_RELAYS.saturating_sub(state.relay_listeners.len())
+        };
+        // The address we are connected to a peer on is only its listen address if we dialed it. For inbound
+        // connections it is the peer's ephemeral source address, thus we use the addresses the peer advertises in
+        // its contact instead.
+        let contact_book = swarm.behaviour().discovery.peer_contact_book();
+        let contacts = contact_book.read();
+        let now = Instant::now();
+        let relays: Vec<(PeerId, Multiaddr)> = connected_peers
+            .read()
+            .keys()
+            .filter(|peer_id| !behind_sentries || pool.is_sentry(peer_id))
+            .filter(|peer_id| {
+                !state
+                    .relay_listeners
+                    .values()
+                    .any(|relay| relay == *peer_id)
+            })
+            .filter(|peer_id| {
+                state
+                    .failed_relays
+                    .get(peer_id)
+                    .map_or(true, |(_, retry_at)| *retry_at <= now)
+            })
+            .filter_map(|peer_id| {
+                let contact = contacts.get(peer_id)?;
+                let address = contact
+                    .addresses()
+                    .find(|address| !address.iter().any(|p| p == Protocol::P2pCircuit))?
+                    .clone();
+                Some((*peer_id, address))
+            })
+            .take(num_relays)
+            .collect();
+        drop(contacts);
+
+        for (relay_peer_id, relay_address) in relays {
+            let address = relay_address
+                .with(Protocol::P2p(relay_peer_id.into()))
+                .with(Protocol::P2pCircuit);
+
+            match swarm.listen_on(address.clone()) {
+                Ok(listener_id) => {
+                    debug!(%relay_peer_id, %address, "Requesting reservation from relay");
+                    state.relay_listeners.insert(listener_id, relay_peer_id);
+                }
+                Err(error) => {
+                    debug!(%relay_peer_id, %address, %error, "Could not listen on relayed address");
+                }
+            }
+        }
+    }
+
     fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut TaskState) {
         // FIXME implement compact debug format for NetworkAction
         // trace!(?action, "performing action");
@@ -1186,10 +1559,15 @@ impl Network {
 
                         state.gossip_topics.insert(topic.hash(), (tx, validate));
 
+                        let topic_params = state
+                            .topic_score_params
+                            .get(&topic_name)
+                            .cloned()
+                            .unwrap_or_default();
                         match swarm
                             .behaviour_mut()
                             .gossipsub
-                            .set_topic_params(topic, TopicScoreParams::default())
+                            .set_topic_params(topic, topic_params)
                         {
                             Ok(_) => {
                                 if output.send(Ok(rx)).is_err() {
@@ -1281,6 +1659,11 @@ impl Network {
                 output,
             } => {
                 let topic = IdentTopic::new(topic_name.clone());
+                state.traffic.note_sent(
+                    None,
+                    TrafficAccounting::topic_label(&topic_name),
+                    data.len(),
+                );
 
                 if output
                     .send(
@@ -1318,10 +1701,18 @@ impl Network {
                 response_channel,
                 output,
             } => {
+                state.traffic.note_sent(
+                    Some(peer_id),
+                    state.traffic.request_type_label(request_type_id),
+                    request.len(),
+                );
                 let request_id = swarm
                     .behaviour_mut()
                     .request_response
                     .send_request(&peer_id, request);
+                state
+                    .outbound_request_types
+                    .insert(request_id, request_type_id);
                 trace!(
                     %request_id,
                     %peer_id,
@@ -1341,6 +1732,13 @@ impl Network {
                 output,
             } => {
                 if let Some(response_channel) = state.response_channels.remove(&request_id) {
+                    if let Some((peer_id, type_id)) = state.inbound_requests.remove(&request_id) {
+                        state.traffic.note_sent(
+                            Some(peer_id),
+                            state.traffic.request_type_label(type_id),
+                            response.len(),
+                        );
+                    }
                     if output
                         .send(
                             swarm
@@ -1478,6 +1876,7 @@ impl Network {
         let (response_tx, response_rx) = oneshot::channel();
 
         let buf = request.serialize_request();
+        self.traffic.register_request_type::<Req>();
 
         if self
             .action_tx
@@ -1575,6 +1974,8 @@ impl Network {
             // there are going to be more requests or none at all.
         }
 
+        self.traffic.register_request_type::<Req>();
+
         let peer_request_limits = Arc::clone(&self.peer_request_limits);
         let action_tx = self.action_tx.clone();
         let action_tx2 = self.action_tx.clone();
@@ -1662,6 +2063,19 @@ impl Network {
         self.connected_peers.read().len()
     }
 
+    /// Returns the signed contacts of all known peers, e.g. to persist them to disk.
+    pub fn export_peer_contacts(&self) -> Vec<SignedPeerContact> {
+        self.contacts.read().export()
+    }
+
+    /// Imports previously exported peer contacts into the contact book.
+    /// Returns the number of contacts that were imported.
+    pub fn import_peer_contacts(&self, contacts: Vec<SignedPeerContact>) -> usize {
+        self.contacts
+            .write()
+            .import(contacts, self.required_services)
+    }
+
     /// Disconnects from (closes the connection to) all peers with a reason
     pub async fn disconnect(&self, reason: CloseReason) {
         for peer_id in self.get_peers() {
@@ -1669,6 +2083,11 @@ impl Network {
         }
     }
 
+    /// Returns the traffic accounted per peer and per message type.
+    pub fn network_stats(&self) -> NetworkStats {
+        self.traffic.stats()
+    }
+
     #[cfg(feature = "metrics")]
     /// Gets the network metrics
     pub fn metrics(&self) -> Arc<NetworkMetrics> {
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use libp2p::gossipsub::TopicHash;
+use libp2p::gossipsub::{MessageAcceptance, TopicHash};
 use prometheus_client::{
     encoding::EncodeLabelSet,
     metrics::{counter::Counter, family::Family, histogram::Histogram},
@@ -10,7 +10,10 @@ use prometheus_client::{
 pub struct NetworkMetrics {
     gossipsub_messages_received: Family<TopicLabels, Counter>,
     gossipsub_messages_published: Family<TopicLabels, Counter>,
+    gossipsub_validation_results: Family<ValidationLabels, Counter>,
     response_times: Histogram,
+    bytes_received: Family<MessageTypeLabels, Counter>,
+    bytes_sent: Family<MessageTypeLabels, Counter>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
@@ -18,6 +21,17 @@ struct TopicLabels {
     topic: String,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ValidationLabels {
+    topic: String,
+    result: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct MessageTypeLabels {
+    message_type: String,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
 struct RequestIdLabels {
     request_id: String,
@@ -28,7 +42,10 @@ impl Default for NetworkMetrics {
         NetworkMetrics {
             gossipsub_messages_received: Default::default(),
             gossipsub_messages_published: Default::default(),
+            gossipsub_validation_results: Default::default(),
             response_times: Histogram::new([0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1.0, 2.0].into_iter()),
+            bytes_received: Default::default(),
+            bytes_sent: Default::default(),
         }
     }
 }
@@ -47,11 +64,29 @@ impl NetworkMetrics {
             self.gossipsub_messages_published.clone(),
         );
 
+        registry.register(
+            "gossipsub_validation_results",
+            "Number of validated gossipsub messages per topic and validation result",
+            self.gossipsub_validation_results.clone(),
+        );
+
         registry.register(
             "request_durations",
             "Time between requests and responses",
             self.response_times.clone(),
         );
+
+        registry.register(
+            "bytes_received",
+            "Number of payload bytes received per message type",
+            self.bytes_received.clone(),
+        );
+
+        registry.register(
+            "bytes_sent",
+            "Number of payload bytes sent per message type",
+            self.bytes_sent.clone(),
+        );
     }
 
     pub(crate) fn note_received_pubsub_message(&self, topic: &TopicHash) {
@@ -78,7 +113,41 @@ impl NetworkMetrics {
             .inc();
     }
 
+    pub(crate) fn note_gossipsub_validation_result(
+        &self,
+        topic_str: &str,
+        acceptance: &MessageAcceptance,
+    ) {
+        let result = match acceptance {
+            MessageAcceptance::Accept => "accept",
+            MessageAcceptance::Reject => "reject",
+            MessageAcceptance::Ignore => "ignore",
+        };
+        self.gossipsub_validation_results
+            .get_or_create(&ValidationLabels {
+                topic: String::from(topic_str),
+                result: String::from(result),
+            })
+            .inc();
+    }
+
     pub(crate) fn note_response_time(&self, duration: Duration) {
         self.response_times.observe(duration.as_secs_f64());
     }
+
+    pub(crate) fn note_received_bytes(&self, message_type: &str, num_bytes: usize) {
+        self.bytes_received
+            .get_or_create(&MessageTypeLabels {
+                message_type: String::from(message_type),
+            })
+            .inc_by(num_bytes as u64);
+    }
+
+    pub(crate) fn note_sent_bytes(&self, message_type: &str, num_bytes: usize) {
+        self.bytes_sent
+            .get_or_create(&MessageTypeLabels {
+                message_type: String::from(message_type),
+            })
+            .inc_by(num_bytes as u64);
+    }
 }
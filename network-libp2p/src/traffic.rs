@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
+
+use libp2p::{gossipsub::MessageAcceptance, PeerId};
+use nimiq_network_interface::request::{RequestCommon, RequestType};
+use parking_lot::RwLock;
+
+#[cfg(feature = "metrics")]
+use crate::network_metrics::NetworkMetrics;
+
+/// Label used for traffic that can't be attributed to a known topic or request type.
+const UNKNOWN_MESSAGE_TYPE: &str = "unknown";
+
+/// Traffic counters of a peer, a message type or the whole network.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrafficCounters {
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+    pub messages_received: u64,
+    pub messages_sent: u64,
+    /// Number of received gossipsub messages that were rejected by the validation.
+    pub invalid_messages: u64,
+}
+
+impl TrafficCounters {
+    fn note_received(&mut self, num_bytes: usize) {
+        self.bytes_received += num_bytes as u64;
+        self.messages_received += 1;
+    }
+
+    fn note_sent(&mut self, num_bytes: usize) {
+        self.bytes_sent += num_bytes as u64;
+        self.messages_sent += 1;
+    }
+}
+
+/// Snapshot of the traffic accounted by the network.
+///
+/// Only the payload of gossipsub messages and requests/responses is accounted, i.e. transport and protocol
+/// overhead (e.g. handshakes, gossipsub control messages, Kademlia) is not included.
+#[derive(Clone, Debug, Default)]
+pub struct NetworkStats {
+    /// Traffic of all peers and message types combined.
+    pub total: TrafficCounters,
+    /// Traffic per connected peer. Peers are removed once they disconnect.
+    pub peers: HashMap<PeerId, TrafficCounters>,
+    /// Traffic per gossipsub topic and request/message type.
+    pub message_types: HashMap<String, TrafficCounters>,
+}
+
+/// Keeps track of the traffic per peer and per message type.
+#[derive(Default)]
+pub(crate) struct TrafficAccounting {
+    stats: RwLock<NetworkStats>,
+    /// Names of the request types we know about, used to label the traffic of requests and their responses.
+    request_type_names: RwLock<HashMap<RequestType, &'static str>>,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<NetworkMetrics>,
+}
+
+impl TrafficAccounting {
+    #[cfg(feature = "metrics")]
+    pub fn new(metrics: Arc<NetworkMetrics>) -> Self {
+        Self {
+            stats: Default::default(),
+            request_type_names: Default::default(),
+            metrics,
+        }
+    }
+
+    /// Registers the name of a request type, such that its traffic can be labelled accordingly.
+    pub fn register_request_type<Req: RequestCommon>(&self) {
+        let type_id = RequestType::from_request::<Req>();
+        if self.request_type_names.read().contains_key(&type_id) {
+            return;
+        }
+
+        // Only keep the name of the type without its module path, e.g. `RequestBlock`.
+        let type_name = std::any::type_name::<Req>();
+        let type_name = type_name.split('<').next().unwrap_or(type_name);
+        let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+        self.request_type_names.write().insert(type_id, type_name);
+    }
+
+    /// Returns the label used for the traffic of the given request type.
+    pub fn request_type_label(&self, type_id: RequestType) -> String {
+        self.request_type_names
+            .read()
+            .get(&type_id)
+            .map(|type_name| type_name.to_string())
+            .unwrap_or_else(|| UNKNOWN_MESSAGE_TYPE.to_string())
+    }
+
+    /// Returns the label used for the traffic of the given gossipsub topic.
+    pub fn topic_label(topic_name: &str) -> String {
+        format!("gossip/{topic_name}")
+    }
+
+    /// Returns the label used for traffic that can't be attributed to a known message type.
+    pub fn unknown_label() -> String {
+        UNKNOWN_MESSAGE_TYPE.to_string()
+    }
+
+    /// Notes `num_bytes` received in a message of type `message_type`. The peer is only known for direct messages.
+    pub fn note_received(&self, peer_id: Option<PeerId>, message_type: String, num_bytes: usize) {
+        #[cfg(feature = "metrics")]
+        self.metrics.note_received_bytes(&message_type, num_bytes);
+
+        let mut stats = self.stats.write();
+        stats.total.note_received(num_bytes);
+        if let Some(peer_id) = peer_id {
+            stats
+                .peers
+                .entry(peer_id)
+                .or_default()
+                .note_received(num_bytes);
+        }
+        stats
+            .message_types
+            .entry(message_type)
+            .or_default()
+            .note_received(num_bytes);
+    }
+
+    /// Notes `num_bytes` sent in a message of type `message_type`. The peer is only known for direct messages.
+    pub fn note_sent(&self, peer_id: Option<PeerId>, message_type: String, num_bytes: usize) {
+        #[cfg(feature = "metrics")]
+        self.metrics.note_sent_bytes(&message_type, num_bytes);
+
+        let mut stats = self.stats.write();
+        stats.total.note_sent(num_bytes);
+        if let Some(peer_id) = peer_id {
+            stats.peers.entry(peer_id).or_default().note_sent(num_bytes);
+        }
+        stats
+            .message_types
+            .entry(message_type)
+            .or_default()
+            .note_sent(num_bytes);
+    }
+
+    /// Notes the validation result of a gossipsub message on topic `topic_name` that was received from `peer_id`.
+    pub fn note_validation_result(
+        &self,
+        peer_id: &PeerId,
+        topic_name: &str,
+        acceptance: &MessageAcceptance,
+    ) {
+        #[cfg(feature = "metrics")]
+        self.metrics
+            .note_gossipsub_validation_result(topic_name, acceptance);
+
+        if !matches!(acceptance, MessageAcceptance::Reject) {
+            return;
+        }
+
+        let mut stats = self.stats.write();
+        stats.total.invalid_messages += 1;
+        // Don't re-add a peer that disconnected in the meantime.
+        if let Some(counters) = stats.peers.get_mut(peer_id) {
+            counters.invalid_messages += 1;
+        }
+        stats
+            .message_types
+            .entry(Self::topic_label(topic_name))
+            .or_default()
+            .invalid_messages += 1;
+    }
+
+    /// Removes the per peer counters of a disconnected peer.
+    pub fn remove_peer(&self, peer_id: &PeerId) {
+        self.stats.write().peers.remove(peer_id);
+    }
+
+    /// Returns a snapshot of the current traffic statistics.
+    pub fn stats(&self) -> NetworkStats {
+        self.stats.read().clone()
+    }
+}
@@ -64,6 +64,7 @@ impl TestNode {
             min_recv_update_interval: Duration::from_secs(1),
             house_keeping_interval: Duration::from_secs(1),
             keep_alive: KeepAlive::Yes,
+            add_observed_addresses: true,
         };
 
         let peer_contact = PeerContact {
@@ -287,3 +288,33 @@ fn test_housekeeping() {
         .get(&old_contact.public_key().clone().to_peer_id())
         .is_none());
 }
+
+#[test]
+fn test_export_import() {
+    let own_contact = random_peer_contact(0, Services::FULL_BLOCKS);
+    let mut peer_contact_book = PeerContactBook::new(own_contact.clone());
+
+    let peer_contacts: Vec<SignedPeerContact> = (1..=10)
+        .map(|n| random_peer_contact(n, Services::FULL_BLOCKS))
+        .collect();
+    for peer_contact in &peer_contacts {
+        peer_contact_book.insert(peer_contact.clone());
+    }
+
+    // Our own contact must not be exported
+    let exported = peer_contact_book.export();
+    assert_eq!(exported.len(), peer_contacts.len());
+    assert!(!exported.contains(&own_contact));
+
+    // Import into a fresh contact book and check that all contacts are there
+    let mut imported_book = PeerContactBook::new(random_peer_contact(11, Services::FULL_BLOCKS));
+    let num_imported = imported_book.import(exported, Services::FULL_BLOCKS);
+    assert_eq!(num_imported, peer_contacts.len());
+    test_peers_in_contact_book(&imported_book, &peer_contacts);
+
+    // Our own contact is skipped when importing
+    assert_eq!(
+        peer_contact_book.import(vec![own_contact], Services::FULL_BLOCKS),
+        0
+    );
+}
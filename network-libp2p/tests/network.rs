@@ -4,8 +4,8 @@ use futures::{Stream, StreamExt};
 use libp2p::{
     gossipsub::GossipsubConfigBuilder,
     identity::Keypair,
-    multiaddr::{multiaddr, Multiaddr},
-    swarm::KeepAlive,
+    multiaddr::{multiaddr, Multiaddr, Protocol},
+    swarm::{DialError, KeepAlive},
     PeerId,
 };
 use nimiq_network_interface::{
@@ -14,13 +14,14 @@ use nimiq_network_interface::{
 };
 use nimiq_network_libp2p::{
     discovery::{behaviour::DiscoveryConfig, peer_contacts::PeerContact},
-    Config, Network,
+    Config, Network, NetworkError,
 };
 use nimiq_test_log::test;
 use nimiq_utils::time::OffsetTime;
 use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
 use tokio::time::timeout;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 
 fn network_config(address: Multiaddr) -> Config {
     let keypair = Keypair::generate_ed25519();
@@ -42,6 +43,10 @@ fn network_config(address: Multiaddr) -> Config {
         keypair,
         peer_contact,
         seeds: Vec::new(),
+        static_peers: Vec::new(),
+        sentries: Vec::new(),
+        protected_peers: Vec::new(),
+        relay_server: false,
         discovery: DiscoveryConfig {
             genesis_hash: Default::default(),
             update_interval: Duration::from_secs(60),
@@ -51,9 +56,12 @@ fn network_config(address: Multiaddr) -> Config {
             min_send_update_interval: Duration::from_secs(30),
             house_keeping_interval: Duration::from_secs(60),
             keep_alive: KeepAlive::No,
+            add_observed_addresses: true,
         },
         kademlia: Default::default(),
         gossipsub,
+        gossipsub_topic_scores: Default::default(),
+        autonat: Default::default(),
         memory_transport: true,
         required_services: Services::all(),
         tls: None,
@@ -544,3 +552,138 @@ async fn test_gossipsub() {
     }
     net1.network_info().await.unwrap();
 }
+
+async fn spawn_network(config: Config) -> Network {
+    let address = config.peer_contact.addresses[0].clone();
+    let net = Network::new(
+        Arc::new(OffsetTime::new()),
+        config,
+        Box::new(|fut| {
+            tokio::spawn(fut);
+        }),
+    )
+    .await;
+    net.listen_on(vec![address]).await;
+    net
+}
+
+/// Waits until `peer_id` joins, ignoring other events.
+async fn wait_for_peer_joined<S>(events: &mut S, peer_id: PeerId, duration: Duration) -> bool
+where
+    S: Stream<Item = Result<NetworkEvent<PeerId>, BroadcastStreamRecvError>> + Unpin,
+{
+    timeout(duration, async {
+        while let Some(event) = events.next().await {
+            if let Ok(NetworkEvent::PeerJoined(joined_peer_id, _)) = event {
+                if joined_peer_id == peer_id {
+                    return true;
+                }
+            }
+        }
+        false
+    })
+    .await
+    .unwrap_or(false)
+}
+
+/// Sets up a relay, a private node that only listens on a circuit through the relay, and a public node. Returns
+/// whether the public node could connect to the private node through the relay.
+async fn connect_through_relay(relay_server: bool) -> bool {
+    let mut rng = thread_rng();
+
+    let relay_address = multiaddr![Memory(rng.gen::<u64>())];
+    let mut relay_config = network_config(relay_address.clone());
+    relay_config.relay_server = relay_server;
+    let relay = spawn_network(relay_config).await;
+    let relay_peer_id = relay.get_local_peer_id();
+
+    // The private node doesn't listen on an address of its own, so it can only be reached through the relay.
+    let private = Network::new(
+        Arc::new(OffsetTime::new()),
+        network_config(multiaddr![Memory(rng.gen::<u64>())]),
+        Box::new(|fut| {
+            tokio::spawn(fut);
+        }),
+    )
+    .await;
+    let private_peer_id = private.get_local_peer_id();
+    let mut private_events = private.subscribe_events();
+    private.dial_address(relay_address.clone()).await.unwrap();
+    assert!(wait_for_peer_joined(&mut private_events, relay_peer_id, Duration::from_secs(5)).await);
+
+    let circuit_address = relay_address
+        .with(Protocol::P2p(relay_peer_id.into()))
+        .with(Protocol::P2pCircuit);
+    private.listen_on(vec![circuit_address.clone()]).await;
+
+    let public = spawn_network(network_config(multiaddr![Memory(rng.gen::<u64>())])).await;
+    let mut public_events = public.subscribe_events();
+    let private_address = circuit_address.with(Protocol::P2p(private_peer_id.into()));
+
+    // The reservation is made asynchronously, so retry until the relay accepted it.
+    for _ in 0..10 {
+        if public.dial_address(private_address.clone()).await.is_ok()
+            && wait_for_peer_joined(&mut public_events, private_peer_id, Duration::from_secs(1))
+                .await
+        {
+            return true;
+        }
+    }
+    false
+}
+
+#[test(tokio::test)]
+async fn peers_can_connect_through_a_relay() {
+    assert!(connect_through_relay(true).await);
+}
+
+#[test(tokio::test)]
+async fn relay_server_is_disabled_by_default() {
+    assert!(!connect_through_relay(false).await);
+}
+
+#[test(tokio::test)]
+async fn sentry_does_not_hand_out_protected_peer_addresses() {
+    let mut rng = thread_rng();
+
+    let mut validator_config = network_config(multiaddr![Memory(rng.gen::<u64>())]);
+    let validator_peer_id = validator_config.keypair.public().to_peer_id();
+
+    let sentry_address = multiaddr![Memory(rng.gen::<u64>())];
+    let mut sentry_config = network_config(sentry_address.clone());
+    sentry_config.protected_peers = vec![validator_peer_id];
+    let sentry = spawn_network(sentry_config).await;
+    let sentry_peer_id = sentry.get_local_peer_id();
+    let mut sentry_events = sentry.subscribe_events();
+
+    // The validator identifies itself to the sentry with its own listen address.
+    validator_config.sentries = vec![sentry_address
+        .clone()
+        .with(Protocol::P2p(sentry_peer_id.into()))];
+    let validator = spawn_network(validator_config).await;
+    validator.start_connecting().await;
+    assert!(
+        wait_for_peer_joined(
+            &mut sentry_events,
+            validator_peer_id,
+            Duration::from_secs(5)
+        )
+        .await
+    );
+
+    // Another peer bootstraps its DHT from the sentry.
+    let outsider = spawn_network(network_config(multiaddr![Memory(rng.gen::<u64>())])).await;
+    let mut outsider_events = outsider.subscribe_events();
+    outsider.dial_address(sentry_address).await.unwrap();
+    assert!(
+        wait_for_peer_joined(&mut outsider_events, sentry_peer_id, Duration::from_secs(5)).await
+    );
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    // It must not have learned any address of the validator.
+    assert!(!outsider.has_peer(validator_peer_id));
+    assert!(matches!(
+        outsider.dial_peer(validator_peer_id).await,
+        Err(NetworkError::Dial(DialError::NoAddresses))
+    ));
+}
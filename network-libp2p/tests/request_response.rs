@@ -293,6 +293,10 @@ fn network_config(address: Multiaddr) -> Config {
         keypair,
         peer_contact,
         seeds: Vec::new(),
+        static_peers: Vec::new(),
+        sentries: Vec::new(),
+        protected_peers: Vec::new(),
+        relay_server: false,
         discovery: DiscoveryConfig {
             genesis_hash: Default::default(),
             update_interval: Duration::from_secs(60),
@@ -302,9 +306,12 @@ fn network_config(address: Multiaddr) -> Config {
             min_send_update_interval: Duration::from_secs(30),
             house_keeping_interval: Duration::from_secs(60),
             keep_alive: KeepAlive::Yes,
+            add_observed_addresses: true,
         },
         kademlia: Default::default(),
         gossipsub,
+        gossipsub_topic_scores: Default::default(),
+        autonat: Default::default(),
         memory_transport: true,
         required_services: Services::all(),
         tls: None,
@@ -394,6 +401,49 @@ async fn test_valid_request_valid_response() {
     };
 }
 
+// Test that the traffic of a request and its response is accounted for the peer and the request type
+#[test(tokio::test)]
+async fn test_request_traffic_is_accounted() {
+    let (net1, net2) = TestNetwork::create_connected_networks().await;
+
+    let test_request = TestRequest { request: 42 };
+    let test_response = TestResponse { response: 43 };
+
+    let net1 = Arc::new(net1);
+
+    tokio::spawn({
+        let net1 = Arc::clone(&net1);
+        let test_request = test_request.clone();
+        async move {
+            respond_requests::<TestRequest, TestRequest>(net1, Some(test_response), test_request)
+                .await
+        }
+    });
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    assert!(net2
+        .request::<TestRequest>(test_request, net1.get_local_peer_id())
+        .await
+        .is_ok());
+
+    // The requesting side sent the request and received the response
+    let stats = net2.network_stats();
+    let request_stats = stats.message_types.get("TestRequest").unwrap();
+    assert_eq!(request_stats.messages_sent, 1);
+    assert_eq!(request_stats.messages_received, 1);
+    let peer_stats = stats.peers.get(&net1.get_local_peer_id()).unwrap();
+    assert!(peer_stats.bytes_sent >= request_stats.bytes_sent);
+    assert!(peer_stats.bytes_received >= request_stats.bytes_received);
+
+    // The responding side received the request and sent the response
+    let stats = net1.network_stats();
+    let request_stats = stats.message_types.get("TestRequest").unwrap();
+    assert_eq!(request_stats.messages_received, 1);
+    assert_eq!(request_stats.messages_sent, 1);
+    assert!(stats.peers.contains_key(&net2.get_local_peer_id()));
+}
+
 // Test that we can send multiple requests and correctly receive the responses given a proper
 // request listener is replying in the peer specified
 #[test(tokio::test(flavor = "multi_thread", worker_threads = 10))]
@@ -27,6 +27,7 @@ pub mod account;
 pub mod extended_transaction;
 pub mod history_proof;
 pub mod inherent;
+pub mod pst;
 pub mod reward;
 
 /// Transaction topic for the Mempool to request transactions from the network
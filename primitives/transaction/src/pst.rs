@@ -0,0 +1,257 @@
+//! Partially signed transactions (PSTs).
+//!
+//! A PST carries an unsigned transaction from the machine that created it to the machines holding the keys that
+//! have to sign it, possibly air-gapped ones, and back. Besides the transaction it records who has to sign it, which
+//! signatures were collected so far and how they are combined into the final proof.
+use std::collections::BTreeMap;
+
+use nimiq_keys::{Address, KeyPair};
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use thiserror::Error;
+
+use crate::{
+    account::{
+        htlc_contract::{AnyHash, OutgoingHTLCTransactionProof, PreImage},
+        staking_contract::IncomingStakingTransactionData,
+    },
+    SignatureProof, Transaction,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PstError {
+    #[error("Unsupported PST version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Deserialization error: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Over-long PST")]
+    OverLong,
+    #[error("{0} is not a signer of this transaction as {1:?}")]
+    UnknownSigner(Address, PstRole),
+    #[error("Invalid signature of {0}")]
+    InvalidSignature(Address),
+    #[error("Missing signature of {0} as {1:?}")]
+    MissingSignature(Address, PstRole),
+    #[error("The proof template doesn't match the signers")]
+    InvalidTemplate,
+}
+
+/// The part of the transaction a signer signs for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum PstRole {
+    /// Signs the proof of the transaction on behalf of the sender, e.g. the owner of a vesting contract or the staker
+    /// of an outgoing staking transaction.
+    Sender,
+    /// Signs the proof of an HTLC redemption on behalf of its recipient.
+    HtlcRecipient,
+    /// Signs the data of an incoming staking transaction, e.g. the staker or the validator's cold or warm key.
+    StakingData,
+}
+
+/// A signer of a PST and its signature, once collected.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PstSigner {
+    pub address: Address,
+    pub role: PstRole,
+    pub signature_proof: Option<SignatureProof>,
+}
+
+/// How the collected signatures are combined into the proof of the transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum PstProofTemplate {
+    /// The signature proof of the sender, as used by basic and vesting accounts and outgoing staking transactions.
+    Signature,
+    /// An HTLC redemption by its recipient, revealing the pre-image.
+    HtlcRegularTransfer {
+        hash_depth: u8,
+        hash_root: AnyHash,
+        pre_image: PreImage,
+    },
+    /// An HTLC redemption agreed to by both its sender and recipient.
+    HtlcEarlyResolve,
+    /// An HTLC redemption by its sender after the timeout.
+    HtlcTimeoutResolve,
+}
+
+/// A transaction together with the signatures collected for it so far.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PartiallySignedTransaction {
+    version: u8,
+    /// The transaction without a proof. For incoming staking transactions, the staking data contains an empty
+    /// signature until the PST is finalized.
+    pub transaction: Transaction,
+    pub proof_template: PstProofTemplate,
+    pub signers: Vec<PstSigner>,
+    /// Free-form information for the signers, e.g. a description of the payment.
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl PartiallySignedTransaction {
+    pub const VERSION: u8 = 1;
+
+    pub fn new(transaction: Transaction, proof_template: PstProofTemplate) -> Self {
+        PartiallySignedTransaction {
+            version: Self::VERSION,
+            transaction,
+            proof_template,
+            signers: vec![],
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Parses a serialized PST, rejecting unknown versions.
+    pub fn parse(bytes: &[u8]) -> Result<Self, PstError> {
+        // The version is serialized first, such that it can be checked before the rest of the PST.
+        let (version, _) = u8::deserialize_take(bytes)?;
+        if version != Self::VERSION {
+            return Err(PstError::UnsupportedVersion(version));
+        }
+
+        let (pst, left_over) = Self::deserialize_take(bytes)?;
+        if !left_over.is_empty() {
+            return Err(PstError::OverLong);
+        }
+        Ok(pst)
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Adds a signer whose signature is required.
+    pub fn add_signer(&mut self, address: Address, role: PstRole) -> &mut Self {
+        self.signers.push(PstSigner {
+            address,
+            role,
+            signature_proof: None,
+        });
+        self
+    }
+
+    /// Returns the signers whose signatures are still missing.
+    pub fn missing_signers(&self) -> impl Iterator<Item = &PstSigner> {
+        self.signers
+            .iter()
+            .filter(|signer| signer.signature_proof.is_none())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.missing_signers().next().is_none()
+    }
+
+    /// Returns the data a signer in the given role has to sign. The sender of an incoming staking transaction signs
+    /// the staking data including its signature, so the staking data has to be signed first.
+    pub fn signing_data(&self, role: PstRole) -> Result<Vec<u8>, PstError> {
+        match role {
+            PstRole::Sender => Ok(self.transaction_with_staking_data()?.serialize_content()),
+            PstRole::HtlcRecipient | PstRole::StakingData => {
+                Ok(self.transaction.serialize_content())
+            }
+        }
+    }
+
+    /// Adds the signature of a signer in the given role after verifying it.
+    pub fn add_signature(
+        &mut self,
+        role: PstRole,
+        signature_proof: SignatureProof,
+    ) -> Result<(), PstError> {
+        let address = signature_proof.compute_signer();
+        if !signature_proof.verify(&self.signing_data(role)?) {
+            return Err(PstError::InvalidSignature(address));
+        }
+
+        let signer = self
+            .signers
+            .iter_mut()
+            .find(|signer| signer.address == address && signer.role == role)
+            .ok_or(PstError::UnknownSigner(address, role))?;
+        signer.signature_proof = Some(signature_proof);
+        Ok(())
+    }
+
+    /// Adds all missing signatures of the key pair's address. The staking data is signed first, since the sender's
+    /// signature covers it. Returns the number of signatures added.
+    pub fn sign_with_key_pair(&mut self, key_pair: &KeyPair) -> Result<usize, PstError> {
+        let address = Address::from(key_pair);
+        let roles: Vec<_> = [
+            PstRole::StakingData,
+            PstRole::Sender,
+            PstRole::HtlcRecipient,
+        ]
+        .into_iter()
+        .filter(|role| {
+            self.missing_signers()
+                .any(|signer| signer.address == address && signer.role == *role)
+        })
+        .collect();
+
+        for role in &roles {
+            let signature = key_pair.sign(&self.signing_data(*role)?);
+            self.add_signature(*role, SignatureProof::from(key_pair.public, signature))?;
+        }
+        Ok(roles.len())
+    }
+
+    /// Combines the collected signatures into the proof and returns the signed transaction.
+    pub fn finalize(&self) -> Result<Transaction, PstError> {
+        let mut transaction = self.transaction_with_staking_data()?;
+        let sender = self.signature(PstRole::Sender);
+        let recipient = self.signature(PstRole::HtlcRecipient);
+
+        transaction.proof = match &self.proof_template {
+            PstProofTemplate::Signature => sender?.serialize_to_vec(),
+            PstProofTemplate::HtlcRegularTransfer {
+                hash_depth,
+                hash_root,
+                pre_image,
+            } => OutgoingHTLCTransactionProof::RegularTransfer {
+                hash_depth: *hash_depth,
+                hash_root: hash_root.clone(),
+                pre_image: pre_image.clone(),
+                signature_proof: recipient?,
+            }
+            .serialize_to_vec(),
+            PstProofTemplate::HtlcEarlyResolve => OutgoingHTLCTransactionProof::EarlyResolve {
+                signature_proof_recipient: recipient?,
+                signature_proof_sender: sender?,
+            }
+            .serialize_to_vec(),
+            PstProofTemplate::HtlcTimeoutResolve => OutgoingHTLCTransactionProof::TimeoutResolve {
+                signature_proof_sender: sender?,
+            }
+            .serialize_to_vec(),
+        };
+        Ok(transaction)
+    }
+
+    /// Returns the signature of the single signer in the given role.
+    fn signature(&self, role: PstRole) -> Result<SignatureProof, PstError> {
+        let mut signers = self.signers.iter().filter(|signer| signer.role == role);
+        let signer = signers.next().ok_or(PstError::InvalidTemplate)?;
+        if signers.next().is_some() {
+            return Err(PstError::InvalidTemplate);
+        }
+        signer
+            .signature_proof
+            .clone()
+            .ok_or_else(|| PstError::MissingSignature(signer.address.clone(), role))
+    }
+
+    /// Returns the transaction with the signature of the staking data filled in, if there is one.
+    fn transaction_with_staking_data(&self) -> Result<Transaction, PstError> {
+        let mut transaction = self.transaction.clone();
+        if self
+            .signers
+            .iter()
+            .any(|signer| signer.role == PstRole::StakingData)
+        {
+            transaction.recipient_data = IncomingStakingTransactionData::set_signature_on_data(
+                &transaction.recipient_data,
+                self.signature(PstRole::StakingData)?,
+            )?;
+        }
+        Ok(transaction)
+    }
+}
@@ -1,9 +1,20 @@
+use std::{fs, path::PathBuf};
+
 use anyhow::Error;
 use async_trait::async_trait;
 use clap::Parser;
-use nimiq_keys::{Address, PublicKey, Signature};
-use nimiq_rpc_interface::{blockchain::BlockchainInterface, wallet::WalletInterface};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::{typed_message::TypedMessage, Address, PublicKey, Signature};
+use nimiq_primitives::coin::Coin;
+use nimiq_rpc_interface::{
+    blockchain::BlockchainInterface,
+    consensus::ConsensusInterface,
+    types::{HashAlgorithm, HtlcProofType, ValidityStartHeight},
+    wallet::WalletInterface,
+};
+use nimiq_transaction::account::htlc_contract::PreImage;
 
+use super::TransactionCommand;
 use crate::Client;
 
 #[async_trait]
@@ -35,6 +46,199 @@ pub enum AccountCommand {
         key_data: String,
     },
 
+    /// Imports an HD wallet from a BIP39 mnemonic. Its accounts have to be derived before they can be used.
+    ImportMnemonic {
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// The password protecting the mnemonic, if any.
+        #[clap(long)]
+        mnemonic_password: Option<String>,
+
+        /// The path the accounts are derived along. Defaults to `m/44'/242'/0'`.
+        #[clap(long)]
+        path: Option<String>,
+
+        /// The words of the mnemonic.
+        #[clap(num_args = 1..)]
+        mnemonic: Vec<String>,
+    },
+
+    /// Derives an account of an HD wallet. The account remains locked after this operation.
+    Derive {
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// The index of the account. Defaults to the lowest index that wasn't derived yet.
+        #[clap(long)]
+        index: Option<u32>,
+
+        /// The ID of the HD wallet.
+        wallet_id: Address,
+    },
+
+    /// Lists the accounts derived from an HD wallet.
+    ListDerived {
+        /// The ID of the HD wallet.
+        wallet_id: Address,
+    },
+
+    /// Derives the address of a multisig account that requires `num_signers` of the given public keys.
+    MultisigAddress {
+        /// The number of signers required.
+        #[clap(long)]
+        num_signers: usize,
+
+        /// The public keys of all possible signers, separated by commas.
+        #[clap(long, value_delimiter = ',', required = true)]
+        public_keys: Vec<PublicKey>,
+    },
+
+    /// Creates a commitment for signing a multisig transaction. The commitment has to be shared with the other
+    /// signers, its random secret is kept by the node.
+    MultisigCommit {},
+
+    /// Partially signs a multisig transaction with the specified account.
+    MultisigSign {
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// The number of signers required by the multisig account.
+        #[clap(long)]
+        num_signers: usize,
+
+        /// The public keys of all possible signers of the multisig account, separated by commas.
+        #[clap(long, value_delimiter = ',', required = true)]
+        public_keys: Vec<PublicKey>,
+
+        /// The public keys of the signers of this transaction, separated by commas.
+        #[clap(long, value_delimiter = ',', required = true)]
+        signers: Vec<PublicKey>,
+
+        /// The commitments of the signers of this transaction, separated by commas.
+        #[clap(long, value_delimiter = ',', required = true)]
+        commitments: Vec<String>,
+
+        /// The commitment of this account, as created by the node. It can only be used once.
+        #[clap(long)]
+        commitment: String,
+
+        /// The serialized transaction in hex.
+        raw_tx: String,
+
+        /// The address of the signing account.
+        address: Address,
+    },
+
+    /// Combines the partial signatures of the signers of a multisig transaction and prints the signed transaction.
+    MultisigCombine {
+        /// The number of signers required by the multisig account.
+        #[clap(long)]
+        num_signers: usize,
+
+        /// The public keys of all possible signers of the multisig account, separated by commas.
+        #[clap(long, value_delimiter = ',', required = true)]
+        public_keys: Vec<PublicKey>,
+
+        /// The public keys of the signers of this transaction, separated by commas.
+        #[clap(long, value_delimiter = ',', required = true)]
+        signers: Vec<PublicKey>,
+
+        /// The commitments of the signers of this transaction, separated by commas.
+        #[clap(long, value_delimiter = ',', required = true)]
+        commitments: Vec<String>,
+
+        /// The partial signatures of the signers of this transaction, separated by commas.
+        #[clap(long, value_delimiter = ',', required = true)]
+        partial_signatures: Vec<String>,
+
+        /// The serialized transaction in hex.
+        raw_tx: String,
+    },
+
+    /// Creates a partially signed transaction (PST) from an unsigned transaction.
+    CreatePst {
+        /// The address signing on behalf of the sender. Defaults to the sender. For incoming staking transactions,
+        /// the address signing the staking data.
+        #[clap(long)]
+        signer: Option<Address>,
+
+        /// How the HTLC is resolved, for transactions sent from an HTLC. The signer is then required: the HTLC's
+        /// recipient for regular transfers and the HTLC's sender otherwise.
+        #[clap(long, value_enum)]
+        htlc_proof_type: Option<HtlcProofType>,
+
+        /// The address of the recipient in the HTLC contract, for early resolves.
+        #[clap(long)]
+        htlc_recipient: Option<Address>,
+
+        /// The pre-image, for regular transfers.
+        #[clap(long)]
+        pre_image: Option<PreImage>,
+
+        /// The result of hashing the pre-image `hash_count` times, for regular transfers.
+        #[clap(long, requires = "hash_algorithm")]
+        hash_root: Option<String>,
+
+        /// Number of times the pre-image was hashed, for regular transfers.
+        #[clap(long)]
+        hash_count: Option<u8>,
+
+        /// The hashing algorithm of the `hash_root`.
+        #[clap(long, value_enum)]
+        hash_algorithm: Option<HashAlgorithm>,
+
+        /// The serialized transaction in hex.
+        raw_tx: String,
+    },
+
+    /// Adds the signatures of the specified account to a PST and prints the updated PST.
+    SignPst {
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// The PST in hex.
+        pst: String,
+
+        /// The address of the signing account.
+        address: Address,
+    },
+
+    /// Combines the signatures of a complete PST and prints the signed transaction.
+    FinalizePst {
+        /// The PST in hex.
+        pst: String,
+    },
+
+    /// Exports accounts and HD wallets to an encrypted backup file. The accounts stay locked with their own
+    /// passwords.
+    ExportBackup {
+        /// The password the backup is encrypted with.
+        #[clap(short = 'B', long)]
+        backup_password: String,
+
+        /// The file the backup is written to.
+        #[clap(short, long)]
+        output: PathBuf,
+
+        /// The addresses of the accounts and the IDs of the HD wallets to export. Exports all of them if omitted.
+        addresses: Vec<Address>,
+    },
+
+    /// Restores the accounts and HD wallets of a backup file.
+    ImportBackup {
+        /// The password the backup is encrypted with.
+        #[clap(short = 'B', long)]
+        backup_password: String,
+
+        /// Replaces accounts and HD wallets that already exist.
+        #[clap(long)]
+        overwrite: bool,
+
+        /// The backup file.
+        file: PathBuf,
+    },
+
     /// Checks if account is imported.
     IsImported {
         /// The account's address.
@@ -92,6 +296,148 @@ pub enum AccountCommand {
         is_hex: bool,
     },
 
+    /// Signs a typed structured message. The message is shown before it is signed.
+    SignTyped {
+        #[clap(short = 'P', long)]
+        password: Option<String>,
+
+        /// The JSON file containing the typed message.
+        file: PathBuf,
+
+        /// The address to sign the message.
+        address: Address,
+    },
+
+    /// Verifies the signature of a typed structured message.
+    VerifyTyped {
+        /// The JSON file containing the typed message.
+        file: PathBuf,
+
+        /// The public key returned upon signing the message.
+        public_key: PublicKey,
+
+        /// The signature returned upon signing the message.
+        signature: Signature,
+    },
+
+    /// Imports an address as a watch-only account. Its balance and history can be queried and unsigned
+    /// transactions can be created from it, but nothing can be signed.
+    ImportWatchOnly {
+        /// The address of the account.
+        address: Address,
+
+        /// The public key of the account, if known.
+        #[clap(long)]
+        public_key: Option<PublicKey>,
+
+        /// An extended public key to watch the derived accounts of. Not supported, since Ed25519 keys are derived
+        /// with hardened derivation only.
+        #[clap(long)]
+        extended_public_key: Option<String>,
+    },
+
+    /// Lists the addresses of all watch-only accounts.
+    ListWatchOnly {},
+
+    /// Removes a watch-only account.
+    RemoveWatchOnly {
+        /// The address of the account.
+        address: Address,
+    },
+
+    /// Shows the balance, pending transactions and history of a watch-only account.
+    GetWatchOnly {
+        /// The address of the account.
+        address: Address,
+
+        /// The maximum number of transactions of the history to show.
+        #[clap(short, long)]
+        max: Option<u16>,
+    },
+
+    /// Creates an unsigned basic transaction from a watch-only account and outputs it as hex string. It can be
+    /// signed offline, e.g. with `nimiq-signtx`.
+    CreateWatchOnlyTx {
+        /// The address of the watch-only account.
+        address: Address,
+
+        /// The recipient of the transaction.
+        recipient: Address,
+
+        /// The amount of NIM to be sent.
+        value: Coin,
+
+        /// The transaction fee. If absent it defaults to 0 NIM.
+        #[clap(short, long, default_value = "0")]
+        fee: Coin,
+
+        /// The block height from which on the transaction could be applied.
+        /// If absent it defaults to the current block height at time of processing.
+        #[clap(short, long, default_value_t)]
+        validity_start_height: ValidityStartHeight,
+    },
+
+    /// Lists the transactions of a wallet address recorded by the node, newest first, with their labels.
+    History {
+        /// The address of the account.
+        address: Address,
+
+        /// The number of transactions to skip.
+        #[clap(long)]
+        offset: Option<u32>,
+
+        /// The maximum number of transactions to list.
+        #[clap(short, long)]
+        limit: Option<u16>,
+    },
+
+    /// Labels a transaction, replacing its previous label.
+    Label {
+        /// The hash of the transaction.
+        hash: Blake2bHash,
+
+        /// The label of the transaction.
+        label: String,
+
+        /// An additional note.
+        #[clap(short, long)]
+        note: Option<String>,
+    },
+
+    /// Removes the label of a transaction.
+    RemoveLabel {
+        /// The hash of the transaction.
+        hash: Blake2bHash,
+    },
+
+    /// Pays `recipient` from one or more unlocked accounts, selecting the accounts automatically. Balances reserved
+    /// by pending transactions are not spent. One transaction is sent per selected account.
+    Fund {
+        /// The recipient of the payment.
+        recipient: Address,
+
+        /// The amount of NIM the recipient receives. If absent, everything the accounts have available is swept.
+        #[clap(long)]
+        value: Option<Coin>,
+
+        /// The fee per byte each transaction pays.
+        #[clap(long, default_value_t = 0)]
+        fee_per_byte: u64,
+
+        /// The block height from which on the transactions could be applied.
+        /// If absent it defaults to the current block height at time of processing.
+        #[clap(short, long, default_value_t)]
+        validity_start_height: ValidityStartHeight,
+
+        /// The accounts that may fund the payment. All unlocked accounts if omitted.
+        #[clap(short, long)]
+        sender: Vec<Address>,
+
+        /// Don't actually send the transactions, but only show the plan.
+        #[clap(long)]
+        dry: bool,
+    },
+
     /// Queries all accounts in the accounts tree
     GetAll {},
 
@@ -127,6 +473,170 @@ impl HandleSubcommand for AccountCommand {
                 let address = client.wallet.import_raw_key(key_data, password).await?;
                 println!("{address:#?}");
             }
+            AccountCommand::ImportMnemonic {
+                password,
+                mnemonic_password,
+                path,
+                mnemonic,
+            } => {
+                let wallet_id = client
+                    .wallet
+                    .import_mnemonic(mnemonic.join(" "), mnemonic_password, path, password)
+                    .await?;
+                println!("{wallet_id:#?}");
+            }
+            AccountCommand::Derive {
+                password,
+                index,
+                wallet_id,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .derive_account(wallet_id, index, password)
+                        .await?
+                );
+            }
+            AccountCommand::ListDerived { wallet_id } => {
+                println!(
+                    "{:#?}",
+                    client.wallet.list_derived_accounts(wallet_id).await?
+                );
+            }
+            AccountCommand::MultisigAddress {
+                num_signers,
+                public_keys,
+            } => {
+                let address = client
+                    .wallet
+                    .derive_multisig_address(public_keys, num_signers)
+                    .await?;
+                println!("{address:#?}");
+            }
+            AccountCommand::MultisigCommit {} => {
+                println!("{:#?}", client.wallet.create_multisig_commitment().await?);
+            }
+            AccountCommand::MultisigSign {
+                password,
+                num_signers,
+                public_keys,
+                signers,
+                commitments,
+                commitment,
+                raw_tx,
+                address,
+            } => {
+                let partial_signature = client
+                    .wallet
+                    .sign_multisig_transaction(
+                        raw_tx,
+                        address,
+                        password,
+                        public_keys,
+                        num_signers,
+                        signers,
+                        commitment,
+                        commitments,
+                    )
+                    .await?;
+                println!("{partial_signature:#?}");
+            }
+            AccountCommand::MultisigCombine {
+                num_signers,
+                public_keys,
+                signers,
+                commitments,
+                partial_signatures,
+                raw_tx,
+            } => {
+                let tx = client
+                    .wallet
+                    .combine_multisig_signatures(
+                        raw_tx,
+                        public_keys,
+                        num_signers,
+                        signers,
+                        commitments,
+                        partial_signatures,
+                    )
+                    .await?;
+                println!("{tx:#?}");
+            }
+            AccountCommand::CreatePst {
+                signer,
+                htlc_proof_type,
+                htlc_recipient,
+                pre_image,
+                hash_root,
+                hash_count,
+                hash_algorithm,
+                raw_tx,
+            } => {
+                let hash_root = match (hash_algorithm, hash_root) {
+                    (Some(hash_algorithm), Some(hash_root)) => {
+                        Some(TransactionCommand::parse_hash(&hash_algorithm, hash_root)?)
+                    }
+                    _ => None,
+                };
+                let pst = client
+                    .wallet
+                    .create_pst(
+                        raw_tx,
+                        signer,
+                        htlc_proof_type,
+                        htlc_recipient,
+                        pre_image,
+                        hash_root,
+                        hash_count,
+                    )
+                    .await?;
+                println!("{pst:#?}");
+            }
+            AccountCommand::SignPst {
+                password,
+                pst,
+                address,
+            } => {
+                let pst = client.wallet.sign_pst(pst, address, password).await?;
+                println!("{pst:#?}");
+            }
+            AccountCommand::FinalizePst { pst } => {
+                let tx = client.wallet.finalize_pst(pst).await?;
+                println!("{tx:#?}");
+            }
+            AccountCommand::ExportBackup {
+                backup_password,
+                output,
+                addresses,
+            } => {
+                let addresses = (!addresses.is_empty()).then_some(addresses);
+                let backup = client
+                    .wallet
+                    .export_wallet_backup(addresses, backup_password)
+                    .await?
+                    .data;
+                fs::write(&output, backup)?;
+                println!("Backup written to {}", output.display());
+            }
+            AccountCommand::ImportBackup {
+                backup_password,
+                overwrite,
+                file,
+            } => {
+                let backup = fs::read_to_string(&file)?;
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .import_wallet_backup(
+                            backup.trim().to_string(),
+                            backup_password,
+                            Some(overwrite)
+                        )
+                        .await?
+                );
+            }
             AccountCommand::IsImported { address } => {
                 println!("{:#?}", client.wallet.is_account_imported(address).await?);
             }
@@ -172,6 +682,141 @@ impl HandleSubcommand for AccountCommand {
                         .await?
                 );
             }
+            AccountCommand::SignTyped {
+                password,
+                file,
+                address,
+            } => {
+                let message: TypedMessage = serde_json::from_str(&fs::read_to_string(&file)?)?;
+                println!("{message}");
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .sign_typed_message(message, address, password)
+                        .await?
+                );
+            }
+            AccountCommand::VerifyTyped {
+                file,
+                public_key,
+                signature,
+            } => {
+                let message: TypedMessage = serde_json::from_str(&fs::read_to_string(&file)?)?;
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .verify_typed_message(message, public_key, signature)
+                        .await?
+                );
+            }
+            AccountCommand::ImportWatchOnly {
+                address,
+                public_key,
+                extended_public_key,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .import_watch_only_account(address, public_key, extended_public_key)
+                        .await?
+                );
+            }
+            AccountCommand::ListWatchOnly {} => {
+                let addresses = client.wallet.list_watch_only_accounts().await?.data;
+                for address in &addresses {
+                    println!("{}", address.to_user_friendly_address());
+                }
+            }
+            AccountCommand::RemoveWatchOnly { address } => {
+                println!(
+                    "{:#?}",
+                    client.wallet.remove_watch_only_account(address).await?
+                );
+            }
+            AccountCommand::GetWatchOnly { address, max } => {
+                println!(
+                    "{:#?}",
+                    client.wallet.get_watch_only_account(address, max).await?
+                );
+            }
+            AccountCommand::CreateWatchOnlyTx {
+                address,
+                recipient,
+                value,
+                fee,
+                validity_start_height,
+            } => {
+                let tx = client
+                    .wallet
+                    .create_watch_only_transaction(
+                        address,
+                        recipient,
+                        value,
+                        fee,
+                        validity_start_height,
+                    )
+                    .await?
+                    .data;
+                println!("{tx}");
+            }
+            AccountCommand::History {
+                address,
+                offset,
+                limit,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .get_wallet_transactions(address, offset, limit)
+                        .await?
+                );
+            }
+            AccountCommand::Label { hash, label, note } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .wallet
+                        .set_transaction_label(hash, label, note)
+                        .await?
+                );
+            }
+            AccountCommand::RemoveLabel { hash } => {
+                println!("{:#?}", client.wallet.remove_transaction_label(hash).await?);
+            }
+            AccountCommand::Fund {
+                recipient,
+                value,
+                fee_per_byte,
+                validity_start_height,
+                sender,
+                dry,
+            } => {
+                let plan = client
+                    .wallet
+                    .create_funded_transactions(
+                        recipient,
+                        value,
+                        fee_per_byte,
+                        validity_start_height,
+                        sender,
+                    )
+                    .await?
+                    .data;
+                println!("{plan:#?}");
+                if !dry {
+                    for input in plan.inputs {
+                        let txid = client
+                            .consensus
+                            .send_raw_transaction(input.raw_transaction)
+                            .await?;
+                        println!("{txid:#?}");
+                    }
+                }
+            }
             AccountCommand::Get { address } => {
                 println!(
                     "{:#?}",
@@ -306,7 +306,10 @@ pub enum TransactionCommand {
 }
 
 impl TransactionCommand {
-    fn parse_hash(hash_algorithm: &HashAlgorithm, hash_str: String) -> Result<AnyHash, Error> {
+    pub(super) fn parse_hash(
+        hash_algorithm: &HashAlgorithm,
+        hash_str: String,
+    ) -> Result<AnyHash, Error> {
         match hash_algorithm {
             HashAlgorithm::Blake2b => Ok(AnyHash::Blake2b(AnyHash32::from_str(&hash_str)?)),
             HashAlgorithm::Sha256 => Ok(AnyHash::Sha256(AnyHash32::from_str(&hash_str)?)),
@@ -1,6 +1,9 @@
+use std::{fs, path::PathBuf};
+
 use anyhow::Error;
 use async_trait::async_trait;
 use clap::Parser;
+use futures::StreamExt;
 use nimiq_keys::Address;
 use nimiq_rpc_interface::{consensus::ConsensusInterface, validator::ValidatorInterface};
 
@@ -28,6 +31,45 @@ pub enum ValidatorCommand {
     /// Returns the voting key of the local validator.
     ValidatorVotingKey {},
 
+    /// Returns the staking state and the slots of the local validator.
+    ValidatorStatus {},
+
+    /// Returns the performance statistics of the local validator in the current epoch.
+    ValidatorStats {},
+
+    /// Schedules a maintenance of the local validator. It deactivates itself shortly before the next election block.
+    /// Use maintenance-status to check when it is safe to stop the node.
+    ScheduleMaintenance {
+        /// Reactivate the validator once the node is restarted after the maintenance.
+        #[clap(long)]
+        reactivate_after_restart: bool,
+    },
+
+    /// Returns the status of the scheduled maintenance of the local validator.
+    MaintenanceStatus {},
+
+    /// Cancels the scheduled maintenance of the local validator.
+    CancelMaintenance {},
+
+    /// Returns the diagnostics of the most recent skip block and Tendermint vote aggregations of the local validator.
+    AggregationDiagnostics {},
+
+    /// Follows the progress of the Tendermint instances producing macro blocks on the local validator.
+    FollowTendermint {},
+
+    /// Exports the signing history of the local validator to a file. Import it on the new machine before
+    /// migrating the validator to it, such that it never signs messages conflicting with the ones signed here.
+    ExportSigningHistory {
+        /// The file to write the signing history to.
+        file: PathBuf,
+    },
+
+    /// Imports a signing history exported by export-signing-history into the local validator.
+    ImportSigningHistory {
+        /// The file to read the signing history from.
+        file: PathBuf,
+    },
+
     /// Sends a `new_validator` transaction to the network. You need to provide the address of a basic
     /// account (the sender wallet) to pay the transaction fee and the validator deposit. The sender wallet must be unlocked
     /// prior to this command.
@@ -142,6 +184,70 @@ impl HandleSubcommand for ValidatorCommand {
                 println!("{:#?}", client.validator.get_voting_key().await?);
             }
 
+            ValidatorCommand::ValidatorStatus {} => {
+                println!("{:#?}", client.validator.get_validator_status().await?);
+            }
+
+            ValidatorCommand::ValidatorStats {} => {
+                println!("{:#?}", client.validator.get_validator_stats().await?);
+            }
+
+            ValidatorCommand::ScheduleMaintenance {
+                reactivate_after_restart,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .validator
+                        .schedule_maintenance(reactivate_after_restart)
+                        .await?
+                );
+            }
+
+            ValidatorCommand::MaintenanceStatus {} => {
+                println!("{:#?}", client.validator.get_maintenance().await?);
+            }
+
+            ValidatorCommand::CancelMaintenance {} => {
+                let canceled = client.validator.cancel_maintenance().await?.data;
+                if canceled {
+                    println!("Maintenance canceled");
+                } else {
+                    println!("No maintenance was scheduled");
+                }
+            }
+
+            ValidatorCommand::AggregationDiagnostics {} => {
+                println!(
+                    "{:#?}",
+                    client.validator.get_aggregation_diagnostics().await?
+                );
+            }
+
+            ValidatorCommand::FollowTendermint {} => {
+                let mut stream = client.validator.subscribe_for_tendermint_events().await?;
+
+                while let Some(event) = stream.next().await {
+                    println!("{event:#?}");
+                }
+            }
+
+            ValidatorCommand::ExportSigningHistory { file } => {
+                let history = client.validator.export_signing_history().await?.data;
+                fs::write(&file, history)?;
+                println!("Signing history written to {}", file.display());
+            }
+
+            ValidatorCommand::ImportSigningHistory { file } => {
+                let history = fs::read_to_string(&file)?;
+                let imported = client
+                    .validator
+                    .import_signing_history(history.trim().to_string())
+                    .await?
+                    .data;
+                println!("Imported {imported} signing history records");
+            }
+
             ValidatorCommand::SetAutoReactivateValidator {
                 automatic_reactivate,
             } => {
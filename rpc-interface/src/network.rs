@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::types::RPCResult;
+use crate::types::{NetworkStats, RPCResult};
 
 #[nimiq_jsonrpc_derive::proxy(name = "NetworkProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -12,4 +12,6 @@ pub trait NetworkInterface {
     async fn get_peer_count(&mut self) -> RPCResult<usize, (), Self::Error>;
 
     async fn get_peer_list(&mut self) -> RPCResult<Vec<String>, (), Self::Error>;
+
+    async fn get_network_stats(&mut self) -> RPCResult<NetworkStats, (), Self::Error>;
 }
@@ -94,6 +94,18 @@ pub enum HashAlgorithm {
     Sha512 = 4,
 }
 
+/// How the HTLC sending a transaction is resolved.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "camelCase")]
+pub enum HtlcProofType {
+    /// The HTLC's recipient redeems the funds with the pre-image.
+    RegularTransfer,
+    /// The HTLC's sender and recipient agree to resolve the HTLC before its timeout.
+    EarlyResolve,
+    /// The HTLC's sender reclaims the funds after the timeout.
+    TimeoutResolve,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Block {
@@ -751,6 +763,193 @@ impl Validator {
     }
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ValidatorStakingState {
+    Active,
+    Inactive,
+    NoStake,
+    /// The staking contract is not available, e.g. because the node doesn't have consensus yet.
+    Unknown,
+}
+
+/// The status of the validator run by this node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStatus {
+    pub address: Address,
+    pub block_number: u32,
+    pub epoch_number: u32,
+    pub staking_state: ValidatorStakingState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jail_release: Option<u32>,
+    /// The slot band of the validator in the current epoch, if it is elected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot_band: Option<u16>,
+    pub num_slots: u16,
+}
+
+/// Performance statistics of the validator run by this node in the current epoch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStats {
+    pub epoch_number: u32,
+    /// The block counts only cover the blocks the node saw being added to the chain since this block.
+    pub since_block_number: u32,
+    pub produced_micro_blocks: u32,
+    pub missed_micro_blocks: u32,
+    pub skip_blocks_contributed: u32,
+    pub tendermint_rounds_participated: u32,
+    pub punished_slots_current_batch: u16,
+    pub punished_slots_previous_batch: u16,
+    /// An estimate of the reward paid out at the end of the current batch.
+    pub pending_reward: Coin,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MaintenanceStatus {
+    /// Waiting for the right time to deactivate the validator before the next election block.
+    Scheduled,
+    /// The deactivate transaction was sent, waiting for it to take effect.
+    Deactivating,
+    /// The validator is inactive and no longer elected, the node can be stopped.
+    SafeToStop,
+}
+
+/// A maintenance scheduled for the validator run by this node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorMaintenance {
+    pub status: MaintenanceStatus,
+    /// The block number at which the deactivate transaction was sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deactivate_tx_sent_at: Option<u32>,
+    pub reactivate_after_restart: bool,
+}
+
+/// The kind of a signature aggregation of the validator.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AggregationKind {
+    SkipBlock,
+    Tendermint,
+}
+
+/// The progress of a level of a signature aggregation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregationLevel {
+    pub level: usize,
+    pub peers: Vec<usize>,
+    /// Milliseconds from the start of the aggregation until the level was started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_after: Option<u64>,
+    /// Whether the level was started because the level timeout elapsed.
+    pub started_on_timeout: bool,
+    /// Milliseconds from the start of the aggregation until the contributions of all peers on the level were
+    /// received.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_after: Option<u64>,
+    /// The peers on the level whose contributions are missing.
+    pub missing_peers: Vec<usize>,
+}
+
+/// The contributions of a peer to a signature aggregation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregationPeer {
+    pub peer: usize,
+    pub level_updates: usize,
+    pub individual_contributions: usize,
+    pub wrong_level_updates: usize,
+    pub useless_contributions: usize,
+    /// The evaluator score of the last contribution of the peer.
+    pub last_score: usize,
+    /// The highest evaluator score of a contribution of the peer.
+    pub best_score: usize,
+}
+
+/// Diagnostics of a recent signature aggregation of the validator.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregationDiagnostics {
+    pub kind: AggregationKind,
+    /// The block number or Tendermint round and step the aggregation is for.
+    pub id: String,
+    /// Our peer ID in the aggregation.
+    pub node_id: usize,
+    /// Timestamp in milliseconds.
+    pub started_at: u64,
+    /// The level timeout in milliseconds.
+    pub level_timeout: u64,
+    /// The update interval in milliseconds.
+    pub update_interval: u64,
+    pub levels: Vec<AggregationLevel>,
+    pub peers: Vec<AggregationPeer>,
+    pub invalid_contributions: usize,
+    /// The number of contributors of the best aggregate.
+    pub contributors: usize,
+    /// Milliseconds from the start of the aggregation until all contributions were received.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_after: Option<u64>,
+}
+
+/// A step of a Tendermint round.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TendermintStep {
+    Propose,
+    Prevote,
+    Precommit,
+}
+
+/// How Tendermint handled a received proposal.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TendermintProposalResult {
+    Accepted,
+    Ignored,
+    Rejected,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum TendermintEventKind {
+    #[serde(rename_all = "camelCase")]
+    StepStarted { step: TendermintStep },
+    #[serde(rename_all = "camelCase")]
+    ProposalReceived {
+        /// The slot band of the proposer.
+        proposer: u16,
+        proposal_hash: Blake2sHash,
+        result: TendermintProposalResult,
+    },
+    /// The votes of the step reached 2f+1 slots.
+    #[serde(rename_all = "camelCase")]
+    AggregationCompleted {
+        step: TendermintStep,
+        contributors: usize,
+        /// The proposal that received 2f+1 votes, if any.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        proposal_hash: Option<Blake2sHash>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Decision { block_hash: Blake2bHash },
+}
+
+/// Progress of the Tendermint instance producing a macro block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TendermintEvent {
+    pub block_number: u32,
+    pub round: u32,
+    /// Milliseconds since the Tendermint instance for the block was started.
+    pub elapsed: u64,
+    #[serde(flatten)]
+    pub kind: TendermintEventKind,
+}
+
 pub type RPCResult<T, S, E> = Result<RPCData<T, S>, E>;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1167,3 +1366,42 @@ impl MempoolInfo {
         info
     }
 }
+
+/// Traffic counters of a peer, a message type or the whole network.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficStats {
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+    pub messages_received: u64,
+    pub messages_sent: u64,
+    /// Number of received gossipsub messages that were rejected by the validation.
+    pub invalid_messages: u64,
+}
+
+/// Traffic exchanged with a connected peer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerTrafficStats {
+    pub peer_id: String,
+    #[serde(flatten)]
+    pub traffic: TrafficStats,
+}
+
+/// Traffic of a gossipsub topic (prefixed with `gossip/`) or a request/message type.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageTypeTrafficStats {
+    pub message_type: String,
+    #[serde(flatten)]
+    pub traffic: TrafficStats,
+}
+
+/// Network traffic accounted per peer and per message type since the node started.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStats {
+    pub total: TrafficStats,
+    pub peers: Vec<PeerTrafficStats>,
+    pub message_types: Vec<MessageTypeTrafficStats>,
+}
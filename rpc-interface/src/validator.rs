@@ -1,7 +1,11 @@
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use nimiq_keys::Address;
 
-use crate::types::RPCResult;
+use crate::types::{
+    AggregationDiagnostics, RPCData, RPCResult, TendermintEvent, ValidatorMaintenance,
+    ValidatorStats, ValidatorStatus,
+};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ValidatorProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -18,4 +22,31 @@ pub trait ValidatorInterface {
         &mut self,
         automatic_reactivate: bool,
     ) -> RPCResult<(), (), Self::Error>;
+
+    async fn export_signing_history(&mut self) -> RPCResult<String, (), Self::Error>;
+
+    async fn import_signing_history(&mut self, history: String) -> RPCResult<u32, (), Self::Error>;
+
+    async fn get_validator_status(&mut self) -> RPCResult<ValidatorStatus, (), Self::Error>;
+
+    async fn get_validator_stats(&mut self) -> RPCResult<ValidatorStats, (), Self::Error>;
+
+    async fn schedule_maintenance(
+        &mut self,
+        reactivate_after_restart: bool,
+    ) -> RPCResult<ValidatorMaintenance, (), Self::Error>;
+
+    async fn get_maintenance(&mut self)
+        -> RPCResult<Option<ValidatorMaintenance>, (), Self::Error>;
+
+    async fn cancel_maintenance(&mut self) -> RPCResult<bool, (), Self::Error>;
+
+    async fn get_aggregation_diagnostics(
+        &mut self,
+    ) -> RPCResult<Vec<AggregationDiagnostics>, (), Self::Error>;
+
+    #[stream]
+    async fn subscribe_for_tendermint_events(
+        &mut self,
+    ) -> Result<BoxStream<'static, RPCData<TendermintEvent, ()>>, Self::Error>;
 }
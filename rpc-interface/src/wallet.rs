@@ -1,7 +1,13 @@
 use async_trait::async_trait;
-use nimiq_keys::{Address, PrivateKey, PublicKey, Signature};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::{typed_message::TypedMessage, Address, PrivateKey, PublicKey, Signature};
 
-use crate::types::RPCResult;
+use nimiq_primitives::coin::Coin;
+use nimiq_transaction::account::htlc_contract::{AnyHash, PreImage};
+
+use crate::types::{
+    Account, ExecutedTransaction, HtlcProofType, RPCResult, Transaction, ValidityStartHeight,
+};
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,6 +24,73 @@ pub struct ReturnAccount {
     pub private_key: PrivateKey,
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnDerivedAccount {
+    pub index: u32,
+    pub path: String,
+    pub address: Address,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnWalletBackupImport {
+    pub accounts: Vec<Address>,
+    pub hd_wallets: Vec<Address>,
+    pub watch_only_accounts: Vec<Address>,
+}
+
+/// The state of a watch-only account: its balance, its transactions pending in the mempool and its history.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnWatchOnlyAccount {
+    pub address: Address,
+    pub public_key: Option<PublicKey>,
+    pub account: Account,
+    pub pending_transactions: Vec<Transaction>,
+    pub transactions: Vec<ExecutedTransaction>,
+}
+
+/// A transaction of a wallet address, together with its label.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnWalletTransaction {
+    pub hash: Blake2bHash,
+    pub block_number: u32,
+    pub timestamp: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub value: Coin,
+    pub fee: Coin,
+    pub failed: bool,
+    pub label: Option<String>,
+    pub note: Option<String>,
+}
+
+/// A signed transaction of a funding plan.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnFundingInput {
+    pub sender: Address,
+    pub value: Coin,
+    pub fee: Coin,
+    pub hash: Blake2bHash,
+    /// The hex encoded signed transaction.
+    pub raw_transaction: String,
+}
+
+/// The transactions funding a payment from one or more wallet accounts.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnFundingPlan {
+    pub recipient: Address,
+    /// The total value the recipient receives.
+    pub value: Coin,
+    /// The total fee of all transactions.
+    pub fee: Coin,
+    pub inputs: Vec<ReturnFundingInput>,
+}
+
 #[nimiq_jsonrpc_derive::proxy(name = "WalletProxy", rename_all = "camelCase")]
 #[async_trait]
 pub trait WalletInterface {
@@ -68,4 +141,212 @@ pub trait WalletInterface {
         signature: Signature,
         is_hex: bool,
     ) -> RPCResult<bool, (), Self::Error>;
+
+    /// Signs a typed structured message with the key of `address`. The signature is only valid for the message's
+    /// domain, i.e. the application and network it names. Messages for another network than the one of this node are
+    /// rejected.
+    async fn sign_typed_message(
+        &mut self,
+        message: TypedMessage,
+        address: Address,
+        passphrase: Option<String>,
+    ) -> RPCResult<ReturnSignature, (), Self::Error>;
+
+    /// Verifies the signature of a typed structured message. The caller must check that the message's domain is the
+    /// expected one.
+    async fn verify_typed_message(
+        &mut self,
+        message: TypedMessage,
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> RPCResult<bool, (), Self::Error>;
+
+    /// Imports an HD wallet from a BIP39 mnemonic. Its accounts are derived along `derivation_path`, which defaults
+    /// to `m/44'/242'/0'`. Returns the ID of the wallet.
+    ///
+    /// Re-importing a wallet replaces its passphrase and keeps its derived accounts. It fails if `derivation_path`
+    /// differs from the path the wallet was imported with, which is kept if `derivation_path` is omitted.
+    async fn import_mnemonic(
+        &mut self,
+        mnemonic: String,
+        mnemonic_password: Option<String>,
+        derivation_path: Option<String>,
+        passphrase: Option<String>,
+    ) -> RPCResult<Address, (), Self::Error>;
+
+    /// Derives the account at `index` of an HD wallet, or at the lowest index that wasn't derived yet, and imports it
+    /// with the passphrase of the wallet. The index must be below 2^31. The account remains locked after this
+    /// operation.
+    async fn derive_account(
+        &mut self,
+        wallet_id: Address,
+        index: Option<u32>,
+        passphrase: Option<String>,
+    ) -> RPCResult<ReturnDerivedAccount, (), Self::Error>;
+
+    async fn list_derived_accounts(
+        &mut self,
+        wallet_id: Address,
+    ) -> RPCResult<Vec<ReturnDerivedAccount>, (), Self::Error>;
+
+    /// Returns the address of the multisig account that requires `num_signers` of the given public keys.
+    async fn derive_multisig_address(
+        &mut self,
+        public_keys: Vec<PublicKey>,
+        num_signers: usize,
+    ) -> RPCResult<Address, (), Self::Error>;
+
+    /// Creates a new commitment for signing a multisig transaction and returns it hex encoded. Its random secret is
+    /// kept by the node until it is used to sign with the commitment, but at most for an hour. Of the unused
+    /// commitments, only the latest 1024 are kept.
+    async fn create_multisig_commitment(&mut self) -> RPCResult<String, (), Self::Error>;
+
+    /// Partially signs a multisig transaction with the key of `address`, using `commitment`, which must have been
+    /// created by `create_multisig_commitment` and can only be used once. The signers and their commitments must be
+    /// the same for all signers. Returns the hex encoded partial signature.
+    async fn sign_multisig_transaction(
+        &mut self,
+        raw_tx: String,
+        address: Address,
+        passphrase: Option<String>,
+        public_keys: Vec<PublicKey>,
+        num_signers: usize,
+        signer_public_keys: Vec<PublicKey>,
+        commitment: String,
+        commitments: Vec<String>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Combines the partial signatures of all signers into the proof of a multisig transaction. Returns the signed
+    /// transaction.
+    async fn combine_multisig_signatures(
+        &mut self,
+        raw_tx: String,
+        public_keys: Vec<PublicKey>,
+        num_signers: usize,
+        signer_public_keys: Vec<PublicKey>,
+        commitments: Vec<String>,
+        partial_signatures: Vec<String>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Creates a partially signed transaction (PST) from an unsigned transaction, to be signed on other machines.
+    /// The `signer` signs on behalf of the sender and defaults to the sender. For incoming staking transactions, it
+    /// signs the staking data instead. Returns the hex encoded PST.
+    ///
+    /// Transactions sent from an HTLC require the `htlc_proof_type` and the `signer`, which is the HTLC's recipient
+    /// for regular transfers and the HTLC's sender otherwise. Regular transfers also require the `pre_image`, the
+    /// `hash_root` and the `hash_count`, early resolves the `htlc_recipient`.
+    async fn create_pst(
+        &mut self,
+        raw_tx: String,
+        signer: Option<Address>,
+        htlc_proof_type: Option<HtlcProofType>,
+        htlc_recipient: Option<Address>,
+        pre_image: Option<PreImage>,
+        hash_root: Option<AnyHash>,
+        hash_count: Option<u8>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Adds the signatures of `address` to a PST. Returns the hex encoded PST.
+    async fn sign_pst(
+        &mut self,
+        pst: String,
+        address: Address,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Combines the signatures of a complete PST into the proof of its transaction. Returns the signed transaction.
+    async fn finalize_pst(&mut self, pst: String) -> RPCResult<String, (), Self::Error>;
+
+    /// Exports the accounts, HD wallets and watch-only accounts with the given addresses, or all of them, to a backup
+    /// encrypted with `backup_passphrase`. HD wallets are exported along with their derived accounts. The backup
+    /// includes the labels of their transactions. The accounts stay locked with their own passphrases. Returns the hex
+    /// encoded backup.
+    async fn export_wallet_backup(
+        &mut self,
+        addresses: Option<Vec<Address>>,
+        backup_passphrase: String,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Restores the accounts, HD wallets, watch-only accounts and transaction labels of a hex encoded backup.
+    /// Existing ones are only replaced if `overwrite` is set.
+    async fn import_wallet_backup(
+        &mut self,
+        backup: String,
+        backup_passphrase: String,
+        overwrite: Option<bool>,
+    ) -> RPCResult<ReturnWalletBackupImport, (), Self::Error>;
+
+    /// Imports an address to watch without its private key, e.g. the address of a cold wallet. If the public key is
+    /// given, it must belong to the address.
+    ///
+    /// Extended public keys are rejected, since Ed25519 keys are derived with hardened derivation only, such that
+    /// no addresses can be derived from them.
+    async fn import_watch_only_account(
+        &mut self,
+        address: Address,
+        public_key: Option<PublicKey>,
+        extended_public_key: Option<String>,
+    ) -> RPCResult<Address, (), Self::Error>;
+
+    async fn list_watch_only_accounts(&mut self) -> RPCResult<Vec<Address>, (), Self::Error>;
+
+    async fn remove_watch_only_account(
+        &mut self,
+        address: Address,
+    ) -> RPCResult<bool, (), Self::Error>;
+
+    /// Returns the balance, the pending transactions and the latest `max_transactions` transactions of a watch-only
+    /// account. The history defaults to 500 transactions.
+    async fn get_watch_only_account(
+        &mut self,
+        address: Address,
+        max_transactions: Option<u16>,
+    ) -> RPCResult<ReturnWatchOnlyAccount, (), Self::Error>;
+
+    /// Creates an unsigned basic transaction from a watch-only account, to be signed offline, e.g. as a PST.
+    /// Returns the hex encoded transaction.
+    async fn create_watch_only_transaction(
+        &mut self,
+        address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Returns the transactions of a wallet address recorded by the node, newest first. Only transactions applied
+    /// while the node was running with the address in its wallet are recorded.
+    async fn get_wallet_transactions(
+        &mut self,
+        address: Address,
+        offset: Option<u32>,
+        limit: Option<u16>,
+    ) -> RPCResult<Vec<ReturnWalletTransaction>, (), Self::Error>;
+
+    /// Sets the label and note of a transaction, replacing any existing ones.
+    async fn set_transaction_label(
+        &mut self,
+        hash: Blake2bHash,
+        label: String,
+        note: Option<String>,
+    ) -> RPCResult<(), (), Self::Error>;
+
+    /// Removes the label of a transaction. Returns whether there was one.
+    async fn remove_transaction_label(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> RPCResult<bool, (), Self::Error>;
+
+    /// Selects unlocked wallet accounts to fund a payment of `value` to `recipient`, then builds and signs one basic
+    /// transaction per selected account. Balances reserved by transactions pending in the mempool are not spent.
+    /// Without a value, everything the accounts have available is swept. Only the given `senders` are considered, or
+    /// all unlocked accounts if none are given. The transactions are returned, not sent.
+    async fn create_funded_transactions(
+        &mut self,
+        recipient: Address,
+        value: Option<Coin>,
+        fee_per_byte: u64,
+        validity_start_height: ValidityStartHeight,
+        senders: Vec<Address>,
+    ) -> RPCResult<ReturnFundingPlan, (), Self::Error>;
 }
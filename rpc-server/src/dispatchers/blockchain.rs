@@ -70,6 +70,59 @@ fn get_validator_by_address(
     }
 }
 
+/// Fetches the latest transactions sent from or to the given address, including reward inherents. It
+/// fetches at most `max` transactions, defaulting to 500.
+/// This function requires the read lock acquisition prior to its execution.
+pub(crate) fn get_transactions_by_address(
+    blockchain_proxy: &BlockchainReadProxy,
+    address: &Address,
+    max: Option<u16>,
+) -> Result<Vec<ExecutedTransaction>, Error> {
+    if let BlockchainReadProxy::Full(blockchain) = blockchain_proxy {
+        // Get the transaction hashes for this address.
+        let tx_hashes =
+            blockchain
+                .history_store
+                .get_tx_hashes_by_address(address, max.unwrap_or(500), None);
+
+        let mut txs = vec![];
+
+        for hash in tx_hashes {
+            // Get all the extended transactions that correspond to this hash.
+            let mut extended_tx_vec = blockchain.history_store.get_ext_tx_by_hash(&hash, None);
+
+            // Unpack the transaction or raise an error.
+            let extended_tx = match extended_tx_vec.len() {
+                0 => {
+                    return Err(Error::TransactionNotFound(hash));
+                }
+                1 => extended_tx_vec.pop().unwrap(),
+                _ => {
+                    return Err(Error::MultipleTransactionsFound(hash));
+                }
+            };
+
+            // Convert the extended transaction into a regular transaction. This will also convert
+            // reward inherents.
+            let block_number = extended_tx.block_number;
+            let timestamp = extended_tx.block_time;
+
+            if let Ok(tx) = extended_tx.into_transaction() {
+                txs.push(ExecutedTransaction::from_blockchain(
+                    tx,
+                    block_number,
+                    timestamp,
+                    blockchain.block_number(),
+                ));
+            }
+        }
+
+        Ok(txs)
+    } else {
+        Err(Error::NotSupportedForLightBlockchain)
+    }
+}
+
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
 #[async_trait]
 impl BlockchainInterface for BlockchainDispatcher {
@@ -414,50 +467,7 @@ impl BlockchainInterface for BlockchainDispatcher {
         address: Address,
         max: Option<u16>,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error> {
-        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
-            // Get the transaction hashes for this address.
-            let tx_hashes = blockchain.history_store.get_tx_hashes_by_address(
-                &address,
-                max.unwrap_or(500),
-                None,
-            );
-
-            let mut txs = vec![];
-
-            for hash in tx_hashes {
-                // Get all the extended transactions that correspond to this hash.
-                let mut extended_tx_vec = blockchain.history_store.get_ext_tx_by_hash(&hash, None);
-
-                // Unpack the transaction or raise an error.
-                let extended_tx = match extended_tx_vec.len() {
-                    0 => {
-                        return Err(Error::TransactionNotFound(hash));
-                    }
-                    1 => extended_tx_vec.pop().unwrap(),
-                    _ => {
-                        return Err(Error::MultipleTransactionsFound(hash));
-                    }
-                };
-
-                // Convert the extended transaction into a regular transaction. This will also convert
-                // reward inherents.
-                let block_number = extended_tx.block_number;
-                let timestamp = extended_tx.block_time;
-
-                if let Ok(tx) = extended_tx.into_transaction() {
-                    txs.push(ExecutedTransaction::from_blockchain(
-                        tx,
-                        block_number,
-                        timestamp,
-                        blockchain.block_number(),
-                    ));
-                }
-            }
-
-            Ok(txs.into())
-        } else {
-            Err(Error::NotSupportedForLightBlockchain)
-        }
+        Ok(get_transactions_by_address(&self.blockchain.read(), &address, max)?.into())
     }
 
     /// Tries to fetch the account at the given address.
@@ -2,8 +2,11 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use nimiq_network_interface::network::Network as InterfaceNetwork;
-use nimiq_network_libp2p::Network;
-use nimiq_rpc_interface::{network::NetworkInterface, types::RPCResult};
+use nimiq_network_libp2p::{Network, TrafficCounters};
+use nimiq_rpc_interface::{
+    network::NetworkInterface,
+    types::{MessageTypeTrafficStats, NetworkStats, PeerTrafficStats, RPCResult, TrafficStats},
+};
 
 use crate::error::Error;
 
@@ -42,4 +45,47 @@ impl NetworkInterface for NetworkDispatcher {
             .collect::<Vec<_>>()
             .into())
     }
+
+    /// Returns the traffic accounted per peer and per message type, so it can be seen which protocols consume
+    /// the bandwidth.
+    async fn get_network_stats(&mut self) -> RPCResult<NetworkStats, (), Self::Error> {
+        let stats = self.network.network_stats();
+
+        let mut peers: Vec<PeerTrafficStats> = stats
+            .peers
+            .into_iter()
+            .map(|(peer_id, traffic)| PeerTrafficStats {
+                peer_id: peer_id.to_string(),
+                traffic: traffic_stats(traffic),
+            })
+            .collect();
+        peers.sort_by(|a, b| a.peer_id.cmp(&b.peer_id));
+
+        let mut message_types: Vec<MessageTypeTrafficStats> = stats
+            .message_types
+            .into_iter()
+            .map(|(message_type, traffic)| MessageTypeTrafficStats {
+                message_type,
+                traffic: traffic_stats(traffic),
+            })
+            .collect();
+        message_types.sort_by(|a, b| a.message_type.cmp(&b.message_type));
+
+        Ok(NetworkStats {
+            total: traffic_stats(stats.total),
+            peers,
+            message_types,
+        }
+        .into())
+    }
+}
+
+fn traffic_stats(counters: TrafficCounters) -> TrafficStats {
+    TrafficStats {
+        bytes_received: counters.bytes_received,
+        bytes_sent: counters.bytes_sent,
+        messages_received: counters.messages_received,
+        messages_sent: counters.messages_sent,
+        invalid_messages: counters.invalid_messages,
+    }
 }
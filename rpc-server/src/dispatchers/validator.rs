@@ -1,10 +1,29 @@
 use std::sync::atomic::Ordering;
 
 use async_trait::async_trait;
+use futures::{future, stream::BoxStream, StreamExt};
+use nimiq_handel::diagnostics::AggregationDiagnostics as Diagnostics;
 use nimiq_keys::Address;
-use nimiq_rpc_interface::{types::RPCResult, validator::ValidatorInterface};
-use nimiq_serde::Serialize;
-use nimiq_validator::validator::ValidatorProxy;
+use nimiq_rpc_interface::{
+    types::{
+        AggregationDiagnostics, AggregationKind, AggregationLevel, AggregationPeer,
+        MaintenanceStatus, RPCData, RPCResult, TendermintEvent, TendermintEventKind,
+        TendermintProposalResult, TendermintStep, ValidatorMaintenance, ValidatorStakingState,
+        ValidatorStats, ValidatorStatus,
+    },
+    validator::ValidatorInterface,
+};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_tendermint::Step;
+use nimiq_utils::time::systemtime_to_timestamp;
+use nimiq_validator::{
+    maintenance::{Maintenance, MaintenanceStatus as Status},
+    signing_history::SigningHistoryExport,
+    stats::ValidatorStakingState as StakingState,
+    tendermint_events::{self, ProposalResult},
+    validator::ValidatorProxy,
+};
+use tokio_stream::wrappers::BroadcastStream;
 
 use crate::error::Error;
 
@@ -18,6 +37,106 @@ impl ValidatorDispatcher {
     }
 }
 
+fn to_rpc_maintenance(maintenance: Maintenance) -> ValidatorMaintenance {
+    let (status, deactivate_tx_sent_at) = match maintenance.status {
+        Status::Scheduled => (MaintenanceStatus::Scheduled, None),
+        Status::Deactivating { sent_at } => (MaintenanceStatus::Deactivating, Some(sent_at)),
+        Status::SafeToStop => (MaintenanceStatus::SafeToStop, None),
+    };
+    ValidatorMaintenance {
+        status,
+        deactivate_tx_sent_at,
+        reactivate_after_restart: maintenance.reactivate_after_restart,
+    }
+}
+
+fn to_rpc_aggregation(kind: AggregationKind, diagnostics: Diagnostics) -> AggregationDiagnostics {
+    let millis = |duration: std::time::Duration| duration.as_millis() as u64;
+    AggregationDiagnostics {
+        kind,
+        id: diagnostics.id,
+        node_id: diagnostics.node_id,
+        started_at: systemtime_to_timestamp(diagnostics.started_at),
+        level_timeout: millis(diagnostics.level_timeout),
+        update_interval: millis(diagnostics.update_interval),
+        levels: diagnostics
+            .levels
+            .into_iter()
+            .map(|level| AggregationLevel {
+                level: level.id,
+                peers: level.peers,
+                started_after: level.started_after.map(millis),
+                started_on_timeout: level.started_on_timeout,
+                completed_after: level.completed_after.map(millis),
+                missing_peers: level.missing_peers,
+            })
+            .collect(),
+        peers: diagnostics
+            .peers
+            .into_iter()
+            .map(|(peer, contributions)| AggregationPeer {
+                peer,
+                level_updates: contributions.level_updates,
+                individual_contributions: contributions.individual_contributions,
+                wrong_level_updates: contributions.wrong_level_updates,
+                useless_contributions: contributions.useless_contributions,
+                last_score: contributions.last_score,
+                best_score: contributions.best_score,
+            })
+            .collect(),
+        invalid_contributions: diagnostics.invalid_contributions,
+        contributors: diagnostics.contributors,
+        completed_after: diagnostics.completed_after.map(millis),
+    }
+}
+
+fn to_rpc_step(step: Step) -> TendermintStep {
+    match step {
+        Step::Propose => TendermintStep::Propose,
+        Step::Prevote => TendermintStep::Prevote,
+        Step::Precommit => TendermintStep::Precommit,
+    }
+}
+
+fn to_rpc_tendermint_event(event: tendermint_events::TendermintEvent) -> TendermintEvent {
+    use tendermint_events::TendermintEventKind as Kind;
+
+    let kind = match event.kind {
+        Kind::StepStarted { step } => TendermintEventKind::StepStarted {
+            step: to_rpc_step(step),
+        },
+        Kind::ProposalReceived {
+            proposer,
+            proposal_hash,
+            result,
+        } => TendermintEventKind::ProposalReceived {
+            proposer,
+            proposal_hash,
+            result: match result {
+                ProposalResult::Accepted => TendermintProposalResult::Accepted,
+                ProposalResult::Ignored => TendermintProposalResult::Ignored,
+                ProposalResult::Rejected => TendermintProposalResult::Rejected,
+            },
+        },
+        Kind::AggregationCompleted {
+            step,
+            contributors,
+            proposal_hash,
+        } => TendermintEventKind::AggregationCompleted {
+            step: to_rpc_step(step),
+            contributors,
+            proposal_hash,
+        },
+        Kind::Decision { block_hash } => TendermintEventKind::Decision { block_hash },
+    };
+    TendermintEvent {
+        block_number: event.block_number,
+        round: event.round,
+        elapsed: event.elapsed.as_millis() as u64,
+        kind,
+    }
+}
+
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
 #[async_trait]
 impl ValidatorInterface for ValidatorDispatcher {
@@ -30,19 +149,24 @@ impl ValidatorInterface for ValidatorDispatcher {
 
     /// Returns our validator signing key.
     async fn get_signing_key(&mut self) -> RPCResult<String, (), Self::Error> {
-        Ok(hex::encode(self.validator.signing_key.read().private.serialize_to_vec()).into())
+        let signing_key = self
+            .validator
+            .signer
+            .read()
+            .signing_key_pair()
+            .ok_or(Error::RemoteSignerKeys)?;
+        Ok(hex::encode(signing_key.private.serialize_to_vec()).into())
     }
 
     /// Returns our validator voting key.
     async fn get_voting_key(&mut self) -> RPCResult<String, (), Self::Error> {
-        Ok(hex::encode(
-            self.validator
-                .voting_key
-                .read()
-                .secret_key
-                .serialize_to_vec(),
-        )
-        .into())
+        let voting_key = self
+            .validator
+            .signer
+            .read()
+            .voting_key_pair()
+            .ok_or(Error::RemoteSignerKeys)?;
+        Ok(hex::encode(voting_key.secret_key.serialize_to_vec()).into())
     }
 
     /// Updates the configuration setting to automatically reactivate our validator.
@@ -57,4 +181,137 @@ impl ValidatorInterface for ValidatorDispatcher {
         log::debug!("Automatic reactivation set to {}.", automatic_reactivate);
         Ok(().into())
     }
+
+    /// Returns the signing history of our validator, hex encoded. It can be imported on a different machine
+    /// using `importSigningHistory` to migrate the validator without risking to sign conflicting messages.
+    async fn export_signing_history(&mut self) -> RPCResult<String, (), Self::Error> {
+        Ok(hex::encode(self.validator.signing_history.export().serialize_to_vec()).into())
+    }
+
+    /// Imports a hex encoded signing history exported by `exportSigningHistory`. Returns the number of imported
+    /// records. Nothing is imported if the history conflicts with the signing history of our validator.
+    async fn import_signing_history(&mut self, history: String) -> RPCResult<u32, (), Self::Error> {
+        let history = SigningHistoryExport::deserialize_from_vec(&hex::decode(history)?)?;
+        let imported = self.validator.signing_history.import(&history)?;
+
+        log::info!(imported, "Imported signing history.");
+        Ok((imported as u32).into())
+    }
+
+    /// Returns the status of our validator: its state in the staking contract and its slots in the current epoch.
+    async fn get_validator_status(&mut self) -> RPCResult<ValidatorStatus, (), Self::Error> {
+        let status = self.validator.status();
+        let (staking_state, jail_release) = match status.staking_state {
+            StakingState::Active => (ValidatorStakingState::Active, None),
+            StakingState::Inactive(jail_release) => (ValidatorStakingState::Inactive, jail_release),
+            StakingState::NoStake => (ValidatorStakingState::NoStake, None),
+            StakingState::Unknown => (ValidatorStakingState::Unknown, None),
+        };
+
+        Ok(ValidatorStatus {
+            address: status.address,
+            block_number: status.block_number,
+            epoch_number: status.epoch_number,
+            staking_state,
+            jail_release,
+            slot_band: status.slot_band,
+            num_slots: status.slots.len() as u16,
+        }
+        .into())
+    }
+
+    /// Returns the performance statistics of our validator in the current epoch: The blocks it produced and missed,
+    /// its participation in skip blocks and Tendermint, its penalties and its pending rewards.
+    async fn get_validator_stats(&mut self) -> RPCResult<ValidatorStats, (), Self::Error> {
+        let stats = self.validator.stats()?;
+
+        Ok(ValidatorStats {
+            epoch_number: stats.epoch.epoch_number,
+            since_block_number: stats.epoch.since_block_number,
+            produced_micro_blocks: stats.epoch.produced_micro_blocks,
+            missed_micro_blocks: stats.epoch.missed_micro_blocks,
+            skip_blocks_contributed: stats.epoch.skip_blocks_contributed,
+            tendermint_rounds_participated: stats.epoch.tendermint_rounds_participated,
+            punished_slots_current_batch: stats.punished_slots_current_batch,
+            punished_slots_previous_batch: stats.punished_slots_previous_batch,
+            pending_reward: stats.pending_reward,
+        }
+        .into())
+    }
+
+    /// Schedules a maintenance of our validator. It deactivates itself shortly before the next election block and
+    /// the node can be stopped once the status is `safeToStop`. If `reactivateAfterRestart` is set, the validator
+    /// reactivates itself once the node is restarted, otherwise it stays inactive until the maintenance is canceled.
+    async fn schedule_maintenance(
+        &mut self,
+        reactivate_after_restart: bool,
+    ) -> RPCResult<ValidatorMaintenance, (), Self::Error> {
+        self.validator
+            .maintenance
+            .schedule(reactivate_after_restart);
+
+        log::info!(reactivate_after_restart, "Scheduled validator maintenance.");
+        Ok(to_rpc_maintenance(self.validator.maintenance.get().unwrap()).into())
+    }
+
+    /// Returns the scheduled maintenance of our validator, if any.
+    async fn get_maintenance(
+        &mut self,
+    ) -> RPCResult<Option<ValidatorMaintenance>, (), Self::Error> {
+        Ok(self
+            .validator
+            .maintenance
+            .get()
+            .map(to_rpc_maintenance)
+            .into())
+    }
+
+    /// Cancels the scheduled maintenance of our validator. Returns whether a maintenance was scheduled. This doesn't
+    /// reactivate the validator if it was deactivated already, unless automatic reactivation is enabled.
+    async fn cancel_maintenance(&mut self) -> RPCResult<bool, (), Self::Error> {
+        let canceled = self.validator.maintenance.cancel();
+
+        log::info!(canceled, "Canceled validator maintenance.");
+        Ok(canceled.into())
+    }
+
+    /// Returns the diagnostics of the most recent skip block and Tendermint vote aggregations, the most recent first
+    /// for each kind. They show which levels and peers were missing if an aggregation didn't complete in time.
+    async fn get_aggregation_diagnostics(
+        &mut self,
+    ) -> RPCResult<Vec<AggregationDiagnostics>, (), Self::Error> {
+        let skip_blocks = self
+            .validator
+            .skip_block_diagnostics
+            .recent()
+            .into_iter()
+            .map(|diagnostics| to_rpc_aggregation(AggregationKind::SkipBlock, diagnostics));
+        let tendermint = self
+            .validator
+            .tendermint_diagnostics
+            .recent()
+            .into_iter()
+            .map(|diagnostics| to_rpc_aggregation(AggregationKind::Tendermint, diagnostics));
+
+        Ok(skip_blocks.chain(tendermint).collect::<Vec<_>>().into())
+    }
+
+    /// Subscribes to the progress of the Tendermint instances producing macro blocks: started rounds and steps,
+    /// received proposals, vote aggregations reaching 2f+1 slots and decisions. Events are dropped for subscribers
+    /// that fall behind.
+    #[stream]
+    async fn subscribe_for_tendermint_events(
+        &mut self,
+    ) -> Result<BoxStream<'static, RPCData<TendermintEvent, ()>>, Self::Error> {
+        let stream = BroadcastStream::new(self.validator.tendermint_events.subscribe());
+        Ok(stream
+            .filter_map(|event| {
+                future::ready(
+                    event
+                        .ok()
+                        .map(|event| to_rpc_tendermint_event(event).into()),
+                )
+            })
+            .boxed())
+    }
 }
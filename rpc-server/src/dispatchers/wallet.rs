@@ -1,17 +1,54 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
+use nimiq_account::ReservedBalance;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
 use nimiq_database::traits::WriteTransaction;
-use nimiq_keys::{Address, KeyPair, PrivateKey, PublicKey, Signature};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_key_derivation::ExtendedPrivateKey;
+use nimiq_keys::{
+    multisig::{Commitment, CommitmentPair, PartialSignature, RandomSecret},
+    typed_message::TypedMessage,
+    Address, KeyPair, PrivateKey, PublicKey, SecureGenerate, Signature,
+};
+use nimiq_mempool::mempool::Mempool;
+use nimiq_mnemonic::Mnemonic;
+use nimiq_primitives::coin::Coin;
 use nimiq_rpc_interface::{
-    types::RPCResult,
-    wallet::{ReturnAccount, ReturnSignature, WalletInterface},
+    types::{
+        Account, HtlcProofType, RPCResult, Transaction as RPCTransaction, ValidityStartHeight,
+    },
+    wallet::{
+        ReturnAccount, ReturnDerivedAccount, ReturnFundingInput, ReturnFundingPlan,
+        ReturnSignature, ReturnWalletBackupImport, ReturnWalletTransaction, ReturnWatchOnlyAccount,
+        WalletInterface,
+    },
+};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::{
+    account::htlc_contract::{AnyHash, PreImage},
+    pst::PartiallySignedTransaction,
+    Transaction,
+};
+use nimiq_transaction_builder::{
+    funding::{FundingPlan, FundingSource},
+    multisig::MultisigAccount,
+    proof::HtlcPstProof,
+    TransactionProofBuilder,
+};
+use nimiq_utils::otp::{Locked, Unlocked};
+use nimiq_wallet::{
+    DerivedAccount, HdWallet, HdWalletInfo, StoredHdWallet, TransactionLabel, WalletAccount,
+    WalletBackup, WalletStore, WatchOnlyAccount,
 };
-use nimiq_serde::Deserialize;
-use nimiq_utils::otp::Locked;
-use nimiq_wallet::{WalletAccount, WalletStore};
 use parking_lot::RwLock;
 
+use super::blockchain::get_transactions_by_address;
 use crate::{error::Error, wallets::UnlockedWallets};
 
 fn message_from_maybe_hex(s: String, is_hex: bool) -> Result<Vec<u8>, Error> {
@@ -22,17 +59,77 @@ fn message_from_maybe_hex(s: String, is_hex: bool) -> Result<Vec<u8>, Error> {
     }
 }
 
+fn to_rpc_derived_account(info: &HdWalletInfo, account: &DerivedAccount) -> ReturnDerivedAccount {
+    ReturnDerivedAccount {
+        index: account.index,
+        path: HdWalletInfo::account_path(&info.base_path, account.index),
+        address: account.address.clone(),
+    }
+}
+
+/// Decodes a hex encoded 32 byte value of a multisig signature.
+fn multisig_value_from_hex(s: &str) -> Result<[u8; 32], Error> {
+    hex::decode(s)?
+        .try_into()
+        .map_err(|_| Error::InvalidArgument(format!("Expected 32 bytes: {s}")))
+}
+
+fn commitments_from_hex(commitments: &[String]) -> Result<Vec<Commitment>, Error> {
+    commitments
+        .iter()
+        .map(|commitment| {
+            Commitment::from_bytes(multisig_value_from_hex(commitment)?)
+                .ok_or_else(|| Error::InvalidArgument(format!("Invalid commitment: {commitment}")))
+        })
+        .collect()
+}
+
 pub struct WalletDispatcher {
     wallet_store: Arc<WalletStore>,
     pub unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+    /// Used to report the state of watch-only accounts.
+    blockchain: BlockchainProxy,
+    mempool: Option<Arc<Mempool>>,
+    /// The random secrets of the multisig commitments created by this node and when they were created, by
+    /// commitment. Each of them is removed once it was used to sign or expired.
+    multisig_secrets: HashMap<[u8; 32], (RandomSecret, Instant)>,
 }
 
 impl WalletDispatcher {
-    pub fn new(wallet_store: Arc<WalletStore>) -> Self {
+    /// The time after which an unused multisig commitment can't be signed with anymore.
+    const MULTISIG_SECRET_TTL: Duration = Duration::from_secs(60 * 60);
+    /// The maximum number of unused multisig commitments. Once reached, the oldest one is dropped for a new one.
+    const MAX_MULTISIG_SECRETS: usize = 1024;
+
+    pub fn new(
+        wallet_store: Arc<WalletStore>,
+        blockchain: BlockchainProxy,
+        mempool: Option<Arc<Mempool>>,
+    ) -> Self {
         Self {
             wallet_store,
             unlocked_wallets: Arc::new(RwLock::new(UnlockedWallets::default())),
+            blockchain,
+            mempool,
+            multisig_secrets: HashMap::new(),
+        }
+    }
+
+    /// Returns the key pair of an account, unlocking it with the passphrase if it isn't unlocked already.
+    fn key_pair(&self, address: &Address, passphrase: Option<String>) -> Result<KeyPair, Error> {
+        if let Some(wallet) = self.unlocked_wallets.read().get(address) {
+            return Ok(wallet.key_pair.clone());
         }
+
+        let passphrase = passphrase.unwrap_or_default();
+        Ok(self
+            .wallet_store
+            .get(address, None)
+            .ok_or_else(|| Error::AccountNotFound(address.clone()))?
+            .unlock(passphrase.as_bytes())
+            .map_err(|_locked| Error::WrongPassphrase)?
+            .key_pair
+            .clone())
     }
 }
 
@@ -179,4 +276,654 @@ impl WalletInterface for WalletDispatcher {
         let message = message_from_maybe_hex(message, is_hex)?;
         Ok(WalletAccount::verify_message(&public_key, &message, &signature).into())
     }
+
+    async fn sign_typed_message(
+        &mut self,
+        message: TypedMessage,
+        address: Address,
+        passphrase: Option<String>,
+    ) -> RPCResult<ReturnSignature, (), Self::Error> {
+        // Never sign messages for another network, where they could be replayed.
+        let network_id = self.blockchain.read().network_id();
+        if message.domain.network_id != network_id as u8 {
+            return Err(Error::WrongNetwork {
+                expected: network_id,
+                actual: message.domain.network_id,
+            });
+        }
+
+        let wallet = WalletAccount::from(self.key_pair(&address, passphrase)?);
+        let (public_key, signature) = wallet.sign_typed_message(&message)?;
+
+        Ok(ReturnSignature {
+            public_key,
+            signature,
+        }
+        .into())
+    }
+
+    async fn verify_typed_message(
+        &mut self,
+        message: TypedMessage,
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> RPCResult<bool, (), Self::Error> {
+        Ok(message.verify(&public_key, &signature).into())
+    }
+
+    async fn import_mnemonic(
+        &mut self,
+        mnemonic: String,
+        mnemonic_password: Option<String>,
+        derivation_path: Option<String>,
+        passphrase: Option<String>,
+    ) -> RPCResult<Address, (), Self::Error> {
+        let passphrase = passphrase.unwrap_or_default();
+        if let Some(ref path) = derivation_path {
+            if !ExtendedPrivateKey::is_valid_path(path) {
+                return Err(Error::InvalidDerivationPath(path.clone()));
+            }
+        }
+
+        let mnemonic: Mnemonic = mnemonic
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .parse()
+            .map_err(|_| Error::InvalidMnemonic)?;
+        if !HdWallet::is_valid_mnemonic(&mnemonic) {
+            return Err(Error::InvalidMnemonic);
+        }
+        let wallet = HdWallet::from_mnemonic(&mnemonic, mnemonic_password.as_deref())
+            .map_err(|_| Error::InvalidMnemonic)?;
+        let id = wallet.id().clone();
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        // Re-importing a wallet keeps the accounts derived so far, which requires the same derivation path.
+        let info = match (
+            self.wallet_store.get_hd_wallet(&id, Some(&txn)),
+            derivation_path,
+        ) {
+            (Some(stored), Some(path)) if stored.info.base_path != path => {
+                return Err(Error::DerivationPathMismatch(stored.info.base_path));
+            }
+            (Some(stored), _) => stored.info,
+            (None, path) => {
+                HdWalletInfo::new(path.unwrap_or_else(|| HdWallet::DEFAULT_PATH.to_string()))
+            }
+        };
+        let stored = StoredHdWallet {
+            wallet: Locked::with_defaults(wallet, passphrase.as_bytes())?,
+            info,
+        };
+        self.wallet_store.put_hd_wallet(&id, &stored, &mut txn);
+        txn.commit();
+
+        Ok(id.into())
+    }
+
+    async fn derive_account(
+        &mut self,
+        wallet_id: Address,
+        index: Option<u32>,
+        passphrase: Option<String>,
+    ) -> RPCResult<ReturnDerivedAccount, (), Self::Error> {
+        let passphrase = passphrase.unwrap_or_default();
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        let StoredHdWallet { wallet, mut info } = self
+            .wallet_store
+            .get_hd_wallet(&wallet_id, Some(&txn))
+            .ok_or_else(|| Error::HdWalletNotFound(wallet_id.clone()))?;
+        let wallet = wallet
+            .unlock(passphrase.as_bytes())
+            .map_err(|_locked| Error::WrongPassphrase)?;
+
+        let index = index.unwrap_or_else(|| info.next_index());
+        if index > HdWallet::MAX_ACCOUNT_INDEX {
+            return Err(Error::InvalidAccountIndex(index));
+        }
+        let account = wallet
+            .derive_account(&info.base_path, index)
+            .ok_or_else(|| {
+                Error::InvalidDerivationPath(HdWalletInfo::account_path(&info.base_path, index))
+            })?;
+        let derived = DerivedAccount {
+            index,
+            address: account.address.clone(),
+        };
+
+        let locked_account = Locked::with_defaults(account, passphrase.as_bytes())?;
+        self.wallet_store
+            .put(&derived.address, &locked_account, &mut txn);
+        info.insert(derived.clone());
+        let stored = StoredHdWallet {
+            wallet: Unlocked::lock(wallet),
+            info,
+        };
+        self.wallet_store
+            .put_hd_wallet(&wallet_id, &stored, &mut txn);
+        txn.commit();
+
+        Ok(to_rpc_derived_account(&stored.info, &derived).into())
+    }
+
+    async fn list_derived_accounts(
+        &mut self,
+        wallet_id: Address,
+    ) -> RPCResult<Vec<ReturnDerivedAccount>, (), Self::Error> {
+        let info = self
+            .wallet_store
+            .get_hd_wallet(&wallet_id, None)
+            .ok_or(Error::HdWalletNotFound(wallet_id))?
+            .info;
+
+        Ok(info
+            .accounts
+            .iter()
+            .map(|account| to_rpc_derived_account(&info, account))
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    async fn derive_multisig_address(
+        &mut self,
+        public_keys: Vec<PublicKey>,
+        num_signers: usize,
+    ) -> RPCResult<Address, (), Self::Error> {
+        Ok(MultisigAccount::new(public_keys, num_signers)?
+            .address()
+            .into())
+    }
+
+    async fn create_multisig_commitment(&mut self) -> RPCResult<String, (), Self::Error> {
+        let commitment_pair = CommitmentPair::generate_default_csprng();
+        let commitment = commitment_pair.commitment().to_bytes();
+
+        let now = Instant::now();
+        self.multisig_secrets
+            .retain(|_, (_, created)| now.duration_since(*created) < Self::MULTISIG_SECRET_TTL);
+        if self.multisig_secrets.len() >= Self::MAX_MULTISIG_SECRETS {
+            let oldest = self
+                .multisig_secrets
+                .iter()
+                .min_by_key(|(_, (_, created))| *created)
+                .map(|(commitment, _)| *commitment);
+            if let Some(oldest) = oldest {
+                self.multisig_secrets.remove(&oldest);
+            }
+        }
+        self.multisig_secrets
+            .insert(commitment, (*commitment_pair.random_secret(), now));
+        Ok(hex::encode(commitment).into())
+    }
+
+    async fn sign_multisig_transaction(
+        &mut self,
+        raw_tx: String,
+        address: Address,
+        passphrase: Option<String>,
+        public_keys: Vec<PublicKey>,
+        num_signers: usize,
+        signer_public_keys: Vec<PublicKey>,
+        commitment: String,
+        commitments: Vec<String>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let transaction: Transaction = Deserialize::deserialize_from_vec(&hex::decode(raw_tx)?)?;
+        let account = MultisigAccount::new(public_keys, num_signers)?;
+        if transaction.sender != account.address() {
+            return Err(Error::InvalidTransactionParameters);
+        }
+
+        let key_pair = self.key_pair(&address, passphrase)?;
+        let commitment = multisig_value_from_hex(&commitment)?;
+        let commitments = commitments_from_hex(&commitments)?;
+        if !commitments
+            .iter()
+            .any(|other| other.to_bytes() == commitment)
+        {
+            return Err(Error::InvalidArgument(
+                "The commitment must be one of the commitments".to_string(),
+            ));
+        }
+
+        // The random secret must never be used for more than one signature.
+        let random_secret = self
+            .multisig_secrets
+            .remove(&commitment)
+            .filter(|(_, created)| created.elapsed() < Self::MULTISIG_SECRET_TTL)
+            .map(|(random_secret, _)| random_secret)
+            .ok_or_else(|| {
+                Error::InvalidArgument(format!(
+                    "Unknown, expired or already used commitment: {}",
+                    hex::encode(commitment)
+                ))
+            })?;
+        let partial_signature = account.partially_sign(
+            &key_pair,
+            &signer_public_keys,
+            &random_secret,
+            &commitments,
+            &transaction.serialize_content(),
+        )?;
+
+        Ok(hex::encode(partial_signature.as_bytes()).into())
+    }
+
+    async fn combine_multisig_signatures(
+        &mut self,
+        raw_tx: String,
+        public_keys: Vec<PublicKey>,
+        num_signers: usize,
+        signer_public_keys: Vec<PublicKey>,
+        commitments: Vec<String>,
+        partial_signatures: Vec<String>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let mut transaction: Transaction =
+            Deserialize::deserialize_from_vec(&hex::decode(raw_tx)?)?;
+        let account = MultisigAccount::new(public_keys, num_signers)?;
+        if transaction.sender != account.address() {
+            return Err(Error::InvalidTransactionParameters);
+        }
+
+        let partial_signatures = partial_signatures
+            .iter()
+            .map(|partial_signature| {
+                Ok(PartialSignature::from(multisig_value_from_hex(
+                    partial_signature,
+                )?))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let proof = account.create_signature_proof(
+            &signer_public_keys,
+            &commitments_from_hex(&commitments)?,
+            &partial_signatures,
+        )?;
+        if !proof.verify(&transaction.serialize_content()) {
+            return Err(Error::InvalidArgument(
+                "The partial signatures don't combine into a valid signature".to_string(),
+            ));
+        }
+
+        transaction.proof = proof.serialize_to_vec();
+
+        Ok(hex::encode(transaction.serialize_to_vec()).into())
+    }
+
+    async fn create_pst(
+        &mut self,
+        raw_tx: String,
+        signer: Option<Address>,
+        htlc_proof_type: Option<HtlcProofType>,
+        htlc_recipient: Option<Address>,
+        pre_image: Option<PreImage>,
+        hash_root: Option<AnyHash>,
+        hash_count: Option<u8>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let transaction: Transaction = Deserialize::deserialize_from_vec(&hex::decode(raw_tx)?)?;
+        let missing = |name: &str| Error::InvalidArgument(format!("{name} is required"));
+        let htlc_proof = match htlc_proof_type {
+            None => None,
+            Some(HtlcProofType::RegularTransfer) => Some(HtlcPstProof::RegularTransfer {
+                hash_depth: hash_count.ok_or_else(|| missing("hashCount"))?,
+                hash_root: hash_root.ok_or_else(|| missing("hashRoot"))?,
+                pre_image: pre_image.ok_or_else(|| missing("preImage"))?,
+            }),
+            Some(HtlcProofType::EarlyResolve) => Some(HtlcPstProof::EarlyResolve {
+                recipient: htlc_recipient.ok_or_else(|| missing("htlcRecipient"))?,
+            }),
+            Some(HtlcProofType::TimeoutResolve) => Some(HtlcPstProof::TimeoutResolve),
+        };
+        // The sender of an HTLC transaction is the contract, which can't sign.
+        let signer = match signer {
+            Some(signer) => signer,
+            None if htlc_proof.is_some() => return Err(missing("signer")),
+            None => transaction.sender.clone(),
+        };
+        let pst = TransactionProofBuilder::new(transaction)
+            .into_pst(signer, htlc_proof)
+            .ok_or_else(|| {
+                Error::InvalidArgument(
+                    "The HTLC proof type is required for transactions sent from an HTLC and not \
+                     allowed otherwise"
+                        .to_string(),
+                )
+            })?;
+
+        Ok(hex::encode(pst.serialize_to_vec()).into())
+    }
+
+    async fn sign_pst(
+        &mut self,
+        pst: String,
+        address: Address,
+        passphrase: Option<String>,
+    ) -> RPCResult<String, (), Self::Error> {
+        let mut pst = PartiallySignedTransaction::parse(&hex::decode(pst)?)?;
+        let key_pair = self.key_pair(&address, passphrase)?;
+
+        if pst.sign_with_key_pair(&key_pair)? == 0 {
+            return Err(Error::InvalidArgument(format!(
+                "{address} has no missing signature in the PST"
+            )));
+        }
+
+        Ok(hex::encode(pst.serialize_to_vec()).into())
+    }
+
+    async fn finalize_pst(&mut self, pst: String) -> RPCResult<String, (), Self::Error> {
+        let pst = PartiallySignedTransaction::parse(&hex::decode(pst)?)?;
+        Ok(hex::encode(pst.finalize()?.serialize_to_vec()).into())
+    }
+
+    async fn export_wallet_backup(
+        &mut self,
+        addresses: Option<Vec<Address>>,
+        backup_passphrase: String,
+    ) -> RPCResult<String, (), Self::Error> {
+        let contents = self
+            .wallet_store
+            .backup_contents(&addresses.unwrap_or_default())
+            .map_err(Error::AccountNotFound)?;
+        let backup = WalletBackup::new(contents, backup_passphrase.as_bytes())?;
+
+        Ok(hex::encode(backup.serialize_to_vec()).into())
+    }
+
+    async fn import_wallet_backup(
+        &mut self,
+        backup: String,
+        backup_passphrase: String,
+        overwrite: Option<bool>,
+    ) -> RPCResult<ReturnWalletBackupImport, (), Self::Error> {
+        let contents =
+            WalletBackup::parse(&hex::decode(backup)?)?.unlock(backup_passphrase.as_bytes())?;
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        let (accounts, hd_wallets, watch_only_accounts) =
+            self.wallet_store
+                .restore_backup(&contents, overwrite.unwrap_or(false), &mut txn);
+        txn.commit();
+
+        Ok(ReturnWalletBackupImport {
+            accounts,
+            hd_wallets,
+            watch_only_accounts,
+        }
+        .into())
+    }
+
+    async fn import_watch_only_account(
+        &mut self,
+        address: Address,
+        public_key: Option<PublicKey>,
+        extended_public_key: Option<String>,
+    ) -> RPCResult<Address, (), Self::Error> {
+        if extended_public_key.is_some() {
+            return Err(Error::ExtendedPublicKeyNotSupported);
+        }
+        if let Some(public_key) = &public_key {
+            if Address::from(public_key) != address {
+                return Err(Error::InvalidArgument(format!(
+                    "Public key {public_key} doesn't belong to {address}"
+                )));
+            }
+        }
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        self.wallet_store
+            .put_watch_only(&address, &WatchOnlyAccount { public_key }, &mut txn);
+        txn.commit();
+
+        Ok(address.into())
+    }
+
+    async fn list_watch_only_accounts(&mut self) -> RPCResult<Vec<Address>, (), Self::Error> {
+        Ok(self.wallet_store.list_watch_only(None).into())
+    }
+
+    async fn remove_watch_only_account(
+        &mut self,
+        address: Address,
+    ) -> RPCResult<bool, (), Self::Error> {
+        let mut txn = self.wallet_store.create_write_transaction();
+        if self
+            .wallet_store
+            .get_watch_only(&address, Some(&txn))
+            .is_none()
+        {
+            return Ok(false.into());
+        }
+        self.wallet_store.remove_watch_only(&address, &mut txn);
+        txn.commit();
+        Ok(true.into())
+    }
+
+    async fn get_watch_only_account(
+        &mut self,
+        address: Address,
+        max_transactions: Option<u16>,
+    ) -> RPCResult<ReturnWatchOnlyAccount, (), Self::Error> {
+        let watch_only = self
+            .wallet_store
+            .get_watch_only(&address, None)
+            .ok_or_else(|| Error::AccountNotFound(address.clone()))?;
+
+        let blockchain_proxy = self.blockchain.read();
+        let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+        let account = blockchain
+            .get_account_if_complete(&address)
+            .ok_or(Error::NoConsensus)?;
+        let transactions =
+            get_transactions_by_address(&blockchain_proxy, &address, max_transactions)?;
+
+        let pending_transactions = self
+            .mempool
+            .as_ref()
+            .map(|mempool| mempool.get_transactions())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|tx| tx.sender == address || tx.recipient == address)
+            .map(RPCTransaction::from_transaction)
+            .collect();
+
+        Ok(ReturnWatchOnlyAccount {
+            account: Account::from_account(address.clone(), account),
+            address,
+            public_key: watch_only.public_key,
+            pending_transactions,
+            transactions,
+        }
+        .into())
+    }
+
+    async fn create_watch_only_transaction(
+        &mut self,
+        address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        if self.wallet_store.get_watch_only(&address, None).is_none() {
+            return Err(Error::AccountNotFound(address));
+        }
+
+        let blockchain = self.blockchain.read();
+        let transaction = Transaction::new_basic(
+            address,
+            recipient,
+            value,
+            fee,
+            validity_start_height.block_number(blockchain.block_number()),
+            blockchain.network_id(),
+        );
+
+        Ok(hex::encode(transaction.serialize_to_vec()).into())
+    }
+
+    async fn get_wallet_transactions(
+        &mut self,
+        address: Address,
+        offset: Option<u32>,
+        limit: Option<u16>,
+    ) -> RPCResult<Vec<ReturnWalletTransaction>, (), Self::Error> {
+        let txn = self.wallet_store.create_read_transaction();
+        let transactions = self
+            .wallet_store
+            .list_transactions(
+                &address,
+                offset.unwrap_or(0) as usize,
+                limit.unwrap_or(500) as usize,
+                Some(&txn),
+            )
+            .into_iter()
+            .map(|transaction| {
+                let label = self.wallet_store.get_label(&transaction.hash, Some(&txn));
+                ReturnWalletTransaction {
+                    hash: transaction.hash,
+                    block_number: transaction.block_number,
+                    timestamp: transaction.timestamp,
+                    sender: transaction.sender,
+                    recipient: transaction.recipient,
+                    value: transaction.value,
+                    fee: transaction.fee,
+                    failed: transaction.failed,
+                    label: label.as_ref().map(|label| label.label.clone()),
+                    note: label.and_then(|label| label.note),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(transactions.into())
+    }
+
+    async fn set_transaction_label(
+        &mut self,
+        hash: Blake2bHash,
+        label: String,
+        note: Option<String>,
+    ) -> RPCResult<(), (), Self::Error> {
+        if label.is_empty() {
+            return Err(Error::InvalidArgument(
+                "Label must not be empty".to_string(),
+            ));
+        }
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        self.wallet_store
+            .put_label(&hash, &TransactionLabel { label, note }, &mut txn);
+        txn.commit();
+
+        Ok(().into())
+    }
+
+    async fn remove_transaction_label(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> RPCResult<bool, (), Self::Error> {
+        let mut txn = self.wallet_store.create_write_transaction();
+        if self.wallet_store.get_label(&hash, Some(&txn)).is_none() {
+            return Ok(false.into());
+        }
+        self.wallet_store.remove_label(&hash, &mut txn);
+        txn.commit();
+        Ok(true.into())
+    }
+
+    async fn create_funded_transactions(
+        &mut self,
+        recipient: Address,
+        value: Option<Coin>,
+        fee_per_byte: u64,
+        validity_start_height: ValidityStartHeight,
+        senders: Vec<Address>,
+    ) -> RPCResult<ReturnFundingPlan, (), Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+        let unlocked_wallets = self.unlocked_wallets.read();
+        let mut senders = if senders.is_empty() {
+            unlocked_wallets.unlocked_wallets.keys().cloned().collect()
+        } else {
+            senders
+        };
+        // A sender given more than once would otherwise be funded from twice.
+        let mut seen = HashSet::new();
+        senders.retain(|sender| seen.insert(sender.clone()));
+        let pending_transactions = self
+            .mempool
+            .as_ref()
+            .map(|mempool| mempool.get_transactions())
+            .unwrap_or_default();
+
+        // Reserve the balance spent by pending transactions, the same way the mempool does. Like the mempool, skip the
+        // ones that became invalid, e.g. because a block spent the balance since.
+        let mut accounts = HashMap::new();
+        let mut sources = vec![];
+        for address in senders {
+            if unlocked_wallets.get(&address).is_none() {
+                return Err(Error::UnlockedWalletNotFound(address));
+            }
+            let account = blockchain
+                .get_account_if_complete(&address)
+                .ok_or(Error::NoConsensus)?;
+            let mut reserved_balance = ReservedBalance::new(address.clone());
+            for tx in pending_transactions
+                .iter()
+                .filter(|tx| tx.sender == address)
+            {
+                if let Err(error) = blockchain.reserve_balance(&account, tx, &mut reserved_balance)
+                {
+                    log::debug!(
+                        %address,
+                        tx_hash = %tx.hash::<Blake2bHash>(),
+                        %error,
+                        "Skipping pending transaction that can't be funded anymore"
+                    );
+                }
+            }
+
+            sources.push(FundingSource {
+                address: address.clone(),
+                available: account.balance() - reserved_balance.balance(),
+            });
+            accounts.insert(address, (account, reserved_balance));
+        }
+
+        let plan = match value {
+            Some(value) => FundingPlan::for_amount(&sources, value, fee_per_byte)?,
+            None => FundingPlan::sweep(&sources, fee_per_byte)?,
+        };
+        let transactions = plan.sign(
+            &recipient,
+            validity_start_height.block_number(blockchain.block_number()),
+            blockchain.network_id(),
+            |address| unlocked_wallets.get(address).map(|wallet| &wallet.key_pair),
+        )?;
+
+        let mut inputs = vec![];
+        for (input, transaction) in plan.inputs.iter().zip(transactions) {
+            let (account, reserved_balance) = accounts.get_mut(&input.sender).unwrap();
+            blockchain.reserve_balance(account, &transaction, reserved_balance)?;
+            inputs.push(ReturnFundingInput {
+                sender: input.sender.clone(),
+                value: input.value,
+                fee: input.fee,
+                hash: transaction.hash::<Blake2bHash>(),
+                raw_transaction: hex::encode(transaction.serialize_to_vec()),
+            });
+        }
+
+        Ok(ReturnFundingPlan {
+            recipient,
+            value: plan.value(),
+            fee: plan.fee(),
+            inputs,
+        }
+        .into())
+    }
 }
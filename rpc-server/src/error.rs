@@ -2,6 +2,7 @@ use nimiq_hash::Blake2bHash;
 use nimiq_jsonrpc_core::RpcError;
 use nimiq_keys::Address;
 use nimiq_mempool::verify::VerifyErr;
+use nimiq_primitives::networks::NetworkId;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -69,6 +70,45 @@ pub enum Error {
     #[error("No unlocked wallet with address: {0}")]
     UnlockedWalletNotFound(Address),
 
+    #[error("No HD wallet with ID: {0}")]
+    HdWalletNotFound(Address),
+
+    #[error("Invalid mnemonic")]
+    InvalidMnemonic,
+
+    #[error("Message is for network {actual}, but this node is on network {expected}")]
+    WrongNetwork { expected: NetworkId, actual: u8 },
+
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+
+    #[error("Invalid account index: {0}")]
+    InvalidAccountIndex(u32),
+
+    #[error("The HD wallet was already imported with derivation path {0}")]
+    DerivationPathMismatch(String),
+
+    #[error("Extended public keys can't be imported, since Ed25519 keys are derived with hardened derivation only")]
+    ExtendedPublicKeyNotSupported,
+
+    #[error("Multisig error: {0}")]
+    Multisig(#[from] nimiq_transaction_builder::multisig::MultisigError),
+
+    #[error("Funding error: {0}")]
+    Funding(#[from] nimiq_transaction_builder::funding::FundingError),
+
+    #[error("Account error: {0}")]
+    Account(#[from] nimiq_primitives::account::AccountError),
+
+    #[error("PST error: {0}")]
+    Pst(#[from] nimiq_transaction::pst::PstError),
+
+    #[error("Wallet backup error: {0}")]
+    WalletBackup(#[from] nimiq_wallet::WalletBackupError),
+
+    #[error("Typed message error: {0}")]
+    TypedMessage(#[from] nimiq_keys::typed_message::TypedMessageError),
+
     #[error("Invalid hex: {0}")]
     HexError(#[from] hex::FromHexError),
 
@@ -92,6 +132,15 @@ pub enum Error {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Signing history error: {0}")]
+    SigningHistory(#[from] nimiq_validator::signing_history::SigningHistoryError),
+
+    #[error("Validator stats error: {0}")]
+    ValidatorStats(#[from] nimiq_validator::stats::ValidatorStatsError),
+
+    #[error("The validator keys are held by a remote signer")]
+    RemoteSignerKeys,
 }
 
 impl From<Error> for nimiq_jsonrpc_core::RpcError {
@@ -3,4 +3,5 @@ pub use nimiq_jsonrpc_server::{Config, Server};
 
 pub mod dispatchers;
 pub mod error;
+pub mod wallet_history;
 pub mod wallets;
@@ -0,0 +1,433 @@
+use std::sync::Arc;
+
+use futures::StreamExt;
+use nimiq_account::{BlockLog, Log, TransactionLog};
+use nimiq_blockchain::HistoryStore;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
+use nimiq_database::traits::WriteTransaction;
+use nimiq_primitives::coin::Coin;
+use nimiq_transaction::{extended_transaction::ExtTxData, ExecutedTransaction};
+use nimiq_wallet::{WalletStore, WalletTransaction};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+
+/// The maximum number of transactions per address that are restored from the history store after block logs were
+/// missed.
+const BACKFILL_LIMIT: u16 = 500;
+
+/// Records the transactions of the wallet's addresses in the wallet store as blocks are applied, and removes them
+/// again when their blocks are reverted. Only the full blockchain emits logs, so nothing is recorded otherwise.
+///
+/// If block logs are missed because the recorder fell behind, the history since the last recorded block is restored
+/// from the history store instead.
+pub async fn record_wallet_history(wallet_store: Arc<WalletStore>, blockchain: BlockchainProxy) {
+    let (log_notifier, mut last_block_number) = match blockchain.read() {
+        BlockchainReadProxy::Full(blockchain) => (
+            blockchain.log_notifier.subscribe(),
+            blockchain.block_number(),
+        ),
+        BlockchainReadProxy::Light(_) => return,
+    };
+
+    let mut stream = BroadcastStream::new(log_notifier);
+    while let Some(event) = stream.next().await {
+        match event {
+            Ok(block_log) => {
+                last_block_number = match &block_log {
+                    BlockLog::AppliedBlock { block_number, .. } => *block_number,
+                    BlockLog::RevertedBlock { block_number, .. } => block_number - 1,
+                };
+                record_block_log(&wallet_store, &block_log);
+            }
+            Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                log::warn!(
+                    missed,
+                    since = last_block_number,
+                    "Wallet history missed block logs, restoring them from the history store"
+                );
+                last_block_number = backfill(&wallet_store, &blockchain, last_block_number);
+            }
+        }
+    }
+}
+
+/// Replaces the recorded transactions of the tracked addresses after `block_number` with those in the history store.
+/// Returns the block number up to which the history is complete again.
+fn backfill(wallet_store: &WalletStore, blockchain: &BlockchainProxy, block_number: u32) -> u32 {
+    let BlockchainReadProxy::Full(blockchain) = blockchain.read() else {
+        return block_number;
+    };
+
+    restore_history(wallet_store, &blockchain.history_store, block_number);
+    blockchain.block_number()
+}
+
+fn restore_history(wallet_store: &WalletStore, history_store: &HistoryStore, block_number: u32) {
+    // Blocks after `block_number` might have been reverted since, so their recorded transactions are replaced rather
+    // than merged with the ones from the history store.
+    let mut stale = vec![];
+    let mut restored = vec![];
+    for address in wallet_store.tracked_addresses(None) {
+        for transaction in wallet_store
+            .list_transactions(&address, 0, BACKFILL_LIMIT as usize, None)
+            .into_iter()
+            .take_while(|transaction| transaction.block_number > block_number)
+        {
+            stale.push((address.clone(), transaction));
+        }
+
+        // The hashes are ordered from the newest transaction to the oldest.
+        let hashes = history_store.get_tx_hashes_by_address(&address, BACKFILL_LIMIT, None);
+        let mut complete = hashes.len() < BACKFILL_LIMIT as usize;
+        'hashes: for hash in hashes {
+            for ext_tx in history_store.get_ext_tx_by_hash(&hash, None) {
+                if ext_tx.block_number <= block_number {
+                    complete = true;
+                    break 'hashes;
+                }
+                if let ExtTxData::Basic(tx) = &ext_tx.data {
+                    let transaction =
+                        from_executed_transaction(tx, ext_tx.block_number, ext_tx.block_time);
+                    restored.push((address.clone(), transaction));
+                }
+            }
+        }
+        if !complete {
+            log::warn!(
+                %address,
+                since = block_number,
+                "Wallet history could only be partially restored"
+            );
+        }
+    }
+
+    let mut txn = wallet_store.create_write_transaction();
+    for (address, transaction) in stale {
+        wallet_store.remove_transaction(
+            &address,
+            transaction.block_number,
+            &transaction.hash,
+            &mut txn,
+        );
+    }
+    for (address, transaction) in restored {
+        wallet_store.put_transaction(&address, &transaction, &mut txn);
+    }
+    txn.commit();
+}
+
+fn record_block_log(wallet_store: &WalletStore, block_log: &BlockLog) {
+    let addresses = wallet_store.tracked_addresses(None);
+    if addresses.is_empty() {
+        return;
+    }
+
+    let mut txn = wallet_store.create_write_transaction();
+    match block_log {
+        BlockLog::AppliedBlock {
+            block_number,
+            timestamp,
+            tx_logs,
+            ..
+        } => {
+            for tx_log in tx_logs {
+                let Some(transaction) = to_wallet_transaction(tx_log, *block_number, *timestamp)
+                else {
+                    continue;
+                };
+                for address in &addresses {
+                    if tx_log
+                        .logs
+                        .iter()
+                        .any(|log| log.is_related_to_address(address))
+                    {
+                        wallet_store.put_transaction(address, &transaction, &mut txn);
+                    }
+                }
+            }
+        }
+        BlockLog::RevertedBlock {
+            block_number,
+            tx_logs,
+            ..
+        } => {
+            for tx_log in tx_logs {
+                for address in &addresses {
+                    if tx_log
+                        .logs
+                        .iter()
+                        .any(|log| log.is_related_to_address(address))
+                    {
+                        wallet_store.remove_transaction(
+                            address,
+                            *block_number,
+                            &tx_log.tx_hash,
+                            &mut txn,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    txn.commit();
+}
+
+/// Summarizes a transaction from its logs. Every transaction pays its fee and then either transfers its value or
+/// fails.
+fn to_wallet_transaction(
+    tx_log: &TransactionLog,
+    block_number: u32,
+    timestamp: u64,
+) -> Option<WalletTransaction> {
+    let fee = tx_log.logs.iter().find_map(|log| match log {
+        Log::PayFee { fee, .. } => Some(*fee),
+        _ => None,
+    });
+    let (sender, recipient, value) = tx_log.logs.iter().find_map(|log| match log {
+        Log::FailedTransaction { from, to, .. } if tx_log.failed => {
+            Some((from.clone(), to.clone(), Coin::ZERO))
+        }
+        Log::Transfer {
+            from, to, amount, ..
+        } if !tx_log.failed => Some((from.clone(), to.clone(), *amount)),
+        _ => None,
+    })?;
+
+    Some(WalletTransaction {
+        hash: tx_log.tx_hash.clone(),
+        block_number,
+        timestamp,
+        sender,
+        recipient,
+        value,
+        fee: fee.unwrap_or(Coin::ZERO),
+        failed: tx_log.failed,
+    })
+}
+
+/// Summarizes a transaction from the history store the same way as [`to_wallet_transaction`] does from its logs.
+fn from_executed_transaction(
+    tx: &ExecutedTransaction,
+    block_number: u32,
+    timestamp: u64,
+) -> WalletTransaction {
+    let raw = tx.get_raw_transaction();
+    WalletTransaction {
+        hash: tx.hash(),
+        block_number,
+        timestamp,
+        sender: raw.sender.clone(),
+        recipient: raw.recipient.clone(),
+        value: if tx.failed() { Coin::ZERO } else { raw.value },
+        fee: raw.fee,
+        failed: tx.failed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_database::volatile::VolatileDatabase;
+    use nimiq_hash::Blake2bHash;
+    use nimiq_keys::Address;
+    use nimiq_primitives::{account::FailReason, networks::NetworkId};
+    use nimiq_test_log::test;
+    use nimiq_transaction::{extended_transaction::ExtendedTransaction, Transaction};
+    use nimiq_wallet::WatchOnlyAccount;
+
+    use super::*;
+
+    fn address(byte: u8) -> Address {
+        Address::from([byte; Address::SIZE])
+    }
+
+    fn coin(value: u64) -> Coin {
+        Coin::from_u64_unchecked(value)
+    }
+
+    /// A wallet store that tracks the history of `address(1)`.
+    fn wallet_store() -> WalletStore {
+        let wallet_store = WalletStore::new(VolatileDatabase::new(20).unwrap());
+        let mut txn = wallet_store.create_write_transaction();
+        wallet_store.put_watch_only(&address(1), &WatchOnlyAccount::default(), &mut txn);
+        txn.commit();
+        wallet_store
+    }
+
+    fn transfer_log(hash: u8, from: Address, to: Address) -> TransactionLog {
+        TransactionLog::new(
+            Blake2bHash::from([hash; 32]),
+            vec![
+                Log::PayFee {
+                    from: from.clone(),
+                    fee: coin(2),
+                },
+                Log::Transfer {
+                    from,
+                    to,
+                    amount: coin(100),
+                    data: None,
+                },
+            ],
+        )
+    }
+
+    fn failed_log(hash: u8, from: Address, to: Address) -> TransactionLog {
+        TransactionLog {
+            tx_hash: Blake2bHash::from([hash; 32]),
+            logs: vec![
+                Log::PayFee {
+                    from: from.clone(),
+                    fee: coin(2),
+                },
+                Log::FailedTransaction {
+                    from,
+                    to,
+                    failure_reason: FailReason::InsufficientFunds,
+                },
+            ],
+            failed: true,
+        }
+    }
+
+    fn history_transaction(block_number: u32, value: u64) -> ExtendedTransaction {
+        ExtendedTransaction {
+            network_id: NetworkId::UnitAlbatross,
+            block_number,
+            block_time: u64::from(block_number),
+            data: ExtTxData::Basic(ExecutedTransaction::Ok(Transaction::new_basic(
+                address(1),
+                address(2),
+                coin(value),
+                coin(1),
+                block_number,
+                NetworkId::UnitAlbatross,
+            ))),
+        }
+    }
+
+    #[test]
+    fn to_wallet_transaction_works() {
+        let transaction =
+            to_wallet_transaction(&transfer_log(1, address(1), address(2)), 5, 1000).unwrap();
+        assert_eq!(transaction.hash, Blake2bHash::from([1; 32]));
+        assert_eq!(transaction.block_number, 5);
+        assert_eq!(transaction.timestamp, 1000);
+        assert_eq!(transaction.sender, address(1));
+        assert_eq!(transaction.recipient, address(2));
+        assert_eq!(transaction.value, coin(100));
+        assert_eq!(transaction.fee, coin(2));
+        assert!(!transaction.failed);
+
+        // A failed transaction only pays its fee.
+        let transaction =
+            to_wallet_transaction(&failed_log(2, address(1), address(2)), 5, 1000).unwrap();
+        assert_eq!(transaction.sender, address(1));
+        assert_eq!(transaction.recipient, address(2));
+        assert_eq!(transaction.value, Coin::ZERO);
+        assert_eq!(transaction.fee, coin(2));
+        assert!(transaction.failed);
+
+        // Without a transfer there is nothing to summarize.
+        let tx_log = TransactionLog::new(
+            Blake2bHash::from([3; 32]),
+            vec![Log::PayFee {
+                from: address(1),
+                fee: coin(2),
+            }],
+        );
+        assert!(to_wallet_transaction(&tx_log, 5, 1000).is_none());
+    }
+
+    #[test]
+    fn record_block_log_applies_and_reverts_blocks() {
+        let wallet_store = wallet_store();
+        let tx_logs = vec![
+            transfer_log(1, address(2), address(1)),
+            failed_log(2, address(1), address(3)),
+            transfer_log(3, address(2), address(3)),
+        ];
+
+        record_block_log(
+            &wallet_store,
+            &BlockLog::AppliedBlock {
+                inherent_logs: vec![],
+                block_hash: Blake2bHash::default(),
+                block_number: 5,
+                timestamp: 1000,
+                tx_logs: tx_logs.clone(),
+                total_tx_size: 0,
+            },
+        );
+
+        // Only the transactions of the tracked address are recorded, including the failed one.
+        let transactions = wallet_store.list_transactions(&address(1), 0, 10, None);
+        assert_eq!(transactions.len(), 2);
+        assert!(transactions
+            .iter()
+            .all(|transaction| transaction.block_number == 5));
+        let failed = transactions
+            .iter()
+            .find(|transaction| transaction.hash == Blake2bHash::from([2; 32]))
+            .unwrap();
+        assert!(failed.failed);
+        assert_eq!(failed.value, Coin::ZERO);
+        assert!(wallet_store
+            .list_transactions(&address(3), 0, 10, None)
+            .is_empty());
+
+        record_block_log(
+            &wallet_store,
+            &BlockLog::RevertedBlock {
+                inherent_logs: vec![],
+                block_hash: Blake2bHash::default(),
+                block_number: 5,
+                tx_logs,
+                total_tx_size: 0,
+            },
+        );
+        assert!(wallet_store
+            .list_transactions(&address(1), 0, 10, None)
+            .is_empty());
+    }
+
+    #[test]
+    fn restore_history_replaces_transactions_after_block() {
+        let env = VolatileDatabase::new(20).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+        let history = vec![
+            history_transaction(1, 10),
+            history_transaction(3, 30),
+            history_transaction(5, 50),
+        ];
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, 1, &history);
+        txn.commit();
+
+        // The transaction of block 1 was recorded, the one of block 4 was reverted since.
+        let wallet_store = wallet_store();
+        let recorded = |ext_tx: &ExtendedTransaction| {
+            let ExtTxData::Basic(tx) = &ext_tx.data else {
+                unreachable!()
+            };
+            from_executed_transaction(tx, ext_tx.block_number, ext_tx.block_time)
+        };
+        let mut stale = recorded(&history_transaction(4, 40));
+        stale.hash = Blake2bHash::from([4; 32]);
+        let mut txn = wallet_store.create_write_transaction();
+        wallet_store.put_transaction(&address(1), &recorded(&history[0]), &mut txn);
+        wallet_store.put_transaction(&address(1), &stale, &mut txn);
+        txn.commit();
+
+        restore_history(&wallet_store, &history_store, 2);
+
+        let transactions = wallet_store.list_transactions(&address(1), 0, 10, None);
+        assert_eq!(
+            transactions,
+            vec![
+                recorded(&history[2]),
+                recorded(&history[1]),
+                recorded(&history[0]),
+            ]
+        );
+    }
+}
@@ -231,6 +231,7 @@ async fn main_inner() -> Result<(), Error> {
             client.mempool(),
             client.consensus_proxy(),
             client.network(),
+            client.validator_proxy(),
             &[],
         )
     }
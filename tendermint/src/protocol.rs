@@ -12,6 +12,8 @@ pub enum ProposalError {
     InvalidProposal,
     /// Proposals with invalid signatures must be ignored, but the peer who broadcasted it may be banned.
     InvalidSignature,
+    /// Our own proposal could not be created or signed. The round is skipped as if the proposal timed out.
+    SigningFailed,
     /// Collectively used for all problems not accounted for by InvalidProposal and InvalidSignature.
     Other,
 }
@@ -93,13 +95,16 @@ pub trait Protocol: Clone + Send + Sync + Unpin + Sized + 'static {
     fn is_proposer(&self, round: u32) -> bool;
 
     /// Creates the proposal for given `round`
-    fn create_proposal(&self, round: u32) -> (ProposalMessage<Self::Proposal>, Self::Inherent);
+    fn create_proposal(
+        &self,
+        round: u32,
+    ) -> Result<(ProposalMessage<Self::Proposal>, Self::Inherent), ProposalError>;
 
     /// Signs a given `proposal_message` for sending it over the wire
     fn sign_proposal(
         &self,
         proposal_message: &ProposalMessage<Self::Proposal>,
-    ) -> Self::ProposalSignature;
+    ) -> Result<Self::ProposalSignature, ProposalError>;
 
     /// Verifies a given `proposal`. Optionally a precomputed `precalculated_inherent` can be provided if the inherent has been computed before.
     /// as well as all checks can be skipped except for the signature verification.
@@ -1,5 +1,7 @@
 use crate::{
-    protocol::{Inherent, Proposal, ProposalMessage, Protocol, SignedProposalMessage},
+    protocol::{
+        Inherent, Proposal, ProposalError, ProposalMessage, Protocol, SignedProposalMessage,
+    },
     utils::{Return, Step},
     Tendermint,
 };
@@ -118,7 +120,10 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
             };
 
             // Sign the proposal message
-            let signature = self.protocol.sign_proposal(&message);
+            let signature = match self.protocol.sign_proposal(&message) {
+                Ok(signature) => signature,
+                Err(error) => return Some(self.skip_proposal(error)),
+            };
 
             // Store the proposal for the current round.
             proposals.insert(proposal_hash.clone(), (Some(*valid_round), signature));
@@ -132,11 +137,17 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
                 "Our turn, setting fresh proposal",
             );
 
-            // Create a new proposal.
-            let (message, inherent) = self.protocol.create_proposal(self.state.current_round);
-
-            // Sign the proposal message
-            let signature = self.protocol.sign_proposal(&message);
+            // Create a new proposal and sign the proposal message.
+            let (message, inherent, signature) = match self
+                .protocol
+                .create_proposal(self.state.current_round)
+                .and_then(|(message, inherent)| {
+                    let signature = self.protocol.sign_proposal(&message)?;
+                    Ok((message, inherent, signature))
+                }) {
+                Ok(proposal) => proposal,
+                Err(error) => return Some(self.skip_proposal(error)),
+            };
 
             // Hash it for identification and voting.
             let proposal_hash = message.proposal.hash();
@@ -159,4 +170,21 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
             Some(Return::Update(self.state.clone()))
         }
     }
+
+    /// Skips our proposal for the current round if it could not be created or signed, e.g. because the signer is
+    /// unavailable. Like for a proposal timeout, the node votes nil and progresses to the prevote step.
+    fn skip_proposal(&mut self, error: ProposalError) -> Return<TProtocol> {
+        log::warn!(
+            current_round = self.state.current_round,
+            ?error,
+            "Failed to create our proposal, voting nil",
+        );
+
+        self.state
+            .votes
+            .insert((self.state.current_round, Step::Prevote), None);
+        self.state.current_step = Step::Prevote;
+
+        Return::Update(self.state.clone())
+    }
 }
@@ -133,22 +133,25 @@ impl Protocol for Validator {
         *self.propose.get(round as usize).expect("Exceeded rounds")
     }
 
-    fn create_proposal(&self, round: u32) -> (ProposalMessage<Self::Proposal>, Self::Inherent) {
-        (
+    fn create_proposal(
+        &self,
+        round: u32,
+    ) -> Result<(ProposalMessage<Self::Proposal>, Self::Inherent), ProposalError> {
+        Ok((
             ProposalMessage {
                 round,
                 valid_round: None,
                 proposal: TestProposal(round),
             },
             TestInherent(round),
-        )
+        ))
     }
 
     fn sign_proposal(
         &self,
         _proposal_message: &ProposalMessage<Self::Proposal>,
-    ) -> Self::ProposalSignature {
-        true
+    ) -> Result<Self::ProposalSignature, ProposalError> {
+        Ok(true)
     }
 
     fn verify_proposal(
@@ -0,0 +1,399 @@
+//! Byzantine behaviour of validators in tests.
+//!
+//! A `ByzantineNetwork` wraps the validator network of a validator and applies the rules of a `Scenario` to the
+//! messages the validator sends and the items it publishes: it can withhold or delay them, replace them by tampered
+//! ones, e.g. with invalid contributions, or send a conflicting message along with the original one to equivocate.
+//! The scenario switches its rules as the chain progresses, such that a validator can e.g. be honest at first and
+//! only misbehave while producing a specific block. Incoming messages are never altered.
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, SigHash, Signature};
+use nimiq_database::DatabaseProxy;
+use nimiq_network_interface::{
+    network::{MsgAcceptance, Network, Topic},
+    request::{Message, Request, RequestCommon},
+};
+use nimiq_network_mock::MockHub;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_validator::validator::Validator;
+use nimiq_validator_network::{
+    network_impl::ValidatorNetworkImpl, MessageStream, NetworkError, ValidatorNetwork,
+};
+use parking_lot::{Mutex, RwLock};
+use tokio::task::JoinSet;
+
+use crate::{test_network::TestNetwork, validator::build_validators_with_networks};
+
+/// The messages a rule applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MessageFilter {
+    /// All messages and published items.
+    All,
+    /// Messages with the given type ID sent to other validators.
+    Message(u16),
+    /// Items published on the gossipsub topic with the given name.
+    Topic(&'static str),
+}
+
+/// Alters a message of a specific type. The function returns whether it altered the message. Messages of other types
+/// are left untouched.
+#[derive(Clone)]
+pub struct Tamper(Arc<dyn Fn(&mut dyn Any) -> bool + Send + Sync>);
+
+impl Tamper {
+    pub fn new<T: 'static, F: Fn(&mut T) -> bool + Send + Sync + 'static>(f: F) -> Self {
+        Self(Arc::new(move |message: &mut dyn Any| {
+            message.downcast_mut::<T>().map_or(false, &f)
+        }))
+    }
+
+    /// Returns whether the message was altered.
+    fn apply<T: 'static>(&self, message: &mut T) -> bool {
+        (self.0)(message)
+    }
+}
+
+impl fmt::Debug for Tamper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Tamper")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Action {
+    /// The message is not sent at all.
+    Withhold,
+    /// The message is sent after the given delay, without holding up the validator in the meantime. Messages to other
+    /// validators are sent from a task owned by the network, while published items are held back in the task the
+    /// validator publishes them from.
+    Delay(Duration),
+    /// A tampered message is sent instead of the original one.
+    Tamper(Tamper),
+    /// The original message is sent, followed by a tampered, conflicting one.
+    Equivocate(Tamper),
+}
+
+#[derive(Clone, Debug)]
+pub struct Rule {
+    filter: MessageFilter,
+    /// The validators the rule applies to, or all of them. Published items are only affected by rules for all
+    /// validators.
+    recipients: Option<Vec<usize>>,
+    action: Action,
+}
+
+impl Rule {
+    pub fn new(filter: MessageFilter, action: Action) -> Self {
+        Self {
+            filter,
+            recipients: None,
+            action,
+        }
+    }
+
+    /// A rule for the messages of type `M`.
+    pub fn message<M: RequestCommon>(action: Action) -> Self {
+        Self::new(MessageFilter::Message(M::TYPE_ID), action)
+    }
+
+    /// A rule for the items published on the topic `T`.
+    pub fn topic<T: Topic>(action: Action) -> Self {
+        Self::new(MessageFilter::Topic(T::NAME), action)
+    }
+
+    /// Restricts the rule to the messages sent to the given validators.
+    pub fn to(mut self, recipients: Vec<usize>) -> Self {
+        self.recipients = Some(recipients);
+        self
+    }
+
+    fn applies_to(&self, target: &MessageFilter, recipient: Option<usize>) -> bool {
+        let matches_recipient = match (&self.recipients, recipient) {
+            (None, _) => true,
+            (Some(recipients), Some(recipient)) => recipients.contains(&recipient),
+            (Some(_), None) => false,
+        };
+        matches_recipient && (self.filter == MessageFilter::All || self.filter == *target)
+    }
+}
+
+/// A script of Byzantine behaviour. Each phase starts at a block number and applies its rules while the validator is
+/// working on that block and the following ones, until the next phase starts.
+#[derive(Clone, Debug, Default)]
+pub struct Scenario {
+    phases: BTreeMap<u32, Vec<Rule>>,
+}
+
+impl Scenario {
+    /// A scenario without any Byzantine behaviour.
+    pub fn honest() -> Self {
+        Self::default()
+    }
+
+    /// A scenario applying `rules` from the start.
+    pub fn always(rules: Vec<Rule>) -> Self {
+        Self::honest().from_block(0, rules)
+    }
+
+    /// Applies `rules` from `block_number` on. An empty list of rules makes the validator honest again.
+    pub fn from_block(mut self, block_number: u32, rules: Vec<Rule>) -> Self {
+        self.phases.insert(block_number, rules);
+        self
+    }
+
+    fn rules_at(&self, block_number: u32) -> &[Rule] {
+        self.phases
+            .range(..=block_number)
+            .next_back()
+            .map_or(&[], |(_, rules)| rules)
+    }
+}
+
+/// Controls the scenario of a `ByzantineNetwork` while the validator is running.
+pub struct ScenarioControl {
+    scenario: RwLock<Scenario>,
+    /// The number of messages and items the actions of the rules took effect on.
+    applied: AtomicUsize,
+}
+
+impl ScenarioControl {
+    /// Replaces the scenario, e.g. to script behaviour that doesn't depend on the block number.
+    pub fn set_scenario(&self, scenario: Scenario) {
+        *self.scenario.write() = scenario;
+    }
+
+    /// Returns the number of messages and published items the actions of the scenario took effect on.
+    pub fn applied(&self) -> usize {
+        self.applied.load(Ordering::Relaxed)
+    }
+}
+
+/// A validator network that misbehaves according to a `Scenario`.
+pub struct ByzantineNetwork<TValidatorNetwork> {
+    inner: Arc<TValidatorNetwork>,
+    blockchain: Arc<RwLock<Blockchain>>,
+    control: Arc<ScenarioControl>,
+    /// The delayed messages that weren't sent yet. They are dropped along with the network.
+    delayed: Mutex<JoinSet<()>>,
+}
+
+impl<TValidatorNetwork: ValidatorNetwork> ByzantineNetwork<TValidatorNetwork> {
+    pub fn new(
+        inner: TValidatorNetwork,
+        blockchain: Arc<RwLock<Blockchain>>,
+        scenario: Scenario,
+    ) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            blockchain,
+            control: Arc::new(ScenarioControl {
+                scenario: RwLock::new(scenario),
+                applied: AtomicUsize::new(0),
+            }),
+            delayed: Mutex::new(JoinSet::new()),
+        }
+    }
+
+    pub fn control(&self) -> Arc<ScenarioControl> {
+        Arc::clone(&self.control)
+    }
+
+    /// Returns the action of the first rule applying to the message, if any.
+    fn action(&self, target: MessageFilter, recipient: Option<usize>) -> Option<Action> {
+        // The rules apply to the block the validator is working on.
+        let block_number = self.blockchain.read().block_number() + 1;
+        self.control
+            .scenario
+            .read()
+            .rules_at(block_number)
+            .iter()
+            .find(|rule| rule.applies_to(&target, recipient))
+            .map(|rule| rule.action.clone())
+    }
+
+    /// Records that an action took effect.
+    fn applied(&self) {
+        self.control.applied.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Builds validators like `build_validators`, each of them misbehaving according to the scenario at its index. Returns
+/// the validators along with the controls of their scenarios.
+pub async fn build_byzantine_validators<N>(
+    env: DatabaseProxy,
+    peer_ids: &[u64],
+    hub: &mut Option<MockHub>,
+    scenarios: Vec<Scenario>,
+) -> Vec<(
+    Validator<N, ByzantineNetwork<ValidatorNetworkImpl<N>>>,
+    Arc<ScenarioControl>,
+)>
+where
+    N: TestNetwork + Network,
+    N::Error: Send,
+    N::PeerId: Deserialize + Serialize,
+{
+    assert_eq!(peer_ids.len(), scenarios.len());
+    let mut controls = vec![];
+    let validators =
+        build_validators_with_networks(env, peer_ids, hub, false, |i, network, blockchain| {
+            let network =
+                ByzantineNetwork::new(network, Arc::clone(blockchain), scenarios[i].clone());
+            controls.push(network.control());
+            network
+        })
+        .await;
+    validators.into_iter().zip(controls).collect()
+}
+
+#[async_trait]
+impl<TValidatorNetwork: ValidatorNetwork + 'static> ValidatorNetwork
+    for ByzantineNetwork<TValidatorNetwork>
+{
+    type Error = TValidatorNetwork::Error;
+    type NetworkType = TValidatorNetwork::NetworkType;
+    type PubsubId = TValidatorNetwork::PubsubId;
+
+    async fn set_validators(&self, validator_keys: Vec<LazyPublicKey>) {
+        self.inner.set_validators(validator_keys).await
+    }
+
+    async fn send_to<M: Message + Clone>(
+        &self,
+        validator_id: usize,
+        mut msg: M,
+    ) -> Result<(), Self::Error> {
+        match self.action(MessageFilter::Message(M::TYPE_ID), Some(validator_id)) {
+            None => self.inner.send_to(validator_id, msg).await,
+            Some(Action::Withhold) => {
+                self.applied();
+                Ok(())
+            }
+            Some(Action::Delay(delay)) => {
+                self.applied();
+                let inner = Arc::clone(&self.inner);
+                self.delayed.lock().spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    if let Err(error) = inner.send_to(validator_id, msg).await {
+                        log::warn!(%error, validator_id, "Failed to send delayed message");
+                    }
+                });
+                Ok(())
+            }
+            Some(Action::Tamper(tamper)) => {
+                if tamper.apply(&mut msg) {
+                    self.applied();
+                }
+                self.inner.send_to(validator_id, msg).await
+            }
+            Some(Action::Equivocate(tamper)) => {
+                let mut conflicting = msg.clone();
+                let tampered = tamper.apply(&mut conflicting);
+                self.inner.send_to(validator_id, msg).await?;
+                if !tampered {
+                    return Ok(());
+                }
+                self.applied();
+                self.inner.send_to(validator_id, conflicting).await
+            }
+        }
+    }
+
+    async fn request<TRequest: Request>(
+        &self,
+        request: TRequest,
+        validator_id: usize,
+    ) -> Result<
+        <TRequest as RequestCommon>::Response,
+        NetworkError<<Self::NetworkType as Network>::Error>,
+    > {
+        self.inner.request(request, validator_id).await
+    }
+
+    fn receive<M>(&self) -> MessageStream<M, <Self::NetworkType as Network>::PeerId>
+    where
+        M: Message + Clone,
+    {
+        self.inner.receive()
+    }
+
+    async fn publish<TTopic: Topic + Sync>(
+        &self,
+        mut item: TTopic::Item,
+    ) -> Result<(), Self::Error> {
+        match self.action(MessageFilter::Topic(TTopic::NAME), None) {
+            None => self.inner.publish::<TTopic>(item).await,
+            Some(Action::Withhold) => {
+                self.applied();
+                Ok(())
+            }
+            Some(Action::Delay(delay)) => {
+                // The validator publishes every item from a task of its own, so holding it back here only delays
+                // the item.
+                self.applied();
+                tokio::time::sleep(delay).await;
+                self.inner.publish::<TTopic>(item).await
+            }
+            Some(Action::Tamper(tamper)) => {
+                if tamper.apply(&mut item) {
+                    self.applied();
+                }
+                self.inner.publish::<TTopic>(item).await
+            }
+            Some(Action::Equivocate(tamper)) => {
+                // Topic items aren't necessarily `Clone`, but they are serializable.
+                let mut conflicting = TTopic::Item::deserialize_from_vec(&item.serialize_to_vec())
+                    .expect("Topic items must deserialize from their serialization");
+                let tampered = tamper.apply(&mut conflicting);
+                self.inner.publish::<TTopic>(item).await?;
+                if !tampered {
+                    return Ok(());
+                }
+                self.applied();
+                self.inner.publish::<TTopic>(conflicting).await
+            }
+        }
+    }
+
+    async fn subscribe<'a, TTopic: Topic + Sync>(
+        &self,
+    ) -> Result<BoxStream<'a, (TTopic::Item, Self::PubsubId)>, Self::Error> {
+        self.inner.subscribe::<TTopic>().await
+    }
+
+    fn cache<M: Message>(&self, buffer_size: usize, lifetime: Duration) {
+        self.inner.cache::<M>(buffer_size, lifetime)
+    }
+
+    fn validator_record_hash(&self) -> SigHash {
+        self.inner.validator_record_hash()
+    }
+
+    async fn set_public_key(
+        &self,
+        public_key: &CompressedPublicKey,
+        signature: Signature,
+    ) -> Result<(), Self::Error> {
+        self.inner.set_public_key(public_key, signature).await
+    }
+
+    fn validate_message<TTopic>(&self, id: Self::PubsubId, acceptance: MsgAcceptance)
+    where
+        TTopic: Topic + Sync,
+    {
+        self.inner.validate_message::<TTopic>(id, acceptance)
+    }
+}
@@ -68,6 +68,7 @@ impl TestNetwork for Network {
             peer_key,
             peer_contact,
             Vec::new(),
+            Vec::new(),
             genesis_hash.clone(),
             true,
             Services::all(),
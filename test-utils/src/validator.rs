@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
 use futures::{future, StreamExt};
+use nimiq_block_production::signer::LocalSigner;
+use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_bls::KeyPair as BlsKeyPair;
 use nimiq_consensus::{Consensus, ConsensusEvent};
@@ -12,7 +14,8 @@ use nimiq_network_interface::network::Network as NetworkInterface;
 use nimiq_network_mock::MockHub;
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_validator::validator::Validator;
-use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
+use nimiq_validator_network::{network_impl::ValidatorNetworkImpl, ValidatorNetwork};
+use parking_lot::RwLock;
 use rand::{rngs::StdRng, SeedableRng};
 use tokio_stream::wrappers::BroadcastStream;
 
@@ -36,22 +39,62 @@ pub async fn build_validator<N: TestNetwork + NetworkInterface>(
 where
     N::Error: Send,
     N::PeerId: Deserialize + Serialize,
+{
+    build_validator_with_network(
+        peer_id,
+        validator_address,
+        automatic_reactivate,
+        signing_key,
+        voting_key,
+        fee_key,
+        genesis_info,
+        hub,
+        is_prover_active,
+        |network, _| network,
+    )
+    .await
+}
+
+/// Builds a validator whose validator network is created by `wrap_network` from the regular one, e.g. to alter the
+/// messages it sends.
+pub async fn build_validator_with_network<N, TValidatorNetwork, F>(
+    peer_id: u64,
+    validator_address: Address,
+    automatic_reactivate: bool,
+    signing_key: SchnorrKeyPair,
+    voting_key: BlsKeyPair,
+    fee_key: SchnorrKeyPair,
+    genesis_info: GenesisInfo,
+    hub: &mut Option<MockHub>,
+    is_prover_active: bool,
+    wrap_network: F,
+) -> (Validator<N, TValidatorNetwork>, Consensus<N>)
+where
+    N: TestNetwork + NetworkInterface,
+    N::Error: Send,
+    N::PeerId: Deserialize + Serialize,
+    TValidatorNetwork: ValidatorNetwork + 'static,
+    TValidatorNetwork::PubsubId: std::fmt::Debug + Unpin,
+    F: FnOnce(ValidatorNetworkImpl<N>, &Arc<RwLock<Blockchain>>) -> TValidatorNetwork,
 {
     let node =
         Node::<N>::history_with_genesis_info(peer_id, genesis_info, hub, is_prover_active).await;
     let consensus = node.consensus.expect("Could not create consensus");
-    let validator_network = Arc::new(ValidatorNetworkImpl::new(Arc::clone(&consensus.network)));
+    let validator_network = Arc::new(wrap_network(
+        ValidatorNetworkImpl::new(Arc::clone(&consensus.network)),
+        &node.blockchain,
+    ));
     (
-        Validator::<N, ValidatorNetworkImpl<N>>::new(
+        Validator::<N, TValidatorNetwork>::new(
             node.environment,
             &consensus,
             node.blockchain,
             validator_network,
             validator_address,
             automatic_reactivate,
-            signing_key,
-            voting_key,
             fee_key,
+            Arc::new(LocalSigner::new(signing_key, voting_key)),
+            None,
             MempoolConfig::default(),
         ),
         consensus,
@@ -67,6 +110,29 @@ pub async fn build_validators<N: TestNetwork + NetworkInterface>(
 where
     N::Error: Send,
     N::PeerId: Deserialize + Serialize,
+{
+    build_validators_with_networks(env, peer_ids, hub, is_prover_active, |_, network, _| {
+        network
+    })
+    .await
+}
+
+/// Builds validators whose validator networks are created by `wrap_network` from the regular ones. It is called with
+/// the index of the validator in `peer_ids`.
+pub async fn build_validators_with_networks<N, TValidatorNetwork, F>(
+    env: DatabaseProxy,
+    peer_ids: &[u64],
+    hub: &mut Option<MockHub>,
+    is_prover_active: bool,
+    mut wrap_network: F,
+) -> Vec<Validator<N, TValidatorNetwork>>
+where
+    N: TestNetwork + NetworkInterface,
+    N::Error: Send,
+    N::PeerId: Deserialize + Serialize,
+    TValidatorNetwork: ValidatorNetwork + 'static,
+    TValidatorNetwork::PubsubId: std::fmt::Debug + Unpin,
+    F: FnMut(usize, ValidatorNetworkImpl<N>, &Arc<RwLock<Blockchain>>) -> TValidatorNetwork,
 {
     let num_validators = peer_ids.len();
     // Generate validator key pairs.
@@ -101,7 +167,7 @@ where
     let mut consensus = vec![];
     let mut networks = vec![];
     for i in 0..num_validators {
-        let (v, c) = build_validator(
+        let (v, c) = build_validator_with_network(
             peer_ids[i],
             Address::from(&validator_keys[i]),
             false,
@@ -111,6 +177,7 @@ where
             genesis.clone(),
             hub,
             is_prover_active,
+            |network, blockchain| wrap_network(i, network, blockchain),
         )
         .await;
         let network: Arc<N> = Arc::clone(&c.network);
@@ -141,14 +208,15 @@ where
     validators
 }
 
-pub fn validator_for_slot<N: TestNetwork + NetworkInterface>(
-    validators: &[Validator<N, ValidatorNetworkImpl<N>>],
+pub fn validator_for_slot<N, TValidatorNetwork>(
+    validators: &[Validator<N, TValidatorNetwork>],
     block_number: u32,
     offset: u32,
-) -> &Validator<N, ValidatorNetworkImpl<N>>
+) -> &Validator<N, TValidatorNetwork>
 where
-    N::Error: Send,
-    N::PeerId: Deserialize + Serialize,
+    N: TestNetwork + NetworkInterface,
+    TValidatorNetwork: ValidatorNetwork + 'static,
+    TValidatorNetwork::PubsubId: std::fmt::Debug + Unpin,
 {
     let consensus = &validators.first().unwrap().consensus;
 
@@ -160,20 +228,19 @@ where
 
     validators
         .iter()
-        .find(|validator| {
-            &validator.voting_key().public_key.compress() == slot.voting_key.compressed()
-        })
+        .find(|validator| &validator.voting_public_key().compress() == slot.voting_key.compressed())
         .unwrap()
 }
 
-pub fn pop_validator_for_slot<N: TestNetwork + NetworkInterface>(
-    validators: &mut Vec<Validator<N, ValidatorNetworkImpl<N>>>,
+pub fn pop_validator_for_slot<N, TValidatorNetwork>(
+    validators: &mut Vec<Validator<N, TValidatorNetwork>>,
     block_number: u32,
     offset: u32,
-) -> Validator<N, ValidatorNetworkImpl<N>>
+) -> Validator<N, TValidatorNetwork>
 where
-    N::Error: Send,
-    N::PeerId: Deserialize + Serialize,
+    N: TestNetwork + NetworkInterface,
+    TValidatorNetwork: ValidatorNetwork + 'static,
+    TValidatorNetwork::PubsubId: std::fmt::Debug + Unpin,
 {
     let consensus = &validators.first().unwrap().consensus;
 
@@ -186,7 +253,7 @@ where
     let index = validators
         .iter()
         .position(|validator| {
-            &validator.voting_key().public_key.compress() == slot.voting_key.compressed()
+            &validator.voting_public_key().compress() == slot.voting_key.compressed()
         })
         .unwrap();
     validators.remove(index)
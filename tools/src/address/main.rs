@@ -1,19 +1,260 @@
-use std::{error::Error, process};
+use std::{
+    error::Error,
+    io::{stdin, BufRead},
+    process,
+};
 
-use clap::{Arg, Command};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use nimiq_key_derivation::ExtendedPrivateKey;
 use nimiq_keys::{Address, PrivateKey, PublicKey, SecureGenerate};
+use nimiq_mnemonic::{
+    key_derivation::ToExtendedPrivateKey, Entropy, Mnemonic, MnemonicType, WORDLIST_EN,
+};
 use nimiq_serde::Deserialize;
+use rand::{rngs::OsRng, RngCore};
+
+/// The path of the first account of a wallet.
+const DEFAULT_PATH: &str = "m/44'/242'/0'/0'";
+
+/// The highest derivation index. All indices are hardened, which limits them to 31 bits.
+const MAX_INDEX: u32 = 0x7fff_ffff;
 
 fn parse_private_key(s: &str) -> Result<PrivateKey, Box<dyn Error>> {
     Ok(PrivateKey::deserialize_from_vec(&hex::decode(s)?)?)
 }
 
+/// Reads the mnemonic from the arguments or, if it isn't given, from STDIN, such that it doesn't end up in the
+/// shell history.
+fn read_mnemonic(matches: &ArgMatches) -> Result<Mnemonic, Box<dyn Error>> {
+    let words = match matches.get_one::<String>("mnemonic") {
+        Some(words) => words.clone(),
+        None => {
+            let mut line = String::new();
+            stdin().lock().read_line(&mut line)?;
+            line
+        }
+    };
+    words
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .parse::<Mnemonic>()
+        .map_err(|_| "Invalid mnemonic".into())
+}
+
+/// Returns `count` paths, starting at `path` and incrementing its last index.
+fn batch_paths(path: &str, count: u32) -> Result<Vec<String>, Box<dyn Error>> {
+    if !ExtendedPrivateKey::is_valid_path(path) {
+        return Err(format!("Invalid derivation path: {path}").into());
+    }
+    if path.split('/').skip(1).any(|segment| {
+        segment
+            .trim_end_matches('\'')
+            .parse::<u32>()
+            .unwrap_or(u32::MAX)
+            > MAX_INDEX
+    }) {
+        return Err(format!("Derivation index out of range: {path}").into());
+    }
+    if count == 1 {
+        return Ok(vec![path.to_string()]);
+    }
+
+    let (parent, last) = path
+        .rsplit_once('/')
+        .ok_or("Can't derive more than one key at the master path")?;
+    let first: u32 = last.trim_end_matches('\'').parse()?;
+    (0..count)
+        .map(|i| -> Result<String, Box<dyn Error>> {
+            let index = first
+                .checked_add(i)
+                .filter(|&index| index <= MAX_INDEX)
+                .ok_or("Derivation index out of range")?;
+            Ok(format!("{parent}/{index}'"))
+        })
+        .collect()
+}
+
+/// Returns the seed of a mnemonic, in hex.
+fn mnemonic_seed(mnemonic: &Mnemonic, passphrase: Option<&str>) -> Result<String, Box<dyn Error>> {
+    let seed = mnemonic
+        .to_seed(passphrase)
+        .map_err(|e| format!("Can't compute seed: {e:?}"))?;
+    Ok(hex::encode(seed))
+}
+
+/// Derives the private keys of a BIP39 mnemonic at the paths given by `batch_paths`.
+fn derive_keys(
+    mnemonic: &Mnemonic,
+    passphrase: Option<&str>,
+    path: &str,
+    count: u32,
+) -> Result<Vec<(String, PrivateKey)>, Box<dyn Error>> {
+    if !matches!(
+        mnemonic.get_type(WORDLIST_EN),
+        MnemonicType::BIP39 | MnemonicType::UNKNOWN
+    ) {
+        return Err("Not a BIP39 mnemonic".into());
+    }
+    let master_key = mnemonic
+        .to_master_key(passphrase)
+        .map_err(|e| format!("Can't compute seed: {e:?}"))?;
+
+    batch_paths(path, count)?
+        .into_iter()
+        .map(|path| -> Result<(String, PrivateKey), Box<dyn Error>> {
+            let key = master_key
+                .derive_path(&path)
+                .ok_or("Derivation index overflow")?;
+            Ok((path, key.into_private_key()))
+        })
+        .collect()
+}
+
+fn print_key(private_key: &PrivateKey, show_private_key: bool) {
+    let public_key = PublicKey::from(private_key);
+    let address = Address::from(&public_key);
+
+    println!("Address:       {}", address.to_user_friendly_address());
+    println!("Address (raw): {}", address.to_hex());
+    println!("Public Key:    {}", public_key.to_hex());
+    if show_private_key {
+        println!("Private Key:   {}", hex::encode(private_key.as_bytes()));
+    }
+}
+
+fn run_subcommand(name: &str, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    match name {
+        "mnemonic-generate" => {
+            // Avoid entropy whose mnemonic would also be a valid legacy mnemonic.
+            let entropy = loop {
+                let mut bytes = [0u8; Entropy::SIZE];
+                OsRng.fill_bytes(&mut bytes);
+                let entropy = Entropy::from(bytes);
+                if !entropy.is_colliding_checksum() {
+                    break entropy;
+                }
+            };
+            println!("{}", entropy.to_mnemonic(WORDLIST_EN));
+        }
+        "mnemonic-check" => {
+            let mnemonic = read_mnemonic(matches)?;
+            match mnemonic.get_type(WORDLIST_EN) {
+                MnemonicType::BIP39 => println!("Type: BIP39"),
+                MnemonicType::LEGACY => {
+                    // Legacy mnemonics encode the private key of a single account.
+                    println!("Type: legacy");
+                    let entropy = mnemonic
+                        .to_entropy_legacy(WORDLIST_EN)
+                        .ok_or("Invalid mnemonic")?;
+                    let private_key = PrivateKey::deserialize_from_vec(entropy.as_slice())?;
+                    print_key(&private_key, matches.get_flag("show_private_key"));
+                }
+                MnemonicType::UNKNOWN => {
+                    println!("Type: ambiguous, both a valid BIP39 and a valid legacy mnemonic")
+                }
+                MnemonicType::INVALID => return Err("Invalid mnemonic".into()),
+            }
+        }
+        "mnemonic-seed" => {
+            let mnemonic = read_mnemonic(matches)?;
+            let passphrase = matches.get_one::<String>("passphrase").map(String::as_str);
+            println!("{}", mnemonic_seed(&mnemonic, passphrase)?);
+        }
+        "mnemonic-derive" => {
+            let mnemonic = read_mnemonic(matches)?;
+            let passphrase = matches.get_one::<String>("passphrase").map(String::as_str);
+            let path = matches.get_one::<String>("path").unwrap();
+            let count = *matches.get_one::<u32>("count").unwrap();
+            let show_private_key = matches.get_flag("show_private_key");
+
+            for (path, private_key) in derive_keys(&mnemonic, passphrase, path, count)? {
+                let address = Address::from(&PublicKey::from(&private_key));
+                if show_private_key {
+                    println!(
+                        "{path} {} {}",
+                        address.to_user_friendly_address(),
+                        hex::encode(private_key.as_bytes())
+                    );
+                } else {
+                    println!("{path} {}", address.to_user_friendly_address());
+                }
+            }
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
 fn main() {
+    let mnemonic_arg = Arg::new("mnemonic")
+        .value_name("MNEMONIC")
+        .help("The words of the mnemonic. Read from STDIN if absent.");
+    let passphrase_arg = Arg::new("passphrase")
+        .short('p')
+        .long("passphrase")
+        .value_name("PASSPHRASE")
+        .help("The BIP39 passphrase protecting the seed.");
+    let show_private_key_arg = Arg::new("show_private_key")
+        .long("show-private-key")
+        .action(ArgAction::SetTrue)
+        .help("Also print the private keys.");
+
     let matches = Command::new("nimiq-address")
         .about("Displays address etc. of a random or specified key")
         .arg(Arg::new("private").value_name("PRIVATE"))
+        .args_conflicts_with_subcommands(true)
+        .subcommand(
+            Command::new("mnemonic-generate").about("Generate a random BIP39 mnemonic of 24 words."),
+        )
+        .subcommand(
+            Command::new("mnemonic-check")
+                .about(
+                    "Check whether a mnemonic is a BIP39 or a legacy mnemonic. Prints the account of \
+                     legacy mnemonics.",
+                )
+                .arg(mnemonic_arg.clone())
+                .arg(show_private_key_arg.clone()),
+        )
+        .subcommand(
+            Command::new("mnemonic-seed")
+                .about("Print the seed of a mnemonic.")
+                .arg(mnemonic_arg.clone())
+                .arg(passphrase_arg.clone()),
+        )
+        .subcommand(
+            Command::new("mnemonic-derive")
+                .about("Derive the addresses of a BIP39 mnemonic, one per line.")
+                .arg(mnemonic_arg)
+                .arg(passphrase_arg)
+                .arg(
+                    Arg::new("path")
+                        .long("path")
+                        .value_name("PATH")
+                        .default_value(DEFAULT_PATH)
+                        .help("The derivation path of the first key. Only hardened indices are supported."),
+                )
+                .arg(
+                    Arg::new("count")
+                        .short('n')
+                        .long("count")
+                        .value_name("COUNT")
+                        .value_parser(value_parser!(u32).range(1..))
+                        .default_value("1")
+                        .help("The number of keys to derive, incrementing the last index of the path."),
+                )
+                .arg(show_private_key_arg),
+        )
         .get_matches();
 
+    if let Some((name, matches)) = matches.subcommand() {
+        if let Err(e) = run_subcommand(name, matches) {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+        return;
+    }
+
     let private_key = if let Some(p) = matches.get_one::<String>("private") {
         match parse_private_key(p) {
             Ok(p) => p,
@@ -25,11 +266,83 @@ fn main() {
     } else {
         PrivateKey::generate_default_csprng()
     };
-    let public_key = PublicKey::from(&private_key);
-    let address = Address::from(&public_key);
+    print_key(&private_key, true);
+}
 
-    println!("Address:       {}", address.to_user_friendly_address());
-    println!("Address (raw): {}", address.to_hex());
-    println!("Public Key:    {}", public_key.to_hex());
-    println!("Private Key:   {}", hex::encode(private_key.as_bytes()));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test vector from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const PASSPHRASE: &str = "TREZOR";
+
+    #[test]
+    fn batch_paths_increment_the_last_index() {
+        assert_eq!(batch_paths(DEFAULT_PATH, 1).unwrap(), vec![DEFAULT_PATH]);
+        assert_eq!(
+            batch_paths("m/44'/242'/0'/5'", 3).unwrap(),
+            vec!["m/44'/242'/0'/5'", "m/44'/242'/0'/6'", "m/44'/242'/0'/7'"]
+        );
+        assert_eq!(batch_paths("m/0'", 2).unwrap(), vec!["m/0'", "m/1'"]);
+    }
+
+    #[test]
+    fn batch_paths_rejects_invalid_paths() {
+        assert!(batch_paths("m/44'/242'/0'/0", 1).is_err());
+        assert!(batch_paths("44'/242'", 1).is_err());
+        assert!(batch_paths("m", 2).is_err());
+        assert_eq!(batch_paths("m", 1).unwrap(), vec!["m"]);
+    }
+
+    #[test]
+    fn batch_paths_rejects_indices_beyond_31_bits() {
+        assert_eq!(
+            batch_paths("m/44'/242'/0'/2147483647'", 1).unwrap(),
+            vec!["m/44'/242'/0'/2147483647'"]
+        );
+        assert!(batch_paths("m/44'/242'/0'/2147483648'", 1).is_err());
+        assert!(batch_paths("m/44'/2147483648'/0'/0'", 1).is_err());
+        assert!(batch_paths("m/44'/242'/0'/2147483647'", 2).is_err());
+        assert!(batch_paths("m/44'/242'/0'/4294967295'", 1).is_err());
+    }
+
+    #[test]
+    fn mnemonic_seed_matches_test_vector() {
+        let mnemonic = MNEMONIC.parse::<Mnemonic>().unwrap();
+        assert_eq!(
+            mnemonic_seed(&mnemonic, Some(PASSPHRASE)).unwrap(),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+
+    #[test]
+    fn derive_keys_matches_test_vector() {
+        let mnemonic = MNEMONIC.parse::<Mnemonic>().unwrap();
+        let keys = derive_keys(&mnemonic, Some(PASSPHRASE), DEFAULT_PATH, 2).unwrap();
+
+        let expected = [
+            (
+                "m/44'/242'/0'/0'",
+                "e598453f7a89a3ff6a5facf4dea2304f32403d58b16d8a15b46e9b4fefe2971a",
+                "NQ39 FKMS UR79 KBV7 TA5Q N0AL LMVP F0NY 8S5A",
+            ),
+            (
+                "m/44'/242'/0'/1'",
+                "f72620cb68e4ac5737e55d4c298b66dc8eb7974ffc21f00d7a12d94d20a3f5e8",
+                "NQ72 S54U HFY1 09L7 NVH7 YRA2 SGKG 56LB 8RQX",
+            ),
+        ];
+        assert_eq!(keys.len(), expected.len());
+        for ((path, private_key), (expected_path, expected_key, expected_address)) in
+            keys.iter().zip(expected)
+        {
+            assert_eq!(path, expected_path);
+            assert_eq!(hex::encode(private_key.as_bytes()), expected_key);
+            assert_eq!(
+                Address::from(&PublicKey::from(private_key)).to_user_friendly_address(),
+                expected_address
+            );
+        }
+    }
 }
@@ -0,0 +1,1010 @@
+//! Offline construction of signed transactions of every type.
+//!
+//! The parameters of an operation are given as command line arguments or as a JSON object in a file, using the same
+//! names as the command line arguments, e.g. `{"recipient": "NQ..", "value": "12.5", "validity-start-height": 1}`.
+//! Arguments given on the command line take precedence over the file.
+use std::{collections::BTreeMap, fmt::Display, fs, str::FromStr};
+
+use anyhow::Error;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::{Address, KeyPair, PrivateKey, PublicKey};
+use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::{
+    account::htlc_contract::{AnyHash, AnyHash32, AnyHash64, PreImage},
+    SignatureProof,
+};
+use nimiq_transaction_builder::TransactionBuilder;
+use serde_json::Value;
+
+use crate::AppError;
+
+/// A parameter of an operation, given as `--<name>` on the command line.
+struct Param {
+    name: &'static str,
+    help: &'static str,
+    flag: bool,
+}
+
+const fn param(name: &'static str, help: &'static str) -> Param {
+    Param {
+        name,
+        help,
+        flag: false,
+    }
+}
+
+/// Parameters used by every operation.
+const COMMON_PARAMS: &[Param] = &[
+    param(
+        "secret-key",
+        "Secret key of the sender, which also pays the fee.",
+    ),
+    param("fee", "Transaction fee in NIM. Defaults to 0."),
+    param("validity-start-height", "Validity start height."),
+    param("network", "Network ID. Defaults to main."),
+];
+
+const PARAMS: &[Param] = &[
+    param("recipient", "Address receiving the funds."),
+    param("value", "Transaction value in NIM."),
+    param("data", "Data attached to the transaction, in hex."),
+    param("owner", "Owner of the vesting contract."),
+    param("start-time", "Start of the vesting, in milliseconds."),
+    param("time-step", "Time between vesting steps, in milliseconds."),
+    param("num-steps", "Number of vesting steps."),
+    param("contract", "Address of the vesting or HTLC contract."),
+    param(
+        "htlc-sender",
+        "Address that can redeem the HTLC after the timeout.",
+    ),
+    param(
+        "htlc-recipient",
+        "Address that can redeem the HTLC with the pre-image.",
+    ),
+    param(
+        "hash-root",
+        "Hash of the pre-image, hashed `hash-count` times, in hex.",
+    ),
+    param("hash-count", "Number of times the pre-image was hashed."),
+    param(
+        "hash-algorithm",
+        "Hash algorithm of the HTLC: blake2b, sha256 or sha512.",
+    ),
+    param("timeout", "Timeout of the HTLC, in milliseconds."),
+    param("pre-image", "Pre-image of the HTLC, in hex."),
+    param(
+        "htlc-sender-signature",
+        "Signature proof of the HTLC sender, in hex, as printed by sign-htlc-early.",
+    ),
+    param(
+        "htlc-recipient-signature",
+        "Signature proof of the HTLC recipient, in hex, as printed by sign-htlc-early.",
+    ),
+    param("staker", "Address of the staker."),
+    param(
+        "staker-key",
+        "Secret key of the staker. Defaults to the secret key of the sender.",
+    ),
+    param("delegation", "Address of the validator to delegate to."),
+    Param {
+        name: "reactivate-all-stake",
+        help: "Reactivate all inactive stake.",
+        flag: true,
+    },
+    param(
+        "cold-key",
+        "Secret key of the validator. Defaults to the secret key of the sender.",
+    ),
+    param("signing-key", "Signing public key of the validator."),
+    param("signing-secret-key", "Signing secret key of the validator."),
+    param(
+        "voting-key",
+        "BLS voting secret key of the validator, in hex.",
+    ),
+    param("reward-address", "Reward address of the validator."),
+    param("signal-data", "Signal data of the validator, in hex."),
+    param("validator", "Address of the validator."),
+];
+
+/// The operations with their description and parameters besides the common ones.
+const OPERATIONS: &[(&str, &str, &[&str])] = &[
+    ("basic", "Transfer funds.", &["recipient", "value", "data"]),
+    (
+        "create-vesting",
+        "Create a vesting contract.",
+        &["owner", "start-time", "time-step", "num-steps", "value"],
+    ),
+    (
+        "redeem-vesting",
+        "Redeem funds from a vesting contract, signed by its owner.",
+        &["contract", "recipient", "value"],
+    ),
+    (
+        "create-htlc",
+        "Create an HTLC contract.",
+        &[
+            "htlc-sender",
+            "htlc-recipient",
+            "hash-root",
+            "hash-count",
+            "hash-algorithm",
+            "timeout",
+            "value",
+        ],
+    ),
+    (
+        "redeem-htlc-regular",
+        "Redeem an HTLC with the pre-image, signed by the HTLC recipient.",
+        &[
+            "contract",
+            "recipient",
+            "pre-image",
+            "hash-root",
+            "hash-count",
+            "hash-algorithm",
+            "value",
+        ],
+    ),
+    (
+        "redeem-htlc-timeout",
+        "Redeem an HTLC after the timeout, signed by the HTLC sender.",
+        &["contract", "recipient", "value"],
+    ),
+    (
+        "sign-htlc-early",
+        "Sign the early resolution of an HTLC, as the HTLC sender or recipient. Prints the signature proof.",
+        &["contract", "recipient", "value"],
+    ),
+    (
+        "redeem-htlc-early",
+        "Redeem an HTLC with the signatures of both the HTLC sender and recipient.",
+        &[
+            "contract",
+            "recipient",
+            "value",
+            "htlc-sender-signature",
+            "htlc-recipient-signature",
+        ],
+    ),
+    (
+        "create-staker",
+        "Create a staker.",
+        &["staker-key", "delegation", "value"],
+    ),
+    (
+        "stake",
+        "Add stake to an existing staker.",
+        &["staker", "value"],
+    ),
+    (
+        "update-staker",
+        "Change the delegation of a staker.",
+        &["staker-key", "delegation", "reactivate-all-stake"],
+    ),
+    (
+        "set-inactive-stake",
+        "Set the inactive stake of a staker.",
+        &["staker-key", "value"],
+    ),
+    (
+        "unstake",
+        "Remove inactive stake of a staker, signed by the staker.",
+        &["recipient", "value"],
+    ),
+    (
+        "create-validator",
+        "Create a validator, depositing the validator deposit.",
+        &[
+            "cold-key",
+            "signing-key",
+            "voting-key",
+            "reward-address",
+            "signal-data",
+        ],
+    ),
+    (
+        "update-validator",
+        "Update the keys, reward address or signal data of a validator.",
+        &[
+            "cold-key",
+            "signing-key",
+            "voting-key",
+            "reward-address",
+            "signal-data",
+        ],
+    ),
+    (
+        "deactivate-validator",
+        "Deactivate a validator, signed by its signing key.",
+        &["validator", "signing-secret-key"],
+    ),
+    (
+        "reactivate-validator",
+        "Reactivate a validator, signed by its signing key.",
+        &["validator", "signing-secret-key"],
+    ),
+    (
+        "retire-validator",
+        "Retire a validator.",
+        &["cold-key"],
+    ),
+    (
+        "delete-validator",
+        "Delete a retired validator and pay out its deposit, signed by the validator.",
+        &["recipient", "value"],
+    ),
+];
+
+/// Returns the parameters with the given names, besides the common ones.
+fn operation_params<'a>(names: &'a [&str]) -> impl Iterator<Item = &'static Param> + 'a {
+    PARAMS
+        .iter()
+        .filter(move |param| names.contains(&param.name))
+}
+
+fn arg(param: &Param) -> Arg {
+    let arg = Arg::new(param.name).long(param.name).help(param.help);
+    if param.flag {
+        arg.action(ArgAction::SetTrue)
+    } else {
+        arg.value_name("VALUE")
+    }
+}
+
+pub fn command() -> Command {
+    let mut command = Command::new("build")
+        .about(
+            "Build and sign a transaction of any type offline. Prints the signed transaction as hex.",
+        )
+        .subcommand_required(true);
+    for (name, about, params) in OPERATIONS {
+        command = command.subcommand(
+            Command::new(*name)
+                .about(*about)
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .value_name("FILE")
+                        .help("Read the parameters from a JSON file."),
+                )
+                .args(COMMON_PARAMS.iter().map(arg))
+                .args(operation_params(params).map(arg)),
+        );
+    }
+    command
+}
+
+/// The parameters of an operation, as strings.
+struct Params(BTreeMap<String, String>);
+
+impl Params {
+    fn collect(params: &[&str], matches: &ArgMatches) -> Result<Self, Error> {
+        let mut values = BTreeMap::new();
+        if let Some(file) = matches.get_one::<String>("json") {
+            let json: BTreeMap<String, Value> = serde_json::from_str(&fs::read_to_string(file)?)?;
+            for (name, value) in json {
+                if !params.contains(&name.as_str())
+                    && !COMMON_PARAMS.iter().any(|param| param.name == name)
+                {
+                    return Err(AppError::UnknownParameter(name).into());
+                }
+                let value = match value {
+                    Value::Null => continue,
+                    Value::String(s) => s,
+                    Value::Bool(_) | Value::Number(_) => value.to_string(),
+                    _ => return Err(AppError::InvalidParameter(name, "not a scalar".into()).into()),
+                };
+                values.insert(name, value);
+            }
+        }
+
+        for param in COMMON_PARAMS.iter().chain(operation_params(params)) {
+            if param.flag {
+                if matches.get_flag(param.name) {
+                    values.insert(param.name.to_string(), "true".to_string());
+                }
+            } else if let Some(value) = matches.get_one::<String>(param.name) {
+                values.insert(param.name.to_string(), value.clone());
+            }
+        }
+        Ok(Params(values))
+    }
+
+    fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, AppError>
+    where
+        T::Err: Display,
+    {
+        self.0
+            .get(name)
+            .map(|value| {
+                T::from_str(value)
+                    .map_err(|e| AppError::InvalidParameter(name.to_string(), e.to_string()))
+            })
+            .transpose()
+    }
+
+    fn require<T: FromStr>(&self, name: &str) -> Result<T, AppError>
+    where
+        T::Err: Display,
+    {
+        self.get(name)?
+            .ok_or_else(|| AppError::MissingParameter(name.to_string()))
+    }
+
+    fn hex(&self, name: &str) -> Result<Option<Vec<u8>>, AppError> {
+        self.0
+            .get(name)
+            .map(|value| {
+                hex::decode(value)
+                    .map_err(|e| AppError::InvalidParameter(name.to_string(), e.to_string()))
+            })
+            .transpose()
+    }
+
+    fn key_pair(&self, name: &str) -> Result<Option<KeyPair>, AppError> {
+        self.hex(name)?
+            .map(|bytes| {
+                PrivateKey::deserialize_from_vec(&bytes)
+                    .map(KeyPair::from)
+                    .map_err(|e| AppError::InvalidParameter(name.to_string(), e.to_string()))
+            })
+            .transpose()
+    }
+
+    fn require_key_pair(&self, name: &str) -> Result<KeyPair, AppError> {
+        self.key_pair(name)?
+            .ok_or_else(|| AppError::MissingParameter(name.to_string()))
+    }
+
+    fn voting_key_pair(&self) -> Result<Option<BlsKeyPair>, AppError> {
+        self.hex("voting-key")?
+            .map(|bytes| {
+                BlsSecretKey::deserialize_from_vec(&bytes)
+                    .map(BlsKeyPair::from)
+                    .map_err(|e| {
+                        AppError::InvalidParameter("voting-key".to_string(), e.to_string())
+                    })
+            })
+            .transpose()
+    }
+
+    fn signature_proof(&self, name: &str) -> Result<SignatureProof, AppError> {
+        let bytes = self
+            .hex(name)?
+            .ok_or_else(|| AppError::MissingParameter(name.to_string()))?;
+        SignatureProof::deserialize_from_vec(&bytes)
+            .map_err(|e| AppError::InvalidParameter(name.to_string(), e.to_string()))
+    }
+
+    fn hash_root(&self) -> Result<AnyHash, AppError> {
+        parse_hash_root(
+            &self.require::<String>("hash-root")?,
+            &self.require::<String>("hash-algorithm")?,
+        )
+    }
+}
+
+/// Parses the hex encoded hash root of an HTLC, hashed with `hash_algorithm`.
+pub(crate) fn parse_hash_root(hash_root: &str, hash_algorithm: &str) -> Result<AnyHash, AppError> {
+    let invalid = |e: String| AppError::InvalidParameter("hash-root".to_string(), e);
+    match hash_algorithm {
+        "blake2b" => AnyHash32::from_str(hash_root)
+            .map(AnyHash::Blake2b)
+            .map_err(|e| invalid(e.to_string())),
+        "sha256" => AnyHash32::from_str(hash_root)
+            .map(AnyHash::Sha256)
+            .map_err(|e| invalid(e.to_string())),
+        "sha512" => AnyHash64::from_str(hash_root)
+            .map(AnyHash::Sha512)
+            .map_err(|e| invalid(e.to_string())),
+        algorithm => Err(AppError::InvalidParameter(
+            "hash-algorithm".to_string(),
+            algorithm.to_string(),
+        )),
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    println!("{}", build(matches)?);
+    Ok(())
+}
+
+/// Builds the transaction of the operation given as subcommand. Returns the signed transaction as hex, or the
+/// signature proof as hex for `sign-htlc-early`.
+fn build(matches: &ArgMatches) -> Result<String, Error> {
+    let (name, matches) = matches.subcommand().ok_or(AppError::MissingOperation)?;
+    let (_, _, params) = OPERATIONS
+        .iter()
+        .find(|(operation, _, _)| *operation == name)
+        .ok_or_else(|| AppError::UnknownOperation(name.to_string()))?;
+    let params = Params::collect(params, matches)?;
+
+    let fee = params.get("fee")?.unwrap_or(Coin::ZERO);
+    let validity_start_height: u32 = params.require("validity-start-height")?;
+    let network_id = params.get("network")?.unwrap_or(NetworkId::Main);
+    let key_pair = || params.require_key_pair("secret-key");
+
+    let tx = match name {
+        "basic" => match params.hex("data")? {
+            Some(data) => TransactionBuilder::new_basic_with_data(
+                &key_pair()?,
+                params.require("recipient")?,
+                data,
+                params.require("value")?,
+                fee,
+                validity_start_height,
+                network_id,
+            )?,
+            None => TransactionBuilder::new_basic(
+                &key_pair()?,
+                params.require("recipient")?,
+                params.require("value")?,
+                fee,
+                validity_start_height,
+                network_id,
+            )?,
+        },
+        "create-vesting" => TransactionBuilder::new_create_vesting(
+            &key_pair()?,
+            params.require("owner")?,
+            params.require("start-time")?,
+            params.require("time-step")?,
+            params.require("num-steps")?,
+            params.require("value")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "redeem-vesting" => TransactionBuilder::new_redeem_vesting(
+            &key_pair()?,
+            params.require("contract")?,
+            params.require("recipient")?,
+            params.require("value")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "create-htlc" => TransactionBuilder::new_create_htlc(
+            &key_pair()?,
+            params.require("htlc-sender")?,
+            params.require("htlc-recipient")?,
+            params.hash_root()?,
+            params.require("hash-count")?,
+            params.require("timeout")?,
+            params.require("value")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "redeem-htlc-regular" => TransactionBuilder::new_redeem_htlc_regular(
+            &key_pair()?,
+            params.require("contract")?,
+            params.require("recipient")?,
+            params.require::<PreImage>("pre-image")?,
+            params.hash_root()?,
+            params.require("hash-count")?,
+            params.require("value")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "redeem-htlc-timeout" => TransactionBuilder::new_redeem_htlc_timeout(
+            &key_pair()?,
+            params.require("contract")?,
+            params.require("recipient")?,
+            params.require("value")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "sign-htlc-early" => {
+            let signature_proof = TransactionBuilder::sign_htlc_early(
+                &key_pair()?,
+                params.require("contract")?,
+                params.require("recipient")?,
+                params.require("value")?,
+                fee,
+                validity_start_height,
+                network_id,
+            )?;
+            return Ok(hex::encode(signature_proof.serialize_to_vec()));
+        }
+        "redeem-htlc-early" => TransactionBuilder::new_redeem_htlc_early(
+            params.require("contract")?,
+            params.require("recipient")?,
+            params.signature_proof("htlc-sender-signature")?,
+            params.signature_proof("htlc-recipient-signature")?,
+            params.require("value")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "create-staker" => {
+            let key_pair = key_pair()?;
+            let staker_key_pair = params.key_pair("staker-key")?;
+            TransactionBuilder::new_create_staker(
+                &key_pair,
+                staker_key_pair.as_ref().unwrap_or(&key_pair),
+                params.get::<Address>("delegation")?,
+                params.require("value")?,
+                fee,
+                validity_start_height,
+                network_id,
+            )?
+        }
+        "stake" => TransactionBuilder::new_stake(
+            &key_pair()?,
+            params.require("staker")?,
+            params.require("value")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "update-staker" => {
+            let key_pair = key_pair()?;
+            let (fee_key_pair, staker_key_pair) = match params.key_pair("staker-key")? {
+                Some(staker_key_pair) => (Some(&key_pair), staker_key_pair),
+                None => (None, key_pair.clone()),
+            };
+            TransactionBuilder::new_update_staker(
+                fee_key_pair,
+                &staker_key_pair,
+                params.get::<Address>("delegation")?,
+                params.get("reactivate-all-stake")?.unwrap_or(false),
+                fee,
+                validity_start_height,
+                network_id,
+            )?
+        }
+        "set-inactive-stake" => {
+            let key_pair = key_pair()?;
+            let (fee_key_pair, staker_key_pair) = match params.key_pair("staker-key")? {
+                Some(staker_key_pair) => (Some(&key_pair), staker_key_pair),
+                None => (None, key_pair.clone()),
+            };
+            TransactionBuilder::new_set_inactive_stake(
+                fee_key_pair,
+                &staker_key_pair,
+                params.require("value")?,
+                fee,
+                validity_start_height,
+                network_id,
+            )?
+        }
+        "unstake" => TransactionBuilder::new_unstake(
+            &key_pair()?,
+            params.require("recipient")?,
+            params.require("value")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "create-validator" => {
+            let key_pair = key_pair()?;
+            let cold_key_pair = params.key_pair("cold-key")?;
+            TransactionBuilder::new_create_validator(
+                &key_pair,
+                cold_key_pair.as_ref().unwrap_or(&key_pair),
+                params.require::<PublicKey>("signing-key")?,
+                &params
+                    .voting_key_pair()?
+                    .ok_or_else(|| AppError::MissingParameter("voting-key".to_string()))?,
+                params.require("reward-address")?,
+                params.get::<Blake2bHash>("signal-data")?,
+                fee,
+                validity_start_height,
+                network_id,
+            )?
+        }
+        "update-validator" => {
+            let key_pair = key_pair()?;
+            let cold_key_pair = params.key_pair("cold-key")?;
+            TransactionBuilder::new_update_validator(
+                &key_pair,
+                cold_key_pair.as_ref().unwrap_or(&key_pair),
+                params.get::<PublicKey>("signing-key")?,
+                params.voting_key_pair()?.as_ref(),
+                params.get::<Address>("reward-address")?,
+                params.get::<Blake2bHash>("signal-data")?.map(Some),
+                fee,
+                validity_start_height,
+                network_id,
+            )?
+        }
+        "deactivate-validator" => TransactionBuilder::new_deactivate_validator(
+            &key_pair()?,
+            params.require("validator")?,
+            &params.require_key_pair("signing-secret-key")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "reactivate-validator" => TransactionBuilder::new_reactivate_validator(
+            &key_pair()?,
+            params.require("validator")?,
+            &params.require_key_pair("signing-secret-key")?,
+            fee,
+            validity_start_height,
+            network_id,
+        )?,
+        "retire-validator" => {
+            let key_pair = key_pair()?;
+            let cold_key_pair = params.key_pair("cold-key")?;
+            TransactionBuilder::new_retire_validator(
+                &key_pair,
+                cold_key_pair.as_ref().unwrap_or(&key_pair),
+                fee,
+                validity_start_height,
+                network_id,
+            )?
+        }
+        "delete-validator" => TransactionBuilder::new_delete_validator(
+            params.require("recipient")?,
+            &key_pair()?,
+            fee,
+            params.require("value")?,
+            validity_start_height,
+            network_id,
+        )?,
+        _ => return Err(AppError::UnknownOperation(name.to_string()).into()),
+    };
+
+    Ok(hex::encode(tx.serialize_to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_bls::KeyPair as BlsKeyPair;
+    use nimiq_hash::{Blake2bHasher, Hasher};
+    use nimiq_keys::{KeyPair, SecureGenerate};
+    use nimiq_primitives::{account::AccountType, networks::NetworkId, policy::Policy};
+    use nimiq_serde::{Deserialize, Serialize};
+    use nimiq_transaction::{SignatureProof, Transaction};
+
+    use super::*;
+
+    fn secret_key(key_pair: &KeyPair) -> String {
+        hex::encode(key_pair.private.serialize_to_vec())
+    }
+
+    fn address(key_pair: &KeyPair) -> String {
+        Address::from(&key_pair.public).to_user_friendly_address()
+    }
+
+    /// Runs `build` for the operation with the given arguments, besides the secret key and validity start height.
+    fn build_with(sender: &KeyPair, operation: &str, args: &[&str]) -> Result<String, Error> {
+        let secret_key = secret_key(sender);
+        let mut command_line = vec![
+            "build",
+            operation,
+            "--secret-key",
+            secret_key.as_str(),
+            "--validity-start-height",
+            "1",
+        ];
+        command_line.extend_from_slice(args);
+        build(&command().try_get_matches_from(command_line)?)
+    }
+
+    /// Builds a transaction and checks that it is valid.
+    fn build_tx(sender: &KeyPair, operation: &str, args: &[&str]) -> Transaction {
+        let tx = Transaction::deserialize_from_vec(
+            &hex::decode(build_with(sender, operation, args).unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(tx.validity_start_height, 1);
+        tx.verify(NetworkId::Main).unwrap();
+        tx
+    }
+
+    #[test]
+    fn operations_only_use_known_parameters() {
+        for (_, _, params) in OPERATIONS {
+            assert_eq!(operation_params(params).count(), params.len());
+        }
+    }
+
+    #[test]
+    fn builds_basic_and_vesting_transactions() {
+        let sender = KeyPair::generate_default_csprng();
+        let recipient = address(&KeyPair::generate_default_csprng());
+
+        let tx = build_tx(
+            &sender,
+            "basic",
+            &[
+                "--recipient",
+                &recipient,
+                "--value",
+                "12.5",
+                "--fee",
+                "0.001",
+            ],
+        );
+        assert_eq!(tx.value, Coin::from_u64_unchecked(1_250_000));
+        assert_eq!(tx.fee, Coin::from_u64_unchecked(100));
+        let tx = build_tx(
+            &sender,
+            "basic",
+            &["--recipient", &recipient, "--value", "1", "--data", "cafe"],
+        );
+        assert_eq!(tx.recipient_data, vec![0xca, 0xfe]);
+
+        let tx = build_tx(
+            &sender,
+            "create-vesting",
+            &[
+                "--owner",
+                &recipient,
+                "--start-time",
+                "1000",
+                "--time-step",
+                "1000",
+                "--num-steps",
+                "10",
+                "--value",
+                "100",
+            ],
+        );
+        assert_eq!(tx.recipient_type, AccountType::Vesting);
+
+        let owner = KeyPair::generate_default_csprng();
+        let tx = build_tx(
+            &owner,
+            "redeem-vesting",
+            &[
+                "--contract",
+                &recipient,
+                "--recipient",
+                &address(&owner),
+                "--value",
+                "10",
+            ],
+        );
+        assert_eq!(tx.sender_type, AccountType::Vesting);
+    }
+
+    #[test]
+    fn builds_htlc_transactions() {
+        let sender = KeyPair::generate_default_csprng();
+        let htlc_sender = KeyPair::generate_default_csprng();
+        let htlc_recipient = KeyPair::generate_default_csprng();
+        let contract = address(&KeyPair::generate_default_csprng());
+        let pre_image = hex::encode([1u8; 32]);
+        let hash_root = Blake2bHasher::default().digest(&[1u8; 32]).to_hex();
+
+        let tx = build_tx(
+            &sender,
+            "create-htlc",
+            &[
+                "--htlc-sender",
+                &address(&htlc_sender),
+                "--htlc-recipient",
+                &address(&htlc_recipient),
+                "--hash-root",
+                &hash_root,
+                "--hash-count",
+                "1",
+                "--hash-algorithm",
+                "blake2b",
+                "--timeout",
+                "1000",
+                "--value",
+                "10",
+            ],
+        );
+        assert_eq!(tx.recipient_type, AccountType::HTLC);
+
+        let recipient = address(&htlc_recipient);
+        let tx = build_tx(
+            &htlc_recipient,
+            "redeem-htlc-regular",
+            &[
+                "--contract",
+                &contract,
+                "--recipient",
+                &recipient,
+                "--pre-image",
+                &pre_image,
+                "--hash-root",
+                &hash_root,
+                "--hash-count",
+                "1",
+                "--hash-algorithm",
+                "blake2b",
+                "--value",
+                "10",
+            ],
+        );
+        assert_eq!(tx.sender_type, AccountType::HTLC);
+
+        build_tx(
+            &htlc_sender,
+            "redeem-htlc-timeout",
+            &[
+                "--contract",
+                &contract,
+                "--recipient",
+                &address(&htlc_sender),
+                "--value",
+                "10",
+            ],
+        );
+
+        let early = [
+            "--contract",
+            contract.as_str(),
+            "--recipient",
+            recipient.as_str(),
+            "--value",
+            "10",
+        ];
+        let sender_signature = build_with(&htlc_sender, "sign-htlc-early", &early).unwrap();
+        let recipient_signature = build_with(&htlc_recipient, "sign-htlc-early", &early).unwrap();
+        let proof =
+            SignatureProof::deserialize_from_vec(&hex::decode(&sender_signature).unwrap()).unwrap();
+        assert_eq!(proof.public_key, htlc_sender.public);
+        build_tx(
+            &sender,
+            "redeem-htlc-early",
+            &[
+                "--contract",
+                &contract,
+                "--recipient",
+                &recipient,
+                "--value",
+                "10",
+                "--htlc-sender-signature",
+                &sender_signature,
+                "--htlc-recipient-signature",
+                &recipient_signature,
+            ],
+        );
+    }
+
+    #[test]
+    fn builds_staker_transactions() {
+        let sender = KeyPair::generate_default_csprng();
+        let staker = KeyPair::generate_default_csprng();
+        let validator = address(&KeyPair::generate_default_csprng());
+
+        let tx = build_tx(
+            &sender,
+            "create-staker",
+            &[
+                "--staker-key",
+                &secret_key(&staker),
+                "--delegation",
+                &validator,
+                "--value",
+                "100",
+            ],
+        );
+        assert_eq!(tx.recipient, Policy::STAKING_CONTRACT_ADDRESS);
+        build_tx(
+            &sender,
+            "stake",
+            &["--staker", &address(&staker), "--value", "100"],
+        );
+        build_tx(
+            &sender,
+            "update-staker",
+            &[
+                "--staker-key",
+                &secret_key(&staker),
+                "--delegation",
+                &validator,
+                "--reactivate-all-stake",
+            ],
+        );
+        build_tx(&staker, "update-staker", &["--delegation", &validator]);
+        build_tx(
+            &sender,
+            "set-inactive-stake",
+            &["--staker-key", &secret_key(&staker), "--value", "50"],
+        );
+        let tx = build_tx(
+            &staker,
+            "unstake",
+            &["--recipient", &address(&staker), "--value", "50"],
+        );
+        assert_eq!(tx.sender, Policy::STAKING_CONTRACT_ADDRESS);
+    }
+
+    #[test]
+    fn builds_validator_transactions() {
+        let sender = KeyPair::generate_default_csprng();
+        let cold_key = KeyPair::generate_default_csprng();
+        let signing_key = KeyPair::generate_default_csprng();
+        let voting_key = hex::encode(
+            BlsKeyPair::generate_default_csprng()
+                .secret_key
+                .serialize_to_vec(),
+        );
+        let validator = address(&cold_key);
+        let signing_public_key = signing_key.public.to_hex();
+        let signal_data = Blake2bHasher::default().digest(b"signal").to_hex();
+
+        let tx = build_tx(
+            &sender,
+            "create-validator",
+            &[
+                "--cold-key",
+                &secret_key(&cold_key),
+                "--signing-key",
+                &signing_public_key,
+                "--voting-key",
+                &voting_key,
+                "--reward-address",
+                &validator,
+                "--signal-data",
+                &signal_data,
+            ],
+        );
+        assert_eq!(
+            tx.value,
+            Coin::from_u64_unchecked(Policy::VALIDATOR_DEPOSIT)
+        );
+        build_tx(
+            &sender,
+            "update-validator",
+            &[
+                "--cold-key",
+                &secret_key(&cold_key),
+                "--reward-address",
+                &address(&sender),
+            ],
+        );
+        build_tx(
+            &sender,
+            "deactivate-validator",
+            &[
+                "--validator",
+                &validator,
+                "--signing-secret-key",
+                &secret_key(&signing_key),
+            ],
+        );
+        build_tx(
+            &sender,
+            "reactivate-validator",
+            &[
+                "--validator",
+                &validator,
+                "--signing-secret-key",
+                &secret_key(&signing_key),
+            ],
+        );
+        build_tx(&cold_key, "retire-validator", &[]);
+        build_tx(
+            &cold_key,
+            "delete-validator",
+            &["--recipient", &address(&sender), "--value", "10"],
+        );
+    }
+
+    #[test]
+    fn rejects_missing_and_invalid_parameters() {
+        let sender = KeyPair::generate_default_csprng();
+
+        let error = build_with(&sender, "basic", &["--value", "1"]).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AppError>(),
+            Some(AppError::MissingParameter(name)) if name == "recipient"
+        ));
+        let error = build_with(
+            &sender,
+            "basic",
+            &["--recipient", &address(&sender), "--value", "one"],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AppError>(),
+            Some(AppError::InvalidParameter(name, _)) if name == "value"
+        ));
+        assert!(build_with(&sender, "unknown", &[]).is_err());
+    }
+}
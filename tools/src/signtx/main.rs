@@ -2,14 +2,236 @@ use std::{io::stdin, process::exit, str::FromStr};
 
 use anyhow::Error;
 use clap::{
-    crate_authors, crate_description, crate_version, value_parser, Arg, ArgAction, Command,
+    crate_authors, crate_description, crate_version, value_parser, Arg, ArgAction, ArgMatches,
+    Command,
+};
+use nimiq_keys::{
+    multisig::{Commitment, CommitmentPair, PartialSignature, RandomSecret},
+    Address, KeyPair, PrivateKey, PublicKey, SecureGenerate,
 };
-use nimiq_keys::{Address, KeyPair, PrivateKey};
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_serde::{Deserialize, Serialize};
-use nimiq_transaction::Transaction;
+use nimiq_transaction::{
+    account::htlc_contract::PreImage, pst::PartiallySignedTransaction, Transaction,
+};
+use nimiq_transaction_builder::{
+    multisig::MultisigAccount, proof::HtlcPstProof, TransactionProofBuilder,
+};
 use thiserror::Error;
 
+mod build;
+
+/// Arguments describing the multisig account and the signers of a transaction.
+fn multisig_args(signing: bool) -> Vec<Arg> {
+    let mut args = vec![
+        Arg::new("num_signers")
+            .short('n')
+            .long("num-signers")
+            .value_name("NUM")
+            .value_parser(value_parser!(usize))
+            .required(true)
+            .help("Number of signers required by the multisig account."),
+        Arg::new("public_keys")
+            .short('p')
+            .long("public-keys")
+            .value_name("PUBLIC_KEYS")
+            .value_delimiter(',')
+            .required(true)
+            .help("Comma separated public keys of all possible signers of the multisig account."),
+    ];
+    if signing {
+        args.push(
+            Arg::new("signers")
+                .short('s')
+                .long("signers")
+                .value_name("PUBLIC_KEYS")
+                .value_delimiter(',')
+                .required(true)
+                .help("Comma separated public keys of the signers of the transaction."),
+        );
+        args.push(
+            Arg::new("commitments")
+                .short('c')
+                .long("commitments")
+                .value_name("COMMITMENTS")
+                .value_delimiter(',')
+                .required(true)
+                .help("Comma separated commitments of the signers of the transaction."),
+        );
+    }
+    args
+}
+
+fn multisig_account(matches: &ArgMatches) -> Result<MultisigAccount, Error> {
+    let public_keys = matches
+        .get_many::<String>("public_keys")
+        .unwrap_or_default()
+        .map(|s| PublicKey::from_str(s))
+        .collect::<Result<Vec<_>, _>>()?;
+    let num_signers = *matches
+        .get_one::<usize>("num_signers")
+        .ok_or(AppError::NumSigners)?;
+    Ok(MultisigAccount::new(public_keys, num_signers)?)
+}
+
+fn signers(matches: &ArgMatches) -> Result<(Vec<PublicKey>, Vec<Commitment>), Error> {
+    let signers = matches
+        .get_many::<String>("signers")
+        .unwrap_or_default()
+        .map(|s| PublicKey::from_str(s))
+        .collect::<Result<Vec<_>, _>>()?;
+    let commitments = matches
+        .get_many::<String>("commitments")
+        .unwrap_or_default()
+        .map(|s| Commitment::from_bytes(multisig_value_from_hex(s)?).ok_or(AppError::Commitment))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((signers, commitments))
+}
+
+fn multisig_value_from_hex(s: &str) -> Result<[u8; 32], AppError> {
+    hex::decode(s)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(AppError::MultisigValue)
+}
+
+/// Reads the transaction of a multisig account as hex from STDIN.
+fn multisig_transaction(account: &MultisigAccount) -> Result<Transaction, Error> {
+    let tx = Transaction::deserialize_from_vec(&read_hex_line()?)?;
+    if tx.sender != account.address() {
+        return Err(AppError::MultisigSender.into());
+    }
+    Ok(tx)
+}
+
+/// Reads how the HTLC sending the transaction of a PST is resolved, if given.
+fn htlc_proof(matches: &ArgMatches) -> Result<Option<HtlcPstProof>, Error> {
+    let require = |name: &str| {
+        matches
+            .get_one::<String>(name)
+            .ok_or_else(|| AppError::MissingParameter(name.replace('_', "-")))
+    };
+    let proof = match matches.get_one::<String>("htlc_proof").map(String::as_str) {
+        None => return Ok(None),
+        Some("regular-transfer") => HtlcPstProof::RegularTransfer {
+            hash_depth: *matches
+                .get_one::<u8>("hash_count")
+                .ok_or_else(|| AppError::MissingParameter("hash-count".to_string()))?,
+            hash_root: build::parse_hash_root(require("hash_root")?, require("hash_algorithm")?)?,
+            pre_image: PreImage::from_str(require("pre_image")?)
+                .map_err(|e| AppError::InvalidParameter("pre-image".to_string(), e.to_string()))?,
+        },
+        Some("early-resolve") => HtlcPstProof::EarlyResolve {
+            recipient: Address::from_user_friendly_address(require("htlc_recipient")?)?,
+        },
+        Some(_) => HtlcPstProof::TimeoutResolve,
+    };
+    Ok(Some(proof))
+}
+
+/// Reads a line of hex from STDIN.
+fn read_hex_line() -> Result<Vec<u8>, Error> {
+    let mut line = String::new();
+    stdin().read_line(&mut line)?;
+    Ok(hex::decode(line.trim_end())?)
+}
+
+fn run_subcommand(name: &str, matches: &ArgMatches) -> Result<(), Error> {
+    match name {
+        "pst-create" => {
+            let tx = Transaction::deserialize_from_vec(&read_hex_line()?)?;
+            let htlc_proof = htlc_proof(matches)?;
+            let signer = match matches.get_one::<String>("signer") {
+                Some(signer) => Address::from_user_friendly_address(signer)?,
+                // The sender of an HTLC transaction is the contract, which can't sign.
+                None if htlc_proof.is_some() => {
+                    return Err(AppError::MissingParameter("signer".to_string()).into())
+                }
+                None => tx.sender.clone(),
+            };
+            let pst = TransactionProofBuilder::new(tx)
+                .into_pst(signer, htlc_proof)
+                .ok_or(AppError::HtlcPst)?;
+            println!("{}", hex::encode(pst.serialize_to_vec()));
+        }
+        "pst-sign" => {
+            let raw_secret_key = hex::decode(
+                matches
+                    .get_one::<String>("secret_key")
+                    .ok_or(AppError::SecretKey)?,
+            )?;
+            let key_pair: KeyPair = PrivateKey::deserialize_from_vec(&raw_secret_key)?.into();
+            let mut pst = PartiallySignedTransaction::parse(&read_hex_line()?)?;
+            if pst.sign_with_key_pair(&key_pair)? == 0 {
+                return Err(AppError::NotASigner.into());
+            }
+            println!("{}", hex::encode(pst.serialize_to_vec()));
+        }
+        "pst-finalize" => {
+            let pst = PartiallySignedTransaction::parse(&read_hex_line()?)?;
+            println!("{}", hex::encode(pst.finalize()?.serialize_to_vec()));
+        }
+        "build" => build::run(matches)?,
+        "multisig-address" => {
+            let account = multisig_account(matches)?;
+            println!("{}", account.address().to_user_friendly_address());
+        }
+        "multisig-commit" => {
+            let commitment_pair = CommitmentPair::generate_default_csprng();
+            println!(
+                "commitment: {}",
+                hex::encode(commitment_pair.commitment().to_bytes())
+            );
+            println!(
+                "random secret: {}",
+                hex::encode(commitment_pair.random_secret().0.as_bytes())
+            );
+        }
+        "multisig-sign" => {
+            let account = multisig_account(matches)?;
+            let (signers, commitments) = signers(matches)?;
+            let raw_secret_key = hex::decode(
+                matches
+                    .get_one::<String>("secret_key")
+                    .ok_or(AppError::SecretKey)?,
+            )?;
+            let key_pair: KeyPair = PrivateKey::deserialize_from_vec(&raw_secret_key)?.into();
+            let random_secret = RandomSecret::from(multisig_value_from_hex(
+                matches
+                    .get_one::<String>("random_secret")
+                    .ok_or(AppError::RandomSecret)?,
+            )?);
+
+            let tx = multisig_transaction(&account)?;
+            let partial_signature = account.partially_sign(
+                &key_pair,
+                &signers,
+                &random_secret,
+                &commitments,
+                tx.serialize_content().as_slice(),
+            )?;
+            println!("{}", hex::encode(partial_signature.as_bytes()));
+        }
+        "multisig-combine" => {
+            let account = multisig_account(matches)?;
+            let (signers, commitments) = signers(matches)?;
+            let partial_signatures = matches
+                .get_many::<String>("partial_signatures")
+                .unwrap_or_default()
+                .map(|s| multisig_value_from_hex(s).map(PartialSignature::from))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut tx = multisig_transaction(&account)?;
+            let proof =
+                account.create_signature_proof(&signers, &commitments, &partial_signatures)?;
+            tx.proof = proof.serialize_to_vec();
+            println!("{}", hex::encode(tx.serialize_to_vec()));
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
 fn run_app() -> Result<(), Error> {
     let matches = Command::new("Sign transaction")
         .version(crate_version!())
@@ -71,8 +293,143 @@ fn run_app() -> Result<(), Error> {
                 .value_name("NETWORK")
                 .help("Set network ID"),
         )
+        .args_conflicts_with_subcommands(true)
+        .subcommand(build::command())
+        .subcommand(
+            Command::new("multisig-address")
+                .about("Derive the address of a multisig account.")
+                .args(multisig_args(false)),
+        )
+        .subcommand(
+            Command::new("multisig-commit")
+                .about("Create a commitment for signing a multisig transaction."),
+        )
+        .subcommand(
+            Command::new("multisig-sign")
+                .about(
+                    "Partially sign a multisig transaction, which is read as hex from STDIN. \
+                     Prints the partial signature.",
+                )
+                .args(multisig_args(true))
+                .arg(
+                    Arg::new("secret_key")
+                        .short('k')
+                        .long("secret-key")
+                        .value_name("SECRET_KEY")
+                        .required(true)
+                        .help("Specify the secret key of the signer."),
+                )
+                .arg(
+                    Arg::new("random_secret")
+                        .short('r')
+                        .long("random-secret")
+                        .value_name("RANDOM_SECRET")
+                        .required(true)
+                        .help("Specify the random secret of the signer's commitment."),
+                ),
+        )
+        .subcommand(
+            Command::new("multisig-combine")
+                .about(
+                    "Combine the partial signatures of a multisig transaction, which is read as hex \
+                     from STDIN. Prints the signed transaction.",
+                )
+                .args(multisig_args(true))
+                .arg(
+                    Arg::new("partial_signatures")
+                        .short('P')
+                        .long("partial-signatures")
+                        .value_name("PARTIAL_SIGNATURES")
+                        .value_delimiter(',')
+                        .required(true)
+                        .help("Comma separated partial signatures of the signers."),
+                ),
+        )
+        .subcommand(
+            Command::new("pst-create")
+                .about(
+                    "Create a partially signed transaction (PST) from a transaction, which is read \
+                     as hex from STDIN.",
+                )
+                .arg(
+                    Arg::new("signer")
+                        .short('s')
+                        .long("signer")
+                        .value_name("ADDRESS")
+                        .help(
+                            "Address signing on behalf of the sender, or signing the staking data of \
+                             incoming staking transactions. Defaults to the sender. Required for HTLC \
+                             transactions: the HTLC recipient for regular transfers, the HTLC sender \
+                             otherwise.",
+                        ),
+                )
+                .arg(
+                    Arg::new("htlc_proof")
+                        .long("htlc-proof")
+                        .value_name("TYPE")
+                        .value_parser(["regular-transfer", "early-resolve", "timeout-resolve"])
+                        .help("How the HTLC is resolved, for transactions sent from an HTLC."),
+                )
+                .arg(
+                    Arg::new("htlc_recipient")
+                        .long("htlc-recipient")
+                        .value_name("ADDRESS")
+                        .help("Address of the HTLC recipient, for early resolves."),
+                )
+                .arg(
+                    Arg::new("pre_image")
+                        .long("pre-image")
+                        .value_name("PRE_IMAGE")
+                        .help("Pre-image of the HTLC in hex, for regular transfers."),
+                )
+                .arg(
+                    Arg::new("hash_root")
+                        .long("hash-root")
+                        .value_name("HASH")
+                        .help(
+                            "Hash of the pre-image, hashed `hash-count` times, in hex, for regular \
+                             transfers.",
+                        ),
+                )
+                .arg(
+                    Arg::new("hash_count")
+                        .long("hash-count")
+                        .value_name("COUNT")
+                        .value_parser(value_parser!(u8))
+                        .help("Number of times the pre-image was hashed, for regular transfers."),
+                )
+                .arg(
+                    Arg::new("hash_algorithm")
+                        .long("hash-algorithm")
+                        .value_name("ALGORITHM")
+                        .value_parser(["blake2b", "sha256", "sha512"])
+                        .help("Hash algorithm of the HTLC, for regular transfers."),
+                ),
+        )
+        .subcommand(
+            Command::new("pst-sign")
+                .about("Sign a PST, which is read as hex from STDIN. Prints the updated PST.")
+                .arg(
+                    Arg::new("secret_key")
+                        .short('k')
+                        .long("secret-key")
+                        .value_name("SECRET_KEY")
+                        .required(true)
+                        .help("Specify the secret key to be used to sign the PST."),
+                ),
+        )
+        .subcommand(
+            Command::new("pst-finalize").about(
+                "Combine the signatures of a PST, which is read as hex from STDIN. Prints the signed \
+                 transaction.",
+            ),
+        )
         .get_matches();
 
+    if let Some((name, matches)) = matches.subcommand() {
+        return run_subcommand(name, matches);
+    }
+
     // read transaction either from arguments or stdin
     let tx = if matches.get_flag("tx_from_stdin") {
         let mut line = String::new();
@@ -145,4 +502,30 @@ enum AppError {
     Fee,
     #[error("Validity start height is missing")]
     ValidityStartHeight,
+    #[error("Number of signers is missing")]
+    NumSigners,
+    #[error("Random secret is missing")]
+    RandomSecret,
+    #[error("Commitments, random secrets and partial signatures must be 32 bytes in hex")]
+    MultisigValue,
+    #[error("Invalid commitment")]
+    Commitment,
+    #[error("Transaction is not sent from the multisig account")]
+    MultisigSender,
+    #[error(
+        "The HTLC proof is required for transactions sent from an HTLC and not allowed otherwise"
+    )]
+    HtlcPst,
+    #[error("The secret key has no missing signature in the PST")]
+    NotASigner,
+    #[error("Parameter {0} is missing")]
+    MissingParameter(String),
+    #[error("Invalid parameter {0}: {1}")]
+    InvalidParameter(String, String),
+    #[error("Unknown parameter {0}")]
+    UnknownParameter(String),
+    #[error("Operation is missing")]
+    MissingOperation,
+    #[error("Unknown operation {0}")]
+    UnknownOperation(String),
 }
@@ -0,0 +1,184 @@
+//! Funding a payment from several basic accounts.
+//!
+//! Given the balances the accounts have available, a [`FundingPlan`] selects the accounts to send from and the value
+//! each of them sends, such that the recipient receives the requested amount in total. Every account pays the fee
+//! of its own transaction. Accounts whose balance can't cover more than the fee are never selected.
+use nimiq_keys::{Address, KeyPair};
+use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_serde::Serialize;
+use nimiq_transaction::{SignatureProof, Transaction};
+use thiserror::Error;
+
+use crate::{TransactionBuilder, TransactionBuilderError};
+
+#[derive(Debug, Error)]
+pub enum FundingError {
+    /// The amount to be sent must not be zero.
+    #[error("The amount must not be zero")]
+    ZeroAmount,
+    /// The accounts can't cover the amount after paying the fees of their transactions.
+    #[error("Insufficient funds: needed {needed}, available {available}")]
+    InsufficientFunds { needed: Coin, available: Coin },
+    /// The fee computation overflowed.
+    #[error("Fee overflow")]
+    FeeOverflow,
+    /// No key pair was given for an account of the plan.
+    #[error("Missing key pair for {0}")]
+    MissingKeyPair(Address),
+    #[error(transparent)]
+    Builder(#[from] TransactionBuilderError),
+}
+
+/// A basic account that can fund a payment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FundingSource {
+    pub address: Address,
+    /// The balance of the account that isn't reserved by pending transactions yet.
+    pub available: Coin,
+}
+
+/// A transaction of a [`FundingPlan`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FundingInput {
+    pub sender: Address,
+    pub value: Coin,
+    pub fee: Coin,
+}
+
+/// The transactions that fund a payment, one per selected account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FundingPlan {
+    pub inputs: Vec<FundingInput>,
+}
+
+impl FundingPlan {
+    /// Selects the accounts to send `amount` from. If a single account can fund the payment, the one with the
+    /// smallest sufficient balance is used. Otherwise, the accounts with the largest balances are used first, which
+    /// keeps the number of transactions and thus the fees low.
+    pub fn for_amount(
+        sources: &[FundingSource],
+        amount: Coin,
+        fee_per_byte: u64,
+    ) -> Result<Self, FundingError> {
+        if amount.is_zero() {
+            return Err(FundingError::ZeroAmount);
+        }
+        let fee = basic_transaction_fee(fee_per_byte)?;
+        let mut usable = usable_sources(sources, fee);
+
+        // Prefer a single transaction from the smallest account that suffices.
+        usable.sort_by_key(|(_, spendable)| *spendable);
+        if let Some((source, _)) = usable.iter().find(|(_, spendable)| *spendable >= amount) {
+            return Ok(FundingPlan {
+                inputs: vec![FundingInput {
+                    sender: source.address.clone(),
+                    value: amount,
+                    fee,
+                }],
+            });
+        }
+
+        let mut inputs = vec![];
+        let mut remaining = amount;
+        for (source, spendable) in usable.iter().rev() {
+            let value = (*spendable).min(remaining);
+            inputs.push(FundingInput {
+                sender: source.address.clone(),
+                value,
+                fee,
+            });
+            remaining -= value;
+            if remaining.is_zero() {
+                return Ok(FundingPlan { inputs });
+            }
+        }
+
+        Err(FundingError::InsufficientFunds {
+            needed: amount,
+            available: amount - remaining,
+        })
+    }
+
+    /// Sends everything the accounts have available, minus the fees. Fails with [`FundingError::ZeroAmount`] if no
+    /// account can send more than the fee.
+    pub fn sweep(sources: &[FundingSource], fee_per_byte: u64) -> Result<Self, FundingError> {
+        let fee = basic_transaction_fee(fee_per_byte)?;
+        let inputs: Vec<_> = usable_sources(sources, fee)
+            .into_iter()
+            .map(|(source, spendable)| FundingInput {
+                sender: source.address.clone(),
+                value: spendable,
+                fee,
+            })
+            .collect();
+
+        if inputs.is_empty() {
+            return Err(FundingError::ZeroAmount);
+        }
+        Ok(FundingPlan { inputs })
+    }
+
+    /// The total value the recipient receives.
+    pub fn value(&self) -> Coin {
+        self.inputs.iter().map(|input| input.value).sum()
+    }
+
+    /// The total fee of all transactions.
+    pub fn fee(&self) -> Coin {
+        self.inputs.iter().map(|input| input.fee).sum()
+    }
+
+    /// Builds and signs the transactions of the plan. `key_pair` returns the key pair of a selected account.
+    pub fn sign<'a>(
+        &self,
+        recipient: &Address,
+        validity_start_height: u32,
+        network_id: NetworkId,
+        key_pair: impl Fn(&Address) -> Option<&'a KeyPair>,
+    ) -> Result<Vec<Transaction>, FundingError> {
+        self.inputs
+            .iter()
+            .map(|input| {
+                let key_pair = key_pair(&input.sender)
+                    .ok_or_else(|| FundingError::MissingKeyPair(input.sender.clone()))?;
+                Ok(TransactionBuilder::new_basic(
+                    key_pair,
+                    recipient.clone(),
+                    input.value,
+                    input.fee,
+                    validity_start_height,
+                    network_id,
+                )?)
+            })
+            .collect()
+    }
+}
+
+/// Returns the fee of a basic transaction signed by a single key at the given fee rate.
+pub fn basic_transaction_fee(fee_per_byte: u64) -> Result<Coin, FundingError> {
+    let mut transaction = Transaction::new_basic(
+        Address::default(),
+        Address::default(),
+        Coin::ZERO,
+        Coin::ZERO,
+        0,
+        NetworkId::Main,
+    );
+    transaction.proof = SignatureProof::default().serialize_to_vec();
+
+    (transaction.serialized_size() as u64)
+        .checked_mul(fee_per_byte)
+        .and_then(|fee| Coin::try_from(fee).ok())
+        .ok_or(FundingError::FeeOverflow)
+}
+
+/// Returns the sources that can send more than the fee, together with the value they can send.
+fn usable_sources(sources: &[FundingSource], fee: Coin) -> Vec<(&FundingSource, Coin)> {
+    sources
+        .iter()
+        .filter_map(|source| {
+            let spendable = source.available.checked_sub(fee)?;
+            (!spendable.is_zero()).then_some((source, spendable))
+        })
+        .collect()
+}
@@ -10,6 +10,8 @@ use thiserror::Error;
 
 pub use crate::{proof::TransactionProofBuilder, recipient::Recipient, sender::Sender};
 
+pub mod funding;
+pub mod multisig;
 pub mod proof;
 pub mod recipient;
 pub mod sender;
@@ -0,0 +1,196 @@
+//! Multisig accounts controlled by `k` out of `n` keys.
+//!
+//! A multisig address commits to the aggregated public keys of all combinations of `k` signers: it is the root of
+//! the Merkle tree over these keys. To spend from it, the `k` signers of one combination each create a commitment
+//! and exchange them, then each of them creates a partial signature over the transaction using all `k`
+//! commitments. The partial signatures are combined into a single signature that verifies against the aggregated
+//! public key of the signers, which is proven to be part of the address by the Merkle path of the `SignatureProof`.
+use nimiq_hash::Blake2bHasher;
+use nimiq_keys::{
+    multisig::{aggregate_public_keys, Commitment, PartialSignature, RandomSecret},
+    Address, KeyPair, PublicKey,
+};
+use nimiq_transaction::SignatureProof;
+use nimiq_utils::merkle::{compute_root_from_content, Blake2bMerklePath};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MultisigError {
+    /// The number of required signers must be between one and the number of public keys.
+    #[error("Invalid number of required signers: {0}")]
+    InvalidNumSigners(usize),
+    /// There are more public keys than supported, see `MultisigAccount::MAX_PUBLIC_KEYS`.
+    #[error("Too many public keys: {0}")]
+    TooManyPublicKeys(usize),
+    /// A public key was given more than once.
+    #[error("Duplicate public key: {0}")]
+    DuplicatePublicKey(PublicKey),
+    /// A public key that doesn't belong to the multisig account was given as a signer.
+    #[error("Public key is not part of the multisig account: {0}")]
+    UnknownPublicKey(PublicKey),
+    /// The number of signers, commitments or partial signatures doesn't match the number of required signers.
+    #[error("Expected {expected} signers, commitments or partial signatures, got {actual}")]
+    WrongNumberOfSigners { expected: usize, actual: usize },
+}
+
+/// A `k`-of-`n` multisig account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultisigAccount {
+    /// The public keys of all `n` possible signers, sorted.
+    public_keys: Vec<PublicKey>,
+    /// The number `k` of signers required.
+    num_signers: usize,
+    /// The aggregated public keys of all combinations of `k` signers.
+    aggregated_public_keys: Vec<PublicKey>,
+}
+
+impl MultisigAccount {
+    /// The maximum number of public keys of a multisig account. The address commits to all combinations of signers,
+    /// whose number grows exponentially with the number of public keys.
+    pub const MAX_PUBLIC_KEYS: usize = 16;
+
+    /// Creates the multisig account requiring `num_signers` of the given public keys. The order of the public keys
+    /// doesn't matter.
+    pub fn new(mut public_keys: Vec<PublicKey>, num_signers: usize) -> Result<Self, MultisigError> {
+        if public_keys.len() > Self::MAX_PUBLIC_KEYS {
+            return Err(MultisigError::TooManyPublicKeys(public_keys.len()));
+        }
+        if num_signers == 0 || num_signers > public_keys.len() {
+            return Err(MultisigError::InvalidNumSigners(num_signers));
+        }
+
+        public_keys.sort();
+        if let Some(keys) = public_keys.windows(2).find(|keys| keys[0] == keys[1]) {
+            return Err(MultisigError::DuplicatePublicKey(keys[0]));
+        }
+
+        let aggregated_public_keys = combinations(&public_keys, num_signers)
+            .iter()
+            .map(|signers| aggregate_public_keys(signers))
+            .collect();
+
+        Ok(MultisigAccount {
+            public_keys,
+            num_signers,
+            aggregated_public_keys,
+        })
+    }
+
+    pub fn address(&self) -> Address {
+        Address::from(compute_root_from_content::<Blake2bHasher, _>(
+            &self.aggregated_public_keys,
+        ))
+    }
+
+    pub fn public_keys(&self) -> &[PublicKey] {
+        &self.public_keys
+    }
+
+    pub fn num_signers(&self) -> usize {
+        self.num_signers
+    }
+
+    /// Checks that the given public keys are `k` signers of this account and returns them in the order they are
+    /// aggregated in.
+    pub fn signers(
+        &self,
+        signer_public_keys: &[PublicKey],
+    ) -> Result<Vec<PublicKey>, MultisigError> {
+        if signer_public_keys.len() != self.num_signers {
+            return Err(MultisigError::WrongNumberOfSigners {
+                expected: self.num_signers,
+                actual: signer_public_keys.len(),
+            });
+        }
+
+        let mut signers = signer_public_keys.to_vec();
+        signers.sort();
+        if let Some(keys) = signers.windows(2).find(|keys| keys[0] == keys[1]) {
+            return Err(MultisigError::DuplicatePublicKey(keys[0]));
+        }
+        if let Some(unknown) = signers
+            .iter()
+            .find(|signer| self.public_keys.binary_search(signer).is_err())
+        {
+            return Err(MultisigError::UnknownPublicKey(*unknown));
+        }
+        Ok(signers)
+    }
+
+    /// Creates the partial signature of one of the signers over `data`, usually the serialized content of a
+    /// transaction. The random secret must be the one of the signer's commitment and must never be used again.
+    pub fn partially_sign(
+        &self,
+        key_pair: &KeyPair,
+        signer_public_keys: &[PublicKey],
+        secret: &RandomSecret,
+        commitments: &[Commitment],
+        data: &[u8],
+    ) -> Result<PartialSignature, MultisigError> {
+        let signers = self.signers(signer_public_keys)?;
+        if !signers.contains(&key_pair.public) {
+            return Err(MultisigError::UnknownPublicKey(key_pair.public));
+        }
+        self.check_len(commitments.len())?;
+
+        let (partial_signature, _, _) = key_pair.partial_sign(&signers, secret, commitments, data);
+        Ok(partial_signature)
+    }
+
+    /// Combines the partial signatures of the signers into a signature proof for this account.
+    pub fn create_signature_proof(
+        &self,
+        signer_public_keys: &[PublicKey],
+        commitments: &[Commitment],
+        partial_signatures: &[PartialSignature],
+    ) -> Result<SignatureProof, MultisigError> {
+        let signers = self.signers(signer_public_keys)?;
+        self.check_len(commitments.len())?;
+        self.check_len(partial_signatures.len())?;
+
+        let aggregated_commitment: Commitment = commitments.iter().sum();
+        let signature = partial_signatures
+            .iter()
+            .sum::<PartialSignature>()
+            .to_signature(&aggregated_commitment);
+
+        let public_key = aggregate_public_keys(&signers);
+        let merkle_path =
+            Blake2bMerklePath::new::<Blake2bHasher, _>(&self.aggregated_public_keys, &public_key);
+
+        Ok(SignatureProof {
+            public_key,
+            merkle_path,
+            signature,
+        })
+    }
+
+    fn check_len(&self, len: usize) -> Result<(), MultisigError> {
+        if len != self.num_signers {
+            return Err(MultisigError::WrongNumberOfSigners {
+                expected: self.num_signers,
+                actual: len,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Returns all combinations of `k` of the given public keys, in lexicographic order of their indices.
+fn combinations(public_keys: &[PublicKey], k: usize) -> Vec<Vec<PublicKey>> {
+    let mut combinations = vec![];
+    let mut indices: Vec<usize> = (0..k).collect();
+    let n = public_keys.len();
+    loop {
+        combinations.push(indices.iter().map(|&i| public_keys[i]).collect());
+
+        // Advance the rightmost index that can still be moved to the right.
+        let Some(i) = (0..k).rev().find(|&i| indices[i] != i + n - k) else {
+            return combinations;
+        };
+        indices[i] += 1;
+        for j in i + 1..k {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+}
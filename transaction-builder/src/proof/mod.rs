@@ -1,10 +1,17 @@
 use std::io;
 
 use nimiq_hash::{HashOutput, SerializeContent};
-use nimiq_keys::KeyPair;
+use nimiq_keys::{Address, KeyPair};
 use nimiq_primitives::account::AccountType;
-use nimiq_serde::Serialize;
-use nimiq_transaction::{SignatureProof, Transaction};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::{
+    account::{
+        htlc_contract::{AnyHash, PreImage},
+        staking_contract::IncomingStakingTransactionData,
+    },
+    pst::{PartiallySignedTransaction, PstProofTemplate, PstRole},
+    SignatureProof, Transaction,
+};
 
 use crate::proof::{
     htlc_contract::HtlcProofBuilder,
@@ -298,6 +305,107 @@ impl TransactionProofBuilder {
             _ => panic!("TransactionProofBuilder was not a StakingProofBuilder"),
         }
     }
+
+    /// Converts the proof builder into a [`PartiallySignedTransaction`], such that the transaction can be signed
+    /// on other machines.
+    ///
+    /// The `signer` is the address that signs on behalf of the sender: the sender itself for basic accounts,
+    /// the owner of a vesting contract and the staker or validator of an outgoing staking transaction.
+    /// For incoming staking transactions, the `signer` signs the staking data, while the sender signs the proof.
+    ///
+    /// The proof of HTLC senders depends on how the HTLC is resolved, which is given by `htlc_proof`. The `signer` is
+    /// then the HTLC's recipient for regular transfers and the HTLC's sender otherwise.
+    /// Returns `None` if `htlc_proof` is missing for an HTLC sender or given for any other sender.
+    ///
+    /// [`PartiallySignedTransaction`]: nimiq_transaction::pst::PartiallySignedTransaction
+    pub fn into_pst(
+        self,
+        signer: Address,
+        htlc_proof: Option<HtlcPstProof>,
+    ) -> Option<PartiallySignedTransaction> {
+        if htlc_proof.is_some() != matches!(self, TransactionProofBuilder::Htlc(_)) {
+            return None;
+        }
+
+        match self {
+            TransactionProofBuilder::Basic(BasicProofBuilder { transaction, .. })
+            | TransactionProofBuilder::Vesting(BasicProofBuilder { transaction, .. })
+            | TransactionProofBuilder::OutStaking(StakingProofBuilder { transaction, .. }) => {
+                let mut pst =
+                    PartiallySignedTransaction::new(transaction, PstProofTemplate::Signature);
+                pst.add_signer(signer, PstRole::Sender);
+                Some(pst)
+            }
+            TransactionProofBuilder::InStaking(StakingDataBuilder { transaction, .. }) => {
+                let data: IncomingStakingTransactionData =
+                    Deserialize::deserialize_from_vec(&transaction.recipient_data[..]).ok()?;
+                let sender = transaction.sender.clone();
+
+                let mut pst =
+                    PartiallySignedTransaction::new(transaction, PstProofTemplate::Signature);
+                // Adding stake doesn't require a signature of the staking data.
+                if !matches!(data, IncomingStakingTransactionData::AddStake { .. }) {
+                    pst.add_signer(signer, PstRole::StakingData);
+                }
+                pst.add_signer(sender, PstRole::Sender);
+                Some(pst)
+            }
+            TransactionProofBuilder::Htlc(HtlcProofBuilder { transaction, .. }) => {
+                let pst = match htlc_proof? {
+                    HtlcPstProof::RegularTransfer {
+                        hash_depth,
+                        hash_root,
+                        pre_image,
+                    } => {
+                        let mut pst = PartiallySignedTransaction::new(
+                            transaction,
+                            PstProofTemplate::HtlcRegularTransfer {
+                                hash_depth,
+                                hash_root,
+                                pre_image,
+                            },
+                        );
+                        pst.add_signer(signer, PstRole::HtlcRecipient);
+                        pst
+                    }
+                    HtlcPstProof::EarlyResolve { recipient } => {
+                        let mut pst = PartiallySignedTransaction::new(
+                            transaction,
+                            PstProofTemplate::HtlcEarlyResolve,
+                        );
+                        pst.add_signer(signer, PstRole::Sender);
+                        pst.add_signer(recipient, PstRole::HtlcRecipient);
+                        pst
+                    }
+                    HtlcPstProof::TimeoutResolve => {
+                        let mut pst = PartiallySignedTransaction::new(
+                            transaction,
+                            PstProofTemplate::HtlcTimeoutResolve,
+                        );
+                        pst.add_signer(signer, PstRole::Sender);
+                        pst
+                    }
+                };
+                Some(pst)
+            }
+        }
+    }
+}
+
+/// How the HTLC sending a transaction is resolved, which determines the signers of its PST.
+/// See [`TransactionProofBuilder::into_pst`].
+#[derive(Clone, Debug)]
+pub enum HtlcPstProof {
+    /// The HTLC's recipient redeems the funds by revealing the pre-image of the hash root.
+    RegularTransfer {
+        hash_depth: u8,
+        hash_root: AnyHash,
+        pre_image: PreImage,
+    },
+    /// The HTLC's sender and `recipient` agree to resolve the HTLC before its timeout.
+    EarlyResolve { recipient: Address },
+    /// The HTLC's sender reclaims the funds after the timeout.
+    TimeoutResolve,
 }
 
 impl SerializeContent for TransactionProofBuilder {
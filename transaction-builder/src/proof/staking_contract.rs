@@ -30,6 +30,13 @@ impl StakingDataBuilder {
     /// This method sets the required `signature` proof by signing the transaction
     /// using a key pair.
     pub fn sign_with_key_pair(&mut self, key_pair: &KeyPair) -> &mut Self {
+        let signature = key_pair.sign(self.transaction.serialize_content().as_slice());
+        self.set_signature(SignatureProof::from(key_pair.public, signature))
+    }
+
+    /// This method sets the required `signature` proof from a signature of the transaction
+    /// content that was created elsewhere, e.g. by a remote signer.
+    pub fn set_signature(&mut self, proof: SignatureProof) -> &mut Self {
         // Deserialize the data.
         let mut data: IncomingStakingTransactionData =
             Deserialize::deserialize_from_vec(&self.transaction.recipient_data[..]).unwrap();
@@ -37,11 +44,7 @@ impl StakingDataBuilder {
         // If this is a stake transaction, we don't need to sign it.
         match data {
             IncomingStakingTransactionData::AddStake { .. } => {}
-            _ => {
-                let signature = key_pair.sign(self.transaction.serialize_content().as_slice());
-                let proof = SignatureProof::from(key_pair.public, signature);
-                data.set_signature(proof);
-            }
+            _ => data.set_signature(proof),
         }
 
         self.data = Some(data);
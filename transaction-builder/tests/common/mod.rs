@@ -0,0 +1,5 @@
+use nimiq_keys::{KeyPair, SecureGenerate};
+
+pub fn key_pairs(n: usize) -> Vec<KeyPair> {
+    (0..n).map(|_| KeyPair::generate_default_csprng()).collect()
+}
@@ -0,0 +1,140 @@
+use nimiq_keys::{Address, KeyPair, SecureGenerate};
+use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_test_log::test;
+use nimiq_transaction_builder::funding::{
+    basic_transaction_fee, FundingError, FundingPlan, FundingSource,
+};
+
+use self::common::key_pairs;
+
+// The tests are also compiled as modules of `mod.rs`, thus the path is given explicitly.
+#[path = "common/mod.rs"]
+mod common;
+
+const FEE_PER_BYTE: u64 = 2;
+
+fn fee() -> Coin {
+    basic_transaction_fee(FEE_PER_BYTE).unwrap()
+}
+
+fn coin(value: u64) -> Coin {
+    Coin::from_u64_unchecked(value)
+}
+
+fn sources(key_pairs: &[KeyPair], balances: &[u64]) -> Vec<FundingSource> {
+    key_pairs
+        .iter()
+        .zip(balances)
+        .map(|(key_pair, balance)| FundingSource {
+            address: Address::from(key_pair),
+            available: coin(*balance) + fee(),
+        })
+        .collect()
+}
+
+#[test]
+fn fee_matches_signed_transaction_size() {
+    let key_pair = KeyPair::generate_default_csprng();
+    let sources = sources(&[key_pair.clone()], &[1000]);
+    let plan = FundingPlan::for_amount(&sources, coin(100), FEE_PER_BYTE).unwrap();
+    let transactions = plan
+        .sign(&Address::default(), 1, NetworkId::UnitAlbatross, |_| {
+            Some(&key_pair)
+        })
+        .unwrap();
+
+    let size = transactions[0].serialized_size() as u64;
+    assert_eq!(transactions[0].fee, coin(size * FEE_PER_BYTE));
+    assert_eq!(fee(), coin(size * FEE_PER_BYTE));
+}
+
+#[test]
+fn single_account_is_preferred() {
+    let key_pairs = key_pairs(3);
+    let sources = sources(&key_pairs, &[50, 500, 200]);
+
+    // The smallest account that can fund the payment on its own is used.
+    let plan = FundingPlan::for_amount(&sources, coin(150), FEE_PER_BYTE).unwrap();
+    assert_eq!(plan.inputs.len(), 1);
+    assert_eq!(plan.inputs[0].sender, sources[2].address);
+    assert_eq!(plan.value(), coin(150));
+    assert_eq!(plan.fee(), fee());
+}
+
+#[test]
+fn largest_accounts_are_combined() {
+    let key_pairs = key_pairs(3);
+    let sources = sources(&key_pairs, &[50, 500, 200]);
+
+    let plan = FundingPlan::for_amount(&sources, coin(600), FEE_PER_BYTE).unwrap();
+    let senders: Vec<_> = plan.inputs.iter().map(|input| &input.sender).collect();
+    assert_eq!(senders, vec![&sources[1].address, &sources[2].address]);
+    assert_eq!(plan.inputs[0].value, coin(500));
+    assert_eq!(plan.inputs[1].value, coin(100));
+    assert_eq!(plan.value(), coin(600));
+    assert_eq!(plan.fee(), fee() + fee());
+
+    let transactions = plan
+        .sign(
+            &Address::default(),
+            1,
+            NetworkId::UnitAlbatross,
+            |address| {
+                key_pairs
+                    .iter()
+                    .find(|key_pair| Address::from(*key_pair) == *address)
+            },
+        )
+        .unwrap();
+    assert_eq!(transactions.len(), 2);
+    for (transaction, input) in transactions.iter().zip(&plan.inputs) {
+        assert_eq!(transaction.sender, input.sender);
+        assert_eq!(transaction.value, input.value);
+        assert!(transaction.verify(NetworkId::UnitAlbatross).is_ok());
+    }
+}
+
+#[test]
+fn insufficient_funds_are_reported() {
+    let key_pairs = key_pairs(2);
+    let mut sources = sources(&key_pairs, &[50, 100]);
+    // An account that can only pay the fee is never selected.
+    sources.push(FundingSource {
+        address: Address::default(),
+        available: fee(),
+    });
+
+    match FundingPlan::for_amount(&sources, coin(200), FEE_PER_BYTE) {
+        Err(FundingError::InsufficientFunds { needed, available }) => {
+            assert_eq!(needed, coin(200));
+            assert_eq!(available, coin(150));
+        }
+        result => panic!("Unexpected result: {result:?}"),
+    }
+    assert!(matches!(
+        FundingPlan::for_amount(&sources, Coin::ZERO, FEE_PER_BYTE),
+        Err(FundingError::ZeroAmount)
+    ));
+}
+
+#[test]
+fn sweep_sends_everything() {
+    let key_pairs = key_pairs(2);
+    let sources = sources(&key_pairs, &[50, 100]);
+
+    let plan = FundingPlan::sweep(&sources, FEE_PER_BYTE).unwrap();
+    assert_eq!(plan.value(), coin(150));
+    assert_eq!(plan.fee(), fee() + fee());
+
+    // Every selected account has to sign.
+    let result = plan.sign(
+        &Address::default(),
+        1,
+        NetworkId::UnitAlbatross,
+        |address| (*address == sources[0].address).then_some(&key_pairs[0]),
+    );
+    assert!(matches!(
+        result,
+        Err(FundingError::MissingKeyPair(address)) if address == sources[1].address
+    ));
+}
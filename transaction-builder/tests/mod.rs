@@ -1,3 +1,6 @@
+mod funding;
 mod htlc_contract;
+mod multisig;
+mod pst;
 mod staking_contract;
 mod vesting_contract;
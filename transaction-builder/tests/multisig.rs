@@ -0,0 +1,144 @@
+use nimiq_keys::{
+    multisig::{CommitmentPair, PartialSignature},
+    Address, KeyPair, PublicKey, SecureGenerate,
+};
+use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_test_log::test;
+use nimiq_transaction::Transaction;
+use nimiq_transaction_builder::{
+    multisig::{MultisigAccount, MultisigError},
+    proof::BasicProofBuilder,
+};
+
+use self::common::key_pairs;
+
+// The tests are also compiled as modules of `mod.rs`, thus the path is given explicitly.
+#[path = "common/mod.rs"]
+mod common;
+
+fn public_keys(key_pairs: &[KeyPair]) -> Vec<PublicKey> {
+    key_pairs.iter().map(|key_pair| key_pair.public).collect()
+}
+
+#[test]
+fn address_does_not_depend_on_key_order() {
+    let key_pairs = key_pairs(3);
+    let mut public_keys = public_keys(&key_pairs);
+
+    let account = MultisigAccount::new(public_keys.clone(), 2).unwrap();
+    public_keys.reverse();
+    let reversed = MultisigAccount::new(public_keys.clone(), 2).unwrap();
+    assert_eq!(account.address(), reversed.address());
+
+    let other = MultisigAccount::new(public_keys.clone(), 3).unwrap();
+    assert_ne!(account.address(), other.address());
+
+    assert_eq!(
+        MultisigAccount::new(public_keys.clone(), 4),
+        Err(MultisigError::InvalidNumSigners(4))
+    );
+    public_keys.push(public_keys[0]);
+    assert_eq!(
+        MultisigAccount::new(public_keys.clone(), 2),
+        Err(MultisigError::DuplicatePublicKey(public_keys[0]))
+    );
+}
+
+#[test]
+fn rejects_too_many_public_keys() {
+    let max = MultisigAccount::MAX_PUBLIC_KEYS;
+    let public_keys = public_keys(&key_pairs(max + 1));
+
+    assert!(MultisigAccount::new(public_keys[..max].to_vec(), max / 2).is_ok());
+    assert_eq!(
+        MultisigAccount::new(public_keys, max / 2),
+        Err(MultisigError::TooManyPublicKeys(max + 1))
+    );
+}
+
+#[test]
+fn two_of_three_can_sign_transaction() {
+    let key_pairs = key_pairs(3);
+    let account = MultisigAccount::new(public_keys(&key_pairs), 2).unwrap();
+
+    let mut transaction = Transaction::new_basic(
+        account.address(),
+        Address::from([1u8; Address::SIZE]),
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(10),
+        1,
+        NetworkId::UnitAlbatross,
+    );
+    let data = transaction.serialize_content();
+
+    // The second and the third key sign, given in the opposite order.
+    let signers = [&key_pairs[2], &key_pairs[1]];
+    let signer_public_keys: Vec<_> = signers.iter().map(|key_pair| key_pair.public).collect();
+    let commitment_pairs: Vec<_> = signers
+        .iter()
+        .map(|_| CommitmentPair::generate_default_csprng())
+        .collect();
+    let commitments: Vec<_> = commitment_pairs
+        .iter()
+        .map(|pair| *pair.commitment())
+        .collect();
+
+    let partial_signatures: Vec<PartialSignature> = signers
+        .iter()
+        .zip(&commitment_pairs)
+        .map(|(key_pair, pair)| {
+            account
+                .partially_sign(
+                    key_pair,
+                    &signer_public_keys,
+                    pair.random_secret(),
+                    &commitments,
+                    &data,
+                )
+                .unwrap()
+        })
+        .collect();
+
+    let proof = account
+        .create_signature_proof(&signer_public_keys, &commitments, &partial_signatures)
+        .unwrap();
+    assert!(proof.is_signed_by(&account.address()));
+    assert!(proof.verify(&data));
+
+    let mut builder = BasicProofBuilder::new(transaction.clone());
+    builder.with_signature_proof(proof);
+    transaction = builder.generate().unwrap();
+    assert_eq!(transaction.verify(NetworkId::UnitAlbatross), Ok(()));
+}
+
+#[test]
+fn rejects_invalid_signers() {
+    let key_pairs = key_pairs(3);
+    let account = MultisigAccount::new(public_keys(&key_pairs[..2]), 2).unwrap();
+    let commitment_pair = CommitmentPair::generate_default_csprng();
+
+    assert_eq!(
+        account.signers(&[key_pairs[0].public]),
+        Err(MultisigError::WrongNumberOfSigners {
+            expected: 2,
+            actual: 1
+        })
+    );
+    assert_eq!(
+        account.signers(&[key_pairs[0].public, key_pairs[2].public]),
+        Err(MultisigError::UnknownPublicKey(key_pairs[2].public))
+    );
+    assert_eq!(
+        account.partially_sign(
+            &key_pairs[0],
+            &public_keys(&key_pairs[..2]),
+            commitment_pair.random_secret(),
+            &[*commitment_pair.commitment()],
+            b"data",
+        ),
+        Err(MultisigError::WrongNumberOfSigners {
+            expected: 2,
+            actual: 1
+        })
+    );
+}
@@ -0,0 +1,250 @@
+use nimiq_hash::{Blake2bHasher, Hasher};
+use nimiq_keys::{Address, KeyPair, SecureGenerate};
+use nimiq_primitives::{account::AccountType, coin::Coin, networks::NetworkId, policy::Policy};
+use nimiq_serde::Serialize;
+use nimiq_test_log::test;
+use nimiq_transaction::{
+    account::{
+        htlc_contract::{AnyHash, AnyHash32, PreImage},
+        staking_contract::IncomingStakingTransactionData,
+    },
+    pst::{PartiallySignedTransaction, PstError, PstRole},
+    SignatureProof, Transaction,
+};
+use nimiq_transaction_builder::{proof::HtlcPstProof, TransactionProofBuilder};
+
+fn sign(pst: &PartiallySignedTransaction, role: PstRole, key_pair: &KeyPair) -> SignatureProof {
+    let data = pst.signing_data(role).unwrap();
+    SignatureProof::from(key_pair.public, key_pair.sign(&data))
+}
+
+#[test]
+fn basic_transaction_roundtrip() {
+    let key_pair = KeyPair::generate_default_csprng();
+    let sender = Address::from(&key_pair);
+    let transaction = Transaction::new_basic(
+        sender.clone(),
+        Address::from([1u8; Address::SIZE]),
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(10),
+        1,
+        NetworkId::Dummy,
+    );
+
+    let mut pst = TransactionProofBuilder::new(transaction)
+        .into_pst(sender, None)
+        .unwrap();
+    pst.metadata
+        .insert("description".to_string(), "Rent".to_string());
+    assert!(!pst.is_complete());
+
+    // The PST is carried to the signer and back.
+    let mut pst = PartiallySignedTransaction::parse(&pst.serialize_to_vec()).unwrap();
+    let signature_proof = sign(&pst, PstRole::Sender, &key_pair);
+    pst.add_signature(PstRole::Sender, signature_proof).unwrap();
+    let pst = PartiallySignedTransaction::parse(&pst.serialize_to_vec()).unwrap();
+
+    assert!(pst.is_complete());
+    assert_eq!(pst.metadata["description"], "Rent");
+    let transaction = pst.finalize().unwrap();
+    assert_eq!(transaction.verify(NetworkId::Dummy), Ok(()));
+}
+
+#[test]
+fn staking_data_is_signed_before_the_sender() {
+    let staker_key_pair = KeyPair::generate_default_csprng();
+    let staker = Address::from(&staker_key_pair);
+    let sender_key_pair = KeyPair::generate_default_csprng();
+    let sender = Address::from(&sender_key_pair);
+    let data = IncomingStakingTransactionData::CreateStaker {
+        delegation: None,
+        proof: Default::default(),
+    };
+    let transaction = Transaction::new_extended(
+        sender.clone(),
+        AccountType::Basic,
+        vec![],
+        Policy::STAKING_CONTRACT_ADDRESS,
+        AccountType::Staking,
+        data.serialize_to_vec(),
+        Coin::from_u64_unchecked(100_000_000),
+        Coin::from_u64_unchecked(100),
+        1,
+        NetworkId::Dummy,
+    );
+
+    let mut pst = TransactionProofBuilder::new(transaction)
+        .into_pst(staker.clone(), None)
+        .unwrap();
+    assert_eq!(pst.missing_signers().count(), 2);
+    assert_eq!(
+        pst.signing_data(PstRole::Sender),
+        Err(PstError::MissingSignature(
+            staker.clone(),
+            PstRole::StakingData
+        ))
+    );
+
+    // The signature of the staking data can't be used in another role.
+    let signature_proof = sign(&pst, PstRole::StakingData, &staker_key_pair);
+    assert_eq!(
+        pst.add_signature(PstRole::HtlcRecipient, signature_proof.clone()),
+        Err(PstError::UnknownSigner(staker, PstRole::HtlcRecipient))
+    );
+    pst.add_signature(PstRole::StakingData, signature_proof)
+        .unwrap();
+
+    let signature_proof = sign(&pst, PstRole::Sender, &sender_key_pair);
+    pst.add_signature(PstRole::Sender, signature_proof).unwrap();
+
+    let transaction = pst.finalize().unwrap();
+    assert_eq!(transaction.verify(NetworkId::Dummy), Ok(()));
+}
+
+#[test]
+fn rejects_invalid_signatures_and_versions() {
+    let key_pair = KeyPair::generate_default_csprng();
+    let sender = Address::from(&key_pair);
+    let transaction = Transaction::new_basic(
+        sender.clone(),
+        Address::from([1u8; Address::SIZE]),
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(10),
+        1,
+        NetworkId::Dummy,
+    );
+    let mut pst = TransactionProofBuilder::new(transaction)
+        .into_pst(sender.clone(), None)
+        .unwrap();
+
+    let signature_proof = SignatureProof::from(key_pair.public, key_pair.sign(b"something else"));
+    assert_eq!(
+        pst.add_signature(PstRole::Sender, signature_proof),
+        Err(PstError::InvalidSignature(sender.clone()))
+    );
+    assert_eq!(
+        pst.finalize().err(),
+        Some(PstError::MissingSignature(sender, PstRole::Sender))
+    );
+
+    let mut bytes = pst.serialize_to_vec();
+    bytes[0] = PartiallySignedTransaction::VERSION + 1;
+    assert_eq!(
+        PartiallySignedTransaction::parse(&bytes).err(),
+        Some(PstError::UnsupportedVersion(
+            PartiallySignedTransaction::VERSION + 1
+        ))
+    );
+}
+
+/// A transaction redeeming the funds of an HTLC contract, without a proof.
+fn htlc_transaction() -> Transaction {
+    Transaction::new_extended(
+        Address::from([2u8; Address::SIZE]),
+        AccountType::HTLC,
+        vec![],
+        Address::from([3u8; Address::SIZE]),
+        AccountType::Basic,
+        vec![],
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(10),
+        1,
+        NetworkId::Dummy,
+    )
+}
+
+/// Carries the PST through serialization and finalizes it.
+fn finalize(pst: &PartiallySignedTransaction) -> Transaction {
+    let pst = PartiallySignedTransaction::parse(&pst.serialize_to_vec()).unwrap();
+    assert!(pst.is_complete());
+    pst.finalize().unwrap()
+}
+
+#[test]
+fn htlc_regular_transfer_roundtrip() {
+    let recipient_key_pair = KeyPair::generate_default_csprng();
+    let recipient = Address::from(&recipient_key_pair);
+    let pre_image = PreImage::PreImage32(AnyHash32::from([7u8; 32]));
+    let hash_root = AnyHash::from(Blake2bHasher::default().digest(pre_image.as_bytes()));
+
+    let proof = HtlcPstProof::RegularTransfer {
+        hash_depth: 1,
+        hash_root,
+        pre_image,
+    };
+    let mut pst = TransactionProofBuilder::new(htlc_transaction())
+        .into_pst(recipient.clone(), Some(proof))
+        .unwrap();
+    assert_eq!(
+        pst.finalize().err(),
+        Some(PstError::MissingSignature(
+            recipient,
+            PstRole::HtlcRecipient
+        ))
+    );
+
+    assert_eq!(pst.sign_with_key_pair(&recipient_key_pair), Ok(1));
+    let transaction = finalize(&pst);
+    assert_eq!(transaction.verify(NetworkId::Dummy), Ok(()));
+}
+
+#[test]
+fn htlc_early_resolve_roundtrip() {
+    let sender_key_pair = KeyPair::generate_default_csprng();
+    let sender = Address::from(&sender_key_pair);
+    let recipient_key_pair = KeyPair::generate_default_csprng();
+    let recipient = Address::from(&recipient_key_pair);
+
+    let proof = HtlcPstProof::EarlyResolve {
+        recipient: recipient.clone(),
+    };
+    let mut pst = TransactionProofBuilder::new(htlc_transaction())
+        .into_pst(sender, Some(proof))
+        .unwrap();
+    assert_eq!(pst.missing_signers().count(), 2);
+
+    assert_eq!(pst.sign_with_key_pair(&sender_key_pair), Ok(1));
+    assert_eq!(
+        pst.finalize().err(),
+        Some(PstError::MissingSignature(
+            recipient,
+            PstRole::HtlcRecipient
+        ))
+    );
+    assert_eq!(pst.sign_with_key_pair(&recipient_key_pair), Ok(1));
+    let transaction = finalize(&pst);
+    assert_eq!(transaction.verify(NetworkId::Dummy), Ok(()));
+}
+
+#[test]
+fn htlc_timeout_resolve_roundtrip() {
+    let sender_key_pair = KeyPair::generate_default_csprng();
+    let sender = Address::from(&sender_key_pair);
+
+    let mut pst = TransactionProofBuilder::new(htlc_transaction())
+        .into_pst(sender, Some(HtlcPstProof::TimeoutResolve))
+        .unwrap();
+    assert_eq!(pst.sign_with_key_pair(&sender_key_pair), Ok(1));
+    let transaction = finalize(&pst);
+    assert_eq!(transaction.verify(NetworkId::Dummy), Ok(()));
+}
+
+#[test]
+fn htlc_proof_is_required_for_htlc_senders_only() {
+    let sender = Address::from([1u8; Address::SIZE]);
+    assert!(TransactionProofBuilder::new(htlc_transaction())
+        .into_pst(sender.clone(), None)
+        .is_none());
+
+    let transaction = Transaction::new_basic(
+        sender.clone(),
+        Address::from([3u8; Address::SIZE]),
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(10),
+        1,
+        NetworkId::Dummy,
+    );
+    assert!(TransactionProofBuilder::new(transaction)
+        .into_pst(sender, Some(HtlcPstProof::TimeoutResolve))
+        .is_none());
+}
@@ -7,7 +7,7 @@ use std::{pin::Pin, time::Duration};
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, Stream};
-use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, SecretKey};
+use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, SigHash, Signature};
 use nimiq_network_interface::{
     network::{MsgAcceptance, Network, PubsubId, Topic},
     request::{Message, Request, RequestCommon},
@@ -68,11 +68,16 @@ pub trait ValidatorNetwork: Send + Sync {
     /// `lifetime` or `buffer_size` of 0 should disable the cache.
     fn cache<M: Message>(&self, buffer_size: usize, lifetime: Duration);
 
-    /// Sets this node peer ID using its secret key and public key.
+    /// Returns the hash of the record announcing this node as the node of our validator. It must be signed with the
+    /// voting key and passed to [`set_public_key`](Self::set_public_key).
+    fn validator_record_hash(&self) -> SigHash;
+
+    /// Sets this node peer ID using the public key and the signature of the
+    /// [`validator_record_hash`](Self::validator_record_hash) by the corresponding secret key.
     async fn set_public_key(
         &self,
         public_key: &CompressedPublicKey,
-        secret_key: &SecretKey,
+        signature: Signature,
     ) -> Result<(), Self::Error>;
 
     /// Signals that a Gossipsup'd message with `id` was verified successfully and can be relayed.
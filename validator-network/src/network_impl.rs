@@ -2,7 +2,7 @@ use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt, TryFutureExt};
-use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, SecretKey};
+use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, SigHash, Signature};
 use nimiq_network_interface::{
     network::{MsgAcceptance, Network, NetworkEvent, Topic},
     request::{Message, Request, RequestCommon},
@@ -280,16 +280,18 @@ where
         unimplemented!()
     }
 
+    fn validator_record_hash(&self) -> SigHash {
+        ValidatorRecord::new(self.network.get_local_peer_id()).signing_hash()
+    }
+
     async fn set_public_key(
         &self,
         public_key: &CompressedPublicKey,
-        secret_key: &SecretKey,
+        signature: Signature,
     ) -> Result<(), Self::Error> {
         let peer_id = self.network.get_local_peer_id();
-        let record = ValidatorRecord::new(peer_id);
-        self.network
-            .dht_put(public_key, &record.sign(secret_key))
-            .await?;
+        let record = ValidatorRecord::new(peer_id).with_signature(signature);
+        self.network.dht_put(public_key, &record).await?;
 
         Ok(())
     }
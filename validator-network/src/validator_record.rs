@@ -1,4 +1,5 @@
-use nimiq_bls::{PublicKey, SecretKey, Signature};
+use nimiq_bls::{PublicKey, SecretKey, SigHash, Signature};
+use nimiq_hash::Hash;
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_utils::tagged_signing::TaggedSignable;
 
@@ -29,9 +30,18 @@ where
     }
 
     pub fn sign(self, secret_key: &SecretKey) -> SignedValidatorRecord<TPeerId> {
-        let data = self.serialize_to_vec();
-        let signature = secret_key.sign(&data);
+        let signature = secret_key.sign_hash(self.signing_hash());
+        self.with_signature(signature)
+    }
+
+    /// Returns the hash of the record that is signed with the voting key.
+    pub fn signing_hash(&self) -> SigHash {
+        self.serialize_to_vec().hash()
+    }
 
+    /// Attaches a signature of the [`signing_hash`](Self::signing_hash) that was created elsewhere, e.g. by a
+    /// remote signer.
+    pub fn with_signature(self, signature: Signature) -> SignedValidatorRecord<TPeerId> {
         SignedValidatorRecord {
             record: self,
             signature,
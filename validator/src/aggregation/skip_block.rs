@@ -12,12 +12,14 @@ use futures::{
     stream::{select, BoxStream, Stream, StreamExt},
 };
 use nimiq_block::{Message, MultiSignature, SignedSkipBlockInfo, SkipBlockInfo, SkipBlockProof};
-use nimiq_bls::{AggregateSignature, KeyPair};
+use nimiq_block_production::signer::{SignerError, ValidatorSigner};
+use nimiq_bls::AggregateSignature;
 use nimiq_collections::BitSet;
 use nimiq_handel::{
     aggregation::Aggregation,
     config::Config,
     contribution::{AggregatableContribution, ContributionError},
+    diagnostics::HandelDiagnostics,
     evaluator::WeightedVote,
     identity::WeightRegistry,
     partitioner::BinomialPartitioner,
@@ -139,8 +141,9 @@ impl AggregatableContribution for SignedSkipBlockMessage {
     }
 }
 
+/// The level update of a skip block aggregation, as sent to the other validators.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-struct SkipBlockUpdate(pub LevelUpdate<SignedSkipBlockMessage>, pub SkipBlockInfo);
+pub struct SkipBlockUpdate(pub LevelUpdate<SignedSkipBlockMessage>, pub SkipBlockInfo);
 
 impl RequestCommon for SkipBlockUpdate {
     type Kind = MessageMarker;
@@ -244,12 +247,13 @@ pub struct SkipBlockAggregation {}
 impl SkipBlockAggregation {
     pub async fn start<N: ValidatorNetwork + 'static>(
         skip_block_info: SkipBlockInfo,
-        voting_key: KeyPair,
+        signer: Arc<dyn ValidatorSigner>,
         // TODO: This seems to be a SlotBand. Change this to a proper Validator ID.
         validator_id: u16,
         active_validators: Validators,
         network: Arc<N>,
-    ) -> (SkipBlockInfo, SkipBlockProof) {
+        diagnostics: Arc<HandelDiagnostics>,
+    ) -> Result<(SkipBlockInfo, SkipBlockProof), SignerError> {
         // TODO expose this somewehere else so we don't need to clone here.
         let weights = Arc::new(ValidatorRegistry::new(active_validators.clone()));
 
@@ -264,11 +268,11 @@ impl SkipBlockAggregation {
                 &skip_block_info,
                 message_hash
             );
-            let signed_skip_block_info = SignedSkipBlockInfo::from_message(
-                skip_block_info.clone(),
-                &voting_key.secret_key,
-                validator_id,
-            );
+            let signed_skip_block_info = SignedSkipBlockInfo {
+                message: skip_block_info.clone(),
+                signer_idx: validator_id,
+                signature: signer.vote(message_hash.clone())?,
+            };
 
             let signature = AggregateSignature::from_signatures(&[signed_skip_block_info
                 .signature
@@ -301,12 +305,13 @@ impl SkipBlockAggregation {
                 skip_block_info.clone(),
             );
 
-            let aggregation = Aggregation::new(
+            let aggregation = Aggregation::with_diagnostics(
                 protocol,
                 Config::default(),
                 own_contribution,
                 Box::pin(input_switch),
                 NetworkWrapper::new(skip_block_info.clone(), Arc::clone(&network)),
+                Some(Arc::clone(&diagnostics)),
             );
 
             let mut stream = select(
@@ -331,7 +336,7 @@ impl SkipBlockAggregation {
                                 trace!("Skip block completed, proof={:?}", &skip_block_proof);
 
                                 // return the SkipBlockProof
-                                return (skip_block_info, skip_block_proof);
+                                return Ok((skip_block_info, skip_block_proof));
                             }
                         }
                     }
@@ -1,13 +1,14 @@
 use std::{collections::BTreeMap, ops};
 
 use nimiq_block::{MultiSignature, TendermintVote};
-use nimiq_bls::{AggregateSignature, SecretKey};
+use nimiq_block_production::signer::{SignerError, ValidatorSigner};
+use nimiq_bls::{AggregateSignature, SigHash};
 use nimiq_collections::bitset::BitSet;
 use nimiq_handel::{
     contribution::{AggregatableContribution, ContributionError},
     update::LevelUpdate,
 };
-use nimiq_hash::Blake2sHash;
+use nimiq_hash::{Blake2sHash, Hash};
 use nimiq_tendermint::Aggregation;
 use serde::{Deserialize, Serialize};
 
@@ -27,13 +28,13 @@ impl std::fmt::Debug for TendermintContribution {
 impl TendermintContribution {
     pub(crate) fn from_vote(
         vote: TendermintVote,
-        secret_key: &SecretKey,
+        signer: &dyn ValidatorSigner,
         validator_slots: ops::Range<u16>,
-    ) -> Self {
+    ) -> Result<Self, SignerError> {
         assert!(!validator_slots.is_empty());
         // sign the hash
-        let signature = AggregateSignature::from_signatures(&[secret_key
-            .sign(&vote)
+        let signature = AggregateSignature::from_signatures(&[signer
+            .vote(vote.hash::<SigHash>())?
             .multiply(validator_slots.len() as u16)]);
 
         // get the slots of the validator ad insert them into the bitset
@@ -47,7 +48,7 @@ impl TendermintContribution {
 
         let mut contributions = BTreeMap::new();
         contributions.insert(vote.proposal_hash, multi_signature);
-        Self { contributions }
+        Ok(Self { contributions })
     }
 }
 
@@ -0,0 +1,94 @@
+//! Rotation of the signing and voting keys of the validator.
+//!
+//! The next keys are registered in the staking contract with an `UpdateValidator` transaction. From then on, the
+//! transactions signaling the staking contract must be signed with the next signing key. For block production and
+//! voting, the next keys only become effective once the validator is elected with them, i.e. at the next election
+//! block. Until then, the validator keeps using its current keys, such that the rotation doesn't cause any downtime.
+//!
+//! Once the next keys are effective, they are written to the [`KeyFiles`] of the validator, if configured, such that
+//! the node keeps using them after a restart.
+use std::path::PathBuf;
+
+use nimiq_bls::{CompressedPublicKey as BlsPublicKey, KeyPair as BlsKeyPair};
+use nimiq_keys::{KeyPair as SchnorrKeyPair, PublicKey as SchnorrPublicKey};
+use nimiq_utils::file_store::{Error as FileStoreError, FileStore};
+
+/// The files the signing and voting keys of the validator are loaded from.
+#[derive(Clone, Debug)]
+pub struct KeyFiles {
+    pub signing_key_path: PathBuf,
+    pub voting_key_path: PathBuf,
+}
+
+impl KeyFiles {
+    /// Replaces the keys stored in the files.
+    pub fn store(
+        &self,
+        signing_key: &SchnorrKeyPair,
+        voting_key: &BlsKeyPair,
+    ) -> Result<(), FileStoreError> {
+        FileStore::new(&self.signing_key_path).store(signing_key)?;
+        FileStore::new(&self.voting_key_path).store(voting_key)
+    }
+}
+
+/// The keys the validator rotates to.
+pub struct KeyRotation {
+    pub(crate) signing_key: SchnorrKeyPair,
+    pub(crate) voting_key: BlsKeyPair,
+    /// The key of the validator address, which authorizes the `UpdateValidator` transaction. Without it, the
+    /// transaction has to be sent by other means.
+    pub(crate) validator_key: Option<SchnorrKeyPair>,
+    /// The block number at which we sent the `UpdateValidator` transaction, if we did.
+    pub(crate) update_sent_at: Option<u32>,
+    /// Whether the next keys are registered in the staking contract.
+    pub(crate) registered: bool,
+    /// The files to write the next keys to once they are effective.
+    pub(crate) key_files: Option<KeyFiles>,
+}
+
+impl KeyRotation {
+    pub fn new(
+        signing_key: SchnorrKeyPair,
+        voting_key: BlsKeyPair,
+        validator_key: Option<SchnorrKeyPair>,
+    ) -> Self {
+        Self {
+            signing_key,
+            voting_key,
+            validator_key,
+            update_sent_at: None,
+            registered: false,
+            key_files: None,
+        }
+    }
+
+    /// Writes the next keys to `key_files` once they are effective.
+    pub fn with_key_files(mut self, key_files: KeyFiles) -> Self {
+        self.key_files = Some(key_files);
+        self
+    }
+
+    /// The key of the validator address, if configured.
+    pub fn validator_key(&self) -> Option<&SchnorrKeyPair> {
+        self.validator_key.as_ref()
+    }
+
+    /// The public keys the validator rotates to.
+    pub fn public_keys(&self) -> (SchnorrPublicKey, BlsPublicKey) {
+        (
+            self.signing_key.public,
+            self.voting_key.public_key.compress(),
+        )
+    }
+
+    /// Checks whether the given public keys are the ones of the next keys.
+    pub(crate) fn matches(
+        &self,
+        signing_key: &SchnorrPublicKey,
+        voting_key: &BlsPublicKey,
+    ) -> bool {
+        *signing_key == self.signing_key.public
+            && *voting_key == self.voting_key.public_key.compress()
+    }
+}
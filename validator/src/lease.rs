@@ -0,0 +1,191 @@
+//! Leases to run a validator with hot standbys.
+//!
+//! Several nodes can be configured with the same validator keys. All of them stay fully synced, but only the node
+//! holding the lease produces blocks and votes. If the active node loses the lease, e.g. because it crashed or lost
+//! consensus, a standby acquires it and takes over.
+//!
+//! Besides the holder, the lease records the highest block number its holder may sign messages for. The holder
+//! never signs above this limit and the next holder never signs at or below it. Thus, the nodes can't sign
+//! conflicting messages even if the previous holder signed something right before it lost the lease.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use nimiq_utils::time::systemtime_to_timestamp;
+
+/// A lease that at most one node running a validator holds at a time.
+pub trait ValidatorLease: Send + Sync {
+    /// Tries to acquire the lease, or to renew it if we already hold it, for `duration`. While we hold the lease,
+    /// we only sign messages for blocks up to `signing_limit`, which is recorded in the lease.
+    ///
+    /// Returns `None` if the lease is held by someone else. Otherwise returns the signing limit recorded by the
+    /// previous holder, at or below which we must not sign anything.
+    fn try_acquire(&self, duration: Duration, signing_limit: u32) -> io::Result<Option<u32>>;
+
+    /// Releases the lease if we hold it, such that a standby can take over immediately. The signing limit stays
+    /// recorded for the next holder.
+    fn release(&self) -> io::Result<()>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct LeaseRecord {
+    holder: String,
+    /// Timestamp in milliseconds.
+    expires_at: u64,
+    signing_limit: u32,
+}
+
+impl LeaseRecord {
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split_whitespace();
+        let record = Self {
+            holder: parts.next()?.to_string(),
+            expires_at: parts.next()?.parse().ok()?,
+            signing_limit: parts.next()?.parse().ok()?,
+        };
+        parts.next().is_none().then_some(record)
+    }
+
+    fn serialize(&self) -> String {
+        format!(
+            "{} {} {}\n",
+            self.holder, self.expires_at, self.signing_limit
+        )
+    }
+}
+
+/// Lease stored in a file, e.g. on a filesystem shared by the active node and its standbys. This is meant as a
+/// simple stand-in for a proper lease service: It relies on the clocks of the nodes being synchronized and on the
+/// filesystem supporting atomic renames and exclusive file creation.
+pub struct FileLease {
+    path: PathBuf,
+    holder: String,
+}
+
+impl FileLease {
+    /// Creates a lease stored in the file at `path`. The `holder` identifies this node and must be
+    /// [valid](Self::is_valid_holder).
+    pub fn new<P: AsRef<Path>>(path: P, holder: String) -> io::Result<Self> {
+        if !Self::is_valid_holder(&holder) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid lease holder: {holder:?}"),
+            ));
+        }
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            holder,
+        })
+    }
+
+    /// Returns whether `holder` can identify a node in the lease file, i.e. it is non-empty and doesn't contain
+    /// whitespace.
+    pub fn is_valid_holder(holder: &str) -> bool {
+        !holder.is_empty() && !holder.contains(char::is_whitespace)
+    }
+
+    fn read(path: &Path) -> io::Result<Option<LeaseRecord>> {
+        match fs::read_to_string(path) {
+            Ok(content) => LeaseRecord::parse(&content)
+                .map(Some)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid lease file")),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Atomically replaces the lease file.
+    fn replace(&self, record: &LeaseRecord) -> io::Result<()> {
+        let tmp_path = self.tmp_path("tmp");
+        fs::write(&tmp_path, record.serialize())?;
+        fs::rename(&tmp_path, &self.path)
+    }
+
+    /// Creates the lease file, failing if it exists already.
+    fn create(&self, record: &LeaseRecord) -> io::Result<bool> {
+        use io::Write;
+
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&self.path)
+        {
+            Ok(mut file) => {
+                file.write_all(record.serialize().as_bytes())?;
+                file.sync_all()?;
+                Ok(true)
+            }
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn tmp_path(&self, suffix: &str) -> PathBuf {
+        let mut file_name = self.path.file_name().unwrap_or_default().to_owned();
+        file_name.push(format!(".{}.{}", self.holder, suffix));
+        self.path.with_file_name(file_name)
+    }
+
+    /// Takes over the expired lease `stale`. Moving the stale lease file away first ensures that only one of
+    /// several standbys taking over at the same time succeeds.
+    fn take_over(&self, stale: &LeaseRecord, new: &LeaseRecord) -> io::Result<bool> {
+        let stale_path = self.tmp_path("stale");
+        match fs::rename(&self.path, &stale_path) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(error) => return Err(error),
+        }
+
+        // Someone else might have taken over in the meantime, in which case we moved away their lease.
+        if Self::read(&stale_path)?.as_ref() != Some(stale) {
+            fs::rename(&stale_path, &self.path)?;
+            return Ok(false);
+        }
+        fs::remove_file(&stale_path)?;
+
+        self.create(new)
+    }
+}
+
+impl ValidatorLease for FileLease {
+    fn try_acquire(&self, duration: Duration, signing_limit: u32) -> io::Result<Option<u32>> {
+        let now = systemtime_to_timestamp(SystemTime::now());
+        let record = LeaseRecord {
+            holder: self.holder.clone(),
+            expires_at: now + duration.as_millis() as u64,
+            signing_limit,
+        };
+
+        match Self::read(&self.path)? {
+            None => Ok(self.create(&record)?.then_some(0)),
+            Some(current) if current.expires_at > now => {
+                if current.holder != self.holder {
+                    return Ok(None);
+                }
+                // Never lower the limit, we might already have signed up to it.
+                let record = LeaseRecord {
+                    signing_limit: signing_limit.max(current.signing_limit),
+                    ..record
+                };
+                self.replace(&record)?;
+                Ok(Some(current.signing_limit))
+            }
+            Some(stale) => {
+                let previous_limit = stale.signing_limit;
+                Ok(self.take_over(&stale, &record)?.then_some(previous_limit))
+            }
+        }
+    }
+
+    fn release(&self) -> io::Result<()> {
+        match Self::read(&self.path)? {
+            Some(current) if current.holder == self.holder => self.replace(&LeaseRecord {
+                expires_at: 0,
+                ..current
+            }),
+            _ => Ok(()),
+        }
+    }
+}
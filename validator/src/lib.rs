@@ -3,7 +3,19 @@ extern crate log;
 
 pub mod aggregation;
 mod jail;
+pub mod key_rotation;
+pub mod lease;
 mod r#macro;
+pub mod maintenance;
 mod micro;
+#[cfg(unix)]
+pub mod remote_signer;
+pub mod signing_history;
+pub mod stats;
 mod tendermint;
+pub mod tendermint_events;
+#[cfg(feature = "metrics")]
+pub mod tendermint_metrics;
 pub mod validator;
+
+pub use r#macro::PROPOSAL_TOPIC;
@@ -10,8 +10,9 @@ use futures::{
     stream::{BoxStream, Stream, StreamExt},
 };
 use nimiq_block::MacroBlock;
-use nimiq_block_production::BlockProducer;
+use nimiq_block_production::signer::ValidatorSigner;
 use nimiq_blockchain::Blockchain;
+use nimiq_handel::diagnostics::HandelDiagnostics;
 use nimiq_keys::Signature as SchnorrSignature;
 use nimiq_network_interface::network::Topic;
 use nimiq_primitives::slots_allocation::Validators;
@@ -25,7 +26,9 @@ use crate::{
         state::MacroState,
         update_message::TendermintUpdate,
     },
+    signing_history::SigningHistory,
     tendermint::TendermintProtocol,
+    tendermint_events::{ProposalResult, TendermintEvents},
 };
 
 pub(crate) enum MappedReturn<TValidatorNetwork: ValidatorNetwork + 'static>
@@ -45,6 +48,9 @@ where
     ),
 }
 
+/// The name of the gossipsub topic Tendermint proposals are published on.
+pub const PROPOSAL_TOPIC: &str = "tendermint-proposal";
+
 pub struct ProposalTopic<TValidatorNetwork> {
     _phantom: PhantomData<TValidatorNetwork>,
 }
@@ -56,7 +62,7 @@ where
     type Item = SignedProposal;
 
     const BUFFER_SIZE: usize = 8;
-    const NAME: &'static str = "tendermint-proposal";
+    const NAME: &'static str = PROPOSAL_TOPIC;
     const VALIDATE: bool = true;
 }
 
@@ -75,7 +81,10 @@ where
     pub fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn ValidatorSigner>,
+        signing_history: Arc<SigningHistory>,
+        aggregation_diagnostics: Arc<HandelDiagnostics>,
+        tendermint_events: Arc<TendermintEvents>,
         validator_slot_band: u16,
         current_validators: Validators,
         block_height: u32,
@@ -102,12 +111,16 @@ where
         let dependencies = TendermintProtocol::new(
             blockchain,
             network,
-            block_producer,
+            signer,
+            signing_history,
+            aggregation_diagnostics,
             current_validators,
             validator_slot_band,
             block_height,
         );
 
+        let mut tracker = tendermint_events.start(block_height);
+
         // create the Tendermint instance, which implements Stream
         let tendermint = Tendermint::new(
             dependencies,
@@ -115,17 +128,26 @@ where
             proposal_stream,
             input,
         )
-        // and map the return value such that a state update can be persisted.
+        // and map the return value such that a state update can be persisted. The progress is reported along the way.
         .map(move |item| match item {
-            TendermintReturn::Decision(decision) => MappedReturn::Decision(decision),
+            TendermintReturn::Decision(decision) => {
+                tracker.decision(&decision);
+                MappedReturn::Decision(decision)
+            }
             TendermintReturn::Update(state) => {
+                tracker.state_updated(&state);
                 MappedReturn::Update(MacroState::from_tendermint_state(block_height, state))
             }
             TendermintReturn::ProposalAccepted(proposal) => {
+                tracker.proposal(&proposal, ProposalResult::Accepted);
                 MappedReturn::ProposalAccepted(proposal)
             }
-            TendermintReturn::ProposalIgnored(proposal) => MappedReturn::ProposalIgnored(proposal),
+            TendermintReturn::ProposalIgnored(proposal) => {
+                tracker.proposal(&proposal, ProposalResult::Ignored);
+                MappedReturn::ProposalIgnored(proposal)
+            }
             TendermintReturn::ProposalRejected(proposal) => {
+                tracker.proposal(&proposal, ProposalResult::Rejected);
                 MappedReturn::ProposalRejected(proposal)
             }
         });
@@ -0,0 +1,175 @@
+//! Scheduled maintenance of the validator.
+//!
+//! Once maintenance is scheduled, the validator deactivates itself shortly before the next election block, such that
+//! it still earns the rewards for the current epoch but isn't elected for the next one. Once it is inactive and no
+//! longer elected, the node can be stopped safely. The schedule is persisted, such that the validator can optionally
+//! reactivate itself once the node is restarted after the maintenance.
+use nimiq_database::{
+    traits::{Database, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableProxy,
+};
+use nimiq_primitives::policy::Policy;
+use nimiq_serde::{Deserialize, Serialize};
+use parking_lot::RwLock;
+
+use crate::stats::ValidatorStakingState;
+
+/// The progress of a scheduled maintenance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MaintenanceStatus {
+    /// Waiting for the right time to deactivate the validator before the next election block.
+    Scheduled,
+    /// The deactivate transaction was sent at the given block number, waiting for it to take effect.
+    Deactivating { sent_at: u32 },
+    /// The validator is inactive and no longer elected, the node can be stopped.
+    SafeToStop,
+}
+
+impl MaintenanceStatus {
+    /// Returns the status following this one at `block_number`, given the state of our validator in the staking
+    /// contract and whether it is elected in the current epoch. Additionally returns whether the deactivate
+    /// transaction must be sent now.
+    ///
+    /// The validator is deactivated within the last batch before the election block, to keep earning rewards for
+    /// this epoch. If the transaction isn't included within a batch, it is sent again.
+    pub fn next(
+        self,
+        staking_state: ValidatorStakingState,
+        elected: bool,
+        block_number: u32,
+    ) -> (MaintenanceStatus, bool) {
+        let deactivate_now = !elected
+            || Policy::election_block_after(block_number) - block_number
+                <= Policy::blocks_per_batch();
+
+        match (self, staking_state) {
+            (_, ValidatorStakingState::Unknown) => (self, false),
+            (_, ValidatorStakingState::Inactive(_) | ValidatorStakingState::NoStake)
+                if !elected =>
+            {
+                (MaintenanceStatus::SafeToStop, false)
+            }
+            (MaintenanceStatus::Scheduled, ValidatorStakingState::Active) if deactivate_now => (
+                MaintenanceStatus::Deactivating {
+                    sent_at: block_number,
+                },
+                true,
+            ),
+            (MaintenanceStatus::Deactivating { sent_at }, ValidatorStakingState::Active)
+                if deactivate_now && block_number >= sent_at + Policy::blocks_per_batch() =>
+            {
+                (
+                    MaintenanceStatus::Deactivating {
+                        sent_at: block_number,
+                    },
+                    true,
+                )
+            }
+            (status, _) => (status, false),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Maintenance {
+    pub status: MaintenanceStatus,
+    /// Whether to reactivate the validator once the node is restarted after the maintenance.
+    pub reactivate_after_restart: bool,
+}
+
+/// The persisted maintenance schedule of the validator, shared between the validator and the RPC server.
+pub struct MaintenanceSchedule {
+    env: DatabaseProxy,
+    table: TableProxy,
+    maintenance: RwLock<Option<Maintenance>>,
+}
+
+impl MaintenanceSchedule {
+    const TABLE_NAME: &'static str = "ValidatorMaintenance";
+    const KEY: &'static str = "maintenance";
+
+    pub fn new(env: DatabaseProxy) -> Self {
+        let table = env.open_table(Self::TABLE_NAME.to_string());
+        let maintenance = env
+            .read_transaction()
+            .get::<str, Vec<u8>>(&table, Self::KEY)
+            .and_then(|bytes| match Maintenance::deserialize_from_vec(&bytes) {
+                Ok(maintenance) => Some(maintenance),
+                Err(error) => {
+                    warn!(%error, "Dropping invalid maintenance schedule");
+                    None
+                }
+            });
+        Self {
+            env,
+            table,
+            maintenance: RwLock::new(maintenance),
+        }
+    }
+
+    /// Returns the scheduled maintenance, if any.
+    pub fn get(&self) -> Option<Maintenance> {
+        self.maintenance.read().clone()
+    }
+
+    /// Schedules maintenance. An already scheduled maintenance keeps its progress.
+    pub fn schedule(&self, reactivate_after_restart: bool) {
+        self.update(|maintenance| {
+            Some(Maintenance {
+                status: maintenance.map_or(MaintenanceStatus::Scheduled, |maintenance| {
+                    maintenance.status
+                }),
+                reactivate_after_restart,
+            })
+        });
+    }
+
+    /// Cancels the scheduled maintenance, returning whether there was any.
+    pub fn cancel(&self) -> bool {
+        self.update(|_| None).is_some()
+    }
+
+    /// Returns whether the validator must be reactivated because the node was restarted after the maintenance.
+    /// In that case, the maintenance is over and removed from the schedule.
+    pub fn finish_after_restart(&self) -> bool {
+        let finished = self.get().map_or(false, |maintenance| {
+            maintenance.status == MaintenanceStatus::SafeToStop
+                && maintenance.reactivate_after_restart
+        });
+        if finished {
+            self.cancel();
+        }
+        finished
+    }
+
+    /// Records the progress of the scheduled maintenance, if any.
+    pub fn set_status(&self, status: MaintenanceStatus) {
+        self.update(|maintenance| {
+            maintenance.map(|maintenance| Maintenance {
+                status,
+                ..maintenance
+            })
+        });
+    }
+
+    /// Replaces the maintenance by the result of `f` and persists it, returning the previous one.
+    fn update<F>(&self, f: F) -> Option<Maintenance>
+    where
+        F: FnOnce(Option<Maintenance>) -> Option<Maintenance>,
+    {
+        let mut current = self.maintenance.write();
+        let previous = current.clone();
+        let maintenance = f(current.take());
+
+        let mut txn = self.env.write_transaction();
+        match maintenance {
+            Some(ref maintenance) => {
+                txn.put::<str, Vec<u8>>(&self.table, Self::KEY, &maintenance.serialize_to_vec())
+            }
+            None => txn.remove(&self.table, Self::KEY),
+        }
+        txn.commit();
+        *current = maintenance;
+        previous
+    }
+}
@@ -8,9 +8,13 @@ use std::{
 
 use futures::{future::BoxFuture, ready, FutureExt, Stream};
 use nimiq_block::{Block, ForkProof, MicroBlock, SkipBlockInfo};
-use nimiq_block_production::BlockProducer;
+use nimiq_block_production::{
+    signer::{SignerError, ValidatorSigner},
+    BlockProducer,
+};
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+use nimiq_handel::diagnostics::HandelDiagnostics;
 use nimiq_mempool::mempool::Mempool;
 use nimiq_utils::time::systemtime_to_timestamp;
 use nimiq_validator_network::ValidatorNetwork;
@@ -18,7 +22,10 @@ use nimiq_vrf::VrfSeed;
 use parking_lot::RwLock;
 use tokio::time;
 
-use crate::aggregation::skip_block::SkipBlockAggregation;
+use crate::{
+    aggregation::skip_block::SkipBlockAggregation,
+    signing_history::{HistoryCheckedSigner, SigningHistory, SigningSlot},
+};
 
 // Ignoring this clippy warning since size difference is not that much (320
 // bytes) and we probably don't want the performance penalty of the allocation.
@@ -32,7 +39,9 @@ struct NextProduceMicroBlockEvent<TValidatorNetwork> {
     blockchain: Arc<RwLock<Blockchain>>,
     mempool: Arc<Mempool>,
     network: Arc<TValidatorNetwork>,
-    block_producer: BlockProducer,
+    signer: Arc<dyn ValidatorSigner>,
+    signing_history: Arc<SigningHistory>,
+    skip_block_diagnostics: Arc<HandelDiagnostics>,
     validator_slot_band: u16,
     fork_proofs: Vec<ForkProof>,
     prev_seed: VrfSeed,
@@ -49,7 +58,9 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
         blockchain: Arc<RwLock<Blockchain>>,
         mempool: Arc<Mempool>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn ValidatorSigner>,
+        signing_history: Arc<SigningHistory>,
+        skip_block_diagnostics: Arc<HandelDiagnostics>,
         validator_slot_band: u16,
         fork_proofs: Vec<ForkProof>,
         prev_seed: VrfSeed,
@@ -61,7 +72,9 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             blockchain,
             mempool,
             network,
-            block_producer,
+            signer,
+            signing_history,
+            skip_block_diagnostics,
             validator_slot_band,
             fork_proofs,
             prev_seed,
@@ -111,7 +124,17 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
                             self.block_number,
                         );
 
-                        let block = self.produce_micro_block(&blockchain);
+                        let block = match self.produce_micro_block(&blockchain) {
+                            Ok(block) => block,
+                            Err(error) => {
+                                error!(
+                                    block_number = self.block_number,
+                                    %error,
+                                    "Failed to sign our micro block"
+                                );
+                                break Some(None);
+                            }
+                        };
                         let num_transactions = block
                             .body
                             .as_ref()
@@ -199,14 +222,32 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             vrf_entropy: self.prev_seed.entropy(),
         };
 
-        let (_, skip_block_proof) = SkipBlockAggregation::start(
+        // Never vote for two different skip blocks at the same block number.
+        let signer = HistoryCheckedSigner::new(
+            Arc::clone(&self.signer),
+            Arc::clone(&self.signing_history),
+            SigningSlot::skip_block(self.block_number),
+        );
+        let skip_block_proof = match SkipBlockAggregation::start(
             skip_block_info.clone(),
-            self.block_producer.voting_key.clone(),
+            Arc::new(signer),
             self.validator_slot_band,
             active_validators.unwrap(),
             Arc::clone(&self.network),
+            Arc::clone(&self.skip_block_diagnostics),
         )
-        .await;
+        .await
+        {
+            Ok((_, skip_block_proof)) => skip_block_proof,
+            Err(error) => {
+                error!(
+                    block_number = self.block_number,
+                    %error,
+                    "Failed to sign our skip block vote"
+                );
+                return (None, self);
+            }
+        };
 
         let result = {
             // Acquire blockchain.upgradable_read() to prevent further changes to the blockchain while
@@ -219,28 +260,39 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             } else {
                 let timestamp = head.timestamp() + self.producer_timeout.as_millis() as u64;
 
-                let block = self.block_producer.next_micro_block(
+                match BlockProducer::next_micro_block_with_signer(
+                    &*self.signer,
                     &blockchain,
                     timestamp,
                     vec![],
                     vec![],
                     vec![], // TODO: Allow validators to set extra data field.
                     Some(skip_block_proof),
-                );
-
-                let block1 = block.clone();
+                ) {
+                    Ok(block) => {
+                        let block1 = block.clone();
 
-                // Use a trusted push since these blocks were generated by this validator
-                let result = if cfg!(feature = "trusted_push") {
-                    Blockchain::trusted_push(blockchain, Block::Micro(block))
-                } else {
-                    Blockchain::push(blockchain, Block::Micro(block))
-                };
+                        // Use a trusted push since these blocks were generated by this validator
+                        let result = if cfg!(feature = "trusted_push") {
+                            Blockchain::trusted_push(blockchain, Block::Micro(block))
+                        } else {
+                            Blockchain::push(blockchain, Block::Micro(block))
+                        };
 
-                if let Err(e) = &result {
-                    error!("Failed to push our own block onto the chain: {:?}", e);
+                        if let Err(e) = &result {
+                            error!("Failed to push our own block onto the chain: {:?}", e);
+                        }
+                        Some((result, block1))
+                    }
+                    Err(error) => {
+                        error!(
+                            block_number = self.block_number,
+                            %error,
+                            "Failed to produce our skip block"
+                        );
+                        None
+                    }
                 }
-                Some((result, block1))
             }
         };
 
@@ -275,7 +327,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
         }
     }
 
-    fn produce_micro_block(&self, blockchain: &Blockchain) -> MicroBlock {
+    fn produce_micro_block(&self, blockchain: &Blockchain) -> Result<MicroBlock, SignerError> {
         let timestamp = u64::max(
             blockchain.timestamp(),
             systemtime_to_timestamp(SystemTime::now()),
@@ -296,7 +348,15 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
 
         transactions.append(&mut regular_transactions);
 
-        self.block_producer.next_micro_block(
+        // Never sign two different micro blocks at the same block number.
+        let signer = HistoryCheckedSigner::new(
+            Arc::clone(&self.signer),
+            Arc::clone(&self.signing_history),
+            SigningSlot::micro_block(self.block_number),
+        );
+
+        BlockProducer::next_micro_block_with_signer(
+            &signer,
             blockchain,
             timestamp,
             self.fork_proofs.clone(),
@@ -335,7 +395,9 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
         blockchain: Arc<RwLock<Blockchain>>,
         mempool: Arc<Mempool>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn ValidatorSigner>,
+        signing_history: Arc<SigningHistory>,
+        skip_block_diagnostics: Arc<HandelDiagnostics>,
         validator_slot_band: u16,
         fork_proofs: Vec<ForkProof>,
         prev_seed: VrfSeed,
@@ -347,7 +409,9 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
             blockchain,
             mempool,
             network,
-            block_producer,
+            signer,
+            signing_history,
+            skip_block_diagnostics,
             validator_slot_band,
             fork_proofs,
             prev_seed,
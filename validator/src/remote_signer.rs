@@ -0,0 +1,244 @@
+//! Signer that forwards the signing requests of the validator to an external signer process over a Unix
+//! domain socket. This allows the validator keys to be held by a separate, hardened process.
+//!
+//! Requests and responses are serialized [`SignerRequest`]s and [`SignerResponse`]s, each prefixed with its
+//! length as big-endian `u32`. The signer process answers the requests of a connection in order.
+//!
+//! The signer is called synchronously, often while the blockchain lock is held. When running on a multi-threaded
+//! Tokio runtime, the socket IO is done in [`block_in_place`](tokio::task::block_in_place), such that the other
+//! tasks of the worker are moved to other threads while we wait for the signer process.
+use std::{
+    io::{self, Read, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use nimiq_block_production::signer::{SignerError, ValidatorSigner};
+use nimiq_bls::{PublicKey as BlsPublicKey, SigHash, Signature as BlsSignature};
+use nimiq_keys::{PublicKey as SchnorrPublicKey, Signature as SchnorrSignature};
+use nimiq_serde::{Deserialize as _, Serialize as _};
+use nimiq_vrf::VrfSeed;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// Maximum size of a request or response. Only hashes and public keys are exchanged, so this is plenty.
+const MAX_MESSAGE_SIZE: u32 = 64 * 1024;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum SignerRequest {
+    /// Requests the public keys of the signing and voting key.
+    PublicKeys,
+    /// Requests a signature of the data with the signing key.
+    Sign(Vec<u8>),
+    /// Requests the VRF seed following the given seed.
+    SignNextSeed(VrfSeed),
+    /// Requests a signature of the hash with the voting key.
+    Vote(SigHash),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum SignerResponse {
+    PublicKeys {
+        signing_key: SchnorrPublicKey,
+        voting_key: BlsPublicKey,
+    },
+    Signature(SchnorrSignature),
+    Seed(VrfSeed),
+    Vote(BlsSignature),
+    /// The signer refused or failed to handle the request.
+    Error(String),
+}
+
+fn write_message<M: Serialize>(stream: &mut UnixStream, message: &M) -> io::Result<()> {
+    let data = message.serialize_to_vec();
+    stream.write_all(&(data.len() as u32).to_be_bytes())?;
+    stream.write_all(&data)?;
+    stream.flush()
+}
+
+fn read_message<M: DeserializeOwned>(stream: &mut UnixStream) -> io::Result<M> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len);
+    if len > MAX_MESSAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Message too large: {len} bytes"),
+        ));
+    }
+
+    let mut data = vec![0u8; len as usize];
+    stream.read_exact(&mut data)?;
+    M::deserialize_from_vec(&data)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// Signer that talks to an external signer process listening on a Unix domain socket.
+///
+/// The public keys are requested once when connecting. If a request fails, the connection is re-established for
+/// the next request.
+pub struct RemoteSigner {
+    path: PathBuf,
+    timeout: Duration,
+    stream: Mutex<Option<UnixStream>>,
+    signing_public_key: SchnorrPublicKey,
+    voting_public_key: BlsPublicKey,
+}
+
+impl RemoteSigner {
+    /// Timeout for a single request to the signer process.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// Connects to the signer process listening on `path` and fetches its public keys.
+    pub fn connect<P: AsRef<Path>>(path: P) -> Result<Self, SignerError> {
+        Self::connect_with_timeout(path, Self::DEFAULT_TIMEOUT)
+    }
+
+    /// Connects to the signer process listening on `path` and fetches its public keys. Each request must be
+    /// answered within `timeout`.
+    pub fn connect_with_timeout<P: AsRef<Path>>(
+        path: P,
+        timeout: Duration,
+    ) -> Result<Self, SignerError> {
+        let path = path.as_ref().to_path_buf();
+        let mut stream = Self::open(&path, timeout)?;
+
+        write_message(&mut stream, &SignerRequest::PublicKeys)?;
+        match read_message(&mut stream)? {
+            SignerResponse::PublicKeys {
+                signing_key,
+                voting_key,
+            } => Ok(Self {
+                path,
+                timeout,
+                stream: Mutex::new(Some(stream)),
+                signing_public_key: signing_key,
+                voting_public_key: voting_key,
+            }),
+            SignerResponse::Error(error) => Err(SignerError::Refused(error)),
+            _ => Err(SignerError::InvalidResponse),
+        }
+    }
+
+    fn open(path: &Path, timeout: Duration) -> io::Result<UnixStream> {
+        let stream = UnixStream::connect(path)?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+        Ok(stream)
+    }
+
+    fn request(&self, request: &SignerRequest) -> Result<SignerResponse, SignerError> {
+        // Don't block the worker thread of the runtime while waiting for the signer process.
+        match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| self.request_blocking(request))
+            }
+            _ => self.request_blocking(request),
+        }
+    }
+
+    fn request_blocking(&self, request: &SignerRequest) -> Result<SignerResponse, SignerError> {
+        let mut stream = self.stream.lock();
+        let connection = match stream.as_mut() {
+            Some(connection) => connection,
+            None => stream.insert(Self::open(&self.path, self.timeout)?),
+        };
+
+        let response = write_message(connection, request).and_then(|_| read_message(connection));
+        match response {
+            Ok(SignerResponse::Error(error)) => Err(SignerError::Refused(error)),
+            Ok(response) => Ok(response),
+            Err(error) => {
+                // The state of the connection is unknown, so reconnect on the next request.
+                *stream = None;
+                Err(error.into())
+            }
+        }
+    }
+}
+
+impl ValidatorSigner for RemoteSigner {
+    fn signing_public_key(&self) -> SchnorrPublicKey {
+        self.signing_public_key
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        self.voting_public_key
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<SchnorrSignature, SignerError> {
+        match self.request(&SignerRequest::Sign(data.to_vec()))? {
+            SignerResponse::Signature(signature)
+                if self.signing_public_key.verify(&signature, data) =>
+            {
+                Ok(signature)
+            }
+            SignerResponse::Signature(_) => Err(SignerError::InvalidSignature),
+            _ => Err(SignerError::InvalidResponse),
+        }
+    }
+
+    fn sign_next_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError> {
+        match self.request(&SignerRequest::SignNextSeed(prev_seed.clone()))? {
+            SignerResponse::Seed(seed)
+                if seed.verify(prev_seed, &self.signing_public_key).is_ok() =>
+            {
+                Ok(seed)
+            }
+            SignerResponse::Seed(_) => Err(SignerError::InvalidSignature),
+            _ => Err(SignerError::InvalidResponse),
+        }
+    }
+
+    fn vote(&self, hash: SigHash) -> Result<BlsSignature, SignerError> {
+        match self.request(&SignerRequest::Vote(hash.clone()))? {
+            SignerResponse::Vote(signature)
+                if self.voting_public_key.verify_hash(hash, &signature) =>
+            {
+                Ok(signature)
+            }
+            SignerResponse::Vote(_) => Err(SignerError::InvalidSignature),
+            _ => Err(SignerError::InvalidResponse),
+        }
+    }
+}
+
+/// Answers the requests of a single connection using `signer` until the connection is closed.
+fn handle_connection(stream: &mut UnixStream, signer: &dyn ValidatorSigner) -> io::Result<()> {
+    loop {
+        let request: SignerRequest = match read_message(stream) {
+            Ok(request) => request,
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(error) => return Err(error),
+        };
+
+        let response = match request {
+            SignerRequest::PublicKeys => Ok(SignerResponse::PublicKeys {
+                signing_key: signer.signing_public_key(),
+                voting_key: signer.voting_public_key(),
+            }),
+            SignerRequest::Sign(data) => signer.sign(&data).map(SignerResponse::Signature),
+            SignerRequest::SignNextSeed(prev_seed) => {
+                signer.sign_next_seed(&prev_seed).map(SignerResponse::Seed)
+            }
+            SignerRequest::Vote(hash) => signer.vote(hash).map(SignerResponse::Vote),
+        };
+
+        let response = response.unwrap_or_else(|error| SignerResponse::Error(error.to_string()));
+        write_message(stream, &response)?;
+    }
+}
+
+/// Serves the signing requests of nodes connecting to `listener` using `signer`. This is the counterpart of the
+/// [`RemoteSigner`] and can be used to implement a signer process. Connections are served one at a time.
+pub fn serve_signer(listener: UnixListener, signer: &dyn ValidatorSigner) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        if let Err(error) = handle_connection(&mut stream, signer) {
+            warn!(%error, "Signer connection failed");
+        }
+    }
+    Ok(())
+}
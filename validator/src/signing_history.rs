@@ -0,0 +1,392 @@
+//! Signing history of the validator.
+//!
+//! Before the validator signs a micro block, a skip block vote or a Tendermint vote, the payload is checked against
+//! the history of everything it signed before and recorded. Signing a different payload for the same slot is
+//! refused, since it would equivocate, e.g. after restoring an old backup of the node or when the same validator
+//! accidentally runs on two machines. For micro blocks, this would lead to a fork proof and jail the validator.
+//!
+//! The history can be exported and imported to migrate a validator to a different machine.
+//!
+//! The history is local to a node, thus it doesn't protect against conflicting messages signed by another node
+//! running the same validator. Hot standbys are coordinated with a lease instead, see [`crate::lease`].
+use std::{convert::TryInto, sync::Arc};
+
+use nimiq_block_production::signer::{SignerError, ValidatorSigner};
+use nimiq_bls::{PublicKey as BlsPublicKey, SigHash, Signature as BlsSignature};
+use nimiq_database::{
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableProxy,
+};
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
+use nimiq_keys::{PublicKey as SchnorrPublicKey, Signature as SchnorrSignature};
+use nimiq_vrf::VrfSeed;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Kind of message signed by the validator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum SignedMessageKind {
+    MicroBlock = 0,
+    SkipBlock = 1,
+    Prevote = 2,
+    Precommit = 3,
+}
+
+impl TryFrom<u8> for SignedMessageKind {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::MicroBlock),
+            1 => Ok(Self::SkipBlock),
+            2 => Ok(Self::Prevote),
+            3 => Ok(Self::Precommit),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A slot in which the validator must sign at most one payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SigningSlot {
+    pub kind: SignedMessageKind,
+    pub block_number: u32,
+    /// The Tendermint round. Always 0 for micro blocks and skip blocks.
+    pub round: u32,
+}
+
+impl SigningSlot {
+    const KEY_SIZE: usize = 1 + 4 + 4;
+
+    pub fn micro_block(block_number: u32) -> Self {
+        Self {
+            kind: SignedMessageKind::MicroBlock,
+            block_number,
+            round: 0,
+        }
+    }
+
+    pub fn skip_block(block_number: u32) -> Self {
+        Self {
+            kind: SignedMessageKind::SkipBlock,
+            block_number,
+            round: 0,
+        }
+    }
+
+    pub fn prevote(block_number: u32, round: u32) -> Self {
+        Self {
+            kind: SignedMessageKind::Prevote,
+            block_number,
+            round,
+        }
+    }
+
+    pub fn precommit(block_number: u32, round: u32) -> Self {
+        Self {
+            kind: SignedMessageKind::Precommit,
+            block_number,
+            round,
+        }
+    }
+
+    fn to_key(self) -> Vec<u8> {
+        let mut key = Vec::with_capacity(Self::KEY_SIZE);
+        key.push(self.kind as u8);
+        key.extend_from_slice(&self.block_number.to_be_bytes());
+        key.extend_from_slice(&self.round.to_be_bytes());
+        key
+    }
+
+    fn from_key(key: &[u8]) -> Option<Self> {
+        if key.len() != Self::KEY_SIZE {
+            return None;
+        }
+        Some(Self {
+            kind: key[0].try_into().ok()?,
+            block_number: u32::from_be_bytes(key[1..5].try_into().unwrap()),
+            round: u32::from_be_bytes(key[5..9].try_into().unwrap()),
+        })
+    }
+}
+
+/// The hash of the data signed by the validator in a slot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningRecord {
+    pub slot: SigningSlot,
+    pub hash: Blake2bHash,
+}
+
+/// Format in which the signing history is exported and imported.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningHistoryExport {
+    pub version: u8,
+    pub records: Vec<SigningRecord>,
+}
+
+impl SigningHistoryExport {
+    pub const VERSION: u8 = 1;
+}
+
+#[derive(Debug, Error)]
+pub enum SigningHistoryError {
+    #[error("Refusing to sign {requested} for {slot:?}, already signed {existing}")]
+    Conflict {
+        slot: SigningSlot,
+        existing: Blake2bHash,
+        requested: Blake2bHash,
+    },
+    #[error("Refusing to sign for {slot:?}, only allowed to sign for blocks after {watermark} and up to {limit:?}")]
+    OutOfRange {
+        slot: SigningSlot,
+        watermark: u32,
+        limit: Option<u32>,
+    },
+    #[error("Unsupported signing history version: {0}")]
+    UnsupportedVersion(u8),
+}
+
+/// Persistent history of the payloads signed by the validator.
+///
+/// Additionally, signing can be restricted to a range of block numbers. This is used to hand over signing between
+/// an active node and a standby, see [`crate::lease`].
+pub struct SigningHistory {
+    env: DatabaseProxy,
+    table: TableProxy,
+    /// Holds the persisted watermark, separately from the records.
+    watermark_table: TableProxy,
+    /// Highest block number we must not sign anything for, persisted in the watermark table.
+    watermark: RwLock<u32>,
+    /// Highest block number we may sign something for, if restricted.
+    signing_limit: RwLock<Option<u32>>,
+}
+
+impl SigningHistory {
+    const TABLE_NAME: &'static str = "ValidatorSigningHistory";
+    const WATERMARK_TABLE_NAME: &'static str = "ValidatorSigningWatermark";
+    const WATERMARK_KEY: &'static str = "watermark";
+
+    pub fn new(env: DatabaseProxy) -> Self {
+        let table = env.open_table(Self::TABLE_NAME.to_string());
+        let watermark_table = env.open_table(Self::WATERMARK_TABLE_NAME.to_string());
+        let watermark = env
+            .read_transaction()
+            .get(&watermark_table, Self::WATERMARK_KEY)
+            .unwrap_or_default();
+        Self {
+            env,
+            table,
+            watermark_table,
+            watermark: RwLock::new(watermark),
+            signing_limit: RwLock::new(None),
+        }
+    }
+
+    /// Returns the highest block number we must not sign anything for.
+    pub fn watermark(&self) -> u32 {
+        *self.watermark.read()
+    }
+
+    /// Refuses signing anything for blocks up to `block_number` from now on. The watermark is never lowered.
+    pub fn raise_watermark(&self, block_number: u32) {
+        let mut watermark = self.watermark.write();
+        if block_number <= *watermark {
+            return;
+        }
+
+        let mut txn = self.env.write_transaction();
+        txn.put(&self.watermark_table, Self::WATERMARK_KEY, &block_number);
+        txn.commit();
+        *watermark = block_number;
+    }
+
+    /// Restricts signing to blocks up to `block_number`, or lifts the restriction if `None`.
+    pub fn set_signing_limit(&self, block_number: Option<u32>) {
+        *self.signing_limit.write() = block_number;
+    }
+
+    /// Returns the highest block number we signed anything for.
+    pub fn highest_block_number(&self) -> Option<u32> {
+        self.records()
+            .iter()
+            .map(|record| record.slot.block_number)
+            .max()
+    }
+
+    /// Returns the hash of the payload signed in `slot`, if any.
+    pub fn get(&self, slot: SigningSlot) -> Option<Blake2bHash> {
+        self.env.read_transaction().get(&self.table, &slot.to_key())
+    }
+
+    /// Returns whether anything has been signed in `slot`.
+    pub fn has_signed(&self, slot: SigningSlot) -> bool {
+        self.get(slot).is_some()
+    }
+
+    /// Records that the payload with the given `hash` is about to be signed in `slot`. This must be called before
+    /// the payload is signed and fails if a different payload has already been signed in the slot. Signing the
+    /// same payload again is allowed.
+    pub fn check_and_record(
+        &self,
+        slot: SigningSlot,
+        hash: Blake2bHash,
+    ) -> Result<(), SigningHistoryError> {
+        let watermark = self.watermark();
+        let limit = *self.signing_limit.read();
+        if slot.block_number <= watermark || limit.map_or(false, |limit| slot.block_number > limit)
+        {
+            return Err(SigningHistoryError::OutOfRange {
+                slot,
+                watermark,
+                limit,
+            });
+        }
+
+        let key = slot.to_key();
+        let mut txn = self.env.write_transaction();
+        match txn.get::<_, Blake2bHash>(&self.table, &key) {
+            Some(existing) if existing == hash => Ok(()),
+            Some(existing) => Err(SigningHistoryError::Conflict {
+                slot,
+                existing,
+                requested: hash,
+            }),
+            None => {
+                txn.put(&self.table, &key, &hash);
+                txn.commit();
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns all records ordered by kind and slot.
+    pub fn records(&self) -> Vec<SigningRecord> {
+        let txn = self.env.read_transaction();
+        txn.cursor(&self.table)
+            .into_iter_start::<Vec<u8>, Vec<u8>>()
+            .filter_map(|(key, hash)| {
+                SigningSlot::from_key(&key).map(|slot| SigningRecord {
+                    slot,
+                    hash: Blake2bHash::from(&hash[..]),
+                })
+            })
+            .collect()
+    }
+
+    /// Removes all records for blocks before `block_number`. This is safe for finalized blocks, since there can't
+    /// be any conflicting payloads for them anymore.
+    pub fn prune(&self, block_number: u32) {
+        let keys: Vec<_> = self
+            .records()
+            .into_iter()
+            .filter(|record| record.slot.block_number < block_number)
+            .map(|record| record.slot.to_key())
+            .collect();
+        if keys.is_empty() {
+            return;
+        }
+
+        let mut txn = self.env.write_transaction();
+        for key in keys {
+            txn.remove(&self.table, &key);
+        }
+        txn.commit();
+    }
+
+    /// Exports the signing history, e.g. to migrate the validator to a different machine.
+    pub fn export(&self) -> SigningHistoryExport {
+        SigningHistoryExport {
+            version: SigningHistoryExport::VERSION,
+            records: self.records(),
+        }
+    }
+
+    /// Imports an exported signing history and returns the number of new records. The import is aborted without
+    /// any changes if a record conflicts with the existing history.
+    pub fn import(&self, history: &SigningHistoryExport) -> Result<usize, SigningHistoryError> {
+        if history.version != SigningHistoryExport::VERSION {
+            return Err(SigningHistoryError::UnsupportedVersion(history.version));
+        }
+
+        let mut txn = self.env.write_transaction();
+        let mut imported = 0;
+        for record in &history.records {
+            let key = record.slot.to_key();
+            match txn.get::<_, Blake2bHash>(&self.table, &key) {
+                Some(existing) if existing == record.hash => {}
+                Some(existing) => {
+                    txn.abort();
+                    return Err(SigningHistoryError::Conflict {
+                        slot: record.slot,
+                        existing,
+                        requested: record.hash.clone(),
+                    });
+                }
+                None => {
+                    txn.put(&self.table, &key, &record.hash);
+                    imported += 1;
+                }
+            }
+        }
+        txn.commit();
+
+        Ok(imported)
+    }
+}
+
+/// Signer that checks the data it signs against the signing history for the given slot and records it. If a
+/// different payload has already been signed in the slot, signing is refused with [`SignerError::Refused`].
+///
+/// VRF seeds are not checked, since they are deterministic and thus can't conflict.
+pub(crate) struct HistoryCheckedSigner {
+    signer: Arc<dyn ValidatorSigner>,
+    history: Arc<SigningHistory>,
+    slot: SigningSlot,
+}
+
+impl HistoryCheckedSigner {
+    pub fn new(
+        signer: Arc<dyn ValidatorSigner>,
+        history: Arc<SigningHistory>,
+        slot: SigningSlot,
+    ) -> Self {
+        Self {
+            signer,
+            history,
+            slot,
+        }
+    }
+
+    fn check_and_record(&self, data: &[u8]) -> Result<(), SignerError> {
+        let hash = Blake2bHasher::default().digest(data);
+        self.history
+            .check_and_record(self.slot, hash)
+            .map_err(|error| SignerError::Refused(error.to_string()))
+    }
+}
+
+impl ValidatorSigner for HistoryCheckedSigner {
+    fn signing_public_key(&self) -> SchnorrPublicKey {
+        self.signer.signing_public_key()
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        self.signer.voting_public_key()
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<SchnorrSignature, SignerError> {
+        self.check_and_record(data)?;
+        self.signer.sign(data)
+    }
+
+    fn sign_next_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError> {
+        self.signer.sign_next_seed(prev_seed)
+    }
+
+    fn vote(&self, hash: SigHash) -> Result<BlsSignature, SignerError> {
+        self.check_and_record(hash.as_slice())?;
+        self.signer.vote(hash)
+    }
+}
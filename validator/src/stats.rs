@@ -0,0 +1,313 @@
+//! Status and performance statistics of our validator, e.g. for monitoring.
+use std::ops::Range;
+
+use nimiq_block::{Block, MicroJustification};
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_collections::BitSet;
+use nimiq_keys::Address;
+use nimiq_primitives::{coin::Coin, policy::Policy, slots_allocation::Validators};
+use thiserror::Error;
+
+/// The state of our validator in the staking contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidatorStakingState {
+    Active,
+    /// Inactive, with the block number the validator is jailed until, if it is jailed.
+    Inactive(Option<u32>),
+    NoStake,
+    /// The staking contract is not available, e.g. because we don't have consensus yet.
+    Unknown,
+}
+
+impl ValidatorStakingState {
+    pub fn of(blockchain: &Blockchain, validator_address: &Address) -> Self {
+        let staking_contract = match blockchain.get_staking_contract_if_complete(None) {
+            Some(contract) => contract,
+            None => return ValidatorStakingState::Unknown,
+        };
+
+        // Then fetch the validator to see if it is active.
+        let data_store = blockchain.get_staking_contract_store();
+        let txn = blockchain.read_transaction();
+        staking_contract
+            .get_validator(&data_store.read(&txn), validator_address)
+            .map_or(
+                ValidatorStakingState::NoStake,
+                |validator| match validator.inactive_since {
+                    Some(_) => ValidatorStakingState::Inactive(validator.jail_release),
+                    None => ValidatorStakingState::Active,
+                },
+            )
+    }
+}
+
+/// The current status of our validator.
+#[derive(Clone, Debug)]
+pub struct ValidatorStatus {
+    pub address: Address,
+    pub block_number: u32,
+    pub epoch_number: u32,
+    pub staking_state: ValidatorStakingState,
+    /// Our slot band in the current epoch, if we are elected.
+    pub slot_band: Option<u16>,
+    /// The slots we own in the current epoch. Empty if we are not elected.
+    pub slots: Range<u16>,
+}
+
+impl ValidatorStatus {
+    pub fn new(blockchain: &Blockchain, validator_address: &Address) -> Self {
+        let block_number = blockchain.block_number();
+        let validators = blockchain.current_validators();
+        let slot_band = validators
+            .as_ref()
+            .and_then(|validators| validators.validator_map.get(validator_address).copied());
+        let slots = validators
+            .as_ref()
+            .and_then(|validators| validators.get_validator_by_address(validator_address))
+            .map_or(0..0, |validator| validator.slots.clone());
+
+        ValidatorStatus {
+            address: validator_address.clone(),
+            block_number,
+            epoch_number: Policy::epoch_at(block_number),
+            staking_state: ValidatorStakingState::of(blockchain, validator_address),
+            slot_band,
+            slots,
+        }
+    }
+}
+
+/// Counts our contributions to the blocks of an epoch. The counts only cover the blocks this node saw being
+/// added to the chain, starting at `since_block_number`.
+#[derive(Clone, Debug, Default)]
+pub struct EpochStats {
+    pub epoch_number: u32,
+    /// The first block covered by these counts.
+    pub since_block_number: u32,
+    /// Micro blocks we produced.
+    pub produced_micro_blocks: u32,
+    /// Micro blocks we were supposed to produce that were skipped.
+    pub missed_micro_blocks: u32,
+    /// Skip blocks our slots signed.
+    pub skip_blocks_contributed: u32,
+    /// Macro blocks our slots precommitted to in the round they were finalized in.
+    pub tendermint_rounds_participated: u32,
+    /// Our slots in this epoch.
+    slots: Option<Range<u16>>,
+}
+
+#[derive(Default)]
+struct BlockContribution {
+    produced: u32,
+    missed: u32,
+    skip_block: u32,
+    tendermint: u32,
+}
+
+impl EpochStats {
+    pub fn new(epoch_number: u32, since_block_number: u32) -> Self {
+        EpochStats {
+            epoch_number,
+            since_block_number,
+            ..Default::default()
+        }
+    }
+
+    /// Counts our contributions to a block that was added to the chain.
+    pub(crate) fn apply_block(
+        &mut self,
+        blockchain: &Blockchain,
+        validator_address: &Address,
+        block: &Block,
+    ) {
+        let epoch_number = block.epoch_number();
+        if epoch_number != self.epoch_number {
+            *self = EpochStats::new(epoch_number, block.block_number());
+        }
+
+        let contribution = self.contribution(blockchain, validator_address, block);
+        self.produced_micro_blocks += contribution.produced;
+        self.missed_micro_blocks += contribution.missed;
+        self.skip_blocks_contributed += contribution.skip_block;
+        self.tendermint_rounds_participated += contribution.tendermint;
+    }
+
+    /// Removes our contributions to a block that was reverted.
+    pub(crate) fn revert_block(
+        &mut self,
+        blockchain: &Blockchain,
+        validator_address: &Address,
+        block: &Block,
+    ) {
+        if block.epoch_number() != self.epoch_number
+            || block.block_number() < self.since_block_number
+        {
+            return;
+        }
+
+        let contribution = self.contribution(blockchain, validator_address, block);
+        self.produced_micro_blocks = self
+            .produced_micro_blocks
+            .saturating_sub(contribution.produced);
+        self.missed_micro_blocks = self.missed_micro_blocks.saturating_sub(contribution.missed);
+        self.skip_blocks_contributed = self
+            .skip_blocks_contributed
+            .saturating_sub(contribution.skip_block);
+        self.tendermint_rounds_participated = self
+            .tendermint_rounds_participated
+            .saturating_sub(contribution.tendermint);
+    }
+
+    fn contribution(
+        &mut self,
+        blockchain: &Blockchain,
+        validator_address: &Address,
+        block: &Block,
+    ) -> BlockContribution {
+        let slots = self
+            .slots
+            .get_or_insert_with(|| {
+                blockchain
+                    .get_validators_for_epoch(block.epoch_number(), None)
+                    .ok()
+                    .and_then(|validators| {
+                        validators
+                            .get_validator_by_address(validator_address)
+                            .map(|validator| validator.slots.clone())
+                    })
+                    .unwrap_or(0..0)
+            })
+            .clone();
+        if slots.is_empty() {
+            return BlockContribution::default();
+        }
+        let signed_by_us =
+            |signers: &BitSet| slots.clone().any(|slot| signers.contains(slot as usize));
+
+        let mut contribution = BlockContribution::default();
+        match block {
+            Block::Micro(micro_block) => {
+                // Both micro and skip blocks are at the slot of the proposer selected with the block number as
+                // offset. The proposer depends on the seed of the parent, which might not be on the main chain
+                // anymore if the block is reverted.
+                let is_ours = blockchain
+                    .get_block(block.parent_hash(), false, None)
+                    .and_then(|parent| {
+                        blockchain.get_proposer_at(
+                            block.block_number(),
+                            block.block_number(),
+                            parent.seed().entropy(),
+                            None,
+                        )
+                    })
+                    .map_or(false, |slot| slot.validator.address == *validator_address);
+
+                match micro_block.justification {
+                    Some(MicroJustification::Skip(ref proof)) => {
+                        contribution.missed = is_ours as u32;
+                        contribution.skip_block = signed_by_us(&proof.sig.signers) as u32;
+                    }
+                    _ => contribution.produced = is_ours as u32,
+                }
+            }
+            Block::Macro(macro_block) => {
+                if let Some(ref proof) = macro_block.justification {
+                    contribution.tendermint = signed_by_us(&proof.sig.signers) as u32;
+                }
+            }
+        }
+        contribution
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ValidatorStatsError {
+    #[error("Overflow in the pending reward")]
+    RewardOverflow,
+}
+
+/// Performance statistics of our validator in the current epoch.
+#[derive(Clone, Debug)]
+pub struct ValidatorStats {
+    pub epoch: EpochStats,
+    /// Our slots that are punished in the current batch.
+    pub punished_slots_current_batch: u16,
+    /// Our slots that are punished in the previous batch, which don't receive rewards at the end of the current
+    /// batch.
+    pub punished_slots_previous_batch: u16,
+    /// An estimate of the rewards we receive at the end of the current batch, not accounting for a delay in
+    /// producing the batch.
+    pub pending_reward: Coin,
+}
+
+impl ValidatorStats {
+    pub fn new(
+        blockchain: &Blockchain,
+        validator_address: &Address,
+        epoch: EpochStats,
+    ) -> Result<Self, ValidatorStatsError> {
+        let mut stats = ValidatorStats {
+            epoch,
+            punished_slots_current_batch: 0,
+            punished_slots_previous_batch: 0,
+            pending_reward: Coin::ZERO,
+        };
+        let staking_contract = match blockchain.get_staking_contract_if_complete(None) {
+            Some(contract) => contract,
+            None => return Ok(stats),
+        };
+        let punished_slots = &staking_contract.punished_slots;
+
+        stats.punished_slots_current_batch = punished_slots
+            .current_batch_punished_slots_map()
+            .get(validator_address)
+            .map_or(0, |slots| slots.len() as u16);
+
+        // The rewards at the end of a batch are for the previous batch, thus they are distributed according to the
+        // slots of the previous epoch at the end of the first batch of an epoch.
+        let state = blockchain.state();
+        let next_macro_block = Policy::macro_block_after(blockchain.block_number());
+        let reward_validators: Option<&Validators> =
+            if Policy::first_batch_of_epoch(next_macro_block) {
+                state.previous_slots.as_ref()
+            } else {
+                state.current_slots.as_ref()
+            };
+        let slots = match reward_validators
+            .and_then(|validators| validators.get_validator_by_address(validator_address))
+        {
+            Some(validator) => validator.slots.clone(),
+            None => return Ok(stats),
+        };
+
+        let previous_batch_punished_slots = punished_slots.previous_batch_punished_slots();
+        stats.punished_slots_previous_batch = slots
+            .clone()
+            .filter(|&slot| previous_batch_punished_slots.contains(slot as usize))
+            .count() as u16;
+
+        // Same as the reward computation when finalizing the batch, with the head as the end of the batch.
+        let (genesis_supply, genesis_timestamp) = blockchain.get_genesis_parameters();
+        let genesis_supply = u64::from(genesis_supply);
+        let batch_reward =
+            Policy::supply_at(genesis_supply, genesis_timestamp, blockchain.timestamp())
+                .saturating_sub(Policy::supply_at(
+                    genesis_supply,
+                    genesis_timestamp,
+                    state.macro_info.head.timestamp(),
+                ));
+        let reward_pot = Coin::try_from(batch_reward)
+            .ok()
+            .and_then(|batch_reward| batch_reward.checked_add(state.macro_info.cum_tx_fees))
+            .ok_or(ValidatorStatsError::RewardOverflow)?;
+        let slot_reward = reward_pot / Policy::SLOTS as u64;
+        let eligible_slots =
+            (slots.len() as u64).saturating_sub(stats.punished_slots_previous_batch as u64);
+        stats.pending_reward = slot_reward
+            .checked_mul(eligible_slots)
+            .ok_or(ValidatorStatsError::RewardOverflow)?;
+
+        Ok(stats)
+    }
+}
@@ -3,17 +3,19 @@ use std::sync::Arc;
 use byteorder::WriteBytesExt;
 use futures::{
     future::{self, FutureExt},
-    stream::{BoxStream, StreamExt},
+    stream::{self, BoxStream, StreamExt},
 };
 use nimiq_account::BlockLogger;
 use nimiq_block::{
     Block, MacroBlock, TendermintIdentifier, TendermintProof, TendermintStep, TendermintVote,
 };
-use nimiq_block_production::BlockProducer;
+use nimiq_block_production::{signer::ValidatorSigner, BlockProducer};
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_collections::BitSet;
-use nimiq_handel::{aggregation::Aggregation, identity::IdentityRegistry};
+use nimiq_handel::{
+    aggregation::Aggregation, diagnostics::HandelDiagnostics, identity::IdentityRegistry,
+};
 use nimiq_hash::{Blake2sHash, Blake2sHasher, Hash, Hasher, SerializeContent};
 use nimiq_keys::Signature as SchnorrSignature;
 use nimiq_primitives::{policy::Policy, slots_allocation::Validators};
@@ -38,6 +40,7 @@ use crate::{
         },
     },
     r#macro::ProposalTopic,
+    signing_history::{HistoryCheckedSigner, SigningHistory, SigningSlot},
 };
 
 // A note for the signing of the proposal:
@@ -101,8 +104,12 @@ pub struct TendermintProtocol<TValidatorNetwork: ValidatorNetwork> {
     pub validator_slot_band: u16,
     // The block number of the macro block to produce.
     pub block_height: u32,
-    // Information relative to our validator that is necessary to produce blocks.
-    pub block_producer: BlockProducer,
+    // Signs the blocks and votes of our validator.
+    pub signer: Arc<dyn ValidatorSigner>,
+    // The history of the votes we signed, to never sign two different votes in the same round and step.
+    signing_history: Arc<SigningHistory>,
+    // Records the diagnostics of the vote aggregations.
+    aggregation_diagnostics: Arc<HandelDiagnostics>,
     // The validators for the current epoch.
     pub current_validators: Validators,
     // The main blockchain struct. Contains all of this validator information about the current chain.
@@ -117,7 +124,9 @@ impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidato
             network: Arc::clone(&self.network),
             validator_slot_band: self.validator_slot_band,
             block_height: self.block_height,
-            block_producer: self.block_producer.clone(),
+            signer: Arc::clone(&self.signer),
+            signing_history: Arc::clone(&self.signing_history),
+            aggregation_diagnostics: Arc::clone(&self.aggregation_diagnostics),
             current_validators: self.current_validators.clone(),
             blockchain: Arc::clone(&self.blockchain),
             validator_registry: Arc::clone(&self.validator_registry),
@@ -134,13 +143,17 @@ where
     pub fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn ValidatorSigner>,
+        signing_history: Arc<SigningHistory>,
+        aggregation_diagnostics: Arc<HandelDiagnostics>,
         current_validators: Validators,
         validator_slot_band: u16,
         block_height: u32,
     ) -> Self {
         Self {
-            block_producer,
+            signer,
+            signing_history,
+            aggregation_diagnostics,
             blockchain,
             block_height,
             validator_slot_band,
@@ -222,26 +235,42 @@ where
         proposer_slot.band == self.validator_slot_band
     }
 
-    fn create_proposal(&self, round: u32) -> (ProposalMessage<Self::Proposal>, Self::Inherent) {
+    fn create_proposal(
+        &self,
+        round: u32,
+    ) -> Result<(ProposalMessage<Self::Proposal>, Self::Inherent), ProposalError> {
         let blockchain = self.blockchain.read();
         let time = blockchain.time.now();
 
-        let block = self
-            .block_producer
-            .next_macro_block_proposal(&blockchain, time, round, vec![]);
+        let block = BlockProducer::next_macro_block_proposal_with_signer(
+            &*self.signer,
+            &blockchain,
+            time,
+            round,
+            vec![],
+        )
+        .map_err(|error| {
+            error!(
+                block_number = self.block_height,
+                round,
+                %error,
+                "Failed to sign our macro block proposal"
+            );
+            ProposalError::SigningFailed
+        })?;
 
         // Always `Some(…)` because the above function always sets it to `Some(…)`.
         let body = block.body.expect("produced blocks always have a body");
 
         // Return the block header and body as the proposal.
-        (
+        Ok((
             ProposalMessage {
                 proposal: Header(block.header, None), // Created proposals do not have a PubSubId
                 round,
                 valid_round: None,
             },
             Body(body),
-        )
+        ))
     }
 
     fn broadcast_proposal(
@@ -463,12 +492,19 @@ where
     fn sign_proposal(
         &self,
         proposal_message: &ProposalMessage<Self::Proposal>,
-    ) -> Self::ProposalSignature {
+    ) -> Result<Self::ProposalSignature, ProposalError> {
         let data = Self::hash_proposal(proposal_message);
-        (
-            self.block_producer.signing_key.sign(data.as_slice()),
-            self.validator_slot_band,
-        )
+        let signature = self.signer.sign(data.as_slice()).map_err(|error| {
+            error!(
+                block_number = self.block_height,
+                round = proposal_message.round,
+                %error,
+                "Failed to sign our proposal"
+            );
+            ProposalError::SigningFailed
+        })?;
+
+        Ok((signature, self.validator_slot_band))
     }
 
     fn create_aggregation(
@@ -482,9 +518,15 @@ where
         let network =
             NetworkWrapper::new(self.block_height, (round, step), Arc::clone(&self.network));
 
-        let step = match step {
-            Step::Precommit => TendermintStep::PreCommit,
-            Step::Prevote => TendermintStep::PreVote,
+        let (step, slot) = match step {
+            Step::Precommit => (
+                TendermintStep::PreCommit,
+                SigningSlot::precommit(self.block_height, round),
+            ),
+            Step::Prevote => (
+                TendermintStep::PreVote,
+                SigningSlot::prevote(self.block_height, round),
+            ),
             _ => panic!("Step must be either prevote or precommit."),
         };
 
@@ -499,11 +541,30 @@ where
             id: id.clone(),
         };
 
-        let own_contribution = TendermintContribution::from_vote(
+        // Never sign two different votes in the same round and step. If we can't sign our vote, we don't take
+        // part in the aggregation, the macro block will still be received once the other validators produced it.
+        let signer = HistoryCheckedSigner::new(
+            Arc::clone(&self.signer),
+            Arc::clone(&self.signing_history),
+            slot,
+        );
+        let own_contribution = match TendermintContribution::from_vote(
             tendermint_vote,
-            &self.block_producer.voting_key.secret_key,
+            &signer,
             self.validator_registry.get_slots(self.validator_slot_band),
-        );
+        ) {
+            Ok(contribution) => contribution,
+            Err(error) => {
+                error!(
+                    block_number = self.block_height,
+                    round,
+                    ?step,
+                    %error,
+                    "Failed to sign our Tendermint vote"
+                );
+                return stream::pending().boxed();
+            }
+        };
 
         let protocol = TendermintAggregationProtocol::new(
             Arc::clone(&self.validator_registry),
@@ -512,12 +573,13 @@ where
             id,
         );
 
-        Aggregation::new(
+        Aggregation::with_diagnostics(
             protocol,
             nimiq_handel::config::Config::default(),
             own_contribution,
             update_stream.map(|item| item.0).boxed(),
             network,
+            Some(Arc::clone(&self.aggregation_diagnostics)),
         )
         .boxed()
     }
@@ -0,0 +1,220 @@
+//! Progress events of the Tendermint instances producing the macro blocks.
+//!
+//! Tendermint only reports its state updates, proposals and decision to the validator. The events derived from them
+//! show when rounds and steps started, how proposals were handled and when the votes of a step reached the 2f+1 slots
+//! Tendermint needs to proceed, such that slow macro blocks can be diagnosed. The events are broadcast to all
+//! subscribers and, if enabled, recorded as metrics.
+use std::{
+    collections::BTreeSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use nimiq_block::MacroBlock;
+use nimiq_hash::{Blake2bHash, Blake2sHash};
+use nimiq_keys::Signature as SchnorrSignature;
+use nimiq_tendermint::{
+    Aggregation, Proposal, Protocol, SignedProposalMessage, State as TendermintState, Step,
+};
+use tokio::sync::broadcast;
+
+use crate::aggregation::tendermint::proposal::Header;
+#[cfg(feature = "metrics")]
+use crate::tendermint_metrics::TendermintMetrics;
+
+/// How Tendermint handled a received proposal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalResult {
+    Accepted,
+    /// The proposal was valid but not relevant, e.g. because it was received too late.
+    Ignored,
+    Rejected,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TendermintEventKind {
+    /// Tendermint entered the given step of the round.
+    StepStarted { step: Step },
+    /// A proposal for the round was received and verified.
+    ProposalReceived {
+        /// The slot band of the proposer.
+        proposer: u16,
+        proposal_hash: Blake2sHash,
+        result: ProposalResult,
+    },
+    /// The votes of the step were aggregated until 2f+1 slots contributed, which lets Tendermint proceed.
+    AggregationCompleted {
+        step: Step,
+        /// The number of slots that contributed.
+        contributors: usize,
+        /// The proposal that received 2f+1 votes, if any.
+        proposal_hash: Option<Blake2sHash>,
+    },
+    /// Tendermint decided on a macro block in the round.
+    Decision { block_hash: Blake2bHash },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TendermintEvent {
+    /// The block number of the macro block being produced.
+    pub block_number: u32,
+    pub round: u32,
+    /// Time since the Tendermint instance for the block was started.
+    pub elapsed: Duration,
+    pub kind: TendermintEventKind,
+}
+
+/// Broadcasts the events of all Tendermint instances of the validator.
+pub struct TendermintEvents {
+    sender: broadcast::Sender<TendermintEvent>,
+    #[cfg(feature = "metrics")]
+    metrics: TendermintMetrics,
+}
+
+impl TendermintEvents {
+    /// The number of events buffered for each subscriber. Subscribers falling behind lose the oldest events.
+    pub const CHANNEL_CAPACITY: usize = 64;
+
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(Self::CHANNEL_CAPACITY);
+        Self {
+            sender,
+            #[cfg(feature = "metrics")]
+            metrics: TendermintMetrics::default(),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<TendermintEvent> {
+        self.sender.subscribe()
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> &TendermintMetrics {
+        &self.metrics
+    }
+
+    /// Starts tracking the Tendermint instance producing the macro block at `block_number`.
+    pub(crate) fn start(self: &Arc<Self>, block_number: u32) -> TendermintTracker {
+        TendermintTracker {
+            events: Arc::clone(self),
+            block_number,
+            started: Instant::now(),
+            current: None,
+            completed: BTreeSet::new(),
+        }
+    }
+
+    fn emit(&self, event: TendermintEvent) {
+        #[cfg(feature = "metrics")]
+        self.metrics.note_event(&event);
+
+        // Sending only fails if there are no subscribers, in which case the event is simply dropped.
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Default for TendermintEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Derives the events of a single Tendermint instance from what it returns.
+pub(crate) struct TendermintTracker {
+    events: Arc<TendermintEvents>,
+    block_number: u32,
+    started: Instant,
+    /// The round and step of the last state update.
+    current: Option<(u32, Step)>,
+    /// The rounds and steps whose aggregation already reached 2f+1 contributors.
+    completed: BTreeSet<(u32, Step)>,
+}
+
+impl TendermintTracker {
+    fn emit(&self, round: u32, kind: TendermintEventKind) {
+        self.events.emit(TendermintEvent {
+            block_number: self.block_number,
+            round,
+            elapsed: self.started.elapsed(),
+            kind,
+        });
+    }
+
+    pub fn state_updated<TProtocol: Protocol<ProposalHash = Blake2sHash>>(
+        &mut self,
+        state: &TendermintState<TProtocol>,
+    ) {
+        let current = (state.current_round, state.current_step);
+        if self.current != Some(current) {
+            self.current = Some(current);
+            self.emit(
+                state.current_round,
+                TendermintEventKind::StepStarted {
+                    step: state.current_step,
+                },
+            );
+        }
+
+        for (&(round, step), aggregation) in &state.best_votes {
+            let contributors = aggregation.all_contributors().len();
+            if contributors < TProtocol::TWO_F_PLUS_ONE || !self.completed.insert((round, step)) {
+                continue;
+            }
+
+            let proposal_hash = aggregation
+                .proposals()
+                .into_iter()
+                .find(|(_, votes)| *votes >= TProtocol::TWO_F_PLUS_ONE)
+                .map(|(proposal_hash, _)| proposal_hash);
+            self.emit(
+                round,
+                TendermintEventKind::AggregationCompleted {
+                    step,
+                    contributors,
+                    proposal_hash,
+                },
+            );
+        }
+    }
+
+    pub fn proposal<Id>(
+        &self,
+        proposal: &SignedProposalMessage<Header<Id>, (SchnorrSignature, u16)>,
+        result: ProposalResult,
+    ) {
+        self.emit(
+            proposal.message.round,
+            TendermintEventKind::ProposalReceived {
+                proposer: proposal.signature.1,
+                proposal_hash: proposal.message.proposal.hash(),
+                result,
+            },
+        );
+    }
+
+    pub fn decision(&mut self, block: &MacroBlock) {
+        // Tendermint decides as soon as the precommits reach 2f+1, without a state update containing them.
+        if let Some(justification) = &block.justification {
+            if self
+                .completed
+                .insert((justification.round, Step::Precommit))
+            {
+                self.emit(
+                    justification.round,
+                    TendermintEventKind::AggregationCompleted {
+                        step: Step::Precommit,
+                        contributors: justification.sig.signers.len(),
+                        proposal_hash: Some(block.hash_blake2s()),
+                    },
+                );
+            }
+        }
+
+        self.emit(
+            block.header.round,
+            TendermintEventKind::Decision {
+                block_hash: block.hash(),
+            },
+        );
+    }
+}
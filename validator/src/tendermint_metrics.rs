@@ -0,0 +1,129 @@
+use nimiq_tendermint::Step;
+use prometheus_client::{
+    encoding::EncodeLabelSet,
+    metrics::{counter::Counter, family::Family, histogram::Histogram},
+    registry::Registry,
+};
+
+use crate::tendermint_events::{ProposalResult, TendermintEvent, TendermintEventKind};
+
+pub struct TendermintMetrics {
+    steps_started: Family<StepLabels, Counter>,
+    proposals: Family<ProposalLabels, Counter>,
+    aggregation_completion_times: Family<StepLabels, Histogram, fn() -> Histogram>,
+    decisions: Counter,
+    decision_times: Histogram,
+    decision_rounds: Histogram,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct StepLabels {
+    step: String,
+}
+
+impl StepLabels {
+    fn new(step: Step) -> Self {
+        let step = match step {
+            Step::Propose => "propose",
+            Step::Prevote => "prevote",
+            Step::Precommit => "precommit",
+        };
+        Self {
+            step: step.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ProposalLabels {
+    result: String,
+}
+
+fn completion_time_histogram() -> Histogram {
+    Histogram::new([0.5, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0].into_iter())
+}
+
+impl Default for TendermintMetrics {
+    fn default() -> Self {
+        Self {
+            steps_started: Family::default(),
+            proposals: Family::default(),
+            aggregation_completion_times: Family::new_with_constructor(completion_time_histogram),
+            decisions: Counter::default(),
+            decision_times: completion_time_histogram(),
+            decision_rounds: Histogram::new([0.0, 1.0, 2.0, 3.0, 5.0, 8.0].into_iter()),
+        }
+    }
+}
+
+impl TendermintMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "steps_started",
+            "Count of Tendermint steps started, by step",
+            self.steps_started.clone(),
+        );
+
+        registry.register(
+            "proposals",
+            "Count of verified proposals received, by how they were handled",
+            self.proposals.clone(),
+        );
+
+        registry.register(
+            "aggregation_completion_times",
+            "Time in seconds from the start of Tendermint until the votes of a step reached 2f+1 slots",
+            self.aggregation_completion_times.clone(),
+        );
+
+        registry.register(
+            "decisions",
+            "Count of macro blocks decided",
+            self.decisions.clone(),
+        );
+
+        registry.register(
+            "decision_times",
+            "Time in seconds from the start of Tendermint until the decision",
+            self.decision_times.clone(),
+        );
+
+        registry.register(
+            "decision_rounds",
+            "The rounds in which macro blocks were decided",
+            self.decision_rounds.clone(),
+        );
+    }
+
+    pub(crate) fn note_event(&self, event: &TendermintEvent) {
+        match event.kind {
+            TendermintEventKind::StepStarted { step } => {
+                self.steps_started
+                    .get_or_create(&StepLabels::new(step))
+                    .inc();
+            }
+            TendermintEventKind::ProposalReceived { result, .. } => {
+                let result = match result {
+                    ProposalResult::Accepted => "accepted",
+                    ProposalResult::Ignored => "ignored",
+                    ProposalResult::Rejected => "rejected",
+                };
+                self.proposals
+                    .get_or_create(&ProposalLabels {
+                        result: result.to_string(),
+                    })
+                    .inc();
+            }
+            TendermintEventKind::AggregationCompleted { step, .. } => {
+                self.aggregation_completion_times
+                    .get_or_create(&StepLabels::new(step))
+                    .observe(event.elapsed.as_secs_f64());
+            }
+            TendermintEventKind::Decision { .. } => {
+                self.decisions.inc();
+                self.decision_times.observe(event.elapsed.as_secs_f64());
+                self.decision_rounds.observe(event.round as f64);
+            }
+        }
+    }
+}
@@ -13,17 +13,21 @@ use std::{
 use futures::stream::{BoxStream, Stream, StreamExt};
 use linked_hash_map::LinkedHashMap;
 use nimiq_block::{Block, BlockHeaderTopic, BlockTopic, BlockType};
-use nimiq_block_production::BlockProducer;
+use nimiq_block_production::signer::{LocalSigner, ValidatorSigner};
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent, ForkEvent, PushResult};
-use nimiq_bls::{lazy::LazyPublicKey, KeyPair as BlsKeyPair};
+use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, PublicKey as BlsPublicKey};
 use nimiq_consensus::{Consensus, ConsensusEvent, ConsensusProxy};
 use nimiq_database::{
     traits::{Database, ReadTransaction, WriteTransaction},
     DatabaseProxy, TableProxy,
 };
+use nimiq_handel::diagnostics::HandelDiagnostics;
 use nimiq_hash::{Blake2bHash, Hash};
-use nimiq_keys::{Address, KeyPair as SchnorrKeyPair, Signature as SchnorrSignature};
+use nimiq_keys::{
+    Address, KeyPair as SchnorrKeyPair, PublicKey as SchnorrPublicKey,
+    Signature as SchnorrSignature,
+};
 use nimiq_macros::store_waker;
 use nimiq_mempool::{config::MempoolConfig, mempool::Mempool};
 use nimiq_network_interface::{
@@ -32,9 +36,14 @@ use nimiq_network_interface::{
 };
 use nimiq_primitives::{coin::Coin, policy::Policy};
 use nimiq_tendermint::SignedProposalMessage;
-use nimiq_transaction_builder::TransactionBuilder;
+use nimiq_transaction::{SignatureProof, Transaction};
+use nimiq_transaction_builder::{
+    recipient::staking_contract::StakingRecipientBuilder, Recipient, Sender, TransactionBuilder,
+    TransactionProofBuilder,
+};
 use nimiq_validator_network::ValidatorNetwork;
 use parking_lot::RwLock;
+use tokio::time::{self, Instant, Interval};
 #[cfg(feature = "metrics")]
 use tokio_metrics::TaskMonitor;
 use tokio_stream::wrappers::BroadcastStream;
@@ -45,22 +54,30 @@ use crate::{
         state::MacroState,
     },
     jail::ForkProofPool,
+    key_rotation::KeyRotation,
+    lease::ValidatorLease,
+    maintenance::{MaintenanceSchedule, MaintenanceStatus},
     micro::{ProduceMicroBlock, ProduceMicroBlockEvent},
     r#macro::{MappedReturn, ProduceMacroBlock, ProposalTopic},
+    signing_history::SigningHistory,
+    stats::{
+        EpochStats, ValidatorStakingState, ValidatorStats, ValidatorStatsError, ValidatorStatus,
+    },
+    tendermint_events::TendermintEvents,
 };
 
-#[derive(PartialEq)]
-enum ValidatorStakingState {
-    Active,
-    Inactive(Option<u32>),
-    NoStake,
-    Unknown,
-}
-
 struct ActiveEpochState {
     validator_slot_band: u16,
 }
 
+/// State of the lease that allows us to be the active node of our validator if it is run with hot standbys.
+struct LeaseState {
+    lease: Arc<dyn ValidatorLease>,
+    /// Until when we hold the lease, if we hold it.
+    held_until: Option<Instant>,
+    renew_interval: Interval,
+}
+
 struct BlockchainState {
     fork_proofs: ForkProofPool,
     can_enforce_validity_window: bool,
@@ -79,20 +96,48 @@ enum MempoolState {
 
 pub struct ValidatorProxy {
     pub validator_address: Arc<RwLock<Address>>,
-    pub signing_key: Arc<RwLock<SchnorrKeyPair>>,
-    pub voting_key: Arc<RwLock<BlsKeyPair>>,
+    pub signer: Arc<RwLock<Arc<dyn ValidatorSigner>>>,
     pub fee_key: Arc<RwLock<SchnorrKeyPair>>,
     pub automatic_reactivate: Arc<AtomicBool>,
+    pub signing_history: Arc<SigningHistory>,
+    pub maintenance: Arc<MaintenanceSchedule>,
+    pub skip_block_diagnostics: Arc<HandelDiagnostics>,
+    pub tendermint_diagnostics: Arc<HandelDiagnostics>,
+    pub tendermint_events: Arc<TendermintEvents>,
+    blockchain: Arc<RwLock<Blockchain>>,
+    epoch_stats: Arc<RwLock<EpochStats>>,
+}
+
+impl ValidatorProxy {
+    /// Returns the current status of our validator.
+    pub fn status(&self) -> ValidatorStatus {
+        ValidatorStatus::new(&self.blockchain.read(), &self.validator_address.read())
+    }
+
+    /// Returns the performance statistics of our validator in the current epoch.
+    pub fn stats(&self) -> Result<ValidatorStats, ValidatorStatsError> {
+        ValidatorStats::new(
+            &self.blockchain.read(),
+            &self.validator_address.read(),
+            self.epoch_stats.read().clone(),
+        )
+    }
 }
 
 impl Clone for ValidatorProxy {
     fn clone(&self) -> Self {
         Self {
             validator_address: Arc::clone(&self.validator_address),
-            signing_key: Arc::clone(&self.signing_key),
-            voting_key: Arc::clone(&self.voting_key),
+            signer: Arc::clone(&self.signer),
             fee_key: Arc::clone(&self.fee_key),
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
+            signing_history: Arc::clone(&self.signing_history),
+            maintenance: Arc::clone(&self.maintenance),
+            skip_block_diagnostics: Arc::clone(&self.skip_block_diagnostics),
+            tendermint_diagnostics: Arc::clone(&self.tendermint_diagnostics),
+            tendermint_events: Arc::clone(&self.tendermint_events),
+            blockchain: Arc::clone(&self.blockchain),
+            epoch_stats: Arc::clone(&self.epoch_stats),
         }
     }
 }
@@ -109,9 +154,25 @@ where
     env: DatabaseProxy,
 
     validator_address: Arc<RwLock<Address>>,
-    signing_key: Arc<RwLock<SchnorrKeyPair>>,
-    voting_key: Arc<RwLock<BlsKeyPair>>,
     fee_key: Arc<RwLock<SchnorrKeyPair>>,
+    /// Signs the blocks, votes and staking transactions of this validator. The keys used by the signer might not be
+    /// held by this node.
+    signer: Arc<RwLock<Arc<dyn ValidatorSigner>>>,
+    /// The history of everything this validator signed, to protect against signing conflicting messages.
+    signing_history: Arc<SigningHistory>,
+    /// The lease, if this node is run as a hot standby. Without a lease, we are always the active node.
+    lease_state: Option<LeaseState>,
+    /// Our contributions to the blocks of the current epoch.
+    epoch_stats: Arc<RwLock<EpochStats>>,
+    /// The scheduled maintenance, during which the validator deactivates itself.
+    maintenance: Arc<MaintenanceSchedule>,
+    /// The keys we rotate to, if a key rotation is pending.
+    key_rotation: Option<KeyRotation>,
+    /// The diagnostics of the recent skip block and Tendermint vote aggregations.
+    skip_block_diagnostics: Arc<HandelDiagnostics>,
+    tendermint_diagnostics: Arc<HandelDiagnostics>,
+    /// The progress events of the Tendermint instances producing macro blocks.
+    tendermint_events: Arc<TendermintEvents>,
 
     proposal_receiver: ProposalReceiver<TValidatorNetwork>,
 
@@ -123,6 +184,8 @@ where
     blockchain_state: BlockchainState,
     validator_state: Option<InactivityState>,
     automatic_reactivate: Arc<AtomicBool>,
+    /// Whether to send a reactivate transaction once, because the node was restarted after the maintenance.
+    reactivate_after_maintenance: bool,
 
     macro_producer: Option<ProduceMacroBlock<TValidatorNetwork>>,
     macro_state: Arc<RwLock<Option<MacroState>>>,
@@ -146,6 +209,10 @@ where
     const PRODUCER_TIMEOUT: Duration = Duration::from_millis(Policy::BLOCK_PRODUCER_TIMEOUT);
     const BLOCK_SEPARATION_TIME: Duration = Duration::from_millis(Policy::BLOCK_SEPARATION_TIME);
     const FORK_PROOFS_MAX_SIZE: usize = 1_000; // bytes
+    const LEASE_DURATION: Duration = Duration::from_secs(10);
+    const LEASE_RENEW_INTERVAL: Duration = Duration::from_secs(2);
+    /// Margin by which we step down before the lease expires, to account for clock drift between the nodes.
+    const LEASE_MARGIN: Duration = Duration::from_secs(2);
 
     pub fn new(
         env: DatabaseProxy,
@@ -154,9 +221,9 @@ where
         network: Arc<TValidatorNetwork>,
         validator_address: Address,
         automatic_reactivate: bool,
-        signing_key: SchnorrKeyPair,
-        voting_key: BlsKeyPair,
         fee_key: SchnorrKeyPair,
+        signer: Arc<dyn ValidatorSigner>,
+        lease: Option<Arc<dyn ValidatorLease>>,
         mempool_config: MempoolConfig,
     ) -> Self {
         let consensus_event_rx = consensus.subscribe_events();
@@ -165,6 +232,10 @@ where
         let blockchain_event_rx = blockchain_rg.notifier_as_stream();
         let fork_event_rx = BroadcastStream::new(blockchain_rg.fork_notifier.subscribe());
         let can_enforce_validity_window = blockchain_rg.can_enforce_validity_window();
+        let epoch_stats = EpochStats::new(
+            Policy::epoch_at(blockchain_rg.block_number()),
+            blockchain_rg.block_number() + 1,
+        );
         drop(blockchain_rg);
 
         let blockchain_state = BlockchainState {
@@ -180,12 +251,30 @@ where
         };
         let macro_state = Arc::new(RwLock::new(macro_state));
 
+        let signing_history = Arc::new(SigningHistory::new(env.clone()));
+
+        // With a lease, we start as a standby and only sign anything once we acquired the lease.
+        let lease_state = lease.map(|lease| {
+            signing_history.set_signing_limit(Some(signing_history.watermark()));
+            LeaseState {
+                lease,
+                held_until: None,
+                renew_interval: time::interval(Self::LEASE_RENEW_INTERVAL),
+            }
+        });
+
         let network1 = Arc::clone(&network);
         let (proposal_sender, proposal_receiver) = ProposalBuffer::new();
 
         let mempool = Arc::new(Mempool::new(Arc::clone(&blockchain), mempool_config));
         let mempool_state = MempoolState::Inactive;
 
+        // Once restarted after the maintenance, reactivate if requested when scheduling it.
+        let maintenance = Arc::new(MaintenanceSchedule::new(env.clone()));
+        let reactivate_after_maintenance = maintenance.finish_after_restart();
+        if reactivate_after_maintenance {
+            info!("Maintenance is over, reactivating the validator");
+        }
         let automatic_reactivate = Arc::new(AtomicBool::new(automatic_reactivate));
 
         let mut this = Self {
@@ -197,9 +286,22 @@ where
             env,
 
             validator_address: Arc::new(RwLock::new(validator_address)),
-            signing_key: Arc::new(RwLock::new(signing_key)),
-            voting_key: Arc::new(RwLock::new(voting_key)),
             fee_key: Arc::new(RwLock::new(fee_key)),
+            signer: Arc::new(RwLock::new(signer)),
+            signing_history,
+            lease_state,
+            epoch_stats: Arc::new(RwLock::new(epoch_stats)),
+            maintenance,
+            key_rotation: None,
+            skip_block_diagnostics: Arc::new(HandelDiagnostics::new(
+                "skip_block",
+                HandelDiagnostics::DEFAULT_CAPACITY,
+            )),
+            tendermint_diagnostics: Arc::new(HandelDiagnostics::new(
+                "tendermint",
+                HandelDiagnostics::DEFAULT_CAPACITY,
+            )),
+            tendermint_events: Arc::new(TendermintEvents::new()),
 
             proposal_receiver,
 
@@ -211,6 +313,7 @@ where
             blockchain_state,
             validator_state: None,
             automatic_reactivate,
+            reactivate_after_maintenance,
 
             macro_producer: None,
             macro_state: Arc::clone(&macro_state),
@@ -269,6 +372,8 @@ where
         self.macro_producer = None;
         self.micro_producer = None;
 
+        self.apply_key_rotation();
+
         let blockchain = self.blockchain.read();
 
         // Check if the unpark/activate transaction was sent
@@ -324,25 +429,31 @@ where
             .iter()
             .map(|validator| validator.voting_key.clone())
             .collect();
-        let key = self.voting_key();
+        // Only the active node of our validator publishes the record, such that the other validators reach it.
+        let signer = self.holds_lease().then(|| self.signer());
         let network = Arc::clone(&self.network);
 
         // TODO might better be done without the task.
         // However we have an entire batch to execute the task so it should not be extremely bad.
         // Also the setting up of our own public key record should probably not be done here but in `init` instead.
         tokio::spawn(async move {
-            if let Err(err) = network
-                .set_public_key(&key.public_key.compress(), &key.secret_key)
-                .await
-            {
-                error!("could not set up DHT record: {:?}", err);
+            if let Some(signer) = signer {
+                match signer.vote(network.validator_record_hash()) {
+                    Ok(signature) => {
+                        let public_key = signer.voting_public_key().compress();
+                        if let Err(err) = network.set_public_key(&public_key, signature).await {
+                            error!("could not set up DHT record: {:?}", err);
+                        }
+                    }
+                    Err(error) => error!(%error, "could not sign DHT record"),
+                }
             }
             network.set_validators(voting_keys).await;
         });
     }
 
     fn init_block_producer(&mut self, event: Option<Blake2bHash>) {
-        if !self.is_active() {
+        if !self.is_active() || !self.holds_lease() {
             return;
         }
 
@@ -359,7 +470,7 @@ where
 
         let head = blockchain.head();
         let next_block_number = head.block_number() + 1;
-        let block_producer = BlockProducer::new(self.signing_key(), self.voting_key());
+        let signer = self.signer();
 
         debug!(
             next_block_number = next_block_number,
@@ -379,7 +490,10 @@ where
                 self.macro_producer = Some(ProduceMacroBlock::new(
                     Arc::clone(&self.blockchain),
                     Arc::clone(&self.network),
-                    block_producer,
+                    signer,
+                    Arc::clone(&self.signing_history),
+                    Arc::clone(&self.tendermint_diagnostics),
+                    Arc::clone(&self.tendermint_events),
                     self.validator_slot_band(),
                     active_validators,
                     next_block_number,
@@ -400,7 +514,9 @@ where
                     Arc::clone(&self.blockchain),
                     Arc::clone(&self.mempool),
                     Arc::clone(&self.network),
-                    block_producer,
+                    signer,
+                    Arc::clone(&self.signing_history),
+                    Arc::clone(&self.skip_block_diagnostics),
                     self.validator_slot_band(),
                     fork_proofs,
                     prev_seed,
@@ -447,6 +563,113 @@ where
     fn pause_validator(&mut self) {
         // When we lose consensus we may no longer be able to enforce the validity window.
         self.blockchain_state.can_enforce_validity_window = false;
+
+        // We can't produce blocks without consensus, so let a standby take over if there is one.
+        if let Some(lease) = self
+            .lease_state
+            .as_ref()
+            .filter(|lease_state| lease_state.held_until.is_some())
+            .map(|lease_state| Arc::clone(&lease_state.lease))
+        {
+            self.step_down("Lost consensus");
+            if let Err(error) = lease.release() {
+                warn!(%error, "Failed to release the validator lease");
+            }
+        }
+    }
+
+    /// Checks whether we are the active node of our validator. This is always the case if we don't have a lease.
+    fn holds_lease(&self) -> bool {
+        self.lease_state
+            .as_ref()
+            .map_or(true, |lease_state| lease_state.held_until.is_some())
+    }
+
+    /// The highest block number we sign anything for until the lease is renewed the next time.
+    fn signing_limit(&self) -> u32 {
+        // The lease is renewed multiple times before it expires, so this must cover at least the blocks produced
+        // during the lease duration.
+        let lease_blocks =
+            2 * Self::LEASE_DURATION.as_millis() as u64 / Policy::BLOCK_SEPARATION_TIME;
+        self.blockchain.read().block_number() + lease_blocks as u32
+    }
+
+    /// Acquires or renews the lease if it is time to do so and steps down if we lost it.
+    fn poll_lease(&mut self, cx: &mut Context<'_>) {
+        let lease_state = match self.lease_state.as_mut() {
+            Some(lease_state) => lease_state,
+            None => return,
+        };
+
+        if matches!(lease_state.held_until, Some(held_until) if Instant::now() >= held_until) {
+            self.step_down("Failed to renew the validator lease in time");
+        }
+
+        while self
+            .lease_state
+            .as_mut()
+            .unwrap()
+            .renew_interval
+            .poll_tick(cx)
+            .is_ready()
+        {
+            // Only become active once we are able to produce blocks.
+            if !self.holds_lease() && !self.can_be_active() {
+                continue;
+            }
+            self.renew_lease();
+        }
+    }
+
+    fn renew_lease(&mut self) {
+        let now = Instant::now();
+        let signing_limit = self.signing_limit();
+        let lease_state = self.lease_state.as_mut().unwrap();
+
+        match lease_state
+            .lease
+            .try_acquire(Self::LEASE_DURATION, signing_limit)
+        {
+            Ok(Some(previous_limit)) => {
+                let acquired = lease_state.held_until.is_none();
+                lease_state.held_until = Some(now + Self::LEASE_DURATION - Self::LEASE_MARGIN);
+
+                // Never sign anything the previous holder might have signed and never sign beyond what we recorded
+                // in the lease.
+                if acquired {
+                    self.signing_history.raise_watermark(previous_limit);
+                }
+                self.signing_history.set_signing_limit(Some(signing_limit));
+
+                if acquired {
+                    info!(
+                        watermark = self.signing_history.watermark(),
+                        signing_limit, "Acquired the validator lease, becoming active"
+                    );
+                    self.init();
+                }
+            }
+            Ok(None) => {
+                if lease_state.held_until.is_some() {
+                    self.step_down("Validator lease was taken over by another node");
+                }
+            }
+            Err(error) => {
+                // If we hold the lease, we step down once it expires.
+                warn!(%error, "Failed to acquire the validator lease");
+            }
+        }
+    }
+
+    /// Stops producing blocks and voting, the validator keeps following the chain as a standby.
+    fn step_down(&mut self, reason: &str) {
+        if let Some(lease_state) = self.lease_state.as_mut() {
+            if lease_state.held_until.take().is_some() {
+                warn!(reason, "Stepping down, switching to standby");
+            }
+        }
+        self.macro_producer = None;
+        self.micro_producer = None;
     }
 
     /// Check and update if we can enforce the tx validity window.
@@ -467,9 +690,13 @@ where
         match event {
             BlockchainEvent::Extended(ref hash) => self.on_blockchain_extended(hash),
             BlockchainEvent::HistoryAdopted(ref hash) => self.on_blockchain_history_adopted(hash),
-            BlockchainEvent::Finalized(ref hash) => self.on_blockchain_extended(hash),
+            BlockchainEvent::Finalized(ref hash) => {
+                self.on_blockchain_extended(hash);
+                self.prune_signing_history();
+            }
             BlockchainEvent::EpochFinalized(ref hash) => {
                 self.on_blockchain_extended(hash);
+                self.prune_signing_history();
                 if self.can_be_active() {
                     self.init_epoch()
                 }
@@ -498,6 +725,11 @@ where
 
         // Update mempool and blockchain state
         self.blockchain_state.fork_proofs.apply_block(&block);
+        self.epoch_stats.write().apply_block(
+            &self.blockchain.read(),
+            &self.validator_address(),
+            &block,
+        );
         // Mempool updates are only done once we can be active.
         if self.can_be_active() {
             self.mempool
@@ -511,18 +743,31 @@ where
         new_chain: &[(Blake2bHash, Block)],
     ) {
         // Update mempool and blockchain state
+        let blockchain = self.blockchain.read();
+        let validator_address = self.validator_address();
+        let mut epoch_stats = self.epoch_stats.write();
         for (_hash, block) in old_chain.iter() {
             self.blockchain_state.fork_proofs.revert_block(block);
+            epoch_stats.revert_block(&blockchain, &validator_address, block);
         }
         for (_hash, block) in new_chain.iter() {
             self.blockchain_state.fork_proofs.apply_block(block);
+            epoch_stats.apply_block(&blockchain, &validator_address, block);
         }
+        drop(epoch_stats);
+        drop(blockchain);
         // Mempool updates are only done once we can be active.
         if self.can_be_active() {
             self.mempool.mempool_update(new_chain, old_chain);
         }
     }
 
+    /// Nothing can be signed anymore for finalized blocks, thus their signing history is no longer needed.
+    fn prune_signing_history(&self) {
+        let macro_block_number = self.blockchain.read().macro_head().block_number();
+        self.signing_history.prune(macro_block_number);
+    }
+
     fn on_fork_event(&mut self, event: ForkEvent) {
         match event {
             ForkEvent::Detected(fork_proof) => self.blockchain_state.fork_proofs.insert(fork_proof),
@@ -686,55 +931,257 @@ where
     }
 
     fn get_staking_state(&self, blockchain: &Blockchain) -> ValidatorStakingState {
-        let validator_address = self.validator_address();
-        let staking_contract = match blockchain.get_staking_contract_if_complete(None) {
-            Some(contract) => contract,
-            None => return ValidatorStakingState::Unknown,
+        ValidatorStakingState::of(blockchain, &self.validator_address())
+    }
+
+    fn reactivate(&self, blockchain: &Blockchain) -> Option<InactivityState> {
+        let validity_start_height = blockchain.block_number();
+
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.reactivate_validator(self.validator_address());
+        let reactivate_transaction =
+            self.create_signaling_transaction(blockchain, recipient, "reactivate")?;
+        let tx_hash = reactivate_transaction.hash();
+        self.send_transaction(reactivate_transaction, "reactivate");
+
+        Some(InactivityState {
+            inactive_tx_hash: tx_hash,
+            inactive_tx_validity_window_start: validity_start_height,
+        })
+    }
+
+    fn deactivate(&self, blockchain: &Blockchain) {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.deactivate_validator(self.validator_address());
+        if let Some(deactivate_transaction) =
+            self.create_signaling_transaction(blockchain, recipient, "deactivate")
+        {
+            self.send_transaction(deactivate_transaction, "deactivate");
+        }
+    }
+
+    /// Creates a transaction signaling the staking contract for our validator, e.g. to reactivate it. The staking
+    /// data is signed with our signing key through the signer, such that the key doesn't need to be held by this
+    /// node. The fee key pays and signs the transaction itself.
+    fn create_signaling_transaction(
+        &self,
+        blockchain: &Blockchain,
+        recipient: StakingRecipientBuilder,
+        kind: &'static str,
+    ) -> Option<Transaction> {
+        let fee_key = self.fee_key();
+        let signer = self.staking_signer();
+
+        let mut builder = TransactionBuilder::new();
+        builder
+            .with_sender(Sender::new_basic(Address::from(&fee_key)))
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(Coin::ZERO)
+            .with_validity_start_height(blockchain.block_number())
+            .with_network_id(blockchain.network_id());
+
+        let mut data_builder = match builder.generate() {
+            Ok(TransactionProofBuilder::InStaking(data_builder)) => data_builder,
+            Ok(_) => unreachable!("Signaling transactions carry staking data"),
+            Err(error) => {
+                error!(%error, "Failed to create {} transaction", kind);
+                return None;
+            }
         };
+        let signature = match signer.sign(&data_builder.transaction.serialize_content()) {
+            Ok(signature) => signature,
+            Err(error) => {
+                error!(%error, "Failed to sign {} transaction", kind);
+                return None;
+            }
+        };
+        data_builder.set_signature(SignatureProof::from(signer.signing_public_key(), signature));
+
+        let mut proof_builder = data_builder.generate()?.unwrap_basic();
+        proof_builder.sign_with_key_pair(&fee_key);
+        proof_builder.generate()
+    }
 
-        // Then fetch the validator to see if it is active.
+    /// Returns the signer for the transactions signaling the staking contract. Once the next keys of a key rotation
+    /// are registered, the staking contract only accepts these transactions signed with the next signing key.
+    fn staking_signer(&self) -> Arc<dyn ValidatorSigner> {
+        match self.key_rotation.as_ref() {
+            Some(key_rotation) if key_rotation.registered => Arc::new(LocalSigner::new(
+                key_rotation.signing_key.clone(),
+                key_rotation.voting_key.clone(),
+            )),
+            _ => self.signer(),
+        }
+    }
+
+    fn send_transaction(&self, transaction: Transaction, kind: &'static str) {
+        let cn = self.consensus.clone();
+        tokio::spawn(async move {
+            debug!("Sending {} transaction to the network", kind);
+            if cn.send_transaction(transaction).await.is_err() {
+                error!("Failed to send {} transaction", kind);
+            }
+        });
+    }
+
+    /// Rotates to the given keys without downtime: They are registered in the staking contract and we switch to them
+    /// once we are elected with them. This is only supported with a local signer.
+    pub fn set_key_rotation(&mut self, key_rotation: KeyRotation) {
+        self.key_rotation = Some(key_rotation);
+    }
+
+    /// Returns the keys registered for the validator in the staking contract.
+    fn registered_keys(
+        validator_address: &Address,
+        blockchain: &Blockchain,
+    ) -> Option<(SchnorrPublicKey, CompressedPublicKey)> {
+        let staking_contract = blockchain.get_staking_contract_if_complete(None)?;
         let data_store = blockchain.get_staking_contract_store();
         let txn = blockchain.read_transaction();
         staking_contract
-            .get_validator(&data_store.read(&txn), &validator_address)
-            .map_or(
-                ValidatorStakingState::NoStake,
-                |validator| match validator.inactive_since {
-                    Some(_) => ValidatorStakingState::Inactive(validator.jail_release),
-                    None => ValidatorStakingState::Active,
-                },
-            )
+            .get_validator(&data_store.read(&txn), validator_address)
+            .map(|validator| (validator.signing_key, validator.voting_key))
     }
 
-    fn reactivate(&self, blockchain: &Blockchain) -> InactivityState {
-        let validity_start_height = blockchain.block_number();
+    /// Registers the next keys in the staking contract if they aren't yet. Once they are, the staking contract only
+    /// accepts transactions signed with the next signing key.
+    fn update_key_rotation(&mut self) {
+        let key_rotation = match self.key_rotation.as_ref() {
+            Some(key_rotation) => key_rotation,
+            None => return,
+        };
+
+        let blockchain = self.blockchain.read();
+        let block_number = blockchain.block_number();
+        let (signing_key, voting_key) =
+            match Self::registered_keys(&self.validator_address(), &blockchain) {
+                Some(keys) => keys,
+                None => return,
+            };
+
+        if key_rotation.matches(&signing_key, &voting_key) {
+            if !key_rotation.registered {
+                info!("Next validator keys are registered, they become effective at the next election block");
+                self.key_rotation.as_mut().unwrap().registered = true;
+            }
+            return;
+        }
 
-        let reactivate_transaction = TransactionBuilder::new_reactivate_validator(
+        // Without the key of the validator address, the update transaction has to be sent by other means.
+        let validator_key = match key_rotation.validator_key.as_ref() {
+            Some(validator_key) => validator_key,
+            None => return,
+        };
+        if key_rotation.update_sent_at.map_or(false, |sent_at| {
+            block_number < sent_at + Policy::blocks_per_batch()
+        }) {
+            return;
+        }
+
+        let update_transaction = match TransactionBuilder::new_update_validator(
             &self.fee_key(),
-            self.validator_address(),
-            &self.signing_key(),
+            validator_key,
+            Some(key_rotation.signing_key.public),
+            Some(&key_rotation.voting_key),
+            None,
+            None,
             Coin::ZERO,
-            validity_start_height,
+            block_number,
             blockchain.network_id(),
-        )
-        .unwrap(); // TODO: Handle transaction creation error
-        let tx_hash = reactivate_transaction.hash();
+        ) {
+            Ok(transaction) => transaction,
+            Err(error) => {
+                error!(%error, "Failed to create update validator transaction");
+                return;
+            }
+        };
+        drop(blockchain);
 
-        let cn = self.consensus.clone();
-        tokio::spawn(async move {
-            debug!("Sending reactivate transaction to the network");
-            if cn
-                .send_transaction(reactivate_transaction.clone())
-                .await
-                .is_err()
+        self.send_transaction(update_transaction, "update validator");
+        self.key_rotation.as_mut().unwrap().update_sent_at = Some(block_number);
+    }
+
+    /// Switches to the next keys once we are elected with them, or once they are registered if we are not elected.
+    fn apply_key_rotation(&mut self) {
+        let key_rotation = match self.key_rotation.as_ref() {
+            Some(key_rotation) => key_rotation,
+            None => return,
+        };
+
+        let blockchain = self.blockchain.read();
+        let effective = match blockchain.current_validators().and_then(|validators| {
+            validators
+                .get_validator_by_address(&self.validator_address())
+                .cloned()
+        }) {
+            Some(validator) => {
+                key_rotation.matches(&validator.signing_key, validator.voting_key.compressed())
+            }
+            None => Self::registered_keys(&self.validator_address(), &blockchain)
+                .map_or(false, |(signing_key, voting_key)| {
+                    key_rotation.matches(&signing_key, &voting_key)
+                }),
+        };
+        drop(blockchain);
+        if !effective {
+            return;
+        }
+
+        let key_rotation = self.key_rotation.take().unwrap();
+        info!(
+            signing_key = %key_rotation.signing_key.public,
+            "Switching to the next validator keys"
+        );
+        if let Some(key_files) = &key_rotation.key_files {
+            if let Err(error) = key_files.store(&key_rotation.signing_key, &key_rotation.voting_key)
             {
-                error!("Failed to send reactivate transaction");
+                error!(
+                    %error,
+                    "Failed to write the next validator keys to the key files, keep them configured as next keys"
+                );
             }
-        });
+        }
+        *self.signer.write() = Arc::new(LocalSigner::new(
+            key_rotation.signing_key,
+            key_rotation.voting_key,
+        ));
+    }
 
-        InactivityState {
-            inactive_tx_hash: tx_hash,
-            inactive_tx_validity_window_start: validity_start_height,
+    /// Advances the scheduled maintenance, if any: Deactivates the validator shortly before the next election block
+    /// and signals that the node can be stopped once the validator is no longer elected.
+    fn update_maintenance(&self) {
+        let maintenance = match self.maintenance.get() {
+            Some(maintenance) => maintenance,
+            None => return,
+        };
+
+        let blockchain = self.blockchain.read();
+        let block_number = blockchain.block_number();
+        let elected = blockchain.current_validators().map_or(false, |validators| {
+            validators
+                .get_validator_by_address(&self.validator_address())
+                .is_some()
+        });
+        let staking_state = self.get_staking_state(&blockchain);
+        let (status, deactivate) = maintenance
+            .status
+            .next(staking_state, elected, block_number);
+        if deactivate {
+            self.deactivate(&blockchain);
+        }
+        drop(blockchain);
+
+        if status != maintenance.status {
+            match status {
+                MaintenanceStatus::SafeToStop => {
+                    info!(
+                        "Validator is inactive and no longer elected, it is safe to stop the node"
+                    )
+                }
+                _ => info!(?status, "Maintenance progressed"),
+            }
+            self.maintenance.set_status(status);
         }
     }
 
@@ -749,12 +1196,16 @@ where
         self.validator_address.read().clone()
     }
 
-    pub fn voting_key(&self) -> BlsKeyPair {
-        self.voting_key.read().clone()
+    pub fn signer(&self) -> Arc<dyn ValidatorSigner> {
+        Arc::clone(&self.signer.read())
+    }
+
+    pub fn voting_public_key(&self) -> BlsPublicKey {
+        self.signer.read().voting_public_key()
     }
 
-    pub fn signing_key(&self) -> SchnorrKeyPair {
-        self.signing_key.read().clone()
+    pub fn signing_public_key(&self) -> SchnorrPublicKey {
+        self.signer.read().signing_public_key()
     }
 
     pub fn fee_key(&self) -> SchnorrKeyPair {
@@ -764,10 +1215,16 @@ where
     pub fn proxy(&self) -> ValidatorProxy {
         ValidatorProxy {
             validator_address: Arc::clone(&self.validator_address),
-            signing_key: Arc::clone(&self.signing_key),
-            voting_key: Arc::clone(&self.voting_key),
+            signer: Arc::clone(&self.signer),
             fee_key: Arc::clone(&self.fee_key),
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
+            signing_history: Arc::clone(&self.signing_history),
+            maintenance: Arc::clone(&self.maintenance),
+            skip_block_diagnostics: Arc::clone(&self.skip_block_diagnostics),
+            tendermint_diagnostics: Arc::clone(&self.tendermint_diagnostics),
+            tendermint_events: Arc::clone(&self.tendermint_events),
+            blockchain: Arc::clone(&self.blockchain),
+            epoch_stats: Arc::clone(&self.epoch_stats),
         }
     }
 }
@@ -839,8 +1296,11 @@ where
             }
         }
 
+        // Acquire the lease or step down if we lost it, if we are run with hot standbys.
+        self.poll_lease(cx);
+
         // If we are an active validator, participate in block production.
-        if self.can_be_active() && self.is_active() {
+        if self.can_be_active() && self.is_active() && self.holds_lease() {
             if self.macro_producer.is_some() {
                 self.poll_macro(cx);
             }
@@ -851,6 +1311,12 @@ where
 
         // Once the validator can be active is established, check the validator staking state.
         if self.can_be_active() {
+            // Only the active node of our validator sends transactions.
+            if self.holds_lease() {
+                self.update_key_rotation();
+                self.update_maintenance();
+            }
+
             let blockchain = self.blockchain.read();
             match self.get_staking_state(&blockchain) {
                 ValidatorStakingState::Active => {
@@ -865,11 +1331,18 @@ where
                         && jail_release
                             .map(|jail_release| blockchain.block_number() >= jail_release)
                             .unwrap_or(true)
-                        && self.automatic_reactivate.load(Ordering::Acquire)
+                        && (self.automatic_reactivate.load(Ordering::Acquire)
+                            || self.reactivate_after_maintenance)
+                        && self.holds_lease()
+                        && self.maintenance.get().is_none()
                     {
                         let inactivity_state = self.reactivate(&blockchain);
                         drop(blockchain);
-                        self.validator_state = Some(inactivity_state);
+                        // Only a single reactivate transaction is sent after the maintenance.
+                        if inactivity_state.is_some() {
+                            self.reactivate_after_maintenance = false;
+                        }
+                        self.validator_state = inactivity_state;
                     }
                 }
                 ValidatorStakingState::NoStake | ValidatorStakingState::Unknown => {}
@@ -0,0 +1,280 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::{future, StreamExt};
+use nimiq_block::{Block, BlockHeaderTopic, BlockTopic, MicroJustification};
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_bls::AggregateSignature;
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_network_interface::network::Topic;
+use nimiq_network_mock::{MockHub, MockNetwork};
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    byzantine::{
+        build_byzantine_validators, Action, ByzantineNetwork, MessageFilter, Rule, Scenario,
+        ScenarioControl, Tamper,
+    },
+    validator::validator_for_slot,
+};
+use nimiq_validator::{
+    aggregation::skip_block::SkipBlockUpdate, stats::ValidatorStakingState,
+    tendermint_events::TendermintEventKind, validator::Validator, PROPOSAL_TOPIC,
+};
+use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
+use parking_lot::RwLock;
+use tokio::time;
+
+type TestValidator = Validator<MockNetwork, ByzantineNetwork<ValidatorNetworkImpl<MockNetwork>>>;
+
+/// Four validators, one of which misbehaves.
+struct Setup {
+    validators: Vec<TestValidator>,
+    controls: Vec<Arc<ScenarioControl>>,
+    /// The index of the misbehaving validator.
+    byzantine: usize,
+}
+
+impl Setup {
+    /// Builds four validators, of which the one in the first slot of `block_number` applies the action to the
+    /// messages matching `filters` while working on that block. The action is created for the misbehaving validator.
+    async fn new(
+        block_number: u32,
+        filters: &[MessageFilter],
+        action: impl FnOnce(&TestValidator) -> Action,
+    ) -> Self {
+        let hub = MockHub::default();
+        let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+        let (validators, controls): (Vec<_>, Vec<_>) = build_byzantine_validators::<MockNetwork>(
+            env,
+            &(1u64..=4u64).collect::<Vec<_>>(),
+            &mut Some(hub),
+            vec![Scenario::honest(); 4],
+        )
+        .await
+        .into_iter()
+        .unzip();
+
+        let byzantine = validator_for_slot(&validators, block_number, 0);
+        let action = action(byzantine);
+        let byzantine = validators
+            .iter()
+            .position(|validator| validator.validator_address() == byzantine.validator_address())
+            .unwrap();
+        let rules = filters
+            .iter()
+            .map(|filter| Rule::new(filter.clone(), action.clone()))
+            .collect();
+        controls[byzantine].set_scenario(only_at(block_number, rules));
+
+        Setup {
+            validators,
+            controls,
+            byzantine,
+        }
+    }
+
+    /// The control of the misbehaving validator.
+    fn control(&self) -> Arc<ScenarioControl> {
+        Arc::clone(&self.controls[self.byzantine])
+    }
+
+    /// A validator other than the misbehaving one.
+    fn honest(&self) -> &TestValidator {
+        &self.validators[(self.byzantine + 1) % self.validators.len()]
+    }
+}
+
+/// A scenario applying `rules` only while working on `block_number`.
+fn only_at(block_number: u32, rules: Vec<Rule>) -> Scenario {
+    Scenario::honest()
+        .from_block(block_number, rules)
+        .from_block(block_number + 1, vec![])
+}
+
+#[test(tokio::test)]
+async fn withheld_proposal_moves_tendermint_to_next_round() {
+    // The proposer of the first round withholds its proposal for the first macro block.
+    let macro_block_number = Policy::macro_block_after(Policy::genesis_block_number());
+    let setup = Setup::new(
+        macro_block_number,
+        &[MessageFilter::Topic(PROPOSAL_TOPIC)],
+        |_| Action::Withhold,
+    )
+    .await;
+
+    let mut events = setup.honest().proxy().tendermint_events.subscribe();
+    let control = setup.control();
+    tokio::spawn(future::join_all(setup.validators));
+
+    let decision_round = time::timeout(Duration::from_secs(120), async move {
+        loop {
+            let event = events.recv().await.unwrap();
+            if let TendermintEventKind::Decision { .. } = event.kind {
+                assert_eq!(event.block_number, macro_block_number);
+                break event.round;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    assert!(decision_round > 0);
+    assert!(control.applied() > 0);
+}
+
+#[test(tokio::test)]
+async fn equivocating_block_producer_is_reported_with_a_fork_proof() {
+    // The producer of the first micro block publishes a second, conflicting block at the same height.
+    let block_number = Policy::genesis_block_number() + 1;
+    let setup = Setup::new(
+        block_number,
+        &[MessageFilter::Topic(BlockTopic::NAME)],
+        |producer| {
+            let signing_key = producer.signer().signing_key_pair().unwrap();
+            Action::Equivocate(Tamper::new(move |block: &mut Block| {
+                let Block::Micro(micro_block) = block else {
+                    return false;
+                };
+                micro_block.header.extra_data = b"equivocation".to_vec();
+                let hash = micro_block.header.hash::<Blake2bHash>();
+                micro_block.justification =
+                    Some(MicroJustification::Micro(signing_key.sign(hash.as_slice())));
+                true
+            }))
+        },
+    )
+    .await;
+
+    let producer_address = setup.validators[setup.byzantine].validator_address();
+    let blockchain = Arc::clone(&setup.honest().blockchain);
+    let control = setup.control();
+    tokio::spawn(future::join_all(setup.validators));
+
+    // Wait for a later block to include the fork proof.
+    let mut events = blockchain.read().notifier_as_stream();
+    time::timeout(Duration::from_secs(120), async {
+        while events.next().await.is_some() {
+            let head = blockchain.read().head();
+            if let Block::Micro(micro_block) = head {
+                if micro_block
+                    .body
+                    .map_or(false, |body| !body.fork_proofs.is_empty())
+                {
+                    return;
+                }
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    // The fork proof jails the producer.
+    assert!(matches!(
+        ValidatorStakingState::of(&blockchain.read(), &producer_address),
+        ValidatorStakingState::Inactive(Some(_))
+    ));
+    assert!(control.applied() > 0);
+}
+
+#[test(tokio::test)]
+async fn delayed_proposal_is_still_decided() {
+    // The proposer of the first round publishes its proposal for the first macro block late.
+    let macro_block_number = Policy::macro_block_after(Policy::genesis_block_number());
+    let setup = Setup::new(
+        macro_block_number,
+        &[MessageFilter::Topic(PROPOSAL_TOPIC)],
+        |_| Action::Delay(Duration::from_millis(500)),
+    )
+    .await;
+
+    let mut events = setup.honest().proxy().tendermint_events.subscribe();
+    let control = setup.control();
+    tokio::spawn(future::join_all(setup.validators));
+
+    time::timeout(Duration::from_secs(120), async move {
+        loop {
+            let event = events.recv().await.unwrap();
+            if let TendermintEventKind::Decision { .. } = event.kind {
+                assert_eq!(event.block_number, macro_block_number);
+                break;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    assert!(control.applied() > 0);
+}
+
+#[test(tokio::test)]
+async fn withheld_micro_block_is_replaced_by_a_skip_block() {
+    // The producer of the first micro block doesn't publish it.
+    let block_number = Policy::genesis_block_number() + 1;
+    let setup = Setup::new(
+        block_number,
+        &[
+            MessageFilter::Topic(BlockTopic::NAME),
+            MessageFilter::Topic(BlockHeaderTopic::NAME),
+        ],
+        |_| Action::Withhold,
+    )
+    .await;
+
+    let blockchain = Arc::clone(&setup.honest().blockchain);
+    let control = setup.control();
+    tokio::spawn(future::join_all(setup.validators));
+
+    wait_for_skip_block(&blockchain, block_number).await;
+    assert!(control.applied() > 0);
+}
+
+#[test(tokio::test)]
+async fn invalid_skip_block_contributions_are_ignored() {
+    // The producer of the first micro block doesn't publish it, so a skip block has to be aggregated, to which
+    // another validator contributes invalid signatures.
+    let block_number = Policy::genesis_block_number() + 1;
+    let setup = Setup::new(
+        block_number,
+        &[
+            MessageFilter::Topic(BlockTopic::NAME),
+            MessageFilter::Topic(BlockHeaderTopic::NAME),
+        ],
+        |_| Action::Withhold,
+    )
+    .await;
+
+    let invalid_index = (setup.byzantine + 1) % setup.validators.len();
+    let invalidate = Tamper::new(|update: &mut SkipBlockUpdate| {
+        update.0.aggregate.proof.signature = AggregateSignature::default();
+        true
+    });
+    let control = Arc::clone(&setup.controls[invalid_index]);
+    control.set_scenario(only_at(
+        block_number,
+        vec![Rule::message::<SkipBlockUpdate>(Action::Tamper(invalidate))],
+    ));
+
+    let blockchain =
+        Arc::clone(&setup.validators[(invalid_index + 1) % setup.validators.len()].blockchain);
+    tokio::spawn(future::join_all(setup.validators));
+
+    wait_for_skip_block(&blockchain, block_number).await;
+    assert!(control.applied() > 0);
+}
+
+/// Waits until the chain has moved past `block_number` and asserts that the block at it is a skip block.
+async fn wait_for_skip_block(blockchain: &Arc<RwLock<Blockchain>>, block_number: u32) {
+    let mut events = blockchain.read().notifier_as_stream();
+    time::timeout(Duration::from_secs(120), async {
+        while blockchain.read().block_number() <= block_number {
+            events.next().await;
+        }
+    })
+    .await
+    .unwrap();
+
+    let block = blockchain.read().get_block_at(block_number, false).unwrap();
+    assert!(block.is_skip());
+}
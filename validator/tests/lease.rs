@@ -0,0 +1,113 @@
+use std::{fs, thread::sleep, time::Duration};
+
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
+use nimiq_test_log::test;
+use nimiq_validator::{
+    lease::{FileLease, ValidatorLease},
+    signing_history::{SigningHistory, SigningHistoryError, SigningSlot},
+};
+
+const DURATION: Duration = Duration::from_secs(60);
+
+fn hash(data: &[u8]) -> Blake2bHash {
+    Blake2bHasher::default().digest(data)
+}
+
+#[test]
+fn only_one_node_holds_the_lease() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("validator.lease");
+    let active = FileLease::new(&path, "active".to_string()).unwrap();
+    let standby = FileLease::new(&path, "standby".to_string()).unwrap();
+
+    // The first node acquires the lease, nobody held it before.
+    assert_eq!(active.try_acquire(DURATION, 100).unwrap(), Some(0));
+    assert_eq!(standby.try_acquire(DURATION, 200).unwrap(), None);
+
+    // Renewing returns our own limit, which is never lowered.
+    assert_eq!(active.try_acquire(DURATION, 50).unwrap(), Some(100));
+    assert_eq!(active.try_acquire(DURATION, 150).unwrap(), Some(100));
+    assert_eq!(standby.try_acquire(DURATION, 200).unwrap(), None);
+
+    // Once released, the standby takes over and learns the limit of the previous holder.
+    active.release().unwrap();
+    assert_eq!(standby.try_acquire(DURATION, 200).unwrap(), Some(150));
+    assert_eq!(active.try_acquire(DURATION, 300).unwrap(), None);
+
+    // Releasing a lease held by someone else has no effect.
+    active.release().unwrap();
+    assert_eq!(active.try_acquire(DURATION, 300).unwrap(), None);
+}
+
+#[test]
+fn standby_takes_over_an_expired_lease() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("validator.lease");
+    let active = FileLease::new(&path, "active".to_string()).unwrap();
+    let standby = FileLease::new(&path, "standby".to_string()).unwrap();
+
+    assert_eq!(
+        active.try_acquire(Duration::from_millis(10), 100).unwrap(),
+        Some(0)
+    );
+    sleep(Duration::from_millis(20));
+
+    assert_eq!(standby.try_acquire(DURATION, 200).unwrap(), Some(100));
+    assert_eq!(active.try_acquire(DURATION, 300).unwrap(), None);
+
+    // No leftovers of the take over.
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn invalid_lease_files_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("validator.lease");
+    fs::write(&path, "not a lease").unwrap();
+
+    let lease = FileLease::new(&path, "active".to_string()).unwrap();
+    assert!(lease.try_acquire(DURATION, 100).is_err());
+}
+
+#[test]
+fn invalid_lease_holders_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("validator.lease");
+
+    assert!(FileLease::new(&path, String::new()).is_err());
+    assert!(FileLease::new(&path, "active node".to_string()).is_err());
+    assert!(FileLease::new(&path, "active\n".to_string()).is_err());
+    assert!(FileLease::new(&path, "active".to_string()).is_ok());
+}
+
+#[test]
+fn signing_history_respects_watermark_and_limit() {
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+    let history = SigningHistory::new(env.clone());
+
+    history.raise_watermark(100);
+    history.set_signing_limit(Some(150));
+
+    assert!(matches!(
+        history.check_and_record(SigningSlot::micro_block(100), hash(b"block")),
+        Err(SigningHistoryError::OutOfRange { .. })
+    ));
+    assert!(matches!(
+        history.check_and_record(SigningSlot::prevote(151, 0), hash(b"vote")),
+        Err(SigningHistoryError::OutOfRange { .. })
+    ));
+    history
+        .check_and_record(SigningSlot::micro_block(101), hash(b"block"))
+        .unwrap();
+    history
+        .check_and_record(SigningSlot::precommit(150, 0), hash(b"vote"))
+        .unwrap();
+
+    // The watermark is never lowered and survives restarts, the watermark itself is not a record.
+    history.raise_watermark(50);
+    let history = SigningHistory::new(env);
+    assert_eq!(history.watermark(), 100);
+    assert_eq!(history.records().len(), 2);
+    assert_eq!(history.highest_block_number(), Some(150));
+}
@@ -0,0 +1,148 @@
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_validator::{
+    maintenance::{Maintenance, MaintenanceSchedule, MaintenanceStatus},
+    stats::ValidatorStakingState,
+};
+
+#[test]
+fn maintenance_schedule_is_persisted() {
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+    let schedule = MaintenanceSchedule::new(env.clone());
+    assert_eq!(schedule.get(), None);
+    assert!(!schedule.cancel());
+
+    schedule.schedule(true);
+    assert_eq!(
+        schedule.get(),
+        Some(Maintenance {
+            status: MaintenanceStatus::Scheduled,
+            reactivate_after_restart: true,
+        })
+    );
+
+    // The schedule survives restarts.
+    let schedule = MaintenanceSchedule::new(env.clone());
+    assert_eq!(
+        schedule.get().map(|maintenance| maintenance.status),
+        Some(MaintenanceStatus::Scheduled)
+    );
+
+    assert!(schedule.cancel());
+    assert_eq!(schedule.get(), None);
+    assert_eq!(MaintenanceSchedule::new(env).get(), None);
+}
+
+#[test]
+fn maintenance_deactivates_before_the_election_block() {
+    let election_block = Policy::election_block_after(Policy::genesis_block_number());
+    let last_batch = election_block - Policy::blocks_per_batch();
+
+    // Nothing happens until the last batch before the election block.
+    assert_eq!(
+        MaintenanceStatus::Scheduled.next(ValidatorStakingState::Active, true, last_batch - 1),
+        (MaintenanceStatus::Scheduled, false)
+    );
+
+    // The deactivate transaction is sent in the last batch.
+    let deactivating = MaintenanceStatus::Deactivating {
+        sent_at: last_batch,
+    };
+    assert_eq!(
+        MaintenanceStatus::Scheduled.next(ValidatorStakingState::Active, true, last_batch),
+        (deactivating, true)
+    );
+
+    // While the transaction is pending, it isn't sent again.
+    assert_eq!(
+        deactivating.next(ValidatorStakingState::Active, true, last_batch + 1),
+        (deactivating, false)
+    );
+
+    // Once inactive, the validator is still elected until the election block.
+    assert_eq!(
+        deactivating.next(
+            ValidatorStakingState::Inactive(None),
+            true,
+            election_block - 1
+        ),
+        (deactivating, false)
+    );
+
+    // Once inactive and no longer elected, the node can be stopped.
+    assert_eq!(
+        deactivating.next(ValidatorStakingState::Inactive(None), false, election_block),
+        (MaintenanceStatus::SafeToStop, false)
+    );
+
+    // Without the staking contract, the status doesn't change.
+    assert_eq!(
+        deactivating.next(ValidatorStakingState::Unknown, false, election_block),
+        (deactivating, false)
+    );
+}
+
+#[test]
+fn maintenance_resends_the_deactivation_after_a_batch() {
+    let election_block = Policy::election_block_after(Policy::genesis_block_number());
+    let sent_at = election_block - Policy::blocks_per_batch();
+    let deactivating = MaintenanceStatus::Deactivating { sent_at };
+
+    // The transaction wasn't included in time and we are elected again. It isn't sent again before the last batch
+    // of the next epoch.
+    let next_election_block = Policy::election_block_after(election_block);
+    let next_last_batch = next_election_block - Policy::blocks_per_batch();
+    assert_eq!(
+        deactivating.next(ValidatorStakingState::Active, true, election_block + 1),
+        (deactivating, false)
+    );
+    assert_eq!(
+        deactivating.next(ValidatorStakingState::Active, true, next_last_batch),
+        (
+            MaintenanceStatus::Deactivating {
+                sent_at: next_last_batch
+            },
+            true
+        )
+    );
+
+    // If we are not elected, it is sent again as soon as a batch has passed.
+    assert_eq!(
+        deactivating.next(ValidatorStakingState::Active, false, sent_at + 1),
+        (deactivating, false)
+    );
+    let resent_at = sent_at + Policy::blocks_per_batch();
+    assert_eq!(
+        deactivating.next(ValidatorStakingState::Active, false, resent_at),
+        (MaintenanceStatus::Deactivating { sent_at: resent_at }, true)
+    );
+}
+
+#[test]
+fn maintenance_reactivates_after_a_restart() {
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+
+    // Not reactivated before it is safe to stop.
+    let schedule = MaintenanceSchedule::new(env.clone());
+    schedule.schedule(true);
+    assert!(!MaintenanceSchedule::new(env.clone()).finish_after_restart());
+
+    // Reactivated once restarted after it was safe to stop, which ends the maintenance.
+    schedule.set_status(MaintenanceStatus::SafeToStop);
+    let schedule = MaintenanceSchedule::new(env.clone());
+    assert!(schedule.finish_after_restart());
+    assert_eq!(schedule.get(), None);
+    assert_eq!(MaintenanceSchedule::new(env.clone()).get(), None);
+
+    // Not reactivated if not requested, the maintenance is kept.
+    let schedule = MaintenanceSchedule::new(env.clone());
+    schedule.schedule(false);
+    schedule.set_status(MaintenanceStatus::SafeToStop);
+    let schedule = MaintenanceSchedule::new(env);
+    assert!(!schedule.finish_after_restart());
+    assert_eq!(
+        schedule.get().map(|maintenance| maintenance.status),
+        Some(MaintenanceStatus::SafeToStop)
+    );
+}
@@ -16,6 +16,7 @@ use nimiq_network_interface::{
 use nimiq_network_libp2p::Network;
 use nimiq_network_mock::{MockHub, MockNetwork};
 use nimiq_primitives::policy::Policy;
+use nimiq_tendermint::Step;
 use nimiq_test_log::test;
 use nimiq_test_utils::{
     test_network::TestNetwork,
@@ -23,7 +24,13 @@ use nimiq_test_utils::{
         build_validator, build_validators, pop_validator_for_slot, seeded_rng, validator_for_slot,
     },
 };
-use nimiq_validator::aggregation::skip_block::SignedSkipBlockMessage;
+use nimiq_utils::file_store::FileStore;
+use nimiq_validator::{
+    aggregation::skip_block::SignedSkipBlockMessage,
+    key_rotation::{KeyFiles, KeyRotation},
+    stats::ValidatorStakingState,
+    tendermint_events::TendermintEventKind,
+};
 use serde::{Deserialize, Serialize};
 use tokio::time;
 
@@ -85,6 +92,234 @@ async fn one_validator_can_create_micro_blocks() {
     assert!(consensus1.blockchain.read().block_number() >= 10 + Policy::genesis_block_number());
 }
 
+#[test(tokio::test)]
+async fn validator_tracks_its_stats() {
+    let hub = MockHub::default();
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+
+    let voting_key = BlsKeyPair::generate(&mut seeded_rng(0));
+    let validator_key = KeyPair::generate(&mut seeded_rng(0));
+    let fee_key = KeyPair::generate(&mut seeded_rng(0));
+    let signing_key = KeyPair::generate(&mut seeded_rng(0));
+    let genesis = GenesisBuilder::default()
+        .with_genesis_validator(
+            Address::from(&validator_key),
+            signing_key.public,
+            voting_key.public_key,
+            Address::default(),
+        )
+        .generate(env)
+        .unwrap();
+
+    let (validator, mut consensus1) = build_validator::<Network>(
+        0,
+        Address::from(&validator_key),
+        false,
+        signing_key,
+        voting_key,
+        fee_key,
+        genesis.clone(),
+        &mut Some(hub),
+        false,
+    )
+    .await;
+
+    consensus1.force_established();
+
+    let blockchain = Arc::clone(&validator.blockchain);
+    let proxy = validator.proxy();
+    tokio::spawn(validator);
+
+    let events1 = blockchain.read().notifier_as_stream();
+    events1.take(10).for_each(|_| future::ready(())).await;
+
+    // We own all slots, so we produce all blocks and sign everything.
+    let status = proxy.status();
+    assert_eq!(status.staking_state, ValidatorStakingState::Active);
+    assert_eq!(status.slot_band, Some(0));
+    assert_eq!(status.slots.len(), Policy::SLOTS as usize);
+
+    let stats = proxy.stats().unwrap();
+    assert!(stats.epoch.produced_micro_blocks > 0);
+    assert_eq!(stats.epoch.missed_micro_blocks, 0);
+    assert_eq!(stats.epoch.skip_blocks_contributed, 0);
+    assert_eq!(stats.punished_slots_current_batch, 0);
+    assert_eq!(stats.punished_slots_previous_batch, 0);
+}
+
+#[test(tokio::test)]
+async fn validator_rotates_its_keys() {
+    let hub = MockHub::default();
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+
+    let voting_key = BlsKeyPair::generate(&mut seeded_rng(0));
+    let validator_key = KeyPair::generate(&mut seeded_rng(0));
+    let fee_key = KeyPair::generate(&mut seeded_rng(0));
+    let signing_key = KeyPair::generate(&mut seeded_rng(0));
+    let genesis = GenesisBuilder::default()
+        .with_genesis_validator(
+            Address::from(&validator_key),
+            signing_key.public,
+            voting_key.public_key,
+            Address::default(),
+        )
+        .generate(env)
+        .unwrap();
+
+    let (mut validator, mut consensus1) = build_validator::<Network>(
+        0,
+        Address::from(&validator_key),
+        false,
+        signing_key,
+        voting_key,
+        fee_key,
+        genesis.clone(),
+        &mut Some(hub),
+        false,
+    )
+    .await;
+
+    let next_signing_key = KeyPair::generate(&mut seeded_rng(1));
+    let next_voting_key = BlsKeyPair::generate(&mut seeded_rng(1));
+    let validator_address = Address::from(&validator_key);
+    let key_dir = tempfile::tempdir().unwrap();
+    let key_files = KeyFiles {
+        signing_key_path: key_dir.path().join("signing_key.dat"),
+        voting_key_path: key_dir.path().join("voting_key.dat"),
+    };
+    let key_rotation = KeyRotation::new(
+        next_signing_key.clone(),
+        next_voting_key,
+        Some(validator_key),
+    )
+    .with_key_files(key_files.clone());
+    let (next_signing_public_key, next_voting_public_key) = key_rotation.public_keys();
+    validator.set_key_rotation(key_rotation);
+
+    consensus1.force_established();
+
+    let blockchain = Arc::clone(&validator.blockchain);
+    let proxy = validator.proxy();
+    tokio::spawn(validator);
+
+    // The next keys are registered right away, but only become effective at the next election block.
+    let election_block_number = Policy::election_block_after(Policy::genesis_block_number());
+    let events1 = blockchain.read().notifier_as_stream();
+    events1
+        .take_while(|_| future::ready(blockchain.read().block_number() < election_block_number))
+        .for_each(|_| future::ready(()))
+        .await;
+
+    // We keep producing blocks with the next keys.
+    let events1 = blockchain.read().notifier_as_stream();
+    events1.take(5).for_each(|_| future::ready(())).await;
+    let signer = Arc::clone(&proxy.signer.read());
+    assert_eq!(signer.signing_public_key(), next_signing_key.public);
+    assert_eq!(
+        signer.voting_public_key().compress(),
+        next_voting_public_key
+    );
+
+    // The next keys are written to the key files, such that they are used after a restart.
+    let stored_signing_key: KeyPair = FileStore::new(&key_files.signing_key_path).load().unwrap();
+    let stored_voting_key: BlsKeyPair = FileStore::new(&key_files.voting_key_path).load().unwrap();
+    assert_eq!(stored_signing_key.public, next_signing_key.public);
+    assert_eq!(
+        stored_voting_key.public_key.compress(),
+        next_voting_public_key
+    );
+
+    let validators = blockchain.read().current_validators().unwrap();
+    let validator = validators
+        .get_validator_by_address(&validator_address)
+        .unwrap();
+    assert_eq!(validator.signing_key, next_signing_public_key);
+    assert_eq!(*validator.voting_key.compressed(), next_voting_public_key);
+}
+
+#[test(tokio::test)]
+async fn validator_reports_tendermint_progress() {
+    let hub = MockHub::default();
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+
+    let voting_key = BlsKeyPair::generate(&mut seeded_rng(0));
+    let validator_key = KeyPair::generate(&mut seeded_rng(0));
+    let fee_key = KeyPair::generate(&mut seeded_rng(0));
+    let signing_key = KeyPair::generate(&mut seeded_rng(0));
+    let genesis = GenesisBuilder::default()
+        .with_genesis_validator(
+            Address::from(&validator_key),
+            signing_key.public,
+            voting_key.public_key,
+            Address::default(),
+        )
+        .generate(env)
+        .unwrap();
+
+    let (validator, mut consensus1) = build_validator::<Network>(
+        0,
+        Address::from(&validator_key),
+        false,
+        signing_key,
+        voting_key,
+        fee_key,
+        genesis.clone(),
+        &mut Some(hub),
+        false,
+    )
+    .await;
+
+    consensus1.force_established();
+
+    let mut events = validator.proxy().tendermint_events.subscribe();
+    tokio::spawn(validator);
+
+    let macro_block_number = Policy::macro_block_after(Policy::genesis_block_number());
+    let mut steps = vec![];
+    let mut completed = vec![];
+    let block_hash = loop {
+        let event = events.recv().await.unwrap();
+        assert_eq!(event.block_number, macro_block_number);
+        assert_eq!(event.round, 0);
+        match event.kind {
+            TendermintEventKind::StepStarted { step } => steps.push(step),
+            TendermintEventKind::AggregationCompleted {
+                step,
+                proposal_hash,
+                ..
+            } => {
+                assert!(proposal_hash.is_some());
+                completed.push(step);
+            }
+            TendermintEventKind::ProposalReceived { .. } => {}
+            TendermintEventKind::Decision { block_hash } => break block_hash,
+        }
+    };
+
+    // A single validator proposes and votes for its own proposal in the first round.
+    assert_eq!(steps.first(), Some(&Step::Propose));
+    assert!(steps.contains(&Step::Prevote));
+    assert!(steps.contains(&Step::Precommit));
+    assert_eq!(completed, vec![Step::Prevote, Step::Precommit]);
+
+    let events1 = consensus1.blockchain.read().notifier_as_stream();
+    events1
+        .take_while(|_| {
+            future::ready(consensus1.blockchain.read().block_number() < macro_block_number)
+        })
+        .for_each(|_| future::ready(()))
+        .await;
+    assert_eq!(
+        consensus1
+            .blockchain
+            .read()
+            .get_block_at(macro_block_number, false)
+            .unwrap()
+            .hash(),
+        block_hash
+    );
+}
+
 #[test(tokio::test)]
 async fn four_validators_can_create_micro_blocks() {
     let hub = MockHub::default();
@@ -279,7 +514,7 @@ async fn validator_can_catch_up() {
     // Manually construct a skip block for the validator
     let vc = create_skip_block_update(
         skip_block_info,
-        validator.voting_key(),
+        validator.signer().voting_key_pair().unwrap(),
         validator.validator_slot_band(),
         &slots,
     );
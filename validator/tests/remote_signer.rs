@@ -0,0 +1,132 @@
+#![cfg(unix)]
+
+use std::{os::unix::net::UnixListener, thread};
+
+use nimiq_block_production::signer::{LocalSigner, SignerError, ValidatorSigner};
+use nimiq_bls::{
+    KeyPair as BlsKeyPair, PublicKey as BlsPublicKey, SigHash, Signature as BlsSignature,
+};
+use nimiq_hash::{Blake2sHasher, Hasher};
+use nimiq_keys::{
+    KeyPair as SchnorrKeyPair, PublicKey as SchnorrPublicKey, SecureGenerate,
+    Signature as SchnorrSignature,
+};
+use nimiq_test_log::test;
+use nimiq_test_utils::validator::seeded_rng;
+use nimiq_validator::remote_signer::{serve_signer, RemoteSigner};
+use nimiq_vrf::VrfSeed;
+
+#[test]
+fn remote_signer_signs_with_the_keys_of_the_signer_process() {
+    let mut rng = seeded_rng(0);
+    let signing_key = SchnorrKeyPair::generate(&mut rng);
+    let voting_key = BlsKeyPair::generate(&mut rng);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("signer.sock");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let local_signer = LocalSigner::new(signing_key.clone(), voting_key.clone());
+    thread::spawn(move || serve_signer(listener, &local_signer));
+
+    let signer = RemoteSigner::connect(&path).unwrap();
+    assert_eq!(signer.signing_public_key(), signing_key.public);
+    assert_eq!(signer.voting_public_key(), voting_key.public_key);
+
+    let data = b"block header";
+    let signature = signer.sign(data).unwrap();
+    assert!(signing_key.public.verify(&signature, data));
+
+    let prev_seed = VrfSeed::default();
+    let seed = signer.sign_next_seed(&prev_seed).unwrap();
+    assert!(seed.verify(&prev_seed, &signing_key.public).is_ok());
+
+    let hash = Blake2sHasher::default().digest(b"tendermint vote");
+    let vote = signer.vote(hash.clone()).unwrap();
+    assert!(voting_key.public_key.verify_hash(hash, &vote));
+}
+
+#[test]
+fn remote_signer_fails_without_signer_process() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(RemoteSigner::connect(dir.path().join("signer.sock")).is_err());
+}
+
+/// Signer announcing the public keys of one signer, but signing with the keys of another.
+struct ImpostorSigner {
+    announced: LocalSigner,
+    signing: LocalSigner,
+}
+
+impl ValidatorSigner for ImpostorSigner {
+    fn signing_public_key(&self) -> SchnorrPublicKey {
+        self.announced.signing_public_key()
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        self.announced.voting_public_key()
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<SchnorrSignature, SignerError> {
+        self.signing.sign(data)
+    }
+
+    fn sign_next_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError> {
+        self.signing.sign_next_seed(prev_seed)
+    }
+
+    fn vote(&self, hash: SigHash) -> Result<BlsSignature, SignerError> {
+        self.signing.vote(hash)
+    }
+}
+
+#[test]
+fn remote_signer_rejects_signatures_of_other_keys() {
+    let mut rng = seeded_rng(0);
+    let announced = LocalSigner::new(
+        SchnorrKeyPair::generate(&mut rng),
+        BlsKeyPair::generate(&mut rng),
+    );
+    let signing = LocalSigner::new(
+        SchnorrKeyPair::generate(&mut rng),
+        BlsKeyPair::generate(&mut rng),
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("signer.sock");
+    let listener = UnixListener::bind(&path).unwrap();
+    thread::spawn(move || serve_signer(listener, &ImpostorSigner { announced, signing }));
+
+    let signer = RemoteSigner::connect(&path).unwrap();
+    assert!(matches!(
+        signer.sign(b"block header"),
+        Err(SignerError::InvalidSignature)
+    ));
+    assert!(matches!(
+        signer.sign_next_seed(&VrfSeed::default()),
+        Err(SignerError::InvalidSignature)
+    ));
+    assert!(matches!(
+        signer.vote(Blake2sHasher::default().digest(b"tendermint vote")),
+        Err(SignerError::InvalidSignature)
+    ));
+}
+
+#[test(tokio::test(flavor = "multi_thread"))]
+async fn remote_signer_can_be_used_from_the_runtime() {
+    let mut rng = seeded_rng(0);
+    let signing_key = SchnorrKeyPair::generate(&mut rng);
+    let voting_key = BlsKeyPair::generate(&mut rng);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("signer.sock");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let local_signer = LocalSigner::new(signing_key.clone(), voting_key);
+    thread::spawn(move || serve_signer(listener, &local_signer));
+
+    let signer = RemoteSigner::connect(&path).unwrap();
+    let data = b"block header";
+    let signature = signer.sign(data).unwrap();
+    assert!(signing_key.public.verify(&signature, data));
+}
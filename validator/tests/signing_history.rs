@@ -0,0 +1,119 @@
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
+use nimiq_test_log::test;
+use nimiq_validator::signing_history::{
+    SigningHistory, SigningHistoryError, SigningHistoryExport, SigningSlot,
+};
+
+fn hash(data: &[u8]) -> Blake2bHash {
+    Blake2bHasher::default().digest(data)
+}
+
+fn signing_history() -> SigningHistory {
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+    SigningHistory::new(env)
+}
+
+#[test]
+fn it_refuses_conflicting_payloads() {
+    let history = signing_history();
+    let slot = SigningSlot::micro_block(42);
+
+    assert!(!history.has_signed(slot));
+    history.check_and_record(slot, hash(b"block a")).unwrap();
+    assert_eq!(history.get(slot), Some(hash(b"block a")));
+
+    // Signing the same payload again is fine.
+    history.check_and_record(slot, hash(b"block a")).unwrap();
+
+    // Signing a different payload in the same slot is refused.
+    assert!(matches!(
+        history.check_and_record(slot, hash(b"block b")),
+        Err(SigningHistoryError::Conflict { .. })
+    ));
+
+    // Other slots are independent.
+    history
+        .check_and_record(SigningSlot::micro_block(43), hash(b"block b"))
+        .unwrap();
+    history
+        .check_and_record(SigningSlot::skip_block(42), hash(b"skip"))
+        .unwrap();
+    history
+        .check_and_record(SigningSlot::prevote(64, 0), hash(b"vote a"))
+        .unwrap();
+    history
+        .check_and_record(SigningSlot::prevote(64, 1), hash(b"vote b"))
+        .unwrap();
+    assert!(history
+        .check_and_record(SigningSlot::prevote(64, 1), hash(b"vote a"))
+        .is_err());
+}
+
+#[test]
+fn it_can_be_exported_and_imported() {
+    let history = signing_history();
+    history
+        .check_and_record(SigningSlot::micro_block(42), hash(b"block"))
+        .unwrap();
+    history
+        .check_and_record(SigningSlot::precommit(64, 2), hash(b"vote"))
+        .unwrap();
+
+    let export = history.export();
+    assert_eq!(export.records.len(), 2);
+
+    let other = signing_history();
+    other
+        .check_and_record(SigningSlot::micro_block(41), hash(b"other block"))
+        .unwrap();
+    assert_eq!(other.import(&export).unwrap(), 2);
+    // Importing again doesn't add anything.
+    assert_eq!(other.import(&export).unwrap(), 0);
+    assert_eq!(other.records().len(), 3);
+
+    // The imported records protect against conflicting payloads.
+    assert!(other
+        .check_and_record(SigningSlot::micro_block(42), hash(b"conflicting block"))
+        .is_err());
+
+    // Conflicting imports are rejected as a whole.
+    let third = signing_history();
+    third
+        .check_and_record(SigningSlot::precommit(64, 2), hash(b"conflicting vote"))
+        .unwrap();
+    assert!(matches!(
+        third.import(&export),
+        Err(SigningHistoryError::Conflict { .. })
+    ));
+    assert!(!third.has_signed(SigningSlot::micro_block(42)));
+
+    let unsupported = SigningHistoryExport {
+        version: SigningHistoryExport::VERSION + 1,
+        records: vec![],
+    };
+    assert!(matches!(
+        history.import(&unsupported),
+        Err(SigningHistoryError::UnsupportedVersion(_))
+    ));
+}
+
+#[test]
+fn it_prunes_finalized_blocks() {
+    let history = signing_history();
+    history
+        .check_and_record(SigningSlot::micro_block(31), hash(b"block"))
+        .unwrap();
+    history
+        .check_and_record(SigningSlot::precommit(32, 0), hash(b"vote"))
+        .unwrap();
+    history
+        .check_and_record(SigningSlot::skip_block(33), hash(b"skip"))
+        .unwrap();
+
+    history.prune(32);
+
+    assert!(!history.has_signed(SigningSlot::micro_block(31)));
+    assert!(history.has_signed(SigningSlot::precommit(32, 0)));
+    assert!(history.has_signed(SigningSlot::skip_block(33)));
+}
@@ -0,0 +1,109 @@
+use nimiq_hash::{argon2kdf::Argon2Error, Blake2bHash};
+use nimiq_keys::Address;
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use nimiq_utils::otp::{Locked, Unlocked, Verify};
+use thiserror::Error;
+
+use crate::{
+    hd_wallet::StoredHdWallet, history::TransactionLabel, wallet_account::WalletAccount,
+    watch_only::WatchOnlyAccount,
+};
+
+#[derive(Debug, Error)]
+pub enum WalletBackupError {
+    #[error("Unsupported backup version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Deserialization error: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Over-long backup")]
+    OverLong,
+    #[error("{0}")]
+    Argon2(#[from] Argon2Error),
+    #[error("Wrong backup passphrase")]
+    WrongPassphrase,
+}
+
+/// The accounts, HD wallets and watch-only accounts of a backup, along with the labels of their transactions. The
+/// accounts and HD wallets stay locked with their own passphrases, the backup passphrase only protects the backup as
+/// a whole.
+#[derive(Default, Serialize, Deserialize)]
+pub struct WalletBackupContents {
+    /// Always `CHECK`, such that a wrong backup passphrase can be detected.
+    check: [u8; 8],
+    pub accounts: Vec<(Address, Locked<WalletAccount>)>,
+    pub hd_wallets: Vec<(Address, StoredHdWallet)>,
+    pub watch_only: Vec<(Address, WatchOnlyAccount)>,
+    pub labels: Vec<(Blake2bHash, TransactionLabel)>,
+}
+
+impl Verify for WalletBackupContents {
+    fn verify(&self) -> bool {
+        self.check == Self::CHECK
+    }
+}
+
+impl WalletBackupContents {
+    const CHECK: [u8; 8] = *b"NIMWALLT";
+
+    pub fn new(
+        accounts: Vec<(Address, Locked<WalletAccount>)>,
+        hd_wallets: Vec<(Address, StoredHdWallet)>,
+        watch_only: Vec<(Address, WatchOnlyAccount)>,
+        labels: Vec<(Blake2bHash, TransactionLabel)>,
+    ) -> Self {
+        WalletBackupContents {
+            check: Self::CHECK,
+            accounts,
+            hd_wallets,
+            watch_only,
+            labels,
+        }
+    }
+}
+
+/// An encrypted backup of wallet accounts, HD wallets and watch-only accounts, e.g. to migrate them to another node.
+#[derive(Serialize, Deserialize)]
+pub struct WalletBackup {
+    version: u8,
+    contents: Locked<WalletBackupContents>,
+}
+
+impl WalletBackup {
+    pub const VERSION: u8 = 1;
+
+    /// Encrypts the contents with the backup passphrase.
+    pub fn new(contents: WalletBackupContents, passphrase: &[u8]) -> Result<Self, Argon2Error> {
+        Ok(WalletBackup {
+            version: Self::VERSION,
+            contents: Locked::with_defaults(contents, passphrase)?,
+        })
+    }
+
+    /// Parses a serialized backup, rejecting unknown versions and trailing bytes.
+    pub fn parse(bytes: &[u8]) -> Result<Self, WalletBackupError> {
+        // The version is serialized first, such that it can be checked before the rest of the backup.
+        let (version, _) = u8::deserialize_take(bytes)?;
+        if version != Self::VERSION {
+            return Err(WalletBackupError::UnsupportedVersion(version));
+        }
+
+        let (backup, left_over) = Self::deserialize_take(bytes)?;
+        if !left_over.is_empty() {
+            return Err(WalletBackupError::OverLong);
+        }
+        Ok(backup)
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Decrypts the contents with the backup passphrase.
+    pub fn unlock(self, passphrase: &[u8]) -> Result<WalletBackupContents, WalletBackupError> {
+        let unlocked = self
+            .contents
+            .unlock(passphrase)
+            .map_err(|_locked| WalletBackupError::WrongPassphrase)?;
+        Ok(Unlocked::into_unlocked_data(unlocked))
+    }
+}
@@ -0,0 +1,157 @@
+use std::{fmt, io};
+
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
+use nimiq_hash::pbkdf2::Pbkdf2Error;
+use nimiq_key_derivation::ExtendedPrivateKey;
+use nimiq_keys::{Address, KeyPair};
+use nimiq_mnemonic::{Mnemonic, MnemonicType, WORDLIST_EN};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_utils::otp::{Locked, Verify};
+
+use crate::wallet_account::WalletAccount;
+
+/// The secret of a hierarchical deterministic wallet, from which its accounts are derived.
+#[derive(Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct HdWallet {
+    /// The seed of the mnemonic the wallet was imported from.
+    seed: Vec<u8>,
+    /// The address of the master key, which identifies the wallet.
+    id: Address,
+}
+
+impl fmt::Debug for HdWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The seed is secret.
+        f.debug_struct("HdWallet").field("id", &self.id).finish()
+    }
+}
+
+impl Verify for HdWallet {
+    fn verify(&self) -> bool {
+        // Check that the seed results in the master key the wallet is identified by.
+        self.master_key().to_address() == self.id
+    }
+}
+
+impl HdWallet {
+    /// The path accounts are derived along by default. The accounts are its hardened children, the first one being
+    /// at `m/44'/242'/0'/0'`.
+    pub const DEFAULT_PATH: &'static str = "m/44'/242'/0'";
+
+    /// The highest index an account can be derived at. Accounts are hardened children, whose indices are limited to
+    /// 31 bits.
+    pub const MAX_ACCOUNT_INDEX: u32 = 0x7fff_ffff;
+
+    /// Creates the wallet of a BIP39 mnemonic, optionally protected by a password.
+    pub fn from_mnemonic(mnemonic: &Mnemonic, password: Option<&str>) -> Result<Self, Pbkdf2Error> {
+        let seed = mnemonic.to_seed(password)?;
+        let id = ExtendedPrivateKey::from_seed(seed.clone()).to_address();
+        Ok(HdWallet { seed, id })
+    }
+
+    /// Checks whether the mnemonic is a valid BIP39 mnemonic.
+    pub fn is_valid_mnemonic(mnemonic: &Mnemonic) -> bool {
+        matches!(
+            mnemonic.get_type(WORDLIST_EN),
+            MnemonicType::BIP39 | MnemonicType::UNKNOWN
+        )
+    }
+
+    pub fn id(&self) -> &Address {
+        &self.id
+    }
+
+    pub fn master_key(&self) -> ExtendedPrivateKey {
+        ExtendedPrivateKey::from_seed(self.seed.clone())
+    }
+
+    /// Derives the account at the given index below `base_path`. Returns `None` if the path or the index is invalid.
+    pub fn derive_account(&self, base_path: &str, index: u32) -> Option<WalletAccount> {
+        if index > Self::MAX_ACCOUNT_INDEX {
+            return None;
+        }
+        let key = self
+            .master_key()
+            .derive_path(&HdWalletInfo::account_path(base_path, index))?;
+        Some(WalletAccount::from(KeyPair::from(key.into_private_key())))
+    }
+}
+
+/// An account derived from an HD wallet.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct DerivedAccount {
+    pub index: u32,
+    pub address: Address,
+}
+
+/// The public information about an HD wallet: the path its accounts are derived along and the accounts derived so
+/// far. Unlike the wallet itself, it is stored unencrypted.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct HdWalletInfo {
+    pub base_path: String,
+    pub accounts: Vec<DerivedAccount>,
+}
+
+impl HdWalletInfo {
+    pub fn new(base_path: String) -> Self {
+        HdWalletInfo {
+            base_path,
+            accounts: vec![],
+        }
+    }
+
+    /// Returns the path of the account at the given index below `base_path`.
+    pub fn account_path(base_path: &str, index: u32) -> String {
+        format!("{base_path}/{index}'")
+    }
+
+    /// Returns the lowest index that no account was derived at yet.
+    pub fn next_index(&self) -> u32 {
+        let mut index = 0;
+        while self.get(index).is_some() {
+            index += 1;
+        }
+        index
+    }
+
+    pub fn get(&self, index: u32) -> Option<&DerivedAccount> {
+        self.accounts.iter().find(|account| account.index == index)
+    }
+
+    /// Records a derived account. Accounts are kept ordered by their index.
+    pub fn insert(&mut self, account: DerivedAccount) {
+        if self.get(account.index).is_none() {
+            let position = self
+                .accounts
+                .partition_point(|derived| derived.index < account.index);
+            self.accounts.insert(position, account);
+        }
+    }
+}
+
+/// An HD wallet as stored in the wallet store.
+#[derive(Serialize, Deserialize)]
+pub struct StoredHdWallet {
+    pub wallet: Locked<HdWallet>,
+    pub info: HdWalletInfo,
+}
+
+impl IntoDatabaseValue for StoredHdWallet {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize_to_writer(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for StoredHdWallet {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Deserialize::deserialize_from_vec(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+    }
+}
@@ -0,0 +1,99 @@
+use std::io;
+
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
+use nimiq_serde::{Deserialize, Serialize};
+
+/// A transaction of a wallet address, as recorded from the logs of the blockchain.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct WalletTransaction {
+    pub hash: Blake2bHash,
+    pub block_number: u32,
+    pub timestamp: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    /// The value that was transferred. Zero for failed transactions.
+    pub value: Coin,
+    pub fee: Coin,
+    pub failed: bool,
+}
+
+impl WalletTransaction {
+    /// The key of the transaction in the history of `address`. Keys are ordered by address first and block number
+    /// second, such that the history of an address can be iterated in chain order.
+    pub(crate) fn key(&self, address: &Address) -> Vec<u8> {
+        Self::key_from_parts(address, self.block_number, &self.hash)
+    }
+
+    pub(crate) fn key_from_parts(
+        address: &Address,
+        block_number: u32,
+        hash: &Blake2bHash,
+    ) -> Vec<u8> {
+        let mut key = Self::address_prefix(address);
+        key.extend_from_slice(&block_number.to_be_bytes());
+        key.extend_from_slice(hash.as_slice());
+        key
+    }
+
+    pub(crate) fn address_prefix(address: &Address) -> Vec<u8> {
+        address.as_bytes().to_vec()
+    }
+
+    /// A key that sorts after every key of `address`, but before the keys of any following address.
+    pub(crate) fn address_prefix_end(address: &Address) -> Vec<u8> {
+        let mut key = Self::key_from_parts(address, u32::MAX, &Blake2bHash::from([0xff; 32]));
+        key.push(0xff);
+        key
+    }
+}
+
+impl IntoDatabaseValue for WalletTransaction {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize_to_writer(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for WalletTransaction {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Deserialize::deserialize_from_vec(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+    }
+}
+
+/// A user-defined label of a transaction. Labels are stored by transaction hash, so they can be set before the
+/// transaction is included in a block.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TransactionLabel {
+    pub label: String,
+    pub note: Option<String>,
+}
+
+impl IntoDatabaseValue for TransactionLabel {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize_to_writer(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for TransactionLabel {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Deserialize::deserialize_from_vec(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+    }
+}
@@ -1,5 +1,13 @@
+pub use backup::{WalletBackup, WalletBackupContents, WalletBackupError};
+pub use hd_wallet::{DerivedAccount, HdWallet, HdWalletInfo, StoredHdWallet};
+pub use history::{TransactionLabel, WalletTransaction};
 pub use wallet_account::WalletAccount;
 pub use wallet_store::WalletStore;
+pub use watch_only::WatchOnlyAccount;
 
+mod backup;
+mod hd_wallet;
+mod history;
 mod wallet_account;
 mod wallet_store;
+mod watch_only;
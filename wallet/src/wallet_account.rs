@@ -2,7 +2,10 @@ use std::io;
 
 use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
 use nimiq_hash::{Hash, HashOutput, Sha256Hash};
-use nimiq_keys::{Address, KeyPair, PublicKey, SecureGenerate, Signature};
+use nimiq_keys::{
+    typed_message::{TypedMessage, TypedMessageError},
+    Address, KeyPair, PublicKey, SecureGenerate, Signature,
+};
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{SignatureProof, Transaction};
@@ -91,6 +94,13 @@ impl WalletAccount {
         let hash = Self::prepare_message_for_signature(message);
         public_key.verify(signature, hash.as_bytes())
     }
+
+    pub fn sign_typed_message(
+        &self,
+        message: &TypedMessage,
+    ) -> Result<(PublicKey, Signature), TypedMessageError> {
+        Ok((self.key_pair.public, message.sign(&self.key_pair)?))
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for WalletAccount {
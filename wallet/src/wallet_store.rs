@@ -2,23 +2,49 @@ use nimiq_database::{
     traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
     DatabaseProxy, TableProxy, TransactionProxy, WriteTransactionProxy,
 };
+use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 use nimiq_utils::otp::Locked;
 
-use crate::wallet_account::WalletAccount;
+use crate::{
+    backup::WalletBackupContents,
+    hd_wallet::StoredHdWallet,
+    history::{TransactionLabel, WalletTransaction},
+    wallet_account::WalletAccount,
+    watch_only::WatchOnlyAccount,
+};
 
 #[derive(Debug)]
 pub struct WalletStore {
     env: DatabaseProxy,
     wallet_db: TableProxy,
+    hd_wallet_db: TableProxy,
+    watch_only_db: TableProxy,
+    history_db: TableProxy,
+    label_db: TableProxy,
 }
 
 impl WalletStore {
     const WALLET_DB_NAME: &'static str = "Wallet";
+    const HD_WALLET_DB_NAME: &'static str = "HdWallet";
+    const WATCH_ONLY_DB_NAME: &'static str = "WatchOnly";
+    const HISTORY_DB_NAME: &'static str = "WalletHistory";
+    const LABEL_DB_NAME: &'static str = "TransactionLabels";
 
     pub fn new(env: DatabaseProxy) -> Self {
         let wallet_db = env.open_table(Self::WALLET_DB_NAME.to_string());
-        WalletStore { env, wallet_db }
+        let hd_wallet_db = env.open_table(Self::HD_WALLET_DB_NAME.to_string());
+        let watch_only_db = env.open_table(Self::WATCH_ONLY_DB_NAME.to_string());
+        let history_db = env.open_table(Self::HISTORY_DB_NAME.to_string());
+        let label_db = env.open_table(Self::LABEL_DB_NAME.to_string());
+        WalletStore {
+            env,
+            wallet_db,
+            hd_wallet_db,
+            watch_only_db,
+            history_db,
+            label_db,
+        }
     }
 
     pub fn create_read_transaction(&self) -> TransactionProxy {
@@ -65,4 +91,340 @@ impl WalletStore {
     ) {
         txn.put_reserve(&self.wallet_db, address, wallet);
     }
+
+    /// Returns the IDs of all HD wallets.
+    pub fn list_hd_wallets(&self, txn_option: Option<&TransactionProxy>) -> Vec<Address> {
+        let read_txn;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.env.read_transaction();
+                &read_txn
+            }
+        };
+
+        let cursor = txn.cursor(&self.hd_wallet_db);
+        cursor
+            .into_iter_start::<_, StoredHdWallet>()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    pub fn get_hd_wallet(
+        &self,
+        id: &Address,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<StoredHdWallet> {
+        match txn_option {
+            Some(txn) => txn.get(&self.hd_wallet_db, id),
+            None => self.env.read_transaction().get(&self.hd_wallet_db, id),
+        }
+    }
+
+    pub fn put_hd_wallet(
+        &self,
+        id: &Address,
+        wallet: &StoredHdWallet,
+        txn: &mut WriteTransactionProxy,
+    ) {
+        txn.put_reserve(&self.hd_wallet_db, id, wallet);
+    }
+
+    /// Returns the addresses of all watch-only accounts.
+    pub fn list_watch_only(&self, txn_option: Option<&TransactionProxy>) -> Vec<Address> {
+        let read_txn;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.env.read_transaction();
+                &read_txn
+            }
+        };
+
+        let cursor = txn.cursor(&self.watch_only_db);
+        cursor
+            .into_iter_start::<_, WatchOnlyAccount>()
+            .map(|(address, _)| address)
+            .collect()
+    }
+
+    pub fn get_watch_only(
+        &self,
+        address: &Address,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<WatchOnlyAccount> {
+        match txn_option {
+            Some(txn) => txn.get(&self.watch_only_db, address),
+            None => self
+                .env
+                .read_transaction()
+                .get(&self.watch_only_db, address),
+        }
+    }
+
+    pub fn put_watch_only(
+        &self,
+        address: &Address,
+        account: &WatchOnlyAccount,
+        txn: &mut WriteTransactionProxy,
+    ) {
+        txn.put_reserve(&self.watch_only_db, address, account);
+    }
+
+    pub fn remove_watch_only(&self, address: &Address, txn: &mut WriteTransactionProxy) {
+        txn.remove(&self.watch_only_db, address);
+    }
+
+    /// Returns the addresses whose history the wallet records: those of the accounts, the derived accounts of the
+    /// HD wallets and the watch-only accounts.
+    pub fn tracked_addresses(&self, txn_option: Option<&TransactionProxy>) -> Vec<Address> {
+        let read_txn;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.env.read_transaction();
+                &read_txn
+            }
+        };
+
+        let mut addresses = self.list(Some(txn));
+        for id in self.list_hd_wallets(Some(txn)) {
+            if let Some(wallet) = self.get_hd_wallet(&id, Some(txn)) {
+                addresses.extend(
+                    wallet
+                        .info
+                        .accounts
+                        .into_iter()
+                        .map(|account| account.address),
+                );
+            }
+        }
+        addresses.extend(self.list_watch_only(Some(txn)));
+        addresses
+    }
+
+    /// Returns the recorded transactions of `address`, newest first. `offset` transactions are skipped and at most
+    /// `limit` are returned.
+    pub fn list_transactions(
+        &self,
+        address: &Address,
+        offset: usize,
+        limit: usize,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<WalletTransaction> {
+        let read_txn;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.env.read_transaction();
+                &read_txn
+            }
+        };
+
+        // Position the cursor on the newest transaction of the address by seeking past the end of its keys and
+        // stepping back.
+        let prefix = WalletTransaction::address_prefix(address);
+        let mut cursor = txn.cursor(&self.history_db);
+        let end = WalletTransaction::address_prefix_end(address);
+        let mut entry = match cursor.seek_range_key::<Vec<u8>, WalletTransaction>(&end) {
+            Some(_) => cursor.prev::<Vec<u8>, WalletTransaction>(),
+            None => cursor.last::<Vec<u8>, WalletTransaction>(),
+        };
+
+        let mut transactions = vec![];
+        let mut skipped = 0;
+        while transactions.len() < limit {
+            let Some((key, transaction)) = entry else {
+                break;
+            };
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            if skipped < offset {
+                skipped += 1;
+            } else {
+                transactions.push(transaction);
+            }
+            entry = cursor.prev();
+        }
+        transactions
+    }
+
+    pub fn put_transaction(
+        &self,
+        address: &Address,
+        transaction: &WalletTransaction,
+        txn: &mut WriteTransactionProxy,
+    ) {
+        txn.put_reserve(&self.history_db, &transaction.key(address), transaction);
+    }
+
+    pub fn remove_transaction(
+        &self,
+        address: &Address,
+        block_number: u32,
+        hash: &Blake2bHash,
+        txn: &mut WriteTransactionProxy,
+    ) {
+        txn.remove(
+            &self.history_db,
+            &WalletTransaction::key_from_parts(address, block_number, hash),
+        );
+    }
+
+    pub fn get_label(
+        &self,
+        hash: &Blake2bHash,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<TransactionLabel> {
+        match txn_option {
+            Some(txn) => txn.get(&self.label_db, hash),
+            None => self.env.read_transaction().get(&self.label_db, hash),
+        }
+    }
+
+    pub fn put_label(
+        &self,
+        hash: &Blake2bHash,
+        label: &TransactionLabel,
+        txn: &mut WriteTransactionProxy,
+    ) {
+        txn.put_reserve(&self.label_db, hash, label);
+    }
+
+    pub fn remove_label(&self, hash: &Blake2bHash, txn: &mut WriteTransactionProxy) {
+        txn.remove(&self.label_db, hash);
+    }
+
+    /// Returns all transaction labels along with the hashes of their transactions.
+    pub fn list_labels(
+        &self,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<(Blake2bHash, TransactionLabel)> {
+        let read_txn;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.env.read_transaction();
+                &read_txn
+            }
+        };
+
+        let cursor = txn.cursor(&self.label_db);
+        cursor.into_iter_start().collect()
+    }
+
+    /// Collects the accounts, HD wallets and watch-only accounts with the given addresses for a backup, or all of
+    /// them if no addresses are given. Returns the first address that is none of them as an error. The derived
+    /// accounts of the HD wallets are always included.
+    ///
+    /// A backup of all accounts includes all transaction labels, otherwise only the labels of the recorded
+    /// transactions of the exported accounts are included.
+    pub fn backup_contents(&self, addresses: &[Address]) -> Result<WalletBackupContents, Address> {
+        let txn = self.env.read_transaction();
+        let export_all = addresses.is_empty();
+        let addresses = if export_all {
+            let mut addresses = self.list(Some(&txn));
+            addresses.extend(self.list_hd_wallets(Some(&txn)));
+            addresses.extend(self.list_watch_only(Some(&txn)));
+            addresses
+        } else {
+            addresses.to_vec()
+        };
+
+        let mut accounts = vec![];
+        let mut hd_wallets = vec![];
+        let mut watch_only = vec![];
+        for address in addresses {
+            if let Some(account) = self.get(&address, Some(&txn)) {
+                accounts.push((address, account));
+            } else if let Some(wallet) = self.get_hd_wallet(&address, Some(&txn)) {
+                hd_wallets.push((address, wallet));
+            } else if let Some(account) = self.get_watch_only(&address, Some(&txn)) {
+                watch_only.push((address, account));
+            } else {
+                return Err(address);
+            }
+        }
+
+        // The derived accounts of an HD wallet are stored as accounts of their own.
+        for (_, wallet) in &hd_wallets {
+            for derived in &wallet.info.accounts {
+                if accounts
+                    .iter()
+                    .any(|(address, _)| *address == derived.address)
+                {
+                    continue;
+                }
+                if let Some(account) = self.get(&derived.address, Some(&txn)) {
+                    accounts.push((derived.address.clone(), account));
+                }
+            }
+        }
+
+        let labels = if export_all {
+            self.list_labels(Some(&txn))
+        } else {
+            let exported = accounts
+                .iter()
+                .map(|(address, _)| address)
+                .chain(watch_only.iter().map(|(address, _)| address));
+            let mut labels: Vec<_> = exported
+                .flat_map(|address| self.list_transactions(address, 0, usize::MAX, Some(&txn)))
+                .filter_map(|transaction| {
+                    self.get_label(&transaction.hash, Some(&txn))
+                        .map(|label| (transaction.hash, label))
+                })
+                .collect();
+            // Transactions between exported accounts are recorded for both.
+            labels.sort_by(|(a, _), (b, _)| a.cmp(b));
+            labels.dedup_by(|(a, _), (b, _)| a == b);
+            labels
+        };
+
+        Ok(WalletBackupContents::new(
+            accounts, hd_wallets, watch_only, labels,
+        ))
+    }
+
+    /// Restores the accounts, HD wallets, watch-only accounts and transaction labels of a backup. Existing ones are
+    /// only replaced if `overwrite` is set. Returns the addresses of the restored accounts, the IDs of the restored HD
+    /// wallets and the addresses of the restored watch-only accounts.
+    pub fn restore_backup(
+        &self,
+        contents: &WalletBackupContents,
+        overwrite: bool,
+        txn: &mut WriteTransactionProxy,
+    ) -> (Vec<Address>, Vec<Address>, Vec<Address>) {
+        let mut accounts = vec![];
+        for (address, account) in &contents.accounts {
+            if overwrite || self.get(address, Some(txn)).is_none() {
+                self.put(address, account, txn);
+                accounts.push(address.clone());
+            }
+        }
+
+        let mut hd_wallets = vec![];
+        for (id, wallet) in &contents.hd_wallets {
+            if overwrite || self.get_hd_wallet(id, Some(txn)).is_none() {
+                self.put_hd_wallet(id, wallet, txn);
+                hd_wallets.push(id.clone());
+            }
+        }
+
+        let mut watch_only = vec![];
+        for (address, account) in &contents.watch_only {
+            if overwrite || self.get_watch_only(address, Some(txn)).is_none() {
+                self.put_watch_only(address, account, txn);
+                watch_only.push(address.clone());
+            }
+        }
+
+        for (hash, label) in &contents.labels {
+            if overwrite || self.get_label(hash, Some(txn)).is_none() {
+                self.put_label(hash, label, txn);
+            }
+        }
+        (accounts, hd_wallets, watch_only)
+    }
 }
@@ -0,0 +1,35 @@
+use std::io;
+
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
+use nimiq_keys::PublicKey;
+use nimiq_serde::{Deserialize, Serialize};
+
+/// An account the wallet tracks without holding its private key, e.g. a cold wallet. Its transactions are built
+/// unsigned and signed elsewhere.
+///
+/// Extended public keys can't be imported, since Ed25519 keys are derived with hardened derivation only.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct WatchOnlyAccount {
+    /// The public key of the account, if known.
+    pub public_key: Option<PublicKey>,
+}
+
+impl IntoDatabaseValue for WatchOnlyAccount {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize_to_writer(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for WatchOnlyAccount {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Deserialize::deserialize_from_vec(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+    }
+}
@@ -1,8 +1,15 @@
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_hash::Blake2bHash;
 use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_mnemonic::Mnemonic;
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_test_log::test;
-use nimiq_wallet::WalletAccount;
+use nimiq_utils::otp::Locked;
+use nimiq_wallet::{
+    DerivedAccount, HdWallet, HdWalletInfo, StoredHdWallet, TransactionLabel, WalletAccount,
+    WalletBackup, WalletBackupError, WalletStore, WalletTransaction, WatchOnlyAccount,
+};
 
 /// This is an example for using doc comment attributes
 ///
@@ -43,3 +50,366 @@ fn test_serialize_deserialize() {
         }
     }
 }
+
+/// The 24 word BIP39 mnemonic of all-zero entropy.
+fn mnemonic() -> Mnemonic {
+    let mut words = vec!["abandon"; 23];
+    words.push("art");
+    words.join(" ").parse().unwrap()
+}
+
+#[test]
+fn test_hd_wallet_derivation() {
+    let mnemonic = mnemonic();
+    assert!(HdWallet::is_valid_mnemonic(&mnemonic));
+    assert!(!HdWallet::is_valid_mnemonic(
+        &"abandon abandon".parse().unwrap()
+    ));
+
+    let wallet = HdWallet::from_mnemonic(&mnemonic, None).unwrap();
+    assert_eq!(
+        wallet,
+        HdWallet::from_mnemonic(&mnemonic, None).unwrap(),
+        "Wallets of the same mnemonic must be equal"
+    );
+    assert_ne!(
+        wallet.id(),
+        HdWallet::from_mnemonic(&mnemonic, Some("password"))
+            .unwrap()
+            .id()
+    );
+
+    let account = wallet
+        .derive_account(HdWallet::DEFAULT_PATH, 1)
+        .expect("Default path must be valid");
+    let expected = wallet.master_key().derive_path("m/44'/242'/0'/1'").unwrap();
+    assert_eq!(account.address, expected.to_address());
+    assert_ne!(
+        account.address,
+        wallet
+            .derive_account(HdWallet::DEFAULT_PATH, 0)
+            .unwrap()
+            .address
+    );
+    assert!(wallet.derive_account("m/44/242", 0).is_none());
+
+    // Hardened indices are limited to 31 bits.
+    assert!(wallet
+        .derive_account(HdWallet::DEFAULT_PATH, HdWallet::MAX_ACCOUNT_INDEX)
+        .is_some());
+    assert!(wallet
+        .derive_account(HdWallet::DEFAULT_PATH, HdWallet::MAX_ACCOUNT_INDEX + 1)
+        .is_none());
+    assert!(wallet
+        .derive_account(HdWallet::DEFAULT_PATH, u32::MAX)
+        .is_none());
+}
+
+#[test]
+fn test_hd_wallet_debug_hides_seed() {
+    let wallet = HdWallet::from_mnemonic(&mnemonic(), None).unwrap();
+    assert_eq!(
+        format!("{:?}", wallet),
+        format!("HdWallet {{ id: {:?} }}", wallet.id())
+    );
+}
+
+#[test]
+fn test_hd_wallet_store() {
+    let env = VolatileDatabase::new(20).unwrap();
+    let store = WalletStore::new(env);
+
+    let wallet = HdWallet::from_mnemonic(&mnemonic(), None).unwrap();
+    let id = wallet.id().clone();
+    let mut info = HdWalletInfo::new(HdWallet::DEFAULT_PATH.to_string());
+    for index in [0, 2] {
+        info.insert(DerivedAccount {
+            index,
+            address: wallet
+                .derive_account(&info.base_path, index)
+                .unwrap()
+                .address,
+        });
+    }
+    assert_eq!(info.next_index(), 1);
+
+    let stored = StoredHdWallet {
+        wallet: Locked::with_defaults(wallet.clone(), b"passphrase").unwrap(),
+        info: info.clone(),
+    };
+    let mut txn = store.create_write_transaction();
+    store.put_hd_wallet(&id, &stored, &mut txn);
+    txn.commit();
+
+    assert_eq!(store.list_hd_wallets(None), vec![id.clone()]);
+    let stored = store.get_hd_wallet(&id, None).unwrap();
+    assert_eq!(stored.info, info);
+    let locked = match stored.wallet.unlock(b"wrong") {
+        Ok(_) => panic!("Unlocking with a wrong passphrase must fail"),
+        Err(locked) => locked,
+    };
+    let unlocked = locked.unlock(b"passphrase").ok().unwrap();
+    assert_eq!(*unlocked, wallet);
+}
+
+#[test]
+fn test_wallet_backup() {
+    let store = WalletStore::new(VolatileDatabase::new(20).unwrap());
+    let account = wallet();
+    let hd_wallet = HdWallet::from_mnemonic(&mnemonic(), None).unwrap();
+    let id = hd_wallet.id().clone();
+
+    let mut txn = store.create_write_transaction();
+    store.put(
+        &account.address,
+        &Locked::with_defaults(account.clone(), b"account").unwrap(),
+        &mut txn,
+    );
+    store.put_hd_wallet(
+        &id,
+        &StoredHdWallet {
+            wallet: Locked::with_defaults(hd_wallet.clone(), b"hd wallet").unwrap(),
+            info: HdWalletInfo::new(HdWallet::DEFAULT_PATH.to_string()),
+        },
+        &mut txn,
+    );
+    // A label of a transaction of the account and one of a transaction that isn't recorded.
+    let label = |text: &str| TransactionLabel {
+        label: text.to_string(),
+        note: None,
+    };
+    let transaction = WalletTransaction {
+        hash: Blake2bHash::from([1u8; 32]),
+        block_number: 1,
+        timestamp: 1000,
+        sender: account.address.clone(),
+        recipient: Address::from([1u8; Address::SIZE]),
+        value: Coin::from_u64_unchecked(100),
+        fee: Coin::from_u64_unchecked(1),
+        failed: false,
+    };
+    store.put_transaction(&account.address, &transaction, &mut txn);
+    store.put_label(&transaction.hash, &label("Rent"), &mut txn);
+    store.put_label(&Blake2bHash::from([2u8; 32]), &label("Pending"), &mut txn);
+    let watched = Address::from([2u8; Address::SIZE]);
+    store.put_watch_only(&watched, &WatchOnlyAccount::default(), &mut txn);
+    txn.commit();
+
+    // Unknown addresses can't be exported.
+    let unknown = Address::from([1u8; Address::SIZE]);
+    assert_eq!(
+        store.backup_contents(&[unknown.clone()]).err(),
+        Some(unknown)
+    );
+
+    // Exporting single accounts only includes the labels of their transactions.
+    let contents = store.backup_contents(&[account.address.clone()]).unwrap();
+    assert_eq!(
+        contents.labels,
+        vec![(transaction.hash.clone(), label("Rent"))]
+    );
+
+    let contents = store.backup_contents(&[]).unwrap();
+    let backup = WalletBackup::new(contents, b"backup")
+        .unwrap()
+        .serialize_to_vec();
+
+    let mut other_version = backup.clone();
+    other_version[0] = WalletBackup::VERSION + 1;
+    assert!(matches!(
+        WalletBackup::parse(&other_version),
+        Err(WalletBackupError::UnsupportedVersion(_))
+    ));
+    let mut over_long = backup.clone();
+    over_long.push(0);
+    assert!(matches!(
+        WalletBackup::parse(&over_long),
+        Err(WalletBackupError::OverLong)
+    ));
+    assert!(matches!(
+        WalletBackup::parse(&backup).unwrap().unlock(b"wrong"),
+        Err(WalletBackupError::WrongPassphrase)
+    ));
+
+    let contents = WalletBackup::parse(&backup)
+        .unwrap()
+        .unlock(b"backup")
+        .unwrap();
+    let restored_store = WalletStore::new(VolatileDatabase::new(20).unwrap());
+    let mut txn = restored_store.create_write_transaction();
+    let (accounts, hd_wallets, watch_only) =
+        restored_store.restore_backup(&contents, false, &mut txn);
+    txn.commit();
+    assert_eq!(accounts, vec![account.address.clone()]);
+    assert_eq!(hd_wallets, vec![id.clone()]);
+    assert_eq!(watch_only, vec![watched.clone()]);
+    assert_eq!(
+        restored_store.get_watch_only(&watched, None),
+        Some(WatchOnlyAccount::default())
+    );
+    assert_eq!(restored_store.list_labels(None), store.list_labels(None));
+    assert_eq!(restored_store.list_labels(None).len(), 2);
+
+    // The accounts are still locked with their own passphrases.
+    let unlocked = restored_store
+        .get(&account.address, None)
+        .unwrap()
+        .unlock(b"account")
+        .ok()
+        .unwrap();
+    assert_eq!(*unlocked, account);
+    let unlocked = restored_store
+        .get_hd_wallet(&id, None)
+        .unwrap()
+        .wallet
+        .unlock(b"hd wallet")
+        .ok()
+        .unwrap();
+    assert_eq!(*unlocked, hd_wallet);
+
+    // Existing entries are only replaced when overwriting.
+    let mut txn = restored_store.create_write_transaction();
+    let (accounts, hd_wallets, watch_only) =
+        restored_store.restore_backup(&contents, false, &mut txn);
+    assert!(accounts.is_empty() && hd_wallets.is_empty() && watch_only.is_empty());
+    let (accounts, ..) = restored_store.restore_backup(&contents, true, &mut txn);
+    assert_eq!(accounts.len(), 1);
+}
+
+#[test]
+fn test_hd_wallet_backup_includes_derived_accounts() {
+    let store = WalletStore::new(VolatileDatabase::new(20).unwrap());
+    let hd_wallet = HdWallet::from_mnemonic(&mnemonic(), None).unwrap();
+    let id = hd_wallet.id().clone();
+    let account = hd_wallet.derive_account(HdWallet::DEFAULT_PATH, 0).unwrap();
+    let mut info = HdWalletInfo::new(HdWallet::DEFAULT_PATH.to_string());
+    info.insert(DerivedAccount {
+        index: 0,
+        address: account.address.clone(),
+    });
+
+    let mut txn = store.create_write_transaction();
+    store.put(
+        &account.address,
+        &Locked::with_defaults(account.clone(), b"hd wallet").unwrap(),
+        &mut txn,
+    );
+    store.put_hd_wallet(
+        &id,
+        &StoredHdWallet {
+            wallet: Locked::with_defaults(hd_wallet, b"hd wallet").unwrap(),
+            info: info.clone(),
+        },
+        &mut txn,
+    );
+    txn.commit();
+
+    // Exporting the HD wallet by its ID includes its derived accounts, even if they are exported explicitly too.
+    for addresses in [vec![id.clone()], vec![id.clone(), account.address.clone()]] {
+        let contents = store.backup_contents(&addresses).unwrap();
+        assert_eq!(contents.hd_wallets.len(), 1);
+        assert_eq!(contents.hd_wallets[0].1.info, info);
+        assert_eq!(
+            contents
+                .accounts
+                .iter()
+                .map(|(address, _)| address.clone())
+                .collect::<Vec<_>>(),
+            vec![account.address.clone()]
+        );
+    }
+}
+
+#[test]
+fn test_watch_only_store() {
+    let env = VolatileDatabase::new(20).unwrap();
+    let store = WalletStore::new(env);
+    let wallet = wallet();
+    let account = WatchOnlyAccount {
+        public_key: Some(wallet.key_pair.public),
+    };
+
+    let mut txn = store.create_write_transaction();
+    store.put_watch_only(&wallet.address, &account, &mut txn);
+    txn.commit();
+
+    // Watch-only accounts are kept apart from the accounts with private keys.
+    assert_eq!(store.list_watch_only(None), vec![wallet.address.clone()]);
+    assert!(store.list(None).is_empty());
+    assert_eq!(store.get_watch_only(&wallet.address, None), Some(account));
+
+    let mut txn = store.create_write_transaction();
+    store.remove_watch_only(&wallet.address, &mut txn);
+    txn.commit();
+    assert!(store.list_watch_only(None).is_empty());
+}
+
+#[test]
+fn test_wallet_history_store() {
+    let env = VolatileDatabase::new(20).unwrap();
+    let store = WalletStore::new(env);
+    let address = wallet().address;
+    let other = Address::from([1u8; Address::SIZE]);
+    let last = Address::from([0xffu8; Address::SIZE]);
+
+    let transaction = |block_number: u32, byte: u8| WalletTransaction {
+        hash: Blake2bHash::from([byte; 32]),
+        block_number,
+        timestamp: block_number as u64 * 1000,
+        sender: address.clone(),
+        recipient: other.clone(),
+        value: Coin::from_u64_unchecked(100),
+        fee: Coin::from_u64_unchecked(1),
+        failed: false,
+    };
+
+    let mut txn = store.create_write_transaction();
+    for (block_number, byte) in [(2, 3), (1, 1), (3, 2)] {
+        store.put_transaction(&address, &transaction(block_number, byte), &mut txn);
+    }
+    store.put_transaction(&other, &transaction(4, 4), &mut txn);
+    store.put_transaction(&last, &transaction(5, 5), &mut txn);
+    txn.commit();
+
+    // Transactions are returned newest first and only for the given address.
+    let blocks = |transactions: Vec<WalletTransaction>| -> Vec<u32> {
+        transactions.iter().map(|tx| tx.block_number).collect()
+    };
+    assert_eq!(
+        blocks(store.list_transactions(&address, 0, 10, None)),
+        vec![3, 2, 1]
+    );
+    assert_eq!(
+        blocks(store.list_transactions(&address, 1, 1, None)),
+        vec![2]
+    );
+    assert_eq!(
+        blocks(store.list_transactions(&other, 0, 10, None)),
+        vec![4]
+    );
+    assert_eq!(blocks(store.list_transactions(&last, 0, 10, None)), vec![5]);
+    assert!(store.list_transactions(&address, 3, 10, None).is_empty());
+
+    let mut txn = store.create_write_transaction();
+    store.remove_transaction(&address, 3, &Blake2bHash::from([2u8; 32]), &mut txn);
+    txn.commit();
+    assert_eq!(
+        blocks(store.list_transactions(&address, 0, 10, None)),
+        vec![2, 1]
+    );
+
+    let hash = Blake2bHash::from([1u8; 32]);
+    let label = TransactionLabel {
+        label: "Rent".to_string(),
+        note: Some("March".to_string()),
+    };
+    let mut txn = store.create_write_transaction();
+    store.put_label(&hash, &label, &mut txn);
+    txn.commit();
+    assert_eq!(store.get_label(&hash, None), Some(label));
+
+    let mut txn = store.create_write_transaction();
+    store.remove_label(&hash, &mut txn);
+    txn.commit();
+    assert_eq!(store.get_label(&hash, None), None);
+}
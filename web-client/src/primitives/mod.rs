@@ -2,8 +2,10 @@ pub mod bls_key_pair;
 pub mod bls_public_key;
 pub mod bls_secret_key;
 pub mod key_pair;
+pub mod partially_signed_transaction;
 pub mod private_key;
 pub mod public_key;
 pub mod signature;
 pub mod signature_proof;
 pub mod transaction_builder;
+pub mod typed_message;
@@ -0,0 +1,144 @@
+use std::str::FromStr;
+
+use nimiq_serde::Serialize;
+use nimiq_transaction::account::htlc_contract::{AnyHash, AnyHash32, AnyHash64, PreImage};
+use nimiq_transaction_builder::{proof::HtlcPstProof, TransactionProofBuilder};
+use wasm_bindgen::prelude::*;
+
+use crate::{address::Address, primitives::key_pair::KeyPair, transaction::Transaction};
+
+/// A partially signed transaction (PST) carries a transaction to the holders of the keys that have to sign it,
+/// e.g. on air-gapped machines, and collects their signatures until it can be finalized.
+#[wasm_bindgen]
+pub struct PartiallySignedTransaction {
+    inner: nimiq_transaction::pst::PartiallySignedTransaction,
+}
+
+#[wasm_bindgen]
+impl PartiallySignedTransaction {
+    /// Creates a PST for an unsigned transaction. The signer signs on behalf of the sender, or signs the staking
+    /// data of transactions to the staking contract.
+    ///
+    /// Throws for HTLC redemption transactions, which have to be created with the HTLC specific methods.
+    #[wasm_bindgen(js_name = fromTransaction)]
+    pub fn from_transaction(
+        transaction: &Transaction,
+        signer: &Address,
+    ) -> Result<PartiallySignedTransaction, JsError> {
+        Self::from_native_transaction(transaction, signer, None)
+    }
+
+    /// Creates a PST for an unsigned transaction redeeming an HTLC with the pre-image, which is signed by the
+    /// HTLC's recipient. The hash root is given in hex and hashed with `hashAlgorithm`, which is one of `blake2b`,
+    /// `sha256` and `sha512`.
+    #[wasm_bindgen(js_name = fromHtlcRegularTransfer)]
+    pub fn from_htlc_regular_transfer(
+        transaction: &Transaction,
+        htlc_recipient: &Address,
+        pre_image: &str,
+        hash_root: &str,
+        hash_algorithm: &str,
+        hash_count: u8,
+    ) -> Result<PartiallySignedTransaction, JsError> {
+        let hash_root = match hash_algorithm {
+            "blake2b" => AnyHash::Blake2b(AnyHash32::from_str(hash_root)?),
+            "sha256" => AnyHash::Sha256(AnyHash32::from_str(hash_root)?),
+            "sha512" => AnyHash::Sha512(AnyHash64::from_str(hash_root)?),
+            _ => return Err(JsError::new("Invalid hash algorithm")),
+        };
+        let proof = HtlcPstProof::RegularTransfer {
+            hash_depth: hash_count,
+            hash_root,
+            pre_image: PreImage::from_str(pre_image)?,
+        };
+        Self::from_native_transaction(transaction, htlc_recipient, Some(proof))
+    }
+
+    /// Creates a PST for an unsigned transaction resolving an HTLC before its timeout, which is signed by both the
+    /// HTLC's sender and recipient.
+    #[wasm_bindgen(js_name = fromHtlcEarlyResolve)]
+    pub fn from_htlc_early_resolve(
+        transaction: &Transaction,
+        htlc_sender: &Address,
+        htlc_recipient: &Address,
+    ) -> Result<PartiallySignedTransaction, JsError> {
+        let proof = HtlcPstProof::EarlyResolve {
+            recipient: htlc_recipient.native(),
+        };
+        Self::from_native_transaction(transaction, htlc_sender, Some(proof))
+    }
+
+    /// Creates a PST for an unsigned transaction reclaiming the funds of an HTLC after its timeout, which is signed
+    /// by the HTLC's sender.
+    #[wasm_bindgen(js_name = fromHtlcTimeoutResolve)]
+    pub fn from_htlc_timeout_resolve(
+        transaction: &Transaction,
+        htlc_sender: &Address,
+    ) -> Result<PartiallySignedTransaction, JsError> {
+        Self::from_native_transaction(transaction, htlc_sender, Some(HtlcPstProof::TimeoutResolve))
+    }
+
+    /// Deserializes a PST from a byte array.
+    ///
+    /// Throws when the bytes are not a PST of a supported version.
+    pub fn unserialize(bytes: &[u8]) -> Result<PartiallySignedTransaction, JsError> {
+        let pst = nimiq_transaction::pst::PartiallySignedTransaction::parse(bytes)?;
+        Ok(PartiallySignedTransaction::from_native(pst))
+    }
+
+    /// Serializes the PST to a byte array.
+    pub fn serialize(&self) -> Vec<u8> {
+        self.inner.serialize_to_vec()
+    }
+
+    /// Adds all signatures the keypair has to make.
+    ///
+    /// Throws when the keypair is not a signer of the PST or has already signed it.
+    pub fn sign(&mut self, key_pair: &KeyPair) -> Result<(), JsError> {
+        if self.inner.sign_with_key_pair(key_pair.native_ref())? == 0 {
+            return Err(JsError::new("The keypair has no missing signature"));
+        }
+        Ok(())
+    }
+
+    /// Whether all required signatures were collected.
+    #[wasm_bindgen(getter, js_name = isComplete)]
+    pub fn is_complete(&self) -> bool {
+        self.inner.is_complete()
+    }
+
+    /// Combines the collected signatures and returns the signed transaction.
+    ///
+    /// Throws when signatures are missing.
+    pub fn finalize(&self) -> Result<Transaction, JsError> {
+        Ok(Transaction::from_native(self.inner.finalize()?))
+    }
+}
+
+impl PartiallySignedTransaction {
+    fn from_native_transaction(
+        transaction: &Transaction,
+        signer: &Address,
+        htlc_proof: Option<HtlcPstProof>,
+    ) -> Result<PartiallySignedTransaction, JsError> {
+        let pst = TransactionProofBuilder::new(transaction.native())
+            .into_pst(signer.native(), htlc_proof)
+            .ok_or_else(|| {
+                JsError::new(
+                    "HTLC redemption transactions require an HTLC specific method, which no other \
+                     transaction accepts",
+                )
+            })?;
+        Ok(PartiallySignedTransaction::from_native(pst))
+    }
+
+    pub fn from_native(
+        pst: nimiq_transaction::pst::PartiallySignedTransaction,
+    ) -> PartiallySignedTransaction {
+        PartiallySignedTransaction { inner: pst }
+    }
+
+    pub fn native_ref(&self) -> &nimiq_transaction::pst::PartiallySignedTransaction {
+        &self.inner
+    }
+}
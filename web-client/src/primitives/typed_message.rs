@@ -0,0 +1,60 @@
+use wasm_bindgen::prelude::*;
+
+use crate::primitives::{key_pair::KeyPair, public_key::PublicKey, signature::Signature};
+
+/// A typed message is a structured message with named, typed fields, signed for a domain that names the requesting
+/// application and the network. Its signature can't be replayed for another application, network or plain message.
+#[wasm_bindgen]
+pub struct TypedMessage {
+    inner: nimiq_keys::typed_message::TypedMessage,
+}
+
+#[wasm_bindgen]
+impl TypedMessage {
+    /// Parses a typed message from a plain object of the form
+    /// `{ domain: { name, version, networkId }, messageType, fields: [{ name, type, value }] }`.
+    ///
+    /// Throws when the object is malformed or contains invalid type or field names.
+    #[wasm_bindgen(js_name = fromPlain)]
+    pub fn from_plain(plain: JsValue) -> Result<TypedMessage, JsError> {
+        let message: nimiq_keys::typed_message::TypedMessage =
+            serde_wasm_bindgen::from_value(plain)?;
+        message.validate()?;
+        Ok(TypedMessage::from_native(message))
+    }
+
+    /// Returns the hash that is signed, e.g. to be signed by an external signer.
+    #[wasm_bindgen(js_name = signingHash)]
+    pub fn signing_hash(&self) -> Vec<u8> {
+        self.inner.signing_hash().as_slice().to_vec()
+    }
+
+    /// Signs the typed message with the keypair.
+    pub fn sign(&self, key_pair: &KeyPair) -> Result<Signature, JsError> {
+        Ok(Signature::from_native(
+            self.inner.sign(key_pair.native_ref())?,
+        ))
+    }
+
+    /// Verifies the signature of the typed message. Checking the domain is up to the caller.
+    pub fn verify(&self, public_key: &PublicKey, signature: &Signature) -> bool {
+        self.inner
+            .verify(public_key.native_ref(), signature.native_ref())
+    }
+
+    /// Formats the typed message for displaying it to the user before signing.
+    #[wasm_bindgen(js_name = toDisplayString)]
+    pub fn to_display_string(&self) -> String {
+        self.inner.to_string()
+    }
+}
+
+impl TypedMessage {
+    pub fn from_native(message: nimiq_keys::typed_message::TypedMessage) -> TypedMessage {
+        TypedMessage { inner: message }
+    }
+
+    pub fn native_ref(&self) -> &nimiq_keys::typed_message::TypedMessage {
+        &self.inner
+    }
+}